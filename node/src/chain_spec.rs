@@ -1,77 +1,27 @@
 //! Chain specification for ARED Edge network.
 //!
-//! Defines genesis configuration for different network types:
+//! Defines the chain specs of the different network types:
 //! - Development (single node for local testing)
 //! - Local testnet (multi-node local network)
 //! - Production (mainnet deployment)
 //!
-//! ## Account Structure
-//!
-//! The ARED Edge network uses a hierarchy of accounts:
-//! - **Root/Sudo**: Administrative control (development only)
-//! - **Bridge Account**: Submits telemetry proofs from the Ingest Service
-//! - **Validator Accounts**: Block producers (Aura) and finalizers (Grandpa)
-//! - **Treasury**: Collects transaction fees (future use)
-//!
 //! ## Genesis Configuration
 //!
-//! Initial state includes:
+//! Genesis state comes from the runtime's genesis presets (`development`,
+//! `local_testnet` and `live`, see `ared_edge_runtime::genesis_config_presets`),
+//! which include:
 //! - Pre-funded accounts for operations
 //! - Initial validator set
 //! - Pallet configurations
 
 use std::env;
 
-use ared_edge_runtime::WASM_BINARY;
+use ared_edge_runtime::{genesis_config_presets::LIVE_RUNTIME_PRESET, WASM_BINARY};
 use sc_service::ChainType;
-use serde_json::json;
-use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_consensus_grandpa::AuthorityId as GrandpaId;
-use sp_core::{sr25519, Pair, Public};
-use sp_runtime::traits::{IdentifyAccount, Verify};
 
 /// Specialized `ChainSpec` for ARED Edge network.
 pub type ChainSpec = sc_service::GenericChainSpec;
 
-/// The type for account identifiers.
-pub type AccountId = <<sp_runtime::MultiSignature as Verify>::Signer as IdentifyAccount>::AccountId;
-
-/// Initial balance for pre-funded accounts (in smallest units).
-const INITIAL_BALANCE: u128 = 1_000_000_000_000_000_000; // 1e18
-
-/// Initial balance for bridge account.
-const BRIDGE_BALANCE: u128 = 100_000_000_000_000_000; // 0.1e18
-
-/// Generate a crypto pair from seed.
-pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
-    TPublic::Pair::from_string(&format!("//{}", seed), None)
-        .expect("static values are valid; qed")
-        .public()
-}
-
-/// Generate an account ID from seed.
-pub fn get_account_id_from_seed<TPublic: Public>(seed: &str) -> AccountId
-where
-    <TPublic::Pair as Pair>::Public: Into<AccountId>,
-{
-    get_from_seed::<TPublic>(seed).into()
-}
-
-/// Generate Aura authority key from seed.
-pub fn get_aura_id_from_seed(seed: &str) -> AuraId {
-    get_from_seed::<AuraId>(seed)
-}
-
-/// Generate Grandpa authority key from seed.
-pub fn get_grandpa_id_from_seed(seed: &str) -> GrandpaId {
-    get_from_seed::<GrandpaId>(seed)
-}
-
-/// Generate authority keys (Aura, Grandpa) from seed.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-    (get_aura_id_from_seed(s), get_grandpa_id_from_seed(s))
-}
-
 /// Development chain configuration (single node).
 ///
 /// Characteristics:
@@ -88,7 +38,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
     .with_id("ared_edge_dev")
     .with_chain_type(ChainType::Development)
     .with_protocol_id("ared-edge-dev")
-    .with_genesis_config_patch(development_genesis_config())
+    .with_genesis_config_preset_name(sp_genesis_builder::DEV_RUNTIME_PRESET)
     .with_properties(chain_properties())
     .build())
 }
@@ -108,7 +58,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
     .with_id("ared_edge_local")
     .with_chain_type(ChainType::Local)
     .with_protocol_id("ared-edge-local")
-    .with_genesis_config_patch(local_testnet_genesis_config())
+    .with_genesis_config_preset_name(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET)
     .with_properties(chain_properties())
     .build())
 }
@@ -117,8 +67,8 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 ///
 /// For real deployments, set `CHAIN_SPEC_PATH` to a JSON chain spec generated
 /// with securely created validator keys (see docs/KEY_GENERATION.md).
-/// If the env var is absent, falls back to seed-derived placeholder keys
-/// suitable only for pre-production testing.
+/// If the env var is absent, falls back to the runtime's `live` preset with
+/// seed-derived placeholder keys suitable only for pre-production testing.
 pub fn production_config() -> Result<ChainSpec, String> {
     if let Ok(path) = env::var("CHAIN_SPEC_PATH") {
         log::info!("Loading production chain spec from {}", path);
@@ -138,7 +88,7 @@ pub fn production_config() -> Result<ChainSpec, String> {
     .with_id("ared_edge_mainnet")
     .with_chain_type(ChainType::Live)
     .with_protocol_id("ared-edge")
-    .with_genesis_config_patch(production_genesis_config())
+    .with_genesis_config_preset_name(LIVE_RUNTIME_PRESET)
     .with_properties(chain_properties())
     .build())
 }
@@ -151,114 +101,3 @@ fn chain_properties() -> serde_json::Map<String, serde_json::Value> {
     properties.insert("ss58Format".into(), 42.into());
     properties
 }
-
-/// Genesis configuration for development network.
-fn development_genesis_config() -> serde_json::Value {
-    let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
-    let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
-    let bridge = get_account_id_from_seed::<sr25519::Public>("Bridge");
-
-    let (alice_aura, alice_grandpa) = authority_keys_from_seed("Alice");
-
-    json!({
-        "balances": {
-            "balances": [
-                [alice.to_string(), INITIAL_BALANCE],
-                [bob.to_string(), INITIAL_BALANCE / 10],
-                [bridge.to_string(), BRIDGE_BALANCE]
-            ]
-        },
-        "aura": {
-            "authorities": [alice_aura.to_string()]
-        },
-        "grandpa": {
-            "authorities": [[alice_grandpa.to_string(), 1]]
-        },
-        "sudo": {
-            "key": alice.to_string()
-        }
-    })
-}
-
-/// Genesis configuration for local testnet.
-fn local_testnet_genesis_config() -> serde_json::Value {
-    let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
-    let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
-    let charlie = get_account_id_from_seed::<sr25519::Public>("Charlie");
-    let bridge = get_account_id_from_seed::<sr25519::Public>("Bridge");
-
-    let (alice_aura, alice_grandpa) = authority_keys_from_seed("Alice");
-    let (bob_aura, bob_grandpa) = authority_keys_from_seed("Bob");
-
-    json!({
-        "balances": {
-            "balances": [
-                [alice.to_string(), INITIAL_BALANCE],
-                [bob.to_string(), INITIAL_BALANCE],
-                [charlie.to_string(), INITIAL_BALANCE / 10],
-                [bridge.to_string(), BRIDGE_BALANCE]
-            ]
-        },
-        "aura": {
-            "authorities": [
-                alice_aura.to_string(),
-                bob_aura.to_string()
-            ]
-        },
-        "grandpa": {
-            "authorities": [
-                [alice_grandpa.to_string(), 1],
-                [bob_grandpa.to_string(), 1]
-            ]
-        },
-        "sudo": {
-            "key": alice.to_string()
-        }
-    })
-}
-
-/// Placeholder genesis configuration for production network.
-///
-/// WARNING: These keys are derived from well-known seeds and are NOT secure.
-/// For mainnet, generate a chain spec JSON externally using `subkey` and
-/// pass its path via `CHAIN_SPEC_PATH` or `--chain=/path/to/spec.json`.
-fn production_genesis_config() -> serde_json::Value {
-    // Seed-derived keys for pre-production testing only.
-    // Real deployments MUST use CHAIN_SPEC_PATH with externally generated keys.
-    let root = get_account_id_from_seed::<sr25519::Public>("Root");
-    let bridge = get_account_id_from_seed::<sr25519::Public>("Bridge");
-    let validator1 = get_account_id_from_seed::<sr25519::Public>("Validator1");
-    let validator2 = get_account_id_from_seed::<sr25519::Public>("Validator2");
-    let validator3 = get_account_id_from_seed::<sr25519::Public>("Validator3");
-
-    let (v1_aura, v1_grandpa) = authority_keys_from_seed("Validator1");
-    let (v2_aura, v2_grandpa) = authority_keys_from_seed("Validator2");
-    let (v3_aura, v3_grandpa) = authority_keys_from_seed("Validator3");
-
-    json!({
-        "balances": {
-            "balances": [
-                [root.to_string(), INITIAL_BALANCE],
-                [bridge.to_string(), BRIDGE_BALANCE],
-                [validator1.to_string(), BRIDGE_BALANCE],
-                [validator2.to_string(), BRIDGE_BALANCE],
-                [validator3.to_string(), BRIDGE_BALANCE]
-            ]
-        },
-        "aura": {
-            "authorities": [
-                v1_aura.to_string(),
-                v2_aura.to_string(),
-                v3_aura.to_string()
-            ]
-        },
-        "grandpa": {
-            "authorities": [
-                [v1_grandpa.to_string(), 1],
-                [v2_grandpa.to_string(), 1],
-                [v3_grandpa.to_string(), 1]
-            ]
-        }
-        // Note: No sudo in production - governance-based administration only
-    })
-}
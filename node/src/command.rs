@@ -5,7 +5,10 @@
 use crate::chain_spec;
 use crate::cli::{Cli, Subcommand};
 use crate::service;
+use ared_edge_runtime::opaque::Block;
+use frame_benchmarking_cli::BenchmarkCmd;
 use sc_cli::SubstrateCli;
+use sp_runtime::traits::HashingFor;
 
 impl SubstrateCli for Cli {
     fn impl_name() -> String {
@@ -60,7 +63,23 @@ pub fn run() -> sc_cli::Result<()> {
         Some(Subcommand::Key(cmd)) => cmd.run(&cli),
         Some(Subcommand::ChainInfo(cmd)) => {
             let runner = cli.create_runner(cmd)?;
-            runner.sync_run(|config| cmd.run::<ared_edge_runtime::opaque::Block>(&config))
+            runner.sync_run(|config| cmd.run::<Block>(&config))
+        }
+        Some(Subcommand::Benchmark(cmd)) => {
+            let runner = cli.create_runner(cmd)?;
+            runner.sync_run(|config| match cmd {
+                BenchmarkCmd::Pallet(cmd) => {
+                    if !cfg!(feature = "runtime-benchmarks") {
+                        return Err(
+                            "Runtime benchmarking wasn't enabled when building the node. \
+                            Enable it with `--features runtime-benchmarks`."
+                                .into(),
+                        );
+                    }
+                    cmd.run_with_spec::<HashingFor<Block>, ()>(Some(config.chain_spec))
+                }
+                _ => Err("Only `benchmark pallet` is supported".into()),
+            })
         }
         _ => {
             // Default: run the full node
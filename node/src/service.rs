@@ -8,11 +8,13 @@
 //! - Finality with Grandpa
 //! - RPC extensions
 //! - Network layer
+//! - Offchain workers (heartbeats and node authorization)
 
 use std::sync::Arc;
 use std::time::Duration;
 
-use sc_client_api::BlockBackend;
+use futures::FutureExt;
+use sc_client_api::{Backend, BlockBackend};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 use sc_consensus_grandpa::SharedVoterState;
 use sc_executor::WasmExecutor;
@@ -232,10 +234,28 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 
     network_starter.start_network();
 
-    // Offchain workers are optional and disabled for now to simplify the build
-    // They can be re-enabled when needed for specific runtime functionality
+    // Offchain workers send ImOnline heartbeats and apply the node
+    // authorization list of the runtime to the network, so a node outside
+    // the well-known nodes only keeps its authorized connections
     if config.offchain_worker.enabled {
-        log::info!("Offchain workers requested but not configured in this build");
+        task_manager.spawn_handle().spawn(
+            "offchain-workers-runner",
+            "offchain-worker",
+            sc_offchain::OffchainWorkers::new(sc_offchain::OffchainWorkerOptions {
+                runtime_api_provider: client.clone(),
+                is_validator: config.role.is_authority(),
+                keystore: Some(keystore_container.keystore()),
+                offchain_db: backend.offchain_storage(),
+                transaction_pool: Some(OffchainTransactionPoolFactory::new(
+                    transaction_pool.clone(),
+                )),
+                network_provider: Arc::new(network.clone()),
+                enable_http_requests: true,
+                custom_extensions: |_| vec![],
+            })
+            .run(client.clone(), task_manager.spawn_handle())
+            .boxed(),
+        );
     }
 
     let role = config.role;
@@ -0,0 +1,54 @@
+//! Benchmarking setup for pallet-block-rewards
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::traits::{fungible::Inspect, EnsureOrigin, Get};
+use pallet_authorship::EventHandler;
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn set_block_reward() -> Result<(), BenchmarkError> {
+        let origin =
+            T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let reward = T::MaxBlockReward::get();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, reward);
+
+        assert_eq!(Pallet::<T>::block_reward(), reward);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_fees_to_author() -> Result<(), BenchmarkError> {
+        let origin =
+            T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, true);
+
+        assert!(Pallet::<T>::fees_to_author());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn reward_author() {
+        let reward = T::MaxBlockReward::get();
+        BlockReward::<T>::put(reward);
+        let author: T::AccountId = account("author", 0, 0);
+
+        #[block]
+        {
+            Pallet::<T>::note_author(author.clone());
+        }
+
+        assert_eq!(T::Currency::balance(&author), reward);
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Test);
+}
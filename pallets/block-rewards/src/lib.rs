@@ -0,0 +1,260 @@
+//! # Block Rewards Pallet
+//!
+//! This pallet pays validators for producing blocks.
+//!
+//! ## Overview
+//!
+//! The Block Rewards pallet enables:
+//! - Minting `BlockReward` ARED to the author of every block
+//! - Adjusting the reward through `UpdateOrigin`, up to `MaxBlockReward`
+//! - Crediting transaction tips to the block author
+//! - Sending base transaction fees to `FeeDestination`, or optionally to the
+//!   block author
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `set_block_reward` - Change the reward minted per block (UpdateOrigin)
+//! - `set_fees_to_author` - Choose whether base fees go to the author (UpdateOrigin)
+//!
+//! ## Rewarding Authors
+//!
+//! The pallet implements `pallet_authorship::EventHandler`. The runtime lists
+//! it as an authorship event handler, so at the start of every block the
+//! author found in the block's pre-runtime digest is noted here and the
+//! pallet mints `BlockReward` to that account. A zero reward mints nothing.
+//!
+//! ## Fees
+//!
+//! `DealWithFees` is an `OnUnbalanced` handler for the transaction payment
+//! pallet, which hands it the base fee and the tip of each extrinsic in that
+//! order. The tip is always credited to the block author, so operators can
+//! tip to prioritize an extrinsic and the tip rewards whoever includes it.
+//! The base fee goes to `FeeDestination`, such as a treasury account or `()`
+//! to burn it, unless `FeesToAuthor` is set, in which case the author gets it
+//! too. Whatever cannot be credited to the author, for instance because the
+//! author is unknown, is burned.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use core::marker::PhantomData;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{
+            fungible::{self, Balanced, Mutate},
+            Imbalance, OnUnbalanced,
+        },
+    };
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::Zero;
+
+    /// Balance of the rewarded currency
+    pub type BalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    /// Fees and tips handed to `DealWithFees`
+    pub type CreditOf<T> =
+        fungible::Credit<<T as frame_system::Config>::AccountId, <T as Config>::Currency>;
+
+    /// The pallet's configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_authorship::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Weight information for extrinsics.
+        type WeightInfo: WeightInfo;
+
+        /// Currency the reward is minted in
+        type Currency: fungible::Mutate<Self::AccountId> + fungible::Balanced<Self::AccountId>;
+
+        /// Origin allowed to change the reward and the fee routing
+        type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Largest reward governance may set, bounding inflation
+        #[pallet::constant]
+        type MaxBlockReward: Get<BalanceOf<Self>>;
+
+        /// Receives base transaction fees not credited to the author; `()`
+        /// burns them
+        type FeeDestination: OnUnbalanced<CreditOf<Self>>;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Amount minted to the author of every block
+    #[pallet::storage]
+    #[pallet::getter(fn block_reward)]
+    pub type BlockReward<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Whether `DealWithFees` credits base fees to the block author rather
+    /// than `FeeDestination`
+    #[pallet::storage]
+    #[pallet::getter(fn fees_to_author)]
+    pub type FeesToAuthor<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Reward minted per block from the first block
+        pub block_reward: BalanceOf<T>,
+        /// Whether base fees go to the block author
+        pub fees_to_author: bool,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            assert!(
+                self.block_reward <= T::MaxBlockReward::get(),
+                "genesis block reward exceeds MaxBlockReward"
+            );
+            BlockReward::<T>::put(self.block_reward);
+            FeesToAuthor::<T>::put(self.fees_to_author);
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The reward minted per block was changed
+        BlockRewardSet { reward: BalanceOf<T> },
+        /// Base fees now go to the block author, or to `FeeDestination`
+        FeesToAuthorSet { enabled: bool },
+        /// The block author was rewarded
+        AuthorRewarded {
+            author: T::AccountId,
+            reward: BalanceOf<T>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The reward exceeds `MaxBlockReward`
+        RewardTooHigh,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Accounts for the reward the authorship pallet triggers this block.
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            T::WeightInfo::reward_author()
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Set the reward minted to every block author.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - UpdateOrigin required
+        /// - `reward` - New reward, at most `MaxBlockReward`
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::set_block_reward())]
+        pub fn set_block_reward(origin: OriginFor<T>, reward: BalanceOf<T>) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            ensure!(
+                reward <= T::MaxBlockReward::get(),
+                Error::<T>::RewardTooHigh
+            );
+
+            BlockReward::<T>::put(reward);
+
+            Self::deposit_event(Event::BlockRewardSet { reward });
+
+            Ok(())
+        }
+
+        /// Choose whether base transaction fees go to the block author.
+        ///
+        /// Tips go to the author either way.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - UpdateOrigin required
+        /// - `enabled` - Credit fees to the author (`true`) or pass them to
+        ///   `FeeDestination` (`false`)
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::set_fees_to_author())]
+        pub fn set_fees_to_author(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            FeesToAuthor::<T>::put(enabled);
+
+            Self::deposit_event(Event::FeesToAuthorSet { enabled });
+
+            Ok(())
+        }
+    }
+
+    /// Mints the block reward to the author noted by `pallet_authorship`.
+    impl<T: Config> pallet_authorship::EventHandler<T::AccountId, BlockNumberFor<T>> for Pallet<T> {
+        fn note_author(author: T::AccountId) {
+            let reward = BlockReward::<T>::get();
+            if reward.is_zero() {
+                return;
+            }
+
+            // Minting only fails for a reward below the existential deposit
+            // of an empty account or on issuance overflow; the block stays
+            // valid and the author goes unrewarded.
+            if T::Currency::mint_into(&author, reward).is_ok() {
+                Self::deposit_event(Event::AuthorRewarded { author, reward });
+            }
+        }
+    }
+
+    /// Transaction payment handler crediting tips to the block author and
+    /// passing base fees to `FeeDestination`, or to the author while
+    /// `FeesToAuthor` is set.
+    ///
+    /// Credits handed over on their own are treated as base fees.
+    pub struct DealWithFees<T>(PhantomData<T>);
+
+    impl<T: Config> DealWithFees<T> {
+        /// Credit `amount` to the current block author.
+        fn credit_author(amount: CreditOf<T>) {
+            if let Some(author) = pallet_authorship::Pallet::<T>::author() {
+                // A credit that cannot be resolved is dropped, which burns it
+                let _ = T::Currency::resolve(&author, amount);
+            }
+        }
+    }
+
+    impl<T: Config> OnUnbalanced<CreditOf<T>> for DealWithFees<T> {
+        fn on_unbalanceds(mut fees_then_tips: impl Iterator<Item = CreditOf<T>>) {
+            if let Some(fees) = fees_then_tips.next() {
+                Self::on_unbalanced(fees);
+            }
+            for tip in fees_then_tips.filter(|tip| !tip.peek().is_zero()) {
+                Self::credit_author(tip);
+            }
+        }
+
+        fn on_nonzero_unbalanced(fees: CreditOf<T>) {
+            if FeesToAuthor::<T>::get() {
+                Self::credit_author(fees);
+            } else {
+                T::FeeDestination::on_unbalanced(fees);
+            }
+        }
+    }
+}
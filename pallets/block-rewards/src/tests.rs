@@ -0,0 +1,234 @@
+//! Unit tests for the Block Rewards pallet.
+
+use crate::{self as pallet_block_rewards, *};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{
+        fungible::{Balanced, Inspect, ResolveTo},
+        ConstU32, ConstU64, FindAuthor, Hooks, OnUnbalanced,
+    },
+};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage, ConsensusEngineId,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Authorship: pallet_authorship,
+        BlockRewards: pallet_block_rewards,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+    type RuntimeTask = ();
+    type SingleBlockMigrations = ();
+    type MultiBlockMigrator = ();
+    type PreInherents = ();
+    type PostInherents = ();
+    type PostTransactions = ();
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU64<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type RuntimeHoldReason = ();
+    type RuntimeFreezeReason = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+}
+
+const AUTHOR: u64 = 7;
+const TREASURY: u64 = 9;
+
+frame_support::parameter_types! {
+    pub static BlockAuthor: Option<u64> = Some(AUTHOR);
+}
+
+/// Reports `BlockAuthor` as the author of every block.
+pub struct StaticAuthor;
+
+impl FindAuthor<u64> for StaticAuthor {
+    fn find_author<'a, I>(_digests: I) -> Option<u64>
+    where
+        I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+    {
+        BlockAuthor::get()
+    }
+}
+
+impl pallet_authorship::Config for Test {
+    type FindAuthor = StaticAuthor;
+    type EventHandler = BlockRewards;
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type UpdateOrigin = frame_system::EnsureRoot<u64>;
+    type MaxBlockReward = ConstU64<100>;
+    type FeeDestination = ResolveTo<ConstU64<TREASURY>, Balances>;
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    pallet_block_rewards::GenesisConfig::<Test> {
+        block_reward: 10,
+        fees_to_author: false,
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Run the authorship hooks of blocks `1..=n`.
+fn author_blocks(n: u64) {
+    for block in 1..=n {
+        System::set_block_number(block);
+        Authorship::on_initialize(block);
+        Authorship::on_finalize(block);
+    }
+}
+
+#[test]
+fn author_is_rewarded_every_block() {
+    new_test_ext().execute_with(|| {
+        author_blocks(3);
+
+        assert_eq!(Balances::balance(&AUTHOR), 30);
+        assert_eq!(Balances::total_issuance(), 30);
+        System::assert_last_event(
+            Event::AuthorRewarded {
+                author: AUTHOR,
+                reward: 10,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn zero_reward_or_unknown_author_mints_nothing() {
+    new_test_ext().execute_with(|| {
+        BlockAuthor::set(None);
+        author_blocks(1);
+        assert_eq!(Balances::total_issuance(), 0);
+
+        BlockAuthor::set(Some(AUTHOR));
+        assert_ok!(BlockRewards::set_block_reward(RuntimeOrigin::root(), 0));
+        author_blocks(2);
+        assert_eq!(Balances::balance(&AUTHOR), 0);
+    });
+}
+
+#[test]
+fn set_block_reward_is_bounded_and_governed() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            BlockRewards::set_block_reward(RuntimeOrigin::signed(AUTHOR), 20),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            BlockRewards::set_block_reward(RuntimeOrigin::root(), 101),
+            Error::<Test>::RewardTooHigh
+        );
+
+        assert_ok!(BlockRewards::set_block_reward(RuntimeOrigin::root(), 100));
+        System::assert_last_event(Event::BlockRewardSet { reward: 100 }.into());
+        author_blocks(1);
+        assert_eq!(Balances::balance(&AUTHOR), 100);
+    });
+}
+
+#[test]
+fn fees_go_to_author_only_when_enabled() {
+    new_test_ext().execute_with(|| {
+        // FeeDestination receives base fees by default
+        DealWithFees::<Test>::on_unbalanced(Balances::issue(40));
+        assert_eq!(Balances::balance(&AUTHOR), 0);
+        assert_eq!(Balances::balance(&TREASURY), 40);
+
+        assert_noop!(
+            BlockRewards::set_fees_to_author(RuntimeOrigin::signed(1), true),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BlockRewards::set_fees_to_author(
+            RuntimeOrigin::root(),
+            true
+        ));
+        System::assert_last_event(Event::FeesToAuthorSet { enabled: true }.into());
+
+        DealWithFees::<Test>::on_unbalanced(Balances::issue(25));
+        assert_eq!(Balances::balance(&AUTHOR), 25);
+        assert_eq!(Balances::balance(&TREASURY), 40);
+        assert_eq!(Balances::total_issuance(), 65);
+    });
+}
+
+#[test]
+fn tips_go_to_author_and_base_fees_to_destination() {
+    let fee_then_tip = || [Balances::issue(30), Balances::issue(5)].into_iter();
+
+    new_test_ext().execute_with(|| {
+        // Without a known author the tip is burned; the fee is unaffected
+        BlockAuthor::set(None);
+        DealWithFees::<Test>::on_unbalanceds(fee_then_tip());
+        assert_eq!(Balances::balance(&TREASURY), 30);
+        assert_eq!(Balances::total_issuance(), 30);
+
+        // Transaction payment hands over the base fee, then the tip
+        BlockAuthor::set(Some(AUTHOR));
+        DealWithFees::<Test>::on_unbalanceds(fee_then_tip());
+        assert_eq!(Balances::balance(&TREASURY), 60);
+        assert_eq!(Balances::balance(&AUTHOR), 5);
+
+        // With FeesToAuthor set, the author gets both
+        assert_ok!(BlockRewards::set_fees_to_author(
+            RuntimeOrigin::root(),
+            true
+        ));
+        DealWithFees::<Test>::on_unbalanceds(fee_then_tip());
+        assert_eq!(Balances::balance(&TREASURY), 60);
+        assert_eq!(Balances::balance(&AUTHOR), 40);
+    });
+}
@@ -0,0 +1,70 @@
+//! Weight calculations for the Block Rewards pallet.
+//!
+//! These weights ensure proper transaction fee calculation
+//! and prevent denial-of-service attacks.
+
+use frame_support::{traits::Get, weights::Weight};
+
+/// Weight functions for the pallet.
+pub trait WeightInfo {
+    /// Weight for setting the block reward.
+    fn set_block_reward() -> Weight;
+
+    /// Weight for switching the fee routing.
+    fn set_fees_to_author() -> Weight;
+
+    /// Weight for rewarding the block author, charged every block.
+    fn reward_author() -> Weight;
+}
+
+/// Default weight implementation.
+///
+/// Placeholder weights that should be replaced with benchmark results.
+pub struct SubstrateWeight<T>(core::marker::PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    /// Weight for setting the block reward.
+    ///
+    /// Operations:
+    /// - Write the block reward
+    /// - Emit event
+    fn set_block_reward() -> Weight {
+        Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for switching the fee routing.
+    ///
+    /// Operations:
+    /// - Write the fee routing flag
+    /// - Emit event
+    fn set_fees_to_author() -> Weight {
+        Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for rewarding the block author.
+    ///
+    /// Operations:
+    /// - Read the block reward
+    /// - Read and write the author's account and the total issuance
+    /// - Emit event
+    fn reward_author() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+}
+
+/// Unit implementation for testing.
+impl WeightInfo for () {
+    fn set_block_reward() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn set_fees_to_author() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn reward_author() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+}
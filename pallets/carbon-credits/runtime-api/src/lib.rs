@@ -0,0 +1,51 @@
+//! # Carbon Credits Runtime API
+//!
+//! Lets indexers and RPC clients query carbon credit balances and network
+//! totals through `state_call` without decoding raw storage keys.
+//! Implemented by the runtime on top of the public helpers of
+//! `pallet-carbon-credits`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+pub use ared_primitives::ClaimPreview;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Network-wide carbon credit totals.
+#[derive(Clone, Default, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+pub struct CarbonStats {
+    /// Credits issued across all devices
+    pub total_credits_issued: u128,
+    /// CO2 avoided across all devices, in kg
+    pub total_co2_avoided_kg: u128,
+    /// Devices that have recorded energy
+    pub active_devices: u32,
+}
+
+sp_api::decl_runtime_apis! {
+    /// Read access to carbon credits.
+    #[api_version(2)]
+    pub trait CarbonCreditsApi {
+        /// Credits held by a device; 0 for unknown or malformed ids.
+        fn device_credits(device_id: Vec<u8>) -> u128;
+
+        /// Energy in Wh recorded for a device but not yet claimed.
+        fn pending_energy(device_id: Vec<u8>) -> u128;
+
+        /// Credits a claim of `energy_wh` would issue at the current
+        /// emission factor.
+        fn calculate_credits(energy_wh: u128) -> u128;
+
+        /// Network-wide totals.
+        fn stats() -> CarbonStats;
+
+        /// What claiming the device's accumulated energy would issue now,
+        /// computed exactly as `claim_credits` does; empty for unknown or
+        /// malformed ids. Added in version 2.
+        fn claimable_credits(device_id: Vec<u8>) -> ClaimPreview;
+    }
+}
@@ -3,21 +3,162 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
+use alloc::{vec, vec::Vec};
 use frame_benchmarking::v2::*;
+use frame_support::{
+    traits::{EnsureOrigin, Get},
+    BoundedVec,
+};
 use frame_system::RawOrigin;
+use pallet_device_registry::DeviceInterface;
+
+/// Register an active device of maximum id length owned by `owner`,
+/// distinct per `seed`.
+fn active_device<T: Config>(owner: &T::AccountId, seed: u32) -> Vec<u8> {
+    let mut device_id = vec![0u8; ared_primitives::MAX_DEVICE_ID_LENGTH as usize];
+    device_id[..4].copy_from_slice(&seed.to_le_bytes());
+    let bounded: DeviceId = device_id.clone().try_into().unwrap();
+    T::Devices::register_active(&bounded, owner);
+    device_id
+}
+
+/// Credit `amount` to the device's `CreditsBalance`.
+fn fund_device<T: Config>(device_id: &[u8], amount: u128) {
+    let bounded: DeviceId = device_id.to_vec().try_into().unwrap();
+    CreditsBalance::<T>::insert(&bounded, amount);
+}
 
 #[benchmarks]
 mod benchmarks {
     use super::*;
 
     #[benchmark]
-    fn issue_credits() {
+    fn record_energy() -> Result<(), BenchmarkError> {
+        let origin =
+            T::SubmitterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let owner: T::AccountId = account("owner", 0, 0);
+        let device_id = active_device::<T>(&owner, 0);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, device_id.clone(), 5_000, None);
+
+        let bounded: DeviceId = device_id.try_into().unwrap();
+        assert_eq!(Pallet::<T>::energy_accumulated(&bounded), 5_000);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn claim_credits() {
+        let caller: T::AccountId = whitelisted_caller();
+        let device_id = active_device::<T>(&caller, 0);
+        let bounded: DeviceId = device_id.clone().try_into().unwrap();
+        EnergyAccumulated::<T>::insert(&bounded, T::MinClaimableEnergy::get() * 1_000);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), device_id);
+
+        assert!(Pallet::<T>::credits_balance(&bounded) > 0);
+    }
+
+    #[benchmark]
+    fn transfer_credits() {
+        let caller: T::AccountId = whitelisted_caller();
+        let from_device = active_device::<T>(&caller, 0);
+        let to_device = active_device::<T>(&caller, 1);
+        fund_device::<T>(&from_device, 1_000);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            from_device,
+            to_device.clone(),
+            1_000,
+        );
+
+        let bounded: DeviceId = to_device.try_into().unwrap();
+        assert_eq!(Pallet::<T>::credits_balance(&bounded), 1_000);
+    }
+
+    #[benchmark]
+    fn withdraw_credits() {
+        let caller: T::AccountId = whitelisted_caller();
+        let device_id = active_device::<T>(&caller, 0);
+        fund_device::<T>(&device_id, 1_000);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), device_id, 1_000);
+
+        assert_eq!(Pallet::<T>::account_credits(&caller), 1_000);
+    }
+
+    #[benchmark]
+    fn announce_emission_factor() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, 2_000);
+
+        assert!(Pallet::<T>::pending_emission_factor().is_some());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn cancel_pending_factor() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        PendingEmissionFactor::<T>::put((2_000, frame_system::Pallet::<T>::block_number()));
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
+
+        assert!(Pallet::<T>::pending_emission_factor().is_none());
+        Ok(())
+    }
+
+    /// Worst case: the adjustment log is full and its oldest entry is dropped.
+    #[benchmark]
+    fn adjust_total_energy() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let owner: T::AccountId = account("owner", 0, 0);
+        let device_id = active_device::<T>(&owner, 0);
+        let bounded: DeviceId = device_id.clone().try_into().unwrap();
+        let reason: BoundedVec<u8, T::MaxAdjustmentReasonLength> =
+            vec![b'r'; T::MaxAdjustmentReasonLength::get() as usize]
+                .try_into()
+                .unwrap();
+        let entry = EnergyAdjustment::<T> {
+            old_total: 0,
+            new_total: 0,
+            reason: reason.clone(),
+            block_number: 0,
+        };
+        AdjustmentLog::<T>::insert(
+            &bounded,
+            BoundedVec::truncate_from(vec![entry; T::MaxAdjustmentLog::get() as usize]),
+        );
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, device_id, 10_000, reason);
+
+        assert_eq!(Pallet::<T>::total_energy(&bounded), 10_000);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn retire_credits() -> Result<(), BenchmarkError> {
         let caller: T::AccountId = whitelisted_caller();
-        let device_id = vec![0u8; 36];
-        let energy_kwh = 100u64;
+        let device_id = active_device::<T>(&caller, 0);
+        fund_device::<T>(&device_id, 1_000);
+        // Withdraw first, so the caller holds the credit token units to burn
+        Pallet::<T>::withdraw_credits(RawOrigin::Signed(caller.clone()).into(), device_id, 1_000)?;
 
         #[extrinsic_call]
-        _(RawOrigin::Signed(caller), device_id, energy_kwh);
+        _(RawOrigin::Signed(caller.clone()), 1_000);
+
+        assert_eq!(Pallet::<T>::account_credits(&caller), 0);
+        Ok(())
     }
 
     impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Test);
@@ -21,14 +21,106 @@
 //!
 //! Formula: credits = (energy_kwh * emission_factor_kg_per_kwh / 1000) * credits_per_ton
 //!
+//! `claim_preview` runs the same computation as `claim_credits` on a device's
+//! accumulated energy without changing state, and `claim_credits` issues
+//! exactly what it previews, so wallets can show what a claim would issue.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
 //! - `record_energy` - Record energy usage for carbon calculation
 //! - `claim_credits` - Claim accumulated carbon credits
-//! - `transfer_credits` - Transfer credits between devices/accounts
-//! - `set_emission_factor` - Update emission factor (governance)
+//! - `transfer_credits` - Move credits from an owned device to another device
+//! - `withdraw_credits` - Move an owned device's credits to the owner's account
+//! - `announce_emission_factor` - Schedule an emission factor change (governance)
+//! - `cancel_pending_factor` - Abort a scheduled emission factor change (governance)
+//! - `adjust_total_energy` - Correct a device's lifetime energy (admin)
+//! - `retire_credits` - Retire account credits, burning their token units
+//!
+//! ## Emission Factor Updates
+//!
+//! Emission factor changes are applied in two phases. Governance
+//! (`AdminOrigin`) announces the new factor, which is held as pending until
+//! `current_block + FactorDelay`. The `on_initialize` hook applies it at the
+//! activation block; claims made before then keep using the previous factor.
+//!
+//! ## Submitter Origin
+//!
+//! `record_energy` only accepts origins admitted by `SubmitterOrigin`. The
+//! runtime uses the same bridge membership origin as the telemetry proofs
+//! pallet, so one membership list controls who reports telemetry and energy.
+//! `record_energy` is marked `feeless_if` the origin passes `SubmitterOrigin`
+//! and its account is in `FeelessSubmitters`, for runtimes that skip fees of
+//! feeless calls with `pallet_skip_feeless_payment`. Like proof submissions
+//! it is `DispatchClass::Operational`, so energy reports use the block space
+//! reserved for operational calls when normal transactions fill the rest.
+//!
+//! ## Device Status
+//!
+//! Energy is only recorded and credits only claimed for devices the
+//! `Devices` interface reports as `Active`; unregistered, suspended and
+//! retired devices are refused.
+//!
+//! ## Energy Plausibility
+//!
+//! Energy recorded against a telemetry proof may not exceed the device
+//! model's maximum power, reported by `Devices`, drawn over the proof's
+//! whole window: `energy_wh <= max_power_w * (window_end - window_start) / 3600`.
+//! The bound covers all energy recorded against the proof, which
+//! `ProofEnergy` keeps per device and proof index, so a proof cannot be
+//! reused to record more.
+//!
+//! Records without a proof are allowed, at no more than the device's maximum
+//! power on average. `UnprovenEnergy` holds a device's unproven energy not
+//! yet covered by elapsed time: it drains at the maximum power as
+//! `TimeProvider` advances and may not exceed `UnprovenEnergyPeriod` seconds
+//! of it. Devices without a known rating are not bounded.
+//!
+//! ## Event Topics
+//!
+//! `EnergyRecorded`, `CreditsClaimed` and `TotalEnergyAdjusted` are indexed
+//! under the device's
+//! topic, `blake2_256(device_id)` over the raw id bytes (see
+//! `DeviceId::topic`), so clients can find a device's events through
+//! `System::event_topics`. Other events are deposited without topics.
+//!
+//! ## Energy With Proofs
+//!
+//! Energy can also arrive together with its telemetry proof: the pallet
+//! implements the telemetry pallet's `OnProofAccepted` hook, which
+//! `submit_proof_with_energy` calls with the new proof's index. The same
+//! checks as `record_energy` apply, and a rejected record discards the proof.
+//! `LatestEnergyRecord` keeps each device's most recent record and the proof
+//! it references.
+//!
+//! ## Energy Corrections
+//!
+//! When reprocessed telemetry shows a device's energy was double-counted,
+//! `AdminOrigin` corrects its lifetime `TotalEnergy` with
+//! `adjust_total_energy`. Each correction is kept with the old and new totals
+//! and a reason in the device's `AdjustmentLog` (the latest
+//! `MaxAdjustmentLog` entries). `ClaimedEnergy` tracks the energy already
+//! converted into credits, and the total cannot be corrected below it.
+//!
+//! ## Decommissioning
+//!
+//! The pallet implements the device registry's `OnDecommission` hook. When a
+//! device is decommissioned its claimable energy is claimed one last time and
+//! its whole `CreditsBalance` moves to the owner's account. Energy
+//! below `MinClaimableEnergy` is left accumulated, as the retired device can
+//! no longer claim it.
+//!
+//! ## Credit Token
+//!
+//! Account credits are held in the `CreditAsset` token, in the runtime a
+//! `pallet-assets` asset. Credits reaching an account, through
+//! `withdraw_credits` or a decommissioning sweep, are minted to it, and
+//! `retire_credits` burns them again, adding them to `TotalCreditsRetired`.
+//! The token balance is the only record of an account's credits, so units
+//! transferred between holders are retired by their new holder. Once device
+//! credits are withdrawn the token's supply is `TotalCreditsIssued` minus
+//! `TotalCreditsRetired`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -42,16 +134,27 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod traits;
 pub mod weights;
+pub use ared_primitives::{ClaimPreview, DeviceId};
+pub use traits::{CreditAsset, FungibleCreditAsset};
 pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use alloc::vec::Vec;
-    use frame_support::pallet_prelude::*;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{Contains, UnixTime},
+    };
     use frame_system::pallet_prelude::*;
-    use sp_runtime::traits::Zero;
+    use pallet_device_registry::{
+        DecommissionReport, DeviceInterface, DeviceStatus, OnDecommission,
+    };
+    use pallet_telemetry_proofs::{OnProofAccepted, ProofProvider};
+    use sp_runtime::{traits::Zero, SaturatedConversion};
 
     /// Energy record with metadata
     #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq)]
@@ -77,6 +180,29 @@ pub mod pallet {
         pub emission_factor: u32,
     }
 
+    /// Governance correction of a device's lifetime `TotalEnergy`
+    #[derive(
+        CloneNoBound,
+        Encode,
+        Decode,
+        TypeInfo,
+        MaxEncodedLen,
+        RuntimeDebugNoBound,
+        PartialEqNoBound,
+        EqNoBound,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct EnergyAdjustment<T: Config> {
+        /// `TotalEnergy` before the adjustment (Wh)
+        pub old_total: u128,
+        /// `TotalEnergy` after the adjustment (Wh)
+        pub new_total: u128,
+        /// Why the figure was corrected, e.g. a reprocessing ticket
+        pub reason: BoundedVec<u8, T::MaxAdjustmentReasonLength>,
+        /// Block when adjusted
+        pub block_number: u32,
+    }
+
     /// The pallet's configuration trait.
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -86,9 +212,11 @@ pub mod pallet {
         /// Weight information for extrinsics.
         type WeightInfo: WeightInfo;
 
-        /// Maximum length of device ID
-        #[pallet::constant]
-        type MaxDeviceIdLength: Get<u32>;
+        /// Origin allowed to record energy
+        type SubmitterOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+        /// Submitters whose `record_energy` calls are feeless
+        type FeelessSubmitters: Contains<Self::AccountId>;
 
         /// Default carbon credits per ton of CO2 avoided
         #[pallet::constant]
@@ -106,9 +234,48 @@ pub mod pallet {
         /// Maximum credit issuance records per device
         #[pallet::constant]
         type MaxIssuanceRecords: Get<u32>;
+
+        /// Number of blocks between announcing an emission factor and applying it
+        #[pallet::constant]
+        type FactorDelay: Get<BlockNumberFor<Self>>;
+
+        /// Telemetry proofs that energy records may reference; only valid
+        /// energy usage proofs are accepted
+        type ProofProvider: ProofProvider<DeviceId>;
+
+        /// Device registry; only `Active` devices may record energy or claim
+        type Devices: DeviceInterface<DeviceId, Self::AccountId>;
+
+        /// Origin allowed to change the emission factor and correct recorded
+        /// energy
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Number of `TotalEnergy` adjustments kept per device
+        #[pallet::constant]
+        type MaxAdjustmentLog: Get<u32>;
+
+        /// Maximum length of an adjustment reason
+        #[pallet::constant]
+        type MaxAdjustmentReasonLength: Get<u32>;
+
+        /// Token mirroring account credits: minted as credits reach an
+        /// account and burned when they are retired
+        type CreditAsset: CreditAsset<Self::AccountId>;
+
+        /// Time source for the unproven energy allowance
+        type TimeProvider: UnixTime;
+
+        /// Seconds of a device's maximum power that may be recorded without
+        /// proofs ahead of elapsed time
+        #[pallet::constant]
+        type UnprovenEnergyPeriod: Get<u64>;
     }
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Current emission factor (kg CO2 per kWh, scaled by 1000)
@@ -116,35 +283,55 @@ pub mod pallet {
     #[pallet::getter(fn emission_factor)]
     pub type EmissionFactor<T: Config> = StorageValue<_, u32, ValueQuery, T::DefaultEmissionFactor>;
 
+    /// Announced emission factor and the block at which it takes effect
+    #[pallet::storage]
+    #[pallet::getter(fn pending_emission_factor)]
+    pub type PendingEmissionFactor<T: Config> =
+        StorageValue<_, (u32, BlockNumberFor<T>), OptionQuery>;
+
     /// Accumulated energy in Wh per device (pending credit calculation)
     #[pallet::storage]
     #[pallet::getter(fn energy_accumulated)]
     pub type EnergyAccumulated<T: Config> =
-        StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxDeviceIdLength>, u128, ValueQuery>;
+        StorageMap<_, Blake2_128Concat, DeviceId, u128, ValueQuery>;
 
     /// Total lifetime energy recorded per device
     #[pallet::storage]
     #[pallet::getter(fn total_energy)]
-    pub type TotalEnergy<T: Config> =
-        StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxDeviceIdLength>, u128, ValueQuery>;
+    pub type TotalEnergy<T: Config> = StorageMap<_, Blake2_128Concat, DeviceId, u128, ValueQuery>;
+
+    /// Energy (Wh) per device already converted into credits by claims
+    #[pallet::storage]
+    #[pallet::getter(fn claimed_energy)]
+    pub type ClaimedEnergy<T: Config> = StorageMap<_, Blake2_128Concat, DeviceId, u128, ValueQuery>;
+
+    /// Latest `MaxAdjustmentLog` corrections of each device's `TotalEnergy`
+    #[pallet::storage]
+    #[pallet::getter(fn adjustment_log)]
+    pub type AdjustmentLog<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        DeviceId,
+        BoundedVec<EnergyAdjustment<T>, T::MaxAdjustmentLog>,
+        ValueQuery,
+    >;
 
     /// Carbon credits balance per device
     #[pallet::storage]
     #[pallet::getter(fn credits_balance)]
     pub type CreditsBalance<T: Config> =
-        StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxDeviceIdLength>, u128, ValueQuery>;
-
-    /// Credits balance per account (for transfers)
-    #[pallet::storage]
-    #[pallet::getter(fn account_credits)]
-    pub type AccountCredits<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::AccountId, u128, ValueQuery>;
+        StorageMap<_, Blake2_128Concat, DeviceId, u128, ValueQuery>;
 
     /// Total credits issued across all devices
     #[pallet::storage]
     #[pallet::getter(fn total_credits_issued)]
     pub type TotalCreditsIssued<T: Config> = StorageValue<_, u128, ValueQuery>;
 
+    /// Total account credits retired
+    #[pallet::storage]
+    #[pallet::getter(fn total_credits_retired)]
+    pub type TotalCreditsRetired<T: Config> = StorageValue<_, u128, ValueQuery>;
+
     /// Total CO2 avoided (kg, scaled by 1000 for precision)
     #[pallet::storage]
     #[pallet::getter(fn total_co2_avoided)]
@@ -153,8 +340,26 @@ pub mod pallet {
     /// Issuance count per device
     #[pallet::storage]
     #[pallet::getter(fn issuance_count)]
-    pub type IssuanceCount<T: Config> =
-        StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxDeviceIdLength>, u32, ValueQuery>;
+    pub type IssuanceCount<T: Config> = StorageMap<_, Blake2_128Concat, DeviceId, u32, ValueQuery>;
+
+    /// Most recent energy record per device, with the proof it references
+    #[pallet::storage]
+    #[pallet::getter(fn latest_energy_record)]
+    pub type LatestEnergyRecord<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeviceId, EnergyRecord, OptionQuery>;
+
+    /// Energy (Wh) recorded against each proof, by device and proof index
+    #[pallet::storage]
+    #[pallet::getter(fn proof_energy)]
+    pub type ProofEnergy<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, DeviceId, Twox64Concat, u64, u128, ValueQuery>;
+
+    /// Unproven energy (Wh) per device not yet covered by elapsed time at
+    /// its maximum power, and when (unix seconds) it was last updated
+    #[pallet::storage]
+    #[pallet::getter(fn unproven_energy)]
+    pub type UnprovenEnergy<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeviceId, (u128, u64), ValueQuery>;
 
     /// Active device count (devices with energy records)
     #[pallet::storage]
@@ -166,31 +371,47 @@ pub mod pallet {
     pub enum Event<T: Config> {
         /// Energy was recorded for a device
         EnergyRecorded {
-            device_id: BoundedVec<u8, T::MaxDeviceIdLength>,
+            device_id: DeviceId,
             energy_wh: u128,
             total_accumulated: u128,
         },
         /// Carbon credits were claimed/issued
         CreditsClaimed {
-            device_id: BoundedVec<u8, T::MaxDeviceIdLength>,
+            device_id: DeviceId,
             credits: u128,
             energy_wh: u128,
             co2_avoided_kg: u128,
         },
         /// Credits transferred between devices
         CreditsTransferred {
-            from_device: BoundedVec<u8, T::MaxDeviceIdLength>,
-            to_device: BoundedVec<u8, T::MaxDeviceIdLength>,
+            from_device: DeviceId,
+            to_device: DeviceId,
             amount: u128,
         },
         /// Credits transferred to account
         CreditsWithdrawn {
-            device_id: BoundedVec<u8, T::MaxDeviceIdLength>,
+            device_id: DeviceId,
             to_account: T::AccountId,
             amount: u128,
         },
+        /// Emission factor change announced
+        EmissionFactorAnnounced {
+            new_factor: u32,
+            activation_block: BlockNumberFor<T>,
+        },
+        /// Pending emission factor change cancelled
+        PendingEmissionFactorCancelled { cancelled_factor: u32 },
         /// Emission factor updated
         EmissionFactorUpdated { old_factor: u32, new_factor: u32 },
+        /// A device's lifetime energy was corrected by governance
+        TotalEnergyAdjusted {
+            device_id: DeviceId,
+            old_total: u128,
+            new_total: u128,
+            reason: BoundedVec<u8, T::MaxAdjustmentReasonLength>,
+        },
+        /// Account credits were retired and their token units burned
+        CreditsRetired { account: T::AccountId, amount: u128 },
     }
 
     #[pallet::error]
@@ -211,6 +432,40 @@ pub mod pallet {
         InvalidEmissionFactor,
         /// Not authorized for governance action
         NotAuthorized,
+        /// No emission factor change is pending
+        NoPendingFactor,
+        /// The referenced telemetry proof does not exist or is not valid
+        ProofNotFound,
+        /// The device is not registered as active
+        DeviceNotActive,
+        /// The energy exceeds the device's maximum power over the proof
+        /// window, or its unproven energy allowance
+        EnergyImplausible,
+        /// The adjusted total would fall below energy already claimed as credits
+        AdjustmentBelowClaimed,
+        /// The caller does not own the device
+        NotDeviceOwner,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            match PendingEmissionFactor::<T>::get() {
+                Some((new_factor, activation_block)) if n >= activation_block => {
+                    let old_factor = EmissionFactor::<T>::get();
+                    EmissionFactor::<T>::put(new_factor);
+                    PendingEmissionFactor::<T>::kill();
+
+                    Self::deposit_event(Event::EmissionFactorUpdated {
+                        old_factor,
+                        new_factor,
+                    });
+
+                    T::DbWeight::get().reads_writes(2, 2)
+                }
+                _ => T::DbWeight::get().reads(1),
+            }
+        }
     }
 
     #[pallet::call]
@@ -222,51 +477,35 @@ pub mod pallet {
         ///
         /// # Arguments
         ///
-        /// - `origin` - Signed origin (bridge account)
+        /// - `origin` - Admitted by `SubmitterOrigin` (the bridge)
         /// - `device_id` - The device identifier
         /// - `energy_wh` - Energy in watt-hours
-        /// - `proof_index` - Optional link to telemetry proof
+        /// - `proof_index` - Optional link to telemetry proof: the per-device
+        ///   index from telemetry-proofs' `ProofSubmitted` event, which must
+        ///   refer to a valid proof of the same device
         #[pallet::call_index(0)]
-        #[pallet::weight(T::WeightInfo::record_energy())]
+        #[pallet::weight((T::WeightInfo::record_energy(), DispatchClass::Operational))]
+        #[pallet::feeless_if(|
+            origin: &OriginFor<T>,
+            _device_id: &Vec<u8>,
+            _energy_wh: &u128,
+            _proof_index: &Option<u64>,
+        | -> bool {
+            T::SubmitterOrigin::try_origin(origin.clone())
+                .is_ok_and(|who| T::FeelessSubmitters::contains(&who))
+        })]
         pub fn record_energy(
             origin: OriginFor<T>,
             device_id: Vec<u8>,
             energy_wh: u128,
-            _proof_index: Option<u64>,
+            proof_index: Option<u64>,
         ) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
+            T::SubmitterOrigin::ensure_origin(origin)?;
 
-            let bounded_device_id: BoundedVec<u8, T::MaxDeviceIdLength> = device_id
+            let bounded_device_id: DeviceId = device_id
                 .try_into()
                 .map_err(|_| Error::<T>::DeviceIdTooLong)?;
-
-            // Track if this is a new device
-            let was_zero = EnergyAccumulated::<T>::get(&bounded_device_id).is_zero()
-                && TotalEnergy::<T>::get(&bounded_device_id).is_zero();
-
-            // Update accumulated energy (pending)
-            let new_accumulated = EnergyAccumulated::<T>::mutate(&bounded_device_id, |total| {
-                *total = total.saturating_add(energy_wh);
-                *total
-            });
-
-            // Update total lifetime energy
-            TotalEnergy::<T>::mutate(&bounded_device_id, |total| {
-                *total = total.saturating_add(energy_wh);
-            });
-
-            // Increment active device count if new
-            if was_zero {
-                ActiveDeviceCount::<T>::mutate(|count| *count += 1);
-            }
-
-            Self::deposit_event(Event::EnergyRecorded {
-                device_id: bounded_device_id,
-                energy_wh,
-                total_accumulated: new_accumulated,
-            });
-
-            Ok(())
+            Self::do_record_energy(bounded_device_id, energy_wh, proof_index)
         }
 
         /// Claim carbon credits based on accumulated energy.
@@ -284,63 +523,11 @@ pub mod pallet {
         pub fn claim_credits(origin: OriginFor<T>, device_id: Vec<u8>) -> DispatchResult {
             let _who = ensure_signed(origin)?;
 
-            let bounded_device_id: BoundedVec<u8, T::MaxDeviceIdLength> = device_id
+            let bounded_device_id: DeviceId = device_id
                 .try_into()
                 .map_err(|_| Error::<T>::DeviceIdTooLong)?;
-
-            let accumulated = EnergyAccumulated::<T>::get(&bounded_device_id);
-            ensure!(!accumulated.is_zero(), Error::<T>::NoCreditsAvailable);
-            ensure!(
-                accumulated >= T::MinClaimableEnergy::get(),
-                Error::<T>::EnergyBelowMinimum
-            );
-
-            // Calculate CO2 avoided
-            // emission_factor is kg CO2 per kWh, scaled by 1000
-            // accumulated is in Wh
-            let emission_factor = EmissionFactor::<T>::get() as u128;
-            let energy_kwh = accumulated / 1000;
-
-            // co2_avoided_kg = energy_kwh * (emission_factor / 1000)
-            let co2_avoided_kg = energy_kwh
-                .saturating_mul(emission_factor)
-                .checked_div(1000)
-                .ok_or(Error::<T>::Overflow)?;
-
-            ensure!(!co2_avoided_kg.is_zero(), Error::<T>::NoCreditsAvailable);
-
-            // Calculate credits
-            // credits = (co2_avoided_kg / 1000) * credits_per_ton
-            let credits = co2_avoided_kg
-                .saturating_mul(T::CreditsPerTonCO2::get())
-                .checked_div(1000)
-                .ok_or(Error::<T>::Overflow)?;
-
-            ensure!(!credits.is_zero(), Error::<T>::NoCreditsAvailable);
-
-            // Update balances
-            CreditsBalance::<T>::mutate(&bounded_device_id, |balance| {
-                *balance = balance.saturating_add(credits);
-            });
-            TotalCreditsIssued::<T>::mutate(|total| {
-                *total = total.saturating_add(credits);
-            });
-            TotalCO2Avoided::<T>::mutate(|total| {
-                *total = total.saturating_add(co2_avoided_kg);
-            });
-            IssuanceCount::<T>::mutate(&bounded_device_id, |count| {
-                *count = count.saturating_add(1);
-            });
-
-            // Reset accumulated energy
-            EnergyAccumulated::<T>::insert(&bounded_device_id, 0u128);
-
-            Self::deposit_event(Event::CreditsClaimed {
-                device_id: bounded_device_id,
-                credits,
-                energy_wh: accumulated,
-                co2_avoided_kg,
-            });
+            Self::ensure_active(&bounded_device_id)?;
+            Self::do_claim_credits(&bounded_device_id)?;
 
             Ok(())
         }
@@ -349,7 +536,7 @@ pub mod pallet {
         ///
         /// # Arguments
         ///
-        /// - `origin` - Signed origin (must be authorized for from_device)
+        /// - `origin` - Signed origin (owner of from_device)
         /// - `from_device` - Source device ID
         /// - `to_device` - Destination device ID
         /// - `amount` - Number of credits to transfer
@@ -361,14 +548,15 @@ pub mod pallet {
             to_device: Vec<u8>,
             amount: u128,
         ) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
 
-            let bounded_from: BoundedVec<u8, T::MaxDeviceIdLength> = from_device
+            let bounded_from: DeviceId = from_device
                 .try_into()
                 .map_err(|_| Error::<T>::DeviceIdTooLong)?;
-            let bounded_to: BoundedVec<u8, T::MaxDeviceIdLength> = to_device
+            let bounded_to: DeviceId = to_device
                 .try_into()
                 .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            Self::ensure_owner(&bounded_from, &who)?;
 
             ensure!(bounded_from != bounded_to, Error::<T>::SameDeviceTransfer);
 
@@ -394,11 +582,12 @@ pub mod pallet {
         /// Withdraw credits from device to account.
         ///
         /// This moves credits from device balance to account balance,
-        /// making them available for external trading/use.
+        /// making them available for external trading/use. The same amount
+        /// of `CreditAsset` is minted to the account.
         ///
         /// # Arguments
         ///
-        /// - `origin` - Signed origin (account receiving credits)
+        /// - `origin` - Signed origin (the device owner, receiving the credits)
         /// - `device_id` - Source device ID
         /// - `amount` - Number of credits to withdraw
         #[pallet::call_index(3)]
@@ -410,9 +599,10 @@ pub mod pallet {
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
-            let bounded_device_id: BoundedVec<u8, T::MaxDeviceIdLength> = device_id
+            let bounded_device_id: DeviceId = device_id
                 .try_into()
                 .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            Self::ensure_owner(&bounded_device_id, &who)?;
 
             let device_balance = CreditsBalance::<T>::get(&bounded_device_id);
             ensure!(device_balance >= amount, Error::<T>::InsufficientCredits);
@@ -420,9 +610,9 @@ pub mod pallet {
             CreditsBalance::<T>::mutate(&bounded_device_id, |balance| {
                 *balance = balance.saturating_sub(amount);
             });
-            AccountCredits::<T>::mutate(&who, |balance| {
-                *balance = balance.saturating_add(amount);
-            });
+            if !amount.is_zero() {
+                T::CreditAsset::mint(&who, amount)?;
+            }
 
             Self::deposit_event(Event::CreditsWithdrawn {
                 device_id: bounded_device_id,
@@ -433,24 +623,140 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Update the emission factor (governance function).
+        /// Announce a new emission factor (governance function).
+        ///
+        /// The factor is stored as pending and applied by `on_initialize` at
+        /// `current_block + FactorDelay`. Announcing again replaces any
+        /// pending change and restarts the delay.
         ///
         /// # Arguments
         ///
-        /// - `origin` - Root origin required
+        /// - `origin` - AdminOrigin required
         /// - `new_factor` - New emission factor (kg CO2/kWh, scaled by 1000)
         #[pallet::call_index(4)]
-        #[pallet::weight(T::WeightInfo::set_emission_factor())]
-        pub fn set_emission_factor(origin: OriginFor<T>, new_factor: u32) -> DispatchResult {
-            ensure_root(origin)?;
+        #[pallet::weight(T::WeightInfo::announce_emission_factor())]
+        pub fn announce_emission_factor(origin: OriginFor<T>, new_factor: u32) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
             ensure!(new_factor > 0, Error::<T>::InvalidEmissionFactor);
 
-            let old_factor = EmissionFactor::<T>::get();
-            EmissionFactor::<T>::put(new_factor);
+            let activation_block =
+                <frame_system::Pallet<T>>::block_number().saturating_add(T::FactorDelay::get());
+            PendingEmissionFactor::<T>::put((new_factor, activation_block));
 
-            Self::deposit_event(Event::EmissionFactorUpdated {
-                old_factor,
+            Self::deposit_event(Event::EmissionFactorAnnounced {
                 new_factor,
+                activation_block,
+            });
+
+            Ok(())
+        }
+
+        /// Cancel a pending emission factor change (governance function).
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - AdminOrigin required
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::cancel_pending_factor())]
+        pub fn cancel_pending_factor(origin: OriginFor<T>) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let (cancelled_factor, _) =
+                PendingEmissionFactor::<T>::take().ok_or(Error::<T>::NoPendingFactor)?;
+
+            Self::deposit_event(Event::PendingEmissionFactorCancelled { cancelled_factor });
+
+            Ok(())
+        }
+
+        /// Correct a device's lifetime `TotalEnergy` (governance function).
+        ///
+        /// Used when reprocessed telemetry shows energy was double-counted or
+        /// missed. The old and new totals and the reason are appended to the
+        /// device's `AdjustmentLog`. The total may not drop below the energy
+        /// already claimed as credits.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - AdminOrigin required
+        /// - `device_id` - The device identifier
+        /// - `new_total` - Corrected lifetime energy in watt-hours
+        /// - `reason` - Why the figure is corrected
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::adjust_total_energy())]
+        pub fn adjust_total_energy(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            new_total: u128,
+            reason: BoundedVec<u8, T::MaxAdjustmentReasonLength>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            ensure!(
+                new_total >= ClaimedEnergy::<T>::get(&bounded_device_id),
+                Error::<T>::AdjustmentBelowClaimed
+            );
+
+            let old_total = TotalEnergy::<T>::get(&bounded_device_id);
+            TotalEnergy::<T>::insert(&bounded_device_id, new_total);
+            AdjustmentLog::<T>::mutate(&bounded_device_id, |log| {
+                if log.is_full() {
+                    log.remove(0);
+                }
+                // Cannot fail: a slot was freed above if the log was full
+                let _ = log.try_push(EnergyAdjustment {
+                    old_total,
+                    new_total,
+                    reason: reason.clone(),
+                    block_number: <frame_system::Pallet<T>>::block_number().saturated_into(),
+                });
+            });
+
+            Self::deposit_device_event(
+                &bounded_device_id,
+                Event::TotalEnergyAdjusted {
+                    device_id: bounded_device_id.clone(),
+                    old_total,
+                    new_total,
+                    reason,
+                },
+            );
+
+            Ok(())
+        }
+
+        /// Retire credits held by the caller's account.
+        ///
+        /// The credits' `CreditAsset` units are burned from the caller, who
+        /// may have withdrawn them or received them by transfer, and they are
+        /// added to `TotalCreditsRetired`.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Signed origin (account retiring credits)
+        /// - `amount` - Number of credits to retire
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::retire_credits())]
+        pub fn retire_credits(origin: OriginFor<T>, amount: u128) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::NoCreditsAvailable);
+
+            ensure!(
+                T::CreditAsset::balance(&who) >= amount,
+                Error::<T>::InsufficientCredits
+            );
+
+            TotalCreditsRetired::<T>::mutate(|total| {
+                *total = total.saturating_add(amount);
+            });
+            T::CreditAsset::burn(&who, amount)?;
+
+            Self::deposit_event(Event::CreditsRetired {
+                account: who,
+                amount,
             });
 
             Ok(())
@@ -459,26 +765,250 @@ pub mod pallet {
 
     // Public query functions
     impl<T: Config> Pallet<T> {
+        /// Convert a device's accumulated energy into credits on its balance.
+        ///
+        /// Returns the credits issued. The caller checks the device status.
+        fn do_claim_credits(device_id: &DeviceId) -> Result<u128, DispatchError> {
+            let ClaimPreview {
+                energy_wh: accumulated,
+                co2_kg: co2_avoided_kg,
+                credits,
+                meets_minimum,
+            } = Self::claim_preview(device_id);
+            ensure!(!accumulated.is_zero(), Error::<T>::NoCreditsAvailable);
+            ensure!(meets_minimum, Error::<T>::EnergyBelowMinimum);
+            ensure!(!credits.is_zero(), Error::<T>::NoCreditsAvailable);
+
+            // Update balances
+            CreditsBalance::<T>::mutate(device_id, |balance| {
+                *balance = balance.saturating_add(credits);
+            });
+            TotalCreditsIssued::<T>::mutate(|total| {
+                *total = total.saturating_add(credits);
+            });
+            TotalCO2Avoided::<T>::mutate(|total| {
+                *total = total.saturating_add(co2_avoided_kg);
+            });
+            IssuanceCount::<T>::mutate(device_id, |count| {
+                *count = count.saturating_add(1);
+            });
+
+            ClaimedEnergy::<T>::mutate(device_id, |claimed| {
+                *claimed = claimed.saturating_add(accumulated);
+            });
+
+            // Reset accumulated energy
+            EnergyAccumulated::<T>::insert(device_id, 0u128);
+
+            Self::deposit_device_event(
+                device_id,
+                Event::CreditsClaimed {
+                    device_id: device_id.clone(),
+                    credits,
+                    energy_wh: accumulated,
+                    co2_avoided_kg,
+                },
+            );
+
+            Ok(credits)
+        }
+
+        /// Accumulate `energy_wh` for an active device, optionally backed by
+        /// the device's proof at `proof_index`.
+        fn do_record_energy(
+            device_id: DeviceId,
+            energy_wh: u128,
+            proof_index: Option<u64>,
+        ) -> DispatchResult {
+            Self::ensure_active(&device_id)?;
+
+            match proof_index {
+                Some(index) => {
+                    ensure!(
+                        T::ProofProvider::proof_exists(&device_id, index),
+                        Error::<T>::ProofNotFound
+                    );
+                    let recorded =
+                        ProofEnergy::<T>::get(&device_id, index).saturating_add(energy_wh);
+                    Self::ensure_plausible(&device_id, index, recorded)?;
+                    ProofEnergy::<T>::insert(&device_id, index, recorded);
+                }
+                None => Self::charge_unproven(&device_id, energy_wh)?,
+            }
+
+            // Track if this is a new device
+            let was_zero = EnergyAccumulated::<T>::get(&device_id).is_zero()
+                && TotalEnergy::<T>::get(&device_id).is_zero();
+
+            // Update accumulated energy (pending)
+            let new_accumulated = EnergyAccumulated::<T>::mutate(&device_id, |total| {
+                *total = total.saturating_add(energy_wh);
+                *total
+            });
+
+            // Update total lifetime energy
+            TotalEnergy::<T>::mutate(&device_id, |total| {
+                *total = total.saturating_add(energy_wh);
+            });
+
+            // Increment active device count if new
+            if was_zero {
+                ActiveDeviceCount::<T>::mutate(|count| *count += 1);
+            }
+
+            LatestEnergyRecord::<T>::insert(
+                &device_id,
+                EnergyRecord {
+                    energy_wh,
+                    block_number: frame_system::Pallet::<T>::block_number().saturated_into(),
+                    proof_index,
+                },
+            );
+
+            Self::deposit_device_event(
+                &device_id,
+                Event::EnergyRecorded {
+                    device_id: device_id.clone(),
+                    energy_wh,
+                    total_accumulated: new_accumulated,
+                },
+            );
+
+            Ok(())
+        }
+
+        /// Deposit `event` indexed under the device's topic
+        /// ([`DeviceId::topic`]) so clients can filter by device.
+        fn deposit_device_event(device_id: &DeviceId, event: Event<T>) {
+            let mut topic = T::Hash::default();
+            let bytes = device_id.topic();
+            let len = topic.as_ref().len().min(bytes.len());
+            topic.as_mut()[..len].copy_from_slice(&bytes[..len]);
+
+            let event: <T as frame_system::Config>::RuntimeEvent =
+                <T as Config>::RuntimeEvent::from(event).into();
+            frame_system::Pallet::<T>::deposit_event_indexed(&[topic], event);
+        }
+
+        /// Ensure the device registry reports the device as active.
+        fn ensure_active(device_id: &DeviceId) -> DispatchResult {
+            ensure!(
+                T::Devices::status(device_id) == Some(DeviceStatus::Active),
+                Error::<T>::DeviceNotActive
+            );
+            Ok(())
+        }
+
+        /// Ensure the device registry lists `who` as the device's owner.
+        fn ensure_owner(device_id: &DeviceId, who: &T::AccountId) -> DispatchResult {
+            ensure!(
+                T::Devices::owner(device_id).as_ref() == Some(who),
+                Error::<T>::NotDeviceOwner
+            );
+            Ok(())
+        }
+
+        /// Ensure `energy_wh`, all energy recorded against the proof at
+        /// `proof_index`, does not exceed the device's maximum power drawn
+        /// over the proof's whole window.
+        fn ensure_plausible(
+            device_id: &DeviceId,
+            proof_index: u64,
+            energy_wh: u128,
+        ) -> DispatchResult {
+            let (Some(max_power_w), Some((window_start, window_end))) = (
+                T::Devices::max_power(device_id),
+                T::ProofProvider::proof_window(device_id, proof_index),
+            ) else {
+                return Ok(());
+            };
+            let elapsed_secs = window_end.saturating_sub(window_start);
+            let max_energy_wh = (max_power_w as u128).saturating_mul(elapsed_secs as u128) / 3600;
+            ensure!(energy_wh <= max_energy_wh, Error::<T>::EnergyImplausible);
+            Ok(())
+        }
+
+        /// Add `energy_wh` recorded without a proof to the device's
+        /// `UnprovenEnergy`, after draining it at the device's maximum power
+        /// for the time since its last update. The result may not exceed
+        /// `UnprovenEnergyPeriod` seconds at that power.
+        fn charge_unproven(device_id: &DeviceId, energy_wh: u128) -> DispatchResult {
+            let Some(max_power_w) = T::Devices::max_power(device_id) else {
+                return Ok(());
+            };
+            let now = T::TimeProvider::now().as_secs();
+            let (outstanding, updated_at) = UnprovenEnergy::<T>::get(device_id);
+            let covered_wh =
+                (max_power_w as u128).saturating_mul(now.saturating_sub(updated_at) as u128) / 3600;
+            let allowance_wh =
+                (max_power_w as u128).saturating_mul(T::UnprovenEnergyPeriod::get() as u128) / 3600;
+            let outstanding = outstanding
+                .saturating_sub(covered_wh)
+                .saturating_add(energy_wh);
+            ensure!(outstanding <= allowance_wh, Error::<T>::EnergyImplausible);
+            // Less than a watt-hour of elapsed time keeps accruing
+            let updated_at = if covered_wh.is_zero() {
+                updated_at
+            } else {
+                now
+            };
+            UnprovenEnergy::<T>::insert(device_id, (outstanding, updated_at));
+            Ok(())
+        }
+
+        /// Credits held by an account: its `CreditAsset` balance.
+        pub fn account_credits(who: &T::AccountId) -> u128 {
+            T::CreditAsset::balance(who)
+        }
+
         /// Get total credits for a device.
-        pub fn get_device_credits(device_id: &BoundedVec<u8, T::MaxDeviceIdLength>) -> u128 {
+        pub fn get_device_credits(device_id: &DeviceId) -> u128 {
             CreditsBalance::<T>::get(device_id)
         }
 
         /// Get pending energy (not yet converted to credits).
-        pub fn get_pending_energy(device_id: &BoundedVec<u8, T::MaxDeviceIdLength>) -> u128 {
+        pub fn get_pending_energy(device_id: &DeviceId) -> u128 {
             EnergyAccumulated::<T>::get(device_id)
         }
 
         /// Calculate credits that would be issued for given energy.
         pub fn calculate_credits(energy_wh: u128) -> u128 {
+            Self::co2_and_credits(energy_wh).1
+        }
+
+        /// Preview claiming a device's accumulated energy.
+        ///
+        /// This is the computation `claim_credits` performs, without changing
+        /// state. It does not check the device's registry status.
+        pub fn claim_preview(device_id: &DeviceId) -> ClaimPreview {
+            let energy_wh = EnergyAccumulated::<T>::get(device_id);
+            let (co2_kg, credits) = Self::co2_and_credits(energy_wh);
+            ClaimPreview {
+                energy_wh,
+                co2_kg,
+                credits,
+                meets_minimum: !energy_wh.is_zero() && energy_wh >= T::MinClaimableEnergy::get(),
+            }
+        }
+
+        /// CO2 avoided, in kg, and the credits it earns for `energy_wh` at
+        /// the current emission factor.
+        fn co2_and_credits(energy_wh: u128) -> (u128, u128) {
+            // emission_factor is kg CO2 per kWh, scaled by 1000
             let emission_factor = EmissionFactor::<T>::get() as u128;
             let energy_kwh = energy_wh / 1000;
+
+            // co2_avoided_kg = energy_kwh * (emission_factor / 1000)
             let co2_avoided_kg = energy_kwh
                 .saturating_mul(emission_factor)
                 .saturating_div(1000);
-            co2_avoided_kg
+
+            // credits = (co2_avoided_kg / 1000) * credits_per_ton
+            let credits = co2_avoided_kg
                 .saturating_mul(T::CreditsPerTonCO2::get())
-                .saturating_div(1000)
+                .saturating_div(1000);
+
+            (co2_avoided_kg, credits)
         }
 
         /// Get statistics summary.
@@ -490,4 +1020,46 @@ pub mod pallet {
             )
         }
     }
+
+    /// Records the energy submitted with a telemetry proof against that
+    /// proof.
+    impl<T: Config> OnProofAccepted<DeviceId> for Pallet<T> {
+        fn on_proof_accepted(
+            device: &DeviceId,
+            proof_index: u64,
+            energy_wh: u128,
+        ) -> DispatchResult {
+            Self::do_record_energy(device.clone(), energy_wh, Some(proof_index))
+        }
+
+        fn weight() -> Weight {
+            T::WeightInfo::record_energy()
+        }
+    }
+
+    /// Claims a decommissioned device's claimable energy and sweeps its
+    /// credits to the owner's account.
+    impl<T: Config> OnDecommission<DeviceId, T::AccountId> for Pallet<T> {
+        fn on_decommission(
+            device: &DeviceId,
+            owner: &T::AccountId,
+        ) -> Result<DecommissionReport, DispatchError> {
+            if EnergyAccumulated::<T>::get(device) >= T::MinClaimableEnergy::get() {
+                // Energy too small to yield a credit stays accumulated
+                let _ = Self::do_claim_credits(device);
+            }
+            let credits_swept = CreditsBalance::<T>::take(device);
+            if !credits_swept.is_zero() {
+                T::CreditAsset::mint(owner, credits_swept)?;
+            }
+            Ok(DecommissionReport {
+                credits_swept,
+                proofs_scheduled: 0,
+            })
+        }
+
+        fn weight() -> Weight {
+            T::WeightInfo::claim_credits().saturating_add(T::DbWeight::get().reads_writes(4, 3))
+        }
+    }
 }
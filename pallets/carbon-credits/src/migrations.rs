@@ -0,0 +1,212 @@
+//! Storage migrations for the Carbon Credits pallet.
+
+use super::*;
+use frame_support::migrations::{MigrationId, SteppedMigration, SteppedMigrationError};
+use frame_support::pallet_prelude::*;
+use frame_support::traits::GetStorageVersion;
+use frame_support::weights::WeightMeter;
+#[cfg(feature = "try-runtime")]
+use {alloc::vec::Vec, sp_runtime::TryRuntimeError};
+
+/// Identifies this pallet's migrations to `pallet-migrations`
+const PALLET_MIGRATIONS_ID: &[u8; 14] = b"carbon-credits";
+
+/// Version 1: the first versioned layout, with `ClaimedEnergy` backfilled.
+pub mod v1 {
+    use super::*;
+
+    /// Brings the pallet under storage versioning and backfills
+    /// `ClaimedEnergy` for claims made before it was introduced.
+    ///
+    /// Energy recorded for a device is either still accumulated or was
+    /// claimed, so a device's claimed energy is its `TotalEnergy` minus its
+    /// `EnergyAccumulated`. That only holds while the total was never
+    /// corrected, so devices with an `AdjustmentLog` keep their
+    /// `ClaimedEnergy` as it is.
+    ///
+    /// Runs under `pallet-migrations`, a bounded number of devices per
+    /// block. The cursor is the last device visited.
+    pub struct MigrateToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> MigrateToV1<T> {
+        /// Weight of backfilling one device.
+        pub fn device_weight() -> Weight {
+            T::DbWeight::get().reads_writes(3, 1)
+        }
+    }
+
+    impl<T: Config> SteppedMigration for MigrateToV1<T> {
+        type Cursor = DeviceId;
+        type Identifier = MigrationId<14>;
+
+        fn id() -> Self::Identifier {
+            MigrationId {
+                pallet_id: *PALLET_MIGRATIONS_ID,
+                version_from: 0,
+                version_to: 1,
+            }
+        }
+
+        fn step(
+            mut cursor: Option<Self::Cursor>,
+            meter: &mut WeightMeter,
+        ) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+            // Reading and bumping the storage version
+            let overhead = T::DbWeight::get().reads_writes(1, 1);
+            let per_device = Self::device_weight();
+            let required = overhead.saturating_add(per_device);
+            if meter.remaining().any_lt(required) {
+                return Err(SteppedMigrationError::InsufficientWeight { required });
+            }
+            meter.consume(overhead);
+
+            if cursor.is_none() && Pallet::<T>::on_chain_storage_version() >= 1 {
+                return Ok(None);
+            }
+
+            let mut devices = match &cursor {
+                Some(device_id) => {
+                    TotalEnergy::<T>::iter_from(TotalEnergy::<T>::hashed_key_for(device_id))
+                }
+                None => TotalEnergy::<T>::iter(),
+            };
+            while meter.try_consume(per_device).is_ok() {
+                let Some((device_id, total)) = devices.next() else {
+                    StorageVersion::new(1).put::<Pallet<T>>();
+                    return Ok(None);
+                };
+                if !AdjustmentLog::<T>::contains_key(&device_id) {
+                    let claimed = total.saturating_sub(EnergyAccumulated::<T>::get(&device_id));
+                    ClaimedEnergy::<T>::insert(&device_id, claimed);
+                }
+                cursor = Some(device_id);
+            }
+
+            Ok(cursor)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            Ok((
+                TotalEnergy::<T>::iter_keys().count() as u64,
+                TotalCreditsIssued::<T>::get(),
+            )
+                .encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            let (devices, issued) = <(u64, u128)>::decode(&mut &state[..])
+                .map_err(|_| TryRuntimeError::Other("invalid pre-upgrade state"))?;
+            ensure!(
+                TotalEnergy::<T>::iter_keys().count() as u64 == devices,
+                TryRuntimeError::Other("devices with energy changed")
+            );
+            ensure!(
+                TotalCreditsIssued::<T>::get() == issued,
+                TryRuntimeError::Other("credits issued changed")
+            );
+            for (device_id, total) in TotalEnergy::<T>::iter() {
+                ensure!(
+                    ClaimedEnergy::<T>::get(&device_id) <= total,
+                    TryRuntimeError::Other("claimed energy exceeds total energy")
+                );
+            }
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() >= 1,
+                TryRuntimeError::Other("storage version not bumped")
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Version 2: account credits are held only in the credit token.
+pub mod v2 {
+    use super::*;
+    use frame_support::storage_alias;
+
+    /// Account credits as kept before version 2, alongside the token.
+    #[storage_alias]
+    pub type AccountCredits<T: Config> = StorageMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        u128,
+        ValueQuery,
+    >;
+
+    /// Removes `AccountCredits`, a bounded number of entries per block.
+    ///
+    /// Every credit it counted was minted to the same account as a
+    /// `CreditAsset` unit, which now holds the balance on its own. Entries
+    /// are drained from the front of the map, so the cursor only marks
+    /// that entries may remain.
+    pub struct MigrateToV2<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> MigrateToV2<T> {
+        /// Weight of removing one entry.
+        pub fn entry_weight() -> Weight {
+            T::DbWeight::get().reads_writes(1, 1)
+        }
+    }
+
+    impl<T: Config> SteppedMigration for MigrateToV2<T> {
+        type Cursor = ();
+        type Identifier = MigrationId<14>;
+
+        fn id() -> Self::Identifier {
+            MigrationId {
+                pallet_id: *PALLET_MIGRATIONS_ID,
+                version_from: 1,
+                version_to: 2,
+            }
+        }
+
+        fn step(
+            cursor: Option<Self::Cursor>,
+            meter: &mut WeightMeter,
+        ) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+            // Reading and bumping the storage version
+            let overhead = T::DbWeight::get().reads_writes(1, 1);
+            let per_entry = Self::entry_weight();
+            let required = overhead.saturating_add(per_entry);
+            if meter.remaining().any_lt(required) {
+                return Err(SteppedMigrationError::InsufficientWeight { required });
+            }
+            meter.consume(overhead);
+
+            if cursor.is_none() && Pallet::<T>::on_chain_storage_version() >= 2 {
+                return Ok(None);
+            }
+
+            let mut entries = AccountCredits::<T>::drain();
+            while meter.try_consume(per_entry).is_ok() {
+                if entries.next().is_none() {
+                    StorageVersion::new(2).put::<Pallet<T>>();
+                    return Ok(None);
+                }
+            }
+
+            Ok(Some(()))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            Ok(Vec::new())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure!(
+                AccountCredits::<T>::iter_keys().next().is_none(),
+                TryRuntimeError::Other("account credits not removed")
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() >= 2,
+                TryRuntimeError::Other("storage version not bumped")
+            );
+            Ok(())
+        }
+    }
+}
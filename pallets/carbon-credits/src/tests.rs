@@ -3,8 +3,7 @@
 use crate::{self as pallet_carbon_credits, *};
 use frame_support::{
     assert_noop, assert_ok,
-    traits::{ConstU128, ConstU32, ConstU64},
-    BoundedVec,
+    traits::{ConstU128, ConstU32, ConstU64, Hooks},
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -64,14 +63,116 @@ impl frame_support::traits::Get<u32> for DefaultEmissionFactor {
 impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
-    type MaxDeviceIdLength = ConstU32<64>;
+    type SubmitterOrigin = frame_system::EnsureSigned<u64>;
+    type FeelessSubmitters = frame_support::traits::IsInVec<FeelessAccounts>;
     type CreditsPerTonCO2 = ConstU128<1000>; // 1000 credits per ton
     type DefaultEmissionFactor = DefaultEmissionFactor;
     type MinClaimableEnergy = ConstU128<1000>; // 1 kWh minimum
     type MaxIssuanceRecords = ConstU32<1000>;
+    type FactorDelay = ConstU64<10>;
+    type ProofProvider = MockProofs;
+    type Devices = MockDevices;
+    type AdminOrigin = frame_system::EnsureRoot<u64>;
+    type MaxAdjustmentLog = ConstU32<2>;
+    type MaxAdjustmentReasonLength = ConstU32<32>;
+    type CreditAsset = MockCreditAsset;
+    type TimeProvider = MockTime;
+    type UnprovenEnergyPeriod = ConstU64<3600>;
 }
 
-fn new_test_ext() -> sp_io::TestExternalities {
+frame_support::parameter_types! {
+    /// Windows of the `EnergyUsage` proofs every device holds, by proof index
+    pub static ProofWindows: Vec<(u64, u64)> = vec![];
+    /// Power rating every device reports
+    pub static MaxPowerW: Option<u32> = None;
+    /// Units of the credit token minted and not burned
+    pub static CreditTokenSupply: u128 = 0;
+    /// Units of the credit token held by each account
+    pub static CreditTokenBalances: std::collections::BTreeMap<u64, u128> = Default::default();
+    /// Submitters whose energy records are feeless
+    pub static FeelessAccounts: Vec<u64> = vec![1];
+    /// Owner every device reports
+    pub static DeviceOwner: Option<u64> = Some(1);
+    /// Current unix time in seconds
+    pub static UnixNow: u64 = 0;
+}
+
+/// Clock reading `UnixNow`
+pub struct MockTime;
+
+impl frame_support::traits::UnixTime for MockTime {
+    fn now() -> core::time::Duration {
+        core::time::Duration::from_secs(UnixNow::get())
+    }
+}
+
+/// Proof provider serving `ProofWindows` for every device
+pub struct MockProofs;
+
+impl pallet_telemetry_proofs::ProofProvider<DeviceId> for MockProofs {
+    fn proof_exists(_device: &DeviceId, index: u64) -> bool {
+        (index as usize) < ProofWindows::get().len()
+    }
+
+    fn proof_window(_device: &DeviceId, index: u64) -> Option<(u64, u64)> {
+        ProofWindows::get().get(index as usize).copied()
+    }
+}
+
+/// Credit token tracking its supply in `CreditTokenSupply` and holdings in
+/// `CreditTokenBalances`
+pub struct MockCreditAsset;
+
+impl MockCreditAsset {
+    /// Move `amount` units from `from` to `to`, as an asset transfer would.
+    fn transfer(from: u64, to: u64, amount: u128) {
+        CreditTokenBalances::mutate(|balances| {
+            *balances.get_mut(&from).unwrap() -= amount;
+            *balances.entry(to).or_default() += amount;
+        });
+    }
+}
+
+impl CreditAsset<u64> for MockCreditAsset {
+    fn balance(who: &u64) -> u128 {
+        CreditTokenBalances::get()
+            .get(who)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn mint(who: &u64, amount: u128) -> frame_support::pallet_prelude::DispatchResult {
+        CreditTokenSupply::mutate(|supply| *supply += amount);
+        CreditTokenBalances::mutate(|balances| *balances.entry(*who).or_default() += amount);
+        Ok(())
+    }
+
+    fn burn(who: &u64, amount: u128) -> frame_support::pallet_prelude::DispatchResult {
+        CreditTokenSupply::mutate(|supply| *supply -= amount);
+        CreditTokenBalances::mutate(|balances| *balances.get_mut(who).unwrap() -= amount);
+        Ok(())
+    }
+}
+
+/// Device registry reporting every device active, owned by `DeviceOwner`
+/// and rated at `MaxPowerW`
+pub struct MockDevices;
+
+impl pallet_device_registry::DeviceInterface<DeviceId, u64> for MockDevices {
+    fn status(_device: &DeviceId) -> Option<pallet_device_registry::DeviceStatus> {
+        Some(pallet_device_registry::DeviceStatus::Active)
+    }
+
+    fn owner(_device: &DeviceId) -> Option<u64> {
+        DeviceOwner::get()
+    }
+
+    fn max_power(_device: &DeviceId) -> Option<u32> {
+        MaxPowerW::get()
+    }
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
     let t = frame_system::GenesisConfig::<Test>::default()
         .build_storage()
         .unwrap();
@@ -82,6 +183,14 @@ fn device_id(id: &str) -> Vec<u8> {
     id.as_bytes().to_vec()
 }
 
+fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        let next = System::block_number() + 1;
+        System::set_block_number(next);
+        CarbonCredits::on_initialize(next);
+    }
+}
+
 #[test]
 fn record_energy_works() {
     new_test_ext().execute_with(|| {
@@ -94,10 +203,65 @@ fn record_energy_works() {
             None,
         ));
 
-        let bounded_dev_id: BoundedVec<u8, ConstU32<64>> = dev_id.try_into().unwrap();
+        let bounded_dev_id: DeviceId = dev_id.try_into().unwrap();
         assert_eq!(CarbonCredits::energy_accumulated(&bounded_dev_id), 5000);
         assert_eq!(CarbonCredits::total_energy(&bounded_dev_id), 5000);
         assert_eq!(CarbonCredits::active_device_count(), 1);
+        assert_eq!(
+            CarbonCredits::latest_energy_record(&bounded_dev_id),
+            Some(EnergyRecord {
+                energy_wh: 5000,
+                block_number: 0,
+                proof_index: None,
+            })
+        );
+    });
+}
+
+#[test]
+fn record_energy_requires_submitter_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            CarbonCredits::record_energy(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                1_000,
+                None,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn record_energy_is_feeless_for_listed_submitters() {
+    use frame_support::traits::CheckIfFeeless;
+
+    new_test_ext().execute_with(|| {
+        let call = RuntimeCall::CarbonCredits(Call::record_energy {
+            device_id: device_id("device-001"),
+            energy_wh: 1_000,
+            proof_index: None,
+        });
+        assert!(call.is_feeless(&RuntimeOrigin::signed(1)));
+        assert!(!call.is_feeless(&RuntimeOrigin::signed(2)));
+        // Not admitted by `SubmitterOrigin`
+        assert!(!call.is_feeless(&RuntimeOrigin::root()));
+    });
+}
+
+#[test]
+fn record_energy_rejects_unknown_proof() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            CarbonCredits::record_energy(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                5000,
+                Some(0),
+            ),
+            Error::<Test>::ProofNotFound
+        );
     });
 }
 
@@ -120,7 +284,7 @@ fn record_energy_accumulates() {
             None,
         ));
 
-        let bounded_dev_id: BoundedVec<u8, ConstU32<64>> = dev_id.try_into().unwrap();
+        let bounded_dev_id: DeviceId = dev_id.try_into().unwrap();
         assert_eq!(CarbonCredits::energy_accumulated(&bounded_dev_id), 5000);
         assert_eq!(CarbonCredits::total_energy(&bounded_dev_id), 5000);
 
@@ -129,6 +293,38 @@ fn record_energy_accumulates() {
     });
 }
 
+#[test]
+fn device_events_are_indexed_by_device_topic() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let dev_id = device_id("device-001");
+
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            10_000,
+            None,
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+        ));
+
+        let topic = H256(sp_io::hashing::blake2_256(b"device-001"));
+        let events = System::events();
+        assert!(matches!(
+            events[0].event,
+            RuntimeEvent::CarbonCredits(Event::EnergyRecorded { .. })
+        ));
+        assert!(matches!(
+            events[1].event,
+            RuntimeEvent::CarbonCredits(Event::CreditsClaimed { .. })
+        ));
+        assert!(events.iter().all(|record| record.topics == vec![topic]));
+        assert_eq!(System::event_topics(topic), vec![(1, 0), (1, 1)]);
+    });
+}
+
 #[test]
 fn claim_credits_works() {
     new_test_ext().execute_with(|| {
@@ -148,7 +344,7 @@ fn claim_credits_works() {
             dev_id.clone(),
         ));
 
-        let bounded_dev_id: BoundedVec<u8, ConstU32<64>> = dev_id.try_into().unwrap();
+        let bounded_dev_id: DeviceId = dev_id.try_into().unwrap();
 
         // Verify accumulated energy is reset
         assert_eq!(CarbonCredits::energy_accumulated(&bounded_dev_id), 0);
@@ -164,6 +360,60 @@ fn claim_credits_works() {
     });
 }
 
+#[test]
+fn claim_preview_matches_the_claim() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let mut device = 0;
+
+        for factor in [1, 700, 1_500, 12_345] {
+            EmissionFactor::<Test>::put(factor);
+            for energy_wh in [999, 1_000, 10_000, 123_456, 5_000_000] {
+                device += 1;
+                let dev_id = device_id(&format!("device-{device:03}"));
+                let bounded_dev_id: DeviceId = dev_id.clone().try_into().unwrap();
+                assert_ok!(CarbonCredits::record_energy(
+                    RuntimeOrigin::signed(1),
+                    dev_id.clone(),
+                    energy_wh,
+                    None,
+                ));
+
+                let preview = CarbonCredits::claim_preview(&bounded_dev_id);
+                assert_eq!(preview.energy_wh, energy_wh);
+                assert_eq!(preview.meets_minimum, energy_wh >= 1_000);
+
+                let claim = CarbonCredits::claim_credits(RuntimeOrigin::signed(1), dev_id);
+                if preview.meets_minimum && preview.credits > 0 {
+                    // The claim issues exactly what was previewed
+                    assert_ok!(claim);
+                    System::assert_last_event(
+                        Event::CreditsClaimed {
+                            device_id: bounded_dev_id.clone(),
+                            credits: preview.credits,
+                            energy_wh,
+                            co2_avoided_kg: preview.co2_kg,
+                        }
+                        .into(),
+                    );
+                    assert_eq!(
+                        CarbonCredits::credits_balance(&bounded_dev_id),
+                        preview.credits
+                    );
+                    assert_eq!(
+                        CarbonCredits::claim_preview(&bounded_dev_id),
+                        ClaimPreview::default()
+                    );
+                } else {
+                    // Nothing claimable: the claim fails and nothing changes
+                    assert!(claim.is_err());
+                    assert_eq!(CarbonCredits::claim_preview(&bounded_dev_id), preview);
+                }
+            }
+        }
+    });
+}
+
 #[test]
 fn claim_credits_requires_minimum_energy() {
     new_test_ext().execute_with(|| {
@@ -215,8 +465,8 @@ fn transfer_credits_works() {
             dev1.clone(),
         ));
 
-        let bounded_dev1: BoundedVec<u8, ConstU32<64>> = dev1.clone().try_into().unwrap();
-        let bounded_dev2: BoundedVec<u8, ConstU32<64>> = dev2.clone().try_into().unwrap();
+        let bounded_dev1: DeviceId = dev1.clone().try_into().unwrap();
+        let bounded_dev2: DeviceId = dev2.clone().try_into().unwrap();
 
         let initial_balance = CarbonCredits::credits_balance(&bounded_dev1);
         assert!(initial_balance > 0);
@@ -272,6 +522,7 @@ fn withdraw_credits_works() {
     new_test_ext().execute_with(|| {
         let dev_id = device_id("device-001");
         let account: u64 = 42;
+        DeviceOwner::set(Some(account));
 
         // Record and claim
         assert_ok!(CarbonCredits::record_energy(
@@ -285,7 +536,7 @@ fn withdraw_credits_works() {
             dev_id.clone(),
         ));
 
-        let bounded_dev_id: BoundedVec<u8, ConstU32<64>> = dev_id.clone().try_into().unwrap();
+        let bounded_dev_id: DeviceId = dev_id.clone().try_into().unwrap();
         let device_balance = CarbonCredits::credits_balance(&bounded_dev_id);
 
         // Withdraw to account
@@ -300,42 +551,236 @@ fn withdraw_credits_works() {
             CarbonCredits::credits_balance(&bounded_dev_id),
             device_balance - withdraw_amount
         );
-        assert_eq!(CarbonCredits::account_credits(account), withdraw_amount);
+        assert_eq!(CarbonCredits::account_credits(&account), withdraw_amount);
+        assert_eq!(CreditTokenSupply::get(), withdraw_amount);
+    });
+}
+
+#[test]
+fn only_the_device_owner_moves_its_credits() {
+    new_test_ext().execute_with(|| {
+        let dev1 = device_id("device-001");
+        let dev2 = device_id("device-002");
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            dev1.clone(),
+            100_000,
+            None,
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(1),
+            dev1.clone(),
+        ));
+        let bounded_dev1: DeviceId = dev1.clone().try_into().unwrap();
+        let balance = CarbonCredits::credits_balance(&bounded_dev1);
+
+        // Account 1 owns every device; account 2 cannot move the credits
+        assert_noop!(
+            CarbonCredits::withdraw_credits(RuntimeOrigin::signed(2), dev1.clone(), balance),
+            Error::<Test>::NotDeviceOwner
+        );
+        assert_noop!(
+            CarbonCredits::transfer_credits(
+                RuntimeOrigin::signed(2),
+                dev1.clone(),
+                dev2.clone(),
+                balance,
+            ),
+            Error::<Test>::NotDeviceOwner
+        );
+        // Nor can anyone while the registry knows no owner
+        DeviceOwner::set(None);
+        assert_noop!(
+            CarbonCredits::withdraw_credits(RuntimeOrigin::signed(1), dev1, balance),
+            Error::<Test>::NotDeviceOwner
+        );
+
+        assert_eq!(CarbonCredits::credits_balance(&bounded_dev1), balance);
+        assert_eq!(CarbonCredits::account_credits(&2), 0);
+        assert_eq!(CreditTokenSupply::get(), 0);
     });
 }
 
 #[test]
-fn set_emission_factor_works() {
+fn retire_credits_burns_token_units() {
     new_test_ext().execute_with(|| {
-        let initial_factor = CarbonCredits::emission_factor();
-        assert_eq!(initial_factor, 1500); // Default
+        System::set_block_number(1);
+        let dev_id = device_id("device-001");
+        let account: u64 = 42;
+        DeviceOwner::set(Some(account));
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            100_000,
+            None,
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+        ));
+        // 150 credits from 100 kWh
+        assert_ok!(CarbonCredits::withdraw_credits(
+            RuntimeOrigin::signed(account),
+            dev_id,
+            150,
+        ));
 
-        // Update factor (requires root)
-        assert_ok!(CarbonCredits::set_emission_factor(
+        assert_noop!(
+            CarbonCredits::retire_credits(RuntimeOrigin::signed(account), 151),
+            Error::<Test>::InsufficientCredits
+        );
+        assert_noop!(
+            CarbonCredits::retire_credits(RuntimeOrigin::signed(account), 0),
+            Error::<Test>::NoCreditsAvailable
+        );
+
+        assert_ok!(CarbonCredits::retire_credits(
+            RuntimeOrigin::signed(account),
+            40
+        ));
+        System::assert_last_event(
+            Event::CreditsRetired {
+                account,
+                amount: 40,
+            }
+            .into(),
+        );
+        assert_eq!(CarbonCredits::account_credits(&account), 110);
+        assert_eq!(CarbonCredits::total_credits_retired(), 40);
+
+        // Units received by transfer are retired by their new holder
+        MockCreditAsset::transfer(account, 43, 30);
+        assert_noop!(
+            CarbonCredits::retire_credits(RuntimeOrigin::signed(account), 81),
+            Error::<Test>::InsufficientCredits
+        );
+        assert_ok!(CarbonCredits::retire_credits(RuntimeOrigin::signed(43), 30));
+        assert_eq!(CarbonCredits::account_credits(&account), 80);
+        assert_eq!(CarbonCredits::account_credits(&43), 0);
+        assert_eq!(CarbonCredits::total_credits_retired(), 70);
+        assert_eq!(
+            CreditTokenSupply::get(),
+            CarbonCredits::total_credits_issued() - CarbonCredits::total_credits_retired()
+        );
+    });
+}
+
+#[test]
+fn announce_emission_factor_applies_after_delay() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_eq!(CarbonCredits::emission_factor(), 1500); // Default
+
+        // Announce factor (requires root), active at block 1 + 10
+        assert_ok!(CarbonCredits::announce_emission_factor(
             RuntimeOrigin::root(),
             2000, // 2.0 kg CO2/kWh
         ));
+        assert_eq!(CarbonCredits::pending_emission_factor(), Some((2000, 11)));
+        assert_eq!(CarbonCredits::emission_factor(), 1500);
+
+        run_to_block(10);
+        assert_eq!(CarbonCredits::emission_factor(), 1500);
 
+        run_to_block(11);
         assert_eq!(CarbonCredits::emission_factor(), 2000);
+        assert!(CarbonCredits::pending_emission_factor().is_none());
+        System::assert_last_event(
+            Event::EmissionFactorUpdated {
+                old_factor: 1500,
+                new_factor: 2000,
+            }
+            .into(),
+        );
     });
 }
 
 #[test]
-fn set_emission_factor_requires_root() {
+fn claims_use_old_factor_until_activation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let dev_id = device_id("device-001");
+        let bounded_dev_id: DeviceId = dev_id.clone().try_into().unwrap();
+
+        assert_ok!(CarbonCredits::announce_emission_factor(
+            RuntimeOrigin::root(),
+            3000,
+        ));
+
+        // Claim before activation: 10 kWh * 1.5 = 15 credits
+        run_to_block(5);
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            10_000,
+            None,
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+        ));
+        assert_eq!(CarbonCredits::credits_balance(&bounded_dev_id), 15);
+
+        // Claim after activation: 10 kWh * 3.0 = 30 credits
+        run_to_block(11);
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            10_000,
+            None,
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(1),
+            dev_id
+        ));
+        assert_eq!(CarbonCredits::credits_balance(&bounded_dev_id), 45);
+    });
+}
+
+#[test]
+fn cancel_pending_factor_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(CarbonCredits::announce_emission_factor(
+            RuntimeOrigin::root(),
+            2000,
+        ));
+        assert_ok!(CarbonCredits::cancel_pending_factor(RuntimeOrigin::root()));
+        assert!(CarbonCredits::pending_emission_factor().is_none());
+
+        // Activation block passes without any change
+        run_to_block(20);
+        assert_eq!(CarbonCredits::emission_factor(), 1500);
+
+        // Nothing left to cancel
+        assert_noop!(
+            CarbonCredits::cancel_pending_factor(RuntimeOrigin::root()),
+            Error::<Test>::NoPendingFactor
+        );
+    });
+}
+
+#[test]
+fn announce_emission_factor_requires_root() {
     new_test_ext().execute_with(|| {
         // Non-root should fail
         assert_noop!(
-            CarbonCredits::set_emission_factor(RuntimeOrigin::signed(1), 2000,),
+            CarbonCredits::announce_emission_factor(RuntimeOrigin::signed(1), 2000,),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            CarbonCredits::cancel_pending_factor(RuntimeOrigin::signed(1)),
             sp_runtime::DispatchError::BadOrigin
         );
     });
 }
 
 #[test]
-fn set_emission_factor_rejects_zero() {
+fn announce_emission_factor_rejects_zero() {
     new_test_ext().execute_with(|| {
         assert_noop!(
-            CarbonCredits::set_emission_factor(RuntimeOrigin::root(), 0,),
+            CarbonCredits::announce_emission_factor(RuntimeOrigin::root(), 0,),
             Error::<Test>::InvalidEmissionFactor
         );
     });
@@ -407,7 +852,7 @@ fn total_energy_persists_after_claim() {
             dev_id.clone(),
         ));
 
-        let bounded_dev_id: BoundedVec<u8, ConstU32<64>> = dev_id.clone().try_into().unwrap();
+        let bounded_dev_id: DeviceId = dev_id.clone().try_into().unwrap();
 
         // Accumulated should be 0, but total should remain
         assert_eq!(CarbonCredits::energy_accumulated(&bounded_dev_id), 0);
@@ -425,3 +870,358 @@ fn total_energy_persists_after_claim() {
         assert_eq!(CarbonCredits::total_energy(&bounded_dev_id), 15_000);
     });
 }
+
+#[test]
+fn record_energy_rejects_more_than_rated_power_allows() {
+    new_test_ext().execute_with(|| {
+        // One hour-long proof window, a 500 W stove
+        ProofWindows::set(vec![(1_000, 4_600)]);
+        MaxPowerW::set(Some(500));
+
+        assert_noop!(
+            CarbonCredits::record_energy(
+                RuntimeOrigin::signed(1),
+                b"device-001".to_vec(),
+                501,
+                Some(0)
+            ),
+            Error::<Test>::EnergyImplausible
+        );
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            b"device-001".to_vec(),
+            300,
+            Some(0)
+        ));
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            b"device-001".to_vec(),
+            200,
+            Some(0)
+        ));
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(CarbonCredits::proof_energy(&bounded_dev_id, 0), 500);
+
+        // The proof's window is used up
+        assert_noop!(
+            CarbonCredits::record_energy(
+                RuntimeOrigin::signed(1),
+                b"device-001".to_vec(),
+                1,
+                Some(0)
+            ),
+            Error::<Test>::EnergyImplausible
+        );
+
+        // Unless the rating is unknown
+        MaxPowerW::set(None);
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            b"device-001".to_vec(),
+            5_000,
+            Some(0)
+        ));
+    });
+}
+
+#[test]
+fn record_energy_without_proof_is_bounded_by_rated_power() {
+    new_test_ext().execute_with(|| {
+        // A 500 W stove may run an hour ahead of the clock
+        MaxPowerW::set(Some(500));
+        let record = |energy_wh: u128| {
+            CarbonCredits::record_energy(
+                RuntimeOrigin::signed(1),
+                b"device-001".to_vec(),
+                energy_wh,
+                None,
+            )
+        };
+
+        assert_ok!(record(500));
+        assert_noop!(record(1), Error::<Test>::EnergyImplausible);
+
+        // 36 seconds at 500 W cover 5 Wh
+        UnixNow::set(36);
+        assert_ok!(record(5));
+        assert_noop!(record(1), Error::<Test>::EnergyImplausible);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(CarbonCredits::unproven_energy(&bounded_dev_id), (500, 36));
+
+        // An idle hour restores the full allowance, and no more
+        UnixNow::set(7_236);
+        assert_noop!(record(501), Error::<Test>::EnergyImplausible);
+        assert_ok!(record(500));
+
+        // Devices without a known rating are not bounded
+        MaxPowerW::set(None);
+        assert_ok!(record(5_000));
+        assert_eq!(CarbonCredits::total_energy(&bounded_dev_id), 6_005);
+    });
+}
+
+#[test]
+fn decommission_claims_energy_and_sweeps_credits_to_owner() {
+    use pallet_device_registry::{DecommissionReport, OnDecommission};
+
+    new_test_ext().execute_with(|| {
+        let dev_id = device_id("device-001");
+        let bounded_dev_id: DeviceId = dev_id.clone().try_into().unwrap();
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            10_000,
+            None,
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(1),
+            dev_id.clone()
+        ));
+        assert_ok!(CarbonCredits::withdraw_credits(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            3
+        ));
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            5_000,
+            None,
+        ));
+
+        // 12 credits left on the device plus 7 from the final 5 kWh claim
+        assert_eq!(
+            <CarbonCredits as OnDecommission<DeviceId, u64>>::on_decommission(&bounded_dev_id, &2),
+            Ok(DecommissionReport {
+                credits_swept: 19,
+                proofs_scheduled: 0,
+            })
+        );
+        assert_eq!(CarbonCredits::credits_balance(&bounded_dev_id), 0);
+        assert_eq!(CarbonCredits::energy_accumulated(&bounded_dev_id), 0);
+        assert_eq!(CarbonCredits::account_credits(&2), 19);
+        assert_eq!(CarbonCredits::account_credits(&1), 3);
+        assert_eq!(CarbonCredits::total_credits_issued(), 22);
+        assert_eq!(CreditTokenSupply::get(), 22);
+        assert_eq!(CarbonCredits::issuance_count(&bounded_dev_id), 2);
+
+        // Energy below the claim minimum stays accumulated
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            dev_id,
+            500,
+            None,
+        ));
+        assert_eq!(
+            <CarbonCredits as OnDecommission<DeviceId, u64>>::on_decommission(&bounded_dev_id, &2),
+            Ok(DecommissionReport::default())
+        );
+        assert_eq!(CarbonCredits::energy_accumulated(&bounded_dev_id), 500);
+        assert_eq!(CarbonCredits::account_credits(&2), 19);
+    });
+}
+
+#[test]
+fn adjust_total_energy_corrects_both_ways_but_not_below_claimed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let dev_id = device_id("device-001");
+        let bounded_dev_id: DeviceId = dev_id.clone().try_into().unwrap();
+        let reason = |text: &str| -> frame_support::BoundedVec<u8, ConstU32<32>> {
+            text.as_bytes().to_vec().try_into().unwrap()
+        };
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            10_000,
+            None,
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(1),
+            dev_id.clone()
+        ));
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            3_000,
+            None,
+        ));
+        assert_eq!(CarbonCredits::claimed_energy(&bounded_dev_id), 10_000);
+        assert_eq!(CarbonCredits::total_energy(&bounded_dev_id), 13_000);
+
+        assert_noop!(
+            CarbonCredits::adjust_total_energy(
+                RuntimeOrigin::signed(1),
+                dev_id.clone(),
+                11_000,
+                reason("double count")
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        // Down: 2 kWh were counted twice
+        assert_ok!(CarbonCredits::adjust_total_energy(
+            RuntimeOrigin::root(),
+            dev_id.clone(),
+            11_000,
+            reason("double count")
+        ));
+        System::assert_last_event(
+            Event::TotalEnergyAdjusted {
+                device_id: bounded_dev_id.clone(),
+                old_total: 13_000,
+                new_total: 11_000,
+                reason: reason("double count"),
+            }
+            .into(),
+        );
+        assert_eq!(CarbonCredits::total_energy(&bounded_dev_id), 11_000);
+
+        // Up: a missed batch is restored
+        assert_ok!(CarbonCredits::adjust_total_energy(
+            RuntimeOrigin::root(),
+            dev_id.clone(),
+            12_500,
+            reason("missed batch")
+        ));
+        assert_eq!(CarbonCredits::total_energy(&bounded_dev_id), 12_500);
+
+        // Never below the energy already claimed as credits
+        assert_noop!(
+            CarbonCredits::adjust_total_energy(
+                RuntimeOrigin::root(),
+                dev_id.clone(),
+                9_999,
+                reason("too low")
+            ),
+            Error::<Test>::AdjustmentBelowClaimed
+        );
+        assert_ok!(CarbonCredits::adjust_total_energy(
+            RuntimeOrigin::root(),
+            dev_id,
+            10_000,
+            reason("exactly claimed")
+        ));
+
+        // The log keeps the latest `MaxAdjustmentLog` corrections
+        let log = CarbonCredits::adjustment_log(&bounded_dev_id);
+        assert_eq!(
+            log.iter()
+                .map(|entry| (entry.old_total, entry.new_total))
+                .collect::<Vec<_>>(),
+            vec![(11_000, 12_500), (12_500, 10_000)]
+        );
+        assert_eq!(log[0].reason, reason("missed batch"));
+        // Pending energy and credits are untouched
+        assert_eq!(CarbonCredits::energy_accumulated(&bounded_dev_id), 3_000);
+        assert_eq!(CarbonCredits::credits_balance(&bounded_dev_id), 15);
+    });
+}
+
+#[test]
+fn migrate_to_v1_backfills_claimed_energy() {
+    use crate::migrations::v1::MigrateToV1;
+    use frame_support::{
+        migrations::SteppedMigration,
+        traits::{GetStorageVersion, StorageVersion},
+        weights::WeightMeter,
+    };
+
+    new_test_ext().execute_with(|| {
+        let migrate = || {
+            let mut cursor = None;
+            loop {
+                cursor = MigrateToV1::<Test>::step(cursor, &mut WeightMeter::new()).unwrap();
+                if cursor.is_none() {
+                    break;
+                }
+            }
+        };
+        let record = |id: &str, energy_wh: u128| {
+            assert_ok!(CarbonCredits::record_energy(
+                RuntimeOrigin::signed(1),
+                device_id(id),
+                energy_wh,
+                None,
+            ));
+        };
+        let claim = |id: &str| {
+            assert_ok!(CarbonCredits::claim_credits(
+                RuntimeOrigin::signed(1),
+                device_id(id),
+            ));
+        };
+        let claimed =
+            |id: &str| CarbonCredits::claimed_energy(&DeviceId::try_from(device_id(id)).unwrap());
+
+        // device-001 claimed 10 kWh and has 3 kWh pending
+        record("device-001", 10_000);
+        claim("device-001");
+        record("device-001", 3_000);
+        // device-002 never claimed
+        record("device-002", 2_000);
+        // device-003 claimed, then had its total corrected
+        record("device-003", 10_000);
+        claim("device-003");
+        assert_ok!(CarbonCredits::adjust_total_energy(
+            RuntimeOrigin::root(),
+            device_id("device-003"),
+            12_000,
+            b"missed batch".to_vec().try_into().unwrap(),
+        ));
+
+        // Simulate the unversioned layout, from before `ClaimedEnergy`
+        let _ = ClaimedEnergy::<Test>::clear(u32::MAX, None);
+        StorageVersion::new(0).put::<CarbonCredits>();
+
+        migrate();
+        assert_eq!(CarbonCredits::on_chain_storage_version(), 1);
+        assert_eq!(claimed("device-001"), 10_000);
+        assert_eq!(claimed("device-002"), 0);
+        // A corrected total no longer tells what was claimed
+        assert_eq!(claimed("device-003"), 0);
+
+        // Running the migration again is a no-op and recomputes nothing
+        let bounded_dev_id: DeviceId = device_id("device-002").try_into().unwrap();
+        ClaimedEnergy::<Test>::insert(&bounded_dev_id, 1);
+        let before = ClaimedEnergy::<Test>::iter().collect::<Vec<_>>();
+        migrate();
+        assert_eq!(ClaimedEnergy::<Test>::iter().collect::<Vec<_>>(), before);
+        assert_eq!(claimed("device-002"), 1);
+    });
+}
+
+#[test]
+fn migrate_to_v2_removes_account_credits() {
+    use crate::migrations::v2::{AccountCredits, MigrateToV2};
+    use frame_support::{
+        migrations::SteppedMigration,
+        traits::{GetStorageVersion, StorageVersion},
+        weights::WeightMeter,
+    };
+
+    new_test_ext().execute_with(|| {
+        for account in 1..=3u64 {
+            AccountCredits::<Test>::insert(account, 10 * account as u128);
+        }
+        StorageVersion::new(1).put::<CarbonCredits>();
+
+        let mut cursor = None;
+        loop {
+            cursor = MigrateToV2::<Test>::step(cursor, &mut WeightMeter::new()).unwrap();
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(AccountCredits::<Test>::iter().count(), 0);
+        assert_eq!(CarbonCredits::on_chain_storage_version(), 2);
+
+        // Running it again leaves new state alone
+        AccountCredits::<Test>::insert(1, 5);
+        assert_eq!(
+            MigrateToV2::<Test>::step(None, &mut WeightMeter::new()),
+            Ok(None)
+        );
+        assert_eq!(AccountCredits::<Test>::get(1), 5);
+    });
+}
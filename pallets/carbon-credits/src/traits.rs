@@ -0,0 +1,55 @@
+//! Interfaces the Carbon Credits pallet uses to reach other pallets.
+
+use core::marker::PhantomData;
+use frame_support::{
+    pallet_prelude::DispatchResult,
+    traits::{
+        fungible,
+        tokens::{Fortitude, Precision, Preservation},
+    },
+};
+
+/// Token holding account credits.
+///
+/// Credits withdrawn or swept to an account are minted as token units and
+/// retired credits are burned, so the token's supply is the account credits
+/// issued and not yet retired. An account's credits are its token balance,
+/// wherever the units were transferred from.
+pub trait CreditAsset<AccountId> {
+    /// Units held by `who`.
+    fn balance(who: &AccountId) -> u128;
+
+    /// Mint `amount` units to `who`.
+    fn mint(who: &AccountId, amount: u128) -> DispatchResult;
+
+    /// Burn `amount` units held by `who`.
+    fn burn(who: &AccountId, amount: u128) -> DispatchResult;
+}
+
+/// Holds credits in a `fungible` token, e.g. one `pallet-assets` asset
+/// selected with `fungible::ItemOf`.
+pub struct FungibleCreditAsset<F>(PhantomData<F>);
+
+impl<AccountId, F> CreditAsset<AccountId> for FungibleCreditAsset<F>
+where
+    F: fungible::Mutate<AccountId, Balance = u128>,
+{
+    fn balance(who: &AccountId) -> u128 {
+        <F as fungible::Inspect<AccountId>>::balance(who)
+    }
+
+    fn mint(who: &AccountId, amount: u128) -> DispatchResult {
+        F::mint_into(who, amount).map(|_| ())
+    }
+
+    fn burn(who: &AccountId, amount: u128) -> DispatchResult {
+        F::burn_from(
+            who,
+            amount,
+            Preservation::Expendable,
+            Precision::Exact,
+            Fortitude::Polite,
+        )
+        .map(|_| ())
+    }
+}
@@ -19,8 +19,17 @@ pub trait WeightInfo {
     /// Weight for withdrawing credits to account.
     fn withdraw_credits() -> Weight;
 
-    /// Weight for setting emission factor.
-    fn set_emission_factor() -> Weight;
+    /// Weight for announcing an emission factor change.
+    fn announce_emission_factor() -> Weight;
+
+    /// Weight for cancelling a pending emission factor change.
+    fn cancel_pending_factor() -> Weight;
+
+    /// Weight for correcting a device's lifetime energy.
+    fn adjust_total_energy() -> Weight;
+
+    /// Weight for retiring account credits.
+    fn retire_credits() -> Weight;
 }
 
 /// Default weight implementation.
@@ -32,22 +41,28 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     /// Weight for recording energy.
     ///
     /// Operations:
+    /// - Read device status
+    /// - Read the device's power rating and proof window
+    /// - Read and write the proof's recorded energy, or the time and the
+    ///   device's unproven energy
     /// - Read accumulated energy
     /// - Read total energy
     /// - Check if new device
     /// - Write accumulated energy
     /// - Write total energy
     /// - Potentially increment device count
+    /// - Write latest energy record
     /// - Emit event
     fn record_energy() -> Weight {
         Weight::from_parts(40_000_000, 0)
-            .saturating_add(T::DbWeight::get().reads(3))
-            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().reads(9))
+            .saturating_add(T::DbWeight::get().writes(5))
     }
 
     /// Weight for claiming credits.
     ///
     /// Operations:
+    /// - Read device status
     /// - Read accumulated energy
     /// - Read emission factor
     /// - Calculate CO2 and credits
@@ -55,12 +70,13 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     /// - Update total credits issued
     /// - Update total CO2 avoided
     /// - Update issuance count
+    /// - Update claimed energy
     /// - Reset accumulated energy
     /// - Emit event
     fn claim_credits() -> Weight {
         Weight::from_parts(60_000_000, 0)
-            .saturating_add(T::DbWeight::get().reads(2))
-            .saturating_add(T::DbWeight::get().writes(5))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(6))
     }
 
     /// Weight for transferring credits.
@@ -81,25 +97,59 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     /// Operations:
     /// - Read device balance
     /// - Write device balance
-    /// - Write account balance
+    /// - Mint credit token (asset details and account)
     /// - Emit event
     fn withdraw_credits() -> Weight {
-        Weight::from_parts(35_000_000, 0)
-            .saturating_add(T::DbWeight::get().reads(1))
-            .saturating_add(T::DbWeight::get().writes(2))
+        Weight::from_parts(50_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    /// Weight for announcing an emission factor change.
+    ///
+    /// Operations:
+    /// - Write pending factor
+    /// - Emit event
+    fn announce_emission_factor() -> Weight {
+        Weight::from_parts(15_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
     }
 
-    /// Weight for setting emission factor.
+    /// Weight for cancelling a pending emission factor change.
     ///
     /// Operations:
-    /// - Read current factor
-    /// - Write new factor
+    /// - Take pending factor
     /// - Emit event
-    fn set_emission_factor() -> Weight {
+    fn cancel_pending_factor() -> Weight {
         Weight::from_parts(15_000_000, 0)
             .saturating_add(T::DbWeight::get().reads(1))
             .saturating_add(T::DbWeight::get().writes(1))
     }
+
+    /// Weight for correcting a device's lifetime energy.
+    ///
+    /// Operations:
+    /// - Read claimed energy
+    /// - Read and write total energy
+    /// - Read and write the adjustment log
+    /// - Emit event
+    fn adjust_total_energy() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Weight for retiring account credits.
+    ///
+    /// Operations:
+    /// - Read the account's credit token balance
+    /// - Read and write total credits retired
+    /// - Burn credit token (asset details and account)
+    /// - Emit event
+    fn retire_credits() -> Weight {
+        Weight::from_parts(50_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
 }
 
 /// Unit implementation for testing.
@@ -120,7 +170,19 @@ impl WeightInfo for () {
         Weight::from_parts(10_000, 0)
     }
 
-    fn set_emission_factor() -> Weight {
+    fn announce_emission_factor() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn cancel_pending_factor() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn adjust_total_energy() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn retire_credits() -> Weight {
         Weight::from_parts(10_000, 0)
     }
 }
@@ -0,0 +1,20 @@
+//! # Device Registry Runtime API
+//!
+//! Lets indexers and RPC clients query the device registry without
+//! decoding raw storage keys. Implemented by the runtime on top of the
+//! public helpers of `pallet-device-registry`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// Read access to the device registry.
+    pub trait DeviceRegistryApi {
+        /// Registered, unretired devices counted in the geohash area
+        /// `prefix` (a geohash's first 6 characters); 0 for longer prefixes.
+        fn devices_in_area(prefix: Vec<u8>) -> u32;
+    }
+}
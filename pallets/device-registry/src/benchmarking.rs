@@ -0,0 +1,288 @@
+//! Benchmarking setup for pallet-device-registry
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use alloc::{vec, vec::Vec};
+use frame_benchmarking::v2::*;
+use frame_support::{
+    traits::{fungible::Mutate, Get},
+    BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_runtime::traits::Saturating;
+
+/// Register a device owned by `owner` without holding a deposit.
+fn register<T: Config>(owner: &T::AccountId) -> DeviceId {
+    let device_id: DeviceId = vec![0u8; ared_primitives::MAX_DEVICE_ID_LENGTH as usize]
+        .try_into()
+        .unwrap();
+    Devices::<T>::insert(
+        &device_id,
+        DeviceInfo {
+            owner: owner.clone(),
+            depositor: owner.clone(),
+            metadata: DeviceMetadata {
+                model: BoundedVec::new(),
+                firmware_hash: H256::repeat_byte(1),
+                location_hash: H256::repeat_byte(2),
+                attestation_hash: None,
+                geohash: Some(geohash()),
+            },
+            deposit: 0u32.into(),
+            registered_at: 0u32.into(),
+            status: DeviceStatus::Active,
+            metadata_version: 0,
+        },
+    );
+    OwnerDevices::<T>::insert(owner, &device_id, ());
+    DevicesByArea::<T>::insert(AreaPrefix::truncate_from(geohash().into_inner()), 1);
+    device_id
+}
+
+/// Add a model with the longest description to the model registry.
+fn insert_model<T: Config>(model_id: &[u8]) {
+    DeviceModels::<T>::insert(
+        BoundedVec::<u8, T::MaxModelLength>::try_from(model_id.to_vec()).unwrap(),
+        DeviceModel::<T> {
+            max_power_w: 2_000,
+            description: vec![0u8; T::MaxModelDescriptionLength::get() as usize]
+                .try_into()
+                .unwrap(),
+        },
+    );
+}
+
+/// Longest valid geohash.
+fn geohash() -> Geohash {
+    vec![b'u'; 12].try_into().unwrap()
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn register_device() {
+        let caller: T::AccountId = whitelisted_caller();
+        let deposit = T::RegistrationDeposit::get();
+        T::NativeBalance::set_balance(&caller, deposit + deposit);
+        let device_id = vec![0u8; ared_primitives::MAX_DEVICE_ID_LENGTH as usize];
+        let metadata = DeviceMetadata::<T> {
+            model: vec![0u8; T::MaxModelLength::get() as usize]
+                .try_into()
+                .unwrap(),
+            firmware_hash: H256::repeat_byte(1),
+            location_hash: H256::repeat_byte(2),
+            attestation_hash: Some(H256::repeat_byte(3)),
+            geohash: Some(geohash()),
+        };
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            device_id.clone(),
+            metadata,
+        );
+
+        let device_id: DeviceId = device_id.try_into().unwrap();
+        assert_eq!(Pallet::<T>::device_owner(&device_id), Some(caller));
+    }
+
+    #[benchmark]
+    fn register_devices_batch(n: Linear<1, { T::MaxBatchSize::get() }>) {
+        let caller: T::AccountId = whitelisted_caller();
+        let owner: T::AccountId = account("owner", 0, 0);
+        let deposit = T::RegistrationDeposit::get();
+        T::NativeBalance::set_balance(&caller, deposit.saturating_mul((n + 1).into()) + deposit);
+        let entries: Vec<_> = (0..n)
+            .map(|i| {
+                let mut device_id = vec![0u8; ared_primitives::MAX_DEVICE_ID_LENGTH as usize];
+                device_id[..4].copy_from_slice(&i.to_le_bytes());
+                let metadata = DeviceMetadata::<T> {
+                    model: vec![0u8; T::MaxModelLength::get() as usize]
+                        .try_into()
+                        .unwrap(),
+                    firmware_hash: H256::repeat_byte(1),
+                    location_hash: H256::repeat_byte(2),
+                    attestation_hash: None,
+                    geohash: Some(geohash()),
+                };
+                (device_id, metadata, owner.clone())
+            })
+            .collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), entries);
+
+        assert_eq!(OwnerDevices::<T>::iter_prefix(&owner).count() as u32, n);
+    }
+
+    #[benchmark]
+    fn transfer_device() {
+        let caller: T::AccountId = whitelisted_caller();
+        let new_owner: T::AccountId = account("new_owner", 0, 0);
+        let device_id = register::<T>(&caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), device_id.to_vec(), new_owner);
+    }
+
+    #[benchmark]
+    fn accept_device() {
+        let owner: T::AccountId = account("owner", 0, 0);
+        let caller: T::AccountId = whitelisted_caller();
+        let device_id = register::<T>(&owner);
+        PendingTransfers::<T>::insert(&device_id, &caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), device_id.to_vec());
+
+        assert_eq!(Pallet::<T>::device_owner(&device_id), Some(caller));
+    }
+
+    #[benchmark]
+    fn update_device_metadata() {
+        let caller: T::AccountId = whitelisted_caller();
+        let device_id = register::<T>(&caller);
+        let history: BoundedVec<H256, T::MaxMetadataHistory> =
+            vec![H256::zero(); T::MaxMetadataHistory::get() as usize]
+                .try_into()
+                .unwrap();
+        MetadataHistory::<T>::insert(&device_id, history);
+        let metadata = DeviceMetadata::<T> {
+            model: vec![0u8; T::MaxModelLength::get() as usize]
+                .try_into()
+                .unwrap(),
+            firmware_hash: H256::repeat_byte(3),
+            location_hash: H256::repeat_byte(4),
+            attestation_hash: None,
+            // Relocate to another area
+            geohash: Some(vec![b'0'; 12].try_into().unwrap()),
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), device_id.to_vec(), metadata);
+
+        assert_eq!(Devices::<T>::get(&device_id).unwrap().metadata_version, 1);
+    }
+
+    #[benchmark]
+    fn set_device_status() {
+        let caller: T::AccountId = whitelisted_caller();
+        let device_id = register::<T>(&caller);
+        PendingTransfers::<T>::insert(&device_id, &caller);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            device_id.to_vec(),
+            DeviceStatus::Retired,
+        );
+
+        assert_eq!(
+            Pallet::<T>::device_status(&device_id),
+            Some(DeviceStatus::Retired)
+        );
+    }
+
+    #[benchmark]
+    fn submit_attestation() -> Result<(), BenchmarkError> {
+        let owner: T::AccountId = account("owner", 0, 0);
+        let device_id = register::<T>(&owner);
+        let origin = T::ManufacturerOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            device_id.to_vec(),
+            H256::repeat_byte(3),
+        );
+
+        assert!(Pallet::<T>::verify_attestation(
+            &device_id,
+            &H256::repeat_byte(3)
+        ));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn check_attestation() {
+        let caller: T::AccountId = whitelisted_caller();
+        let device_id = register::<T>(&caller);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            device_id.to_vec(),
+            H256::repeat_byte(3),
+        );
+    }
+
+    #[benchmark]
+    fn create_model() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let model_id = vec![0u8; T::MaxModelLength::get() as usize];
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            model_id,
+            2_000,
+            vec![0u8; T::MaxModelDescriptionLength::get() as usize],
+        );
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn update_model() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let model_id = vec![0u8; T::MaxModelLength::get() as usize];
+        insert_model::<T>(&model_id);
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            model_id,
+            3_000,
+            vec![0u8; T::MaxModelDescriptionLength::get() as usize],
+        );
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn remove_model() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let model_id = vec![0u8; T::MaxModelLength::get() as usize];
+        insert_model::<T>(&model_id);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, model_id);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn decommission_device() {
+        let caller: T::AccountId = whitelisted_caller();
+        let device_id = register::<T>(&caller);
+        PendingTransfers::<T>::insert(&device_id, &caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), device_id.to_vec());
+
+        assert_eq!(
+            Pallet::<T>::device_status(&device_id),
+            Some(DeviceStatus::Retired)
+        );
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Test);
+}
@@ -0,0 +1,1210 @@
+//! # Device Registry Pallet
+//!
+//! This pallet gives ARED edge devices an on-chain identity before they
+//! report telemetry.
+//!
+//! ## Overview
+//!
+//! The Device Registry pallet enables:
+//! - Registering a device id with its model, firmware and location
+//! - Enforcing that a device id is registered only once
+//! - Registering many devices in one batch for program rollouts
+//! - Holding a registration deposit from the registering account
+//! - Transferring device ownership, optionally with a two-step accept
+//! - Tracking each device's lifecycle status
+//! - Letting other pallets read status and owner through [`DeviceInterface`]
+//! - Anchoring manufacturing attestation hashes for hardware audits
+//! - Versioning metadata updates with a bounded history of past metadata
+//! - Counting devices per geohash area for sampling audits
+//! - Keeping a registry of stove models and their maximum power ratings
+//! - Letting other pallets check registration through [`DeviceRegistry`]
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `register_device` - Register a device and its metadata (any signed account)
+//! - `register_devices_batch` - Register devices for several owners at once (any signed account)
+//! - `transfer_device` - Transfer or offer a device to a new owner (device owner)
+//! - `accept_device` - Accept a pending device transfer (offered account)
+//! - `set_device_status` - Move a device through its lifecycle (owner or AdminOrigin)
+//! - `submit_attestation` - Anchor a device's attestation hash (ManufacturerOrigin or AdminOrigin)
+//! - `check_attestation` - Verify an attestation hash and emit the result (any signed account)
+//! - `update_device_metadata` - Replace a device's model, firmware and location (device owner)
+//! - `create_model` - Add a stove model and its maximum power rating (AdminOrigin)
+//! - `update_model` - Change a stove model's rating and description (AdminOrigin)
+//! - `remove_model` - Remove a stove model (AdminOrigin)
+//! - `decommission_device` - Retire a device and wind down its state in other pallets (owner or AdminOrigin)
+//!
+//! ## Deposits
+//!
+//! Registering a device holds `RegistrationDeposit` from the caller
+//! (`HoldReason::RegistrationDeposit`), paying for the storage the
+//! registration occupies. Devices registered at genesis carry no deposit.
+//! A batch registration holds one deposit from the caller covering every
+//! accepted entry.
+//! The deposit stays held from the account that paid it when the device
+//! changes hands.
+//!
+//! ## Ownership Transfer
+//!
+//! The owner of an active device may hand it to another account with
+//! `transfer_device`. When `RequireTransferAcceptance` is set the transfer
+//! is only recorded as pending and completes once the new owner calls
+//! `accept_device`, so devices cannot be sent to dead accounts by mistake.
+//! Suspended and retired devices cannot be transferred.
+//!
+//! ## Lifecycle
+//!
+//! Devices start out `Registered` (genesis devices start `Active`). The
+//! owner activates a registered device and may retire it at any time;
+//! `AdminOrigin` may additionally suspend and reinstate devices. `Retired`
+//! is final and no device returns to `Registered`. The carbon credits
+//! pallet only credits `Active` devices and the telemetry proofs pallet
+//! refuses proofs from `Retired` ones, both through [`DeviceInterface`].
+//!
+//! ## Decommissioning
+//!
+//! `decommission_device` retires a device in one transactional call. Before
+//! the status changes it runs the `OnDecommission` hooks of the other
+//! pallets: in the runtime the telemetry proofs pallet freezes the device
+//! and schedules its proofs for removal in `on_idle`, and the carbon credits
+//! pallet claims any claimable energy and sweeps the device's credits to the
+//! owner's account. If a hook fails, nothing changes. A single
+//! `DeviceDecommissioned` event reports the swept credits and the number of
+//! proofs scheduled for removal.
+//!
+//! ## Attestation
+//!
+//! A device's `attestation_hash` anchors the hash of its secure-element
+//! certificate so field audits can check hardware authenticity. It can be
+//! supplied at registration or later through `submit_attestation`, and once
+//! set only `AdminOrigin` may replace it. `verify_attestation` compares a
+//! hash against the anchored one.
+//!
+//! ## Metadata History
+//!
+//! Firmware upgrades and relocations are recorded with
+//! `update_device_metadata`. Each update bumps the device's
+//! `metadata_version` and appends the BLAKE2-256 hash of the replaced
+//! metadata to `MetadataHistory`, which keeps the latest
+//! `MaxMetadataHistory` hashes so auditors can detect silent changes.
+//! Updates keep the anchored attestation hash.
+//!
+//! ## Device Areas
+//!
+//! Devices may carry the geohash of their installation site. Geohashes must
+//! use the base32 geohash alphabet. `DevicesByArea` counts the registered,
+//! unretired devices under each geohash's first `AREA_PREFIX_LENGTH`
+//! characters (shorter geohashes count under themselves); counts follow
+//! registrations, relocations through `update_device_metadata`, and
+//! retirements.
+//!
+//! ## Device Models
+//!
+//! A device's `model` names an entry in `DeviceModels`, which records the
+//! model's maximum power rating. [`DeviceInterface::max_power`] reports a
+//! registered device's rating, falling back to `DefaultMaxPowerW` for models
+//! missing from the registry; the carbon credits pallet uses it to bound
+//! recorded energy by the proof window's duration.
+//!
+//! ## Device Identity
+//!
+//! Device ids are the same byte strings the telemetry proofs and carbon
+//! credits pallets key their storage by. Those pallets take a
+//! [`DeviceRegistry`] as a Config type so they can require registration
+//! without reading this pallet's storage directly.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod traits;
+pub mod weights;
+pub use ared_primitives::DeviceId;
+pub use traits::{DecommissionReport, DeviceInterface, DeviceRegistry, OnDecommission};
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use alloc::vec::Vec;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::fungible::{self, MutateHold},
+    };
+    use frame_system::pallet_prelude::*;
+    use sp_core::H256;
+    use sp_runtime::traits::{BlakeTwo256, Hash, Saturating, Zero};
+
+    /// Balance type of the currency holding registration deposits
+    pub type DepositBalanceOf<T> = <<T as Config>::NativeBalance as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    /// Geohash of a device's installation site
+    pub type Geohash = BoundedVec<u8, ConstU32<12>>;
+
+    /// Geohash prefix identifying a device area
+    pub type AreaPrefix = BoundedVec<u8, ConstU32<AREA_PREFIX_LENGTH>>;
+
+    /// Number of geohash characters identifying a device area (about 1.2 km)
+    pub const AREA_PREFIX_LENGTH: u32 = 6;
+
+    /// Characters of the base32 geohash alphabet
+    const GEOHASH_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+    /// Descriptive metadata supplied when registering a device
+    #[derive(
+        CloneNoBound,
+        Encode,
+        Decode,
+        TypeInfo,
+        MaxEncodedLen,
+        RuntimeDebugNoBound,
+        PartialEqNoBound,
+        EqNoBound,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct DeviceMetadata<T: Config> {
+        /// Hardware model identifier
+        pub model: BoundedVec<u8, T::MaxModelLength>,
+        /// Hash of the firmware image the device runs
+        pub firmware_hash: H256,
+        /// Hash of the device's installation location, kept off-chain
+        pub location_hash: H256,
+        /// Hash of the secure-element certificate from manufacturing
+        pub attestation_hash: Option<H256>,
+        /// Geohash of the installation site
+        pub geohash: Option<Geohash>,
+    }
+
+    /// Batch registration entry: (device_id, metadata, owner)
+    pub type BatchDeviceEntry<T> = (
+        Vec<u8>,
+        DeviceMetadata<T>,
+        <T as frame_system::Config>::AccountId,
+    );
+
+    /// Why an entry of a batch registration was not stored
+    #[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+    pub enum BatchRejectReason {
+        /// The device id exceeded `MaxDeviceIdLength`
+        DeviceIdTooLong,
+        /// The device id is already registered, possibly earlier in the batch
+        AlreadyRegistered,
+        /// The geohash is empty or uses characters outside the geohash alphabet
+        InvalidGeohash,
+    }
+
+    /// Lifecycle status of a registered device
+    #[derive(
+        Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq, Default,
+    )]
+    pub enum DeviceStatus {
+        /// The device is registered but not yet in service
+        #[default]
+        Registered,
+        /// The device is in service
+        Active,
+        /// The device is temporarily out of service
+        Suspended,
+        /// The device has been permanently taken out of service
+        Retired,
+    }
+
+    /// A stove model in the model registry
+    #[derive(
+        CloneNoBound,
+        Encode,
+        Decode,
+        TypeInfo,
+        MaxEncodedLen,
+        RuntimeDebugNoBound,
+        PartialEqNoBound,
+        EqNoBound,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct DeviceModel<T: Config> {
+        /// Maximum power draw in watts
+        pub max_power_w: u32,
+        /// Human readable description of the model
+        pub description: BoundedVec<u8, T::MaxModelDescriptionLength>,
+    }
+
+    /// A registered device
+    #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq)]
+    #[scale_info(skip_type_params(T))]
+    pub struct DeviceInfo<T: Config> {
+        /// Account that currently owns the device
+        pub owner: T::AccountId,
+        /// Account the deposit is held from
+        pub depositor: T::AccountId,
+        /// Metadata supplied at registration
+        pub metadata: DeviceMetadata<T>,
+        /// Deposit held from the owner
+        pub deposit: DepositBalanceOf<T>,
+        /// Block at which the device was registered
+        pub registered_at: BlockNumberFor<T>,
+        /// Lifecycle status
+        pub status: DeviceStatus,
+        /// Number of metadata updates since registration
+        pub metadata_version: u32,
+    }
+
+    /// The pallet's configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+
+        /// Origin allowed to suspend, reinstate and retire any device and to
+        /// replace attestation hashes
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to anchor a device's first attestation hash
+        type ManufacturerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// The overarching hold reason.
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// Currency used to hold registration deposits
+        type NativeBalance: fungible::Mutate<Self::AccountId>
+            + fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// Deposit held from the registering account for every device
+        #[pallet::constant]
+        type RegistrationDeposit: Get<DepositBalanceOf<Self>>;
+
+        /// Maximum length of a device model identifier
+        #[pallet::constant]
+        type MaxModelLength: Get<u32>;
+
+        /// Maximum number of devices in one batch registration
+        #[pallet::constant]
+        type MaxBatchSize: Get<u32>;
+
+        /// Number of replaced metadata hashes kept per device
+        #[pallet::constant]
+        type MaxMetadataHistory: Get<u32>;
+
+        /// Maximum length of a device model description
+        #[pallet::constant]
+        type MaxModelDescriptionLength: Get<u32>;
+
+        /// Maximum power in watts assumed for models missing from `DeviceModels`
+        #[pallet::constant]
+        type DefaultMaxPowerW: Get<u32>;
+
+        /// Other pallets' part in `decommission_device`
+        type OnDecommission: OnDecommission<DeviceId, Self::AccountId>;
+
+        /// Whether transfers wait for the new owner to call `accept_device`
+        #[pallet::constant]
+        type RequireTransferAcceptance: Get<bool>;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Registered devices by device id
+    #[pallet::storage]
+    #[pallet::getter(fn devices)]
+    pub type Devices<T: Config> = StorageMap<_, Blake2_128Concat, DeviceId, DeviceInfo<T>>;
+
+    /// Devices owned by each account
+    #[pallet::storage]
+    pub type OwnerDevices<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, DeviceId, ()>;
+
+    /// Transfers waiting for the new owner's acceptance
+    #[pallet::storage]
+    #[pallet::getter(fn pending_transfer)]
+    pub type PendingTransfers<T: Config> = StorageMap<_, Blake2_128Concat, DeviceId, T::AccountId>;
+
+    /// Stove models by model id, the `model` of device metadata
+    #[pallet::storage]
+    #[pallet::getter(fn device_models)]
+    pub type DeviceModels<T: Config> =
+        StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxModelLength>, DeviceModel<T>>;
+
+    /// Hashes of each device's replaced metadata, oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn metadata_history)]
+    pub type MetadataHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        DeviceId,
+        BoundedVec<H256, T::MaxMetadataHistory>,
+        ValueQuery,
+    >;
+
+    /// Registered, unretired devices per geohash area
+    #[pallet::storage]
+    #[pallet::getter(fn devices_in_area)]
+    pub type DevicesByArea<T: Config> =
+        StorageMap<_, Blake2_128Concat, AreaPrefix, u32, ValueQuery>;
+
+    /// Number of registered devices
+    #[pallet::storage]
+    #[pallet::getter(fn device_count)]
+    pub type DeviceCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Devices registered from genesis: owner, device id, model,
+        /// firmware hash and location hash. No deposit is held for them.
+        pub devices: Vec<(T::AccountId, Vec<u8>, Vec<u8>, H256, H256)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for (owner, device_id, model, firmware_hash, location_hash) in &self.devices {
+                let device_id: DeviceId = device_id
+                    .clone()
+                    .try_into()
+                    .expect("genesis device id exceeds MaxDeviceIdLength");
+                let metadata = DeviceMetadata {
+                    model: model
+                        .clone()
+                        .try_into()
+                        .expect("genesis device model exceeds MaxModelLength"),
+                    firmware_hash: *firmware_hash,
+                    location_hash: *location_hash,
+                    attestation_hash: None,
+                    geohash: None,
+                };
+                assert!(
+                    !Devices::<T>::contains_key(&device_id),
+                    "device registered twice in genesis"
+                );
+                Devices::<T>::insert(
+                    &device_id,
+                    DeviceInfo {
+                        owner: owner.clone(),
+                        depositor: owner.clone(),
+                        metadata,
+                        deposit: Zero::zero(),
+                        registered_at: Zero::zero(),
+                        // Genesis devices are in service from the first block
+                        status: DeviceStatus::Active,
+                        metadata_version: 0,
+                    },
+                );
+                OwnerDevices::<T>::insert(owner, &device_id, ());
+                DeviceCount::<T>::mutate(|count| *count = count.saturating_add(1));
+            }
+        }
+    }
+
+    /// Reasons for the pallet placing holds on funds
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// Deposit for a device registration
+        RegistrationDeposit,
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A device was registered
+        DeviceRegistered {
+            device_id: DeviceId,
+            owner: T::AccountId,
+            deposit: DepositBalanceOf<T>,
+        },
+        /// An entry of a batch registration was not stored
+        BatchEntryRejected {
+            index: u32,
+            reason: BatchRejectReason,
+        },
+        /// Outcome summary of a batch registration
+        BatchRegistered {
+            depositor: T::AccountId,
+            submitted: u32,
+            accepted: u32,
+            rejected: u32,
+            deposit: DepositBalanceOf<T>,
+        },
+        /// A device transfer awaits acceptance by the new owner
+        DeviceTransferProposed {
+            device_id: DeviceId,
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+        /// A device changed owner
+        DeviceOwnershipTransferred {
+            device_id: DeviceId,
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+        /// A device moved to a new lifecycle status
+        DeviceStatusChanged {
+            device_id: DeviceId,
+            from: DeviceStatus,
+            to: DeviceStatus,
+        },
+        /// A device's attestation hash was anchored or replaced
+        AttestationSubmitted {
+            device_id: DeviceId,
+            attestation_hash: H256,
+            previous: Option<H256>,
+        },
+        /// A device's metadata was replaced
+        DeviceMetadataUpdated { device_id: DeviceId, version: u32 },
+        /// A stove model was added to the model registry
+        ModelCreated {
+            model_id: BoundedVec<u8, T::MaxModelLength>,
+            max_power_w: u32,
+        },
+        /// A stove model's rating or description changed
+        ModelUpdated {
+            model_id: BoundedVec<u8, T::MaxModelLength>,
+            max_power_w: u32,
+        },
+        /// A stove model was removed from the model registry
+        ModelRemoved {
+            model_id: BoundedVec<u8, T::MaxModelLength>,
+        },
+        /// An attestation hash was checked against a device's anchored one
+        AttestationVerified {
+            device_id: DeviceId,
+            attestation_hash: H256,
+            valid: bool,
+        },
+        /// A device was retired and its state in other pallets wound down
+        DeviceDecommissioned {
+            device_id: DeviceId,
+            owner: T::AccountId,
+            from: DeviceStatus,
+            credits_swept: u128,
+            proofs_scheduled: u64,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Device ID exceeds maximum length
+        DeviceIdTooLong,
+        /// The device id is already registered
+        DeviceAlreadyRegistered,
+        /// The device id is not registered
+        DeviceNotFound,
+        /// The caller does not own the device
+        NotDeviceOwner,
+        /// The device is suspended or retired
+        DeviceNotActive,
+        /// The device is already owned by the target account
+        TransferToSelf,
+        /// No transfer of the device is pending for the caller
+        NoPendingTransfer,
+        /// The device cannot move from its current status to the requested one
+        InvalidStatusTransition,
+        /// The device already has an attestation hash; only AdminOrigin may replace it
+        AttestationAlreadySet,
+        /// The batch has no entries
+        EmptyBatch,
+        /// The batch exceeds `MaxBatchSize`
+        BatchTooLarge,
+        /// The geohash is empty or uses characters outside the geohash alphabet
+        InvalidGeohash,
+        /// Model ID exceeds maximum length
+        ModelIdTooLong,
+        /// Model description exceeds maximum length
+        DescriptionTooLong,
+        /// The model is already in the model registry
+        ModelAlreadyExists,
+        /// The model is not in the model registry
+        ModelNotFound,
+        /// Maximum power ratings must be positive
+        ZeroPowerRating,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Register a device and its metadata.
+        ///
+        /// Holds `RegistrationDeposit` from the caller, who becomes the
+        /// device's owner. Each device id can be registered once.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Any signed account
+        /// - `device_id` - The device identifier (UUID format)
+        /// - `metadata` - Model, firmware hash and location hash of the device
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::register_device())]
+        pub fn register_device(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            metadata: DeviceMetadata<T>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            ensure!(
+                !Devices::<T>::contains_key(&bounded_device_id),
+                Error::<T>::DeviceAlreadyRegistered
+            );
+            ensure!(Self::valid_geohash(&metadata), Error::<T>::InvalidGeohash);
+
+            let deposit = T::RegistrationDeposit::get();
+            if !deposit.is_zero() {
+                T::NativeBalance::hold(&HoldReason::RegistrationDeposit.into(), &owner, deposit)?;
+            }
+
+            Self::insert_device(bounded_device_id, metadata, owner.clone(), owner, deposit);
+
+            Ok(())
+        }
+
+        /// Register devices for several owners at once.
+        ///
+        /// Entries whose id is too long or already registered are skipped
+        /// with a `BatchEntryRejected` event. The caller pays one deposit of
+        /// `RegistrationDeposit` per accepted entry, held in a single hold.
+        /// Charged for the batch length at dispatch and for the accepted
+        /// entries after.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Any signed account, paying the deposits
+        /// - `entries` - Vector of (device_id, metadata, owner)
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::register_devices_batch(
+            entries.len().try_into().unwrap_or(u32::MAX)
+        ))]
+        pub fn register_devices_batch(
+            origin: OriginFor<T>,
+            entries: Vec<BatchDeviceEntry<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let depositor = ensure_signed(origin)?;
+
+            // Compare lengths before narrowing so oversized batches cannot wrap
+            ensure!(
+                entries.len() <= T::MaxBatchSize::get() as usize,
+                Error::<T>::BatchTooLarge
+            );
+            ensure!(!entries.is_empty(), Error::<T>::EmptyBatch);
+
+            let batch_len = entries.len() as u32;
+            let deposit = T::RegistrationDeposit::get();
+
+            let mut accepted: u32 = 0;
+            for (index, (device_id, metadata, owner)) in entries.into_iter().enumerate() {
+                let reason = match DeviceId::try_from(device_id) {
+                    Err(_) => BatchRejectReason::DeviceIdTooLong,
+                    Ok(id) if Devices::<T>::contains_key(&id) => {
+                        BatchRejectReason::AlreadyRegistered
+                    }
+                    Ok(_) if !Self::valid_geohash(&metadata) => BatchRejectReason::InvalidGeohash,
+                    Ok(id) => {
+                        Self::insert_device(id, metadata, owner, depositor.clone(), deposit);
+                        accepted = accepted.saturating_add(1);
+                        continue;
+                    }
+                };
+                Self::deposit_event(Event::BatchEntryRejected {
+                    index: index as u32,
+                    reason,
+                });
+            }
+
+            // One hold covers every accepted entry; failing it undoes the batch
+            let total_deposit = deposit.saturating_mul(accepted.into());
+            if !total_deposit.is_zero() {
+                T::NativeBalance::hold(
+                    &HoldReason::RegistrationDeposit.into(),
+                    &depositor,
+                    total_deposit,
+                )?;
+            }
+
+            Self::deposit_event(Event::BatchRegistered {
+                depositor,
+                submitted: batch_len,
+                accepted,
+                rejected: batch_len.saturating_sub(accepted),
+                deposit: total_deposit,
+            });
+
+            Ok(
+                Some(<T as pallet::Config>::WeightInfo::register_devices_batch(
+                    accepted,
+                ))
+                .into(),
+            )
+        }
+
+        /// Transfer a device to a new owner.
+        ///
+        /// When `RequireTransferAcceptance` is set the transfer is recorded
+        /// as pending until `new_owner` calls `accept_device`; a later call
+        /// replaces the pending transfer. The registration deposit stays
+        /// held from the account that paid it.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - The device owner
+        /// - `device_id` - The device identifier
+        /// - `new_owner` - Account to receive the device
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_device())]
+        pub fn transfer_device(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            new_owner: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            let info = Devices::<T>::get(&bounded_device_id).ok_or(Error::<T>::DeviceNotFound)?;
+            ensure!(info.owner == who, Error::<T>::NotDeviceOwner);
+            ensure!(Self::is_mutable(info.status), Error::<T>::DeviceNotActive);
+            ensure!(new_owner != who, Error::<T>::TransferToSelf);
+
+            if T::RequireTransferAcceptance::get() {
+                PendingTransfers::<T>::insert(&bounded_device_id, &new_owner);
+                Self::deposit_event(Event::DeviceTransferProposed {
+                    device_id: bounded_device_id,
+                    from: who,
+                    to: new_owner,
+                });
+            } else {
+                Self::do_transfer(bounded_device_id, info, new_owner);
+            }
+
+            Ok(())
+        }
+
+        /// Accept a pending device transfer.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - The account the device was offered to
+        /// - `device_id` - The device identifier
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::accept_device())]
+        pub fn accept_device(origin: OriginFor<T>, device_id: Vec<u8>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            ensure!(
+                PendingTransfers::<T>::get(&bounded_device_id).as_ref() == Some(&who),
+                Error::<T>::NoPendingTransfer
+            );
+            let info = Devices::<T>::get(&bounded_device_id).ok_or(Error::<T>::DeviceNotFound)?;
+            ensure!(Self::is_mutable(info.status), Error::<T>::DeviceNotActive);
+
+            Self::do_transfer(bounded_device_id, info, who);
+
+            Ok(())
+        }
+
+        /// Move a device to a new lifecycle status.
+        ///
+        /// The owner may activate a `Registered` device and retire a device
+        /// at any time. `AdminOrigin` may make any transition except leaving
+        /// `Retired` or returning to `Registered`.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - The device owner or AdminOrigin
+        /// - `device_id` - The device identifier
+        /// - `status` - The new status
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_device_status())]
+        pub fn set_device_status(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            status: DeviceStatus,
+        ) -> DispatchResult {
+            let caller = match T::AdminOrigin::try_origin(origin) {
+                Ok(_) => None,
+                Err(origin) => Some(ensure_signed(origin)?),
+            };
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            let mut info =
+                Devices::<T>::get(&bounded_device_id).ok_or(Error::<T>::DeviceNotFound)?;
+            if let Some(who) = &caller {
+                ensure!(info.owner == *who, Error::<T>::NotDeviceOwner);
+            }
+            let from = info.status;
+            ensure!(
+                from != status
+                    && from != DeviceStatus::Retired
+                    && status != DeviceStatus::Registered,
+                Error::<T>::InvalidStatusTransition
+            );
+            if caller.is_some() {
+                ensure!(
+                    status == DeviceStatus::Retired
+                        || (from == DeviceStatus::Registered && status == DeviceStatus::Active),
+                    Error::<T>::InvalidStatusTransition
+                );
+            }
+
+            if status == DeviceStatus::Retired {
+                PendingTransfers::<T>::remove(&bounded_device_id);
+                Self::remove_from_area(&info.metadata);
+            }
+            info.status = status;
+            Devices::<T>::insert(&bounded_device_id, info);
+
+            Self::deposit_event(Event::DeviceStatusChanged {
+                device_id: bounded_device_id,
+                from,
+                to: status,
+            });
+
+            Ok(())
+        }
+
+        /// Anchor a device's manufacturing attestation hash.
+        ///
+        /// `ManufacturerOrigin` may set the hash once; `AdminOrigin` may also
+        /// replace an existing one.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - ManufacturerOrigin or AdminOrigin
+        /// - `device_id` - The device identifier
+        /// - `attestation_hash` - Hash of the device's secure-element certificate
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_attestation())]
+        pub fn submit_attestation(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            attestation_hash: H256,
+        ) -> DispatchResult {
+            let can_replace = match T::AdminOrigin::try_origin(origin) {
+                Ok(_) => true,
+                Err(origin) => {
+                    T::ManufacturerOrigin::ensure_origin(origin)?;
+                    false
+                }
+            };
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            let mut info =
+                Devices::<T>::get(&bounded_device_id).ok_or(Error::<T>::DeviceNotFound)?;
+            let previous = info.metadata.attestation_hash;
+            ensure!(
+                previous.is_none() || can_replace,
+                Error::<T>::AttestationAlreadySet
+            );
+
+            info.metadata.attestation_hash = Some(attestation_hash);
+            Devices::<T>::insert(&bounded_device_id, info);
+
+            Self::deposit_event(Event::AttestationSubmitted {
+                device_id: bounded_device_id,
+                attestation_hash,
+                previous,
+            });
+
+            Ok(())
+        }
+
+        /// Verify an attestation hash against a device's anchored one and
+        /// emit the result.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Any signed account
+        /// - `device_id` - The device identifier
+        /// - `attestation_hash` - The hash to check
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::check_attestation())]
+        pub fn check_attestation(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            attestation_hash: H256,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            let valid = Self::verify_attestation(&bounded_device_id, &attestation_hash);
+
+            Self::deposit_event(Event::AttestationVerified {
+                device_id: bounded_device_id,
+                attestation_hash,
+                valid,
+            });
+
+            Ok(())
+        }
+
+        /// Replace a device's metadata.
+        ///
+        /// Bumps the device's `metadata_version` and records the hash of
+        /// the replaced metadata in `MetadataHistory`, dropping the oldest
+        /// hash once `MaxMetadataHistory` are kept. The anchored attestation
+        /// hash is kept; only `submit_attestation` changes it.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - The device owner
+        /// - `device_id` - The device identifier
+        /// - `new_metadata` - The device's new model, firmware and location hashes
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::update_device_metadata())]
+        pub fn update_device_metadata(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            mut new_metadata: DeviceMetadata<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            let mut info =
+                Devices::<T>::get(&bounded_device_id).ok_or(Error::<T>::DeviceNotFound)?;
+            ensure!(info.owner == who, Error::<T>::NotDeviceOwner);
+            ensure!(Self::is_mutable(info.status), Error::<T>::DeviceNotActive);
+            ensure!(
+                Self::valid_geohash(&new_metadata),
+                Error::<T>::InvalidGeohash
+            );
+
+            new_metadata.attestation_hash = info.metadata.attestation_hash;
+            Self::remove_from_area(&info.metadata);
+            Self::add_to_area(&new_metadata);
+            let previous = core::mem::replace(&mut info.metadata, new_metadata);
+            MetadataHistory::<T>::mutate(&bounded_device_id, |history| {
+                if history.is_full() {
+                    history.remove(0);
+                }
+                // Cannot fail: a slot was freed above if the history was full
+                let _ = history.try_push(BlakeTwo256::hash_of(&previous));
+            });
+            info.metadata_version = info.metadata_version.saturating_add(1);
+            let version = info.metadata_version;
+            Devices::<T>::insert(&bounded_device_id, info);
+
+            Self::deposit_event(Event::DeviceMetadataUpdated {
+                device_id: bounded_device_id,
+                version,
+            });
+
+            Ok(())
+        }
+
+        /// Add a stove model to the model registry.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - AdminOrigin
+        /// - `model_id` - The model identifier devices name in their metadata
+        /// - `max_power_w` - Maximum power draw in watts
+        /// - `description` - Human readable description
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::create_model())]
+        pub fn create_model(
+            origin: OriginFor<T>,
+            model_id: Vec<u8>,
+            max_power_w: u32,
+            description: Vec<u8>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let (model_id, model) = Self::bound_model(model_id, max_power_w, description)?;
+            ensure!(
+                !DeviceModels::<T>::contains_key(&model_id),
+                Error::<T>::ModelAlreadyExists
+            );
+            DeviceModels::<T>::insert(&model_id, model);
+
+            Self::deposit_event(Event::ModelCreated {
+                model_id,
+                max_power_w,
+            });
+
+            Ok(())
+        }
+
+        /// Change a stove model's rating and description.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - AdminOrigin
+        /// - `model_id` - The model identifier
+        /// - `max_power_w` - Maximum power draw in watts
+        /// - `description` - Human readable description
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::update_model())]
+        pub fn update_model(
+            origin: OriginFor<T>,
+            model_id: Vec<u8>,
+            max_power_w: u32,
+            description: Vec<u8>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let (model_id, model) = Self::bound_model(model_id, max_power_w, description)?;
+            ensure!(
+                DeviceModels::<T>::contains_key(&model_id),
+                Error::<T>::ModelNotFound
+            );
+            DeviceModels::<T>::insert(&model_id, model);
+
+            Self::deposit_event(Event::ModelUpdated {
+                model_id,
+                max_power_w,
+            });
+
+            Ok(())
+        }
+
+        /// Remove a stove model from the model registry.
+        ///
+        /// Devices of the model fall back to `DefaultMaxPowerW`.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - AdminOrigin
+        /// - `model_id` - The model identifier
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_model())]
+        pub fn remove_model(origin: OriginFor<T>, model_id: Vec<u8>) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let model_id: BoundedVec<u8, T::MaxModelLength> = model_id
+                .try_into()
+                .map_err(|_| Error::<T>::ModelIdTooLong)?;
+            ensure!(
+                DeviceModels::<T>::take(&model_id).is_some(),
+                Error::<T>::ModelNotFound
+            );
+
+            Self::deposit_event(Event::ModelRemoved { model_id });
+
+            Ok(())
+        }
+
+        /// Retire a device and wind down its state in other pallets.
+        ///
+        /// Runs `T::OnDecommission` with the device's owner, so proof
+        /// submission is frozen, proofs are scheduled for removal and credits
+        /// are swept to the owner, and then retires the device. All of it
+        /// happens in this call or, on any error, none of it.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - The device owner or AdminOrigin
+        /// - `device_id` - The device identifier
+        #[pallet::call_index(11)]
+        #[pallet::weight(
+            <T as pallet::Config>::WeightInfo::decommission_device()
+                .saturating_add(T::OnDecommission::weight())
+        )]
+        pub fn decommission_device(origin: OriginFor<T>, device_id: Vec<u8>) -> DispatchResult {
+            let caller = match T::AdminOrigin::try_origin(origin) {
+                Ok(_) => None,
+                Err(origin) => Some(ensure_signed(origin)?),
+            };
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            let mut info =
+                Devices::<T>::get(&bounded_device_id).ok_or(Error::<T>::DeviceNotFound)?;
+            if let Some(who) = &caller {
+                ensure!(info.owner == *who, Error::<T>::NotDeviceOwner);
+            }
+            let from = info.status;
+            ensure!(
+                from != DeviceStatus::Retired,
+                Error::<T>::InvalidStatusTransition
+            );
+
+            let report = T::OnDecommission::on_decommission(&bounded_device_id, &info.owner)?;
+
+            PendingTransfers::<T>::remove(&bounded_device_id);
+            Self::remove_from_area(&info.metadata);
+            info.status = DeviceStatus::Retired;
+            let owner = info.owner.clone();
+            Devices::<T>::insert(&bounded_device_id, info);
+
+            Self::deposit_event(Event::DeviceDecommissioned {
+                device_id: bounded_device_id,
+                owner,
+                from,
+                credits_swept: report.credits_swept,
+                proofs_scheduled: report.proofs_scheduled,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Store a newly registered device whose deposit is already held.
+        fn insert_device(
+            device_id: DeviceId,
+            metadata: DeviceMetadata<T>,
+            owner: T::AccountId,
+            depositor: T::AccountId,
+            deposit: DepositBalanceOf<T>,
+        ) {
+            Self::add_to_area(&metadata);
+            Devices::<T>::insert(
+                &device_id,
+                DeviceInfo {
+                    owner: owner.clone(),
+                    depositor,
+                    metadata,
+                    deposit,
+                    registered_at: frame_system::Pallet::<T>::block_number(),
+                    status: DeviceStatus::Registered,
+                    metadata_version: 0,
+                },
+            );
+            OwnerDevices::<T>::insert(&owner, &device_id, ());
+            DeviceCount::<T>::mutate(|count| *count = count.saturating_add(1));
+
+            Self::deposit_event(Event::DeviceRegistered {
+                device_id,
+                owner,
+                deposit,
+            });
+        }
+
+        /// Validate and bound the arguments of a model registry call.
+        fn bound_model(
+            model_id: Vec<u8>,
+            max_power_w: u32,
+            description: Vec<u8>,
+        ) -> Result<(BoundedVec<u8, T::MaxModelLength>, DeviceModel<T>), DispatchError> {
+            let model_id: BoundedVec<u8, T::MaxModelLength> = model_id
+                .try_into()
+                .map_err(|_| Error::<T>::ModelIdTooLong)?;
+            ensure!(max_power_w > 0, Error::<T>::ZeroPowerRating);
+            let description = description
+                .try_into()
+                .map_err(|_| Error::<T>::DescriptionTooLong)?;
+            Ok((
+                model_id,
+                DeviceModel {
+                    max_power_w,
+                    description,
+                },
+            ))
+        }
+
+        /// Whether the metadata's geohash, if any, is non-empty and uses
+        /// only the geohash alphabet.
+        fn valid_geohash(metadata: &DeviceMetadata<T>) -> bool {
+            metadata.geohash.as_ref().map_or(true, |geohash| {
+                !geohash.is_empty() && geohash.iter().all(|c| GEOHASH_ALPHABET.contains(c))
+            })
+        }
+
+        /// Area counted for the metadata's geohash.
+        fn area_of(metadata: &DeviceMetadata<T>) -> Option<AreaPrefix> {
+            metadata
+                .geohash
+                .as_ref()
+                .map(|geohash| AreaPrefix::truncate_from(geohash.to_vec()))
+        }
+
+        /// Count a device in its metadata's area.
+        fn add_to_area(metadata: &DeviceMetadata<T>) {
+            if let Some(area) = Self::area_of(metadata) {
+                DevicesByArea::<T>::mutate(area, |count| *count = count.saturating_add(1));
+            }
+        }
+
+        /// Stop counting a device in its metadata's area.
+        fn remove_from_area(metadata: &DeviceMetadata<T>) {
+            if let Some(area) = Self::area_of(metadata) {
+                DevicesByArea::<T>::mutate_exists(area, |count| {
+                    *count = count
+                        .map(|count| count.saturating_sub(1))
+                        .filter(|count| *count > 0);
+                });
+            }
+        }
+
+        /// Whether a device in `status` may change owner or metadata.
+        fn is_mutable(status: DeviceStatus) -> bool {
+            matches!(status, DeviceStatus::Registered | DeviceStatus::Active)
+        }
+
+        /// Move a device to `new_owner`, updating the owner index and
+        /// dropping any pending transfer.
+        fn do_transfer(device_id: DeviceId, mut info: DeviceInfo<T>, new_owner: T::AccountId) {
+            let from = core::mem::replace(&mut info.owner, new_owner.clone());
+            OwnerDevices::<T>::remove(&from, &device_id);
+            OwnerDevices::<T>::insert(&new_owner, &device_id, ());
+            PendingTransfers::<T>::remove(&device_id);
+            Devices::<T>::insert(&device_id, info);
+
+            Self::deposit_event(Event::DeviceOwnershipTransferred {
+                device_id,
+                from,
+                to: new_owner,
+            });
+        }
+
+        /// Whether a device id is registered.
+        pub fn is_registered(device_id: &DeviceId) -> bool {
+            Devices::<T>::contains_key(device_id)
+        }
+
+        /// Lifecycle status of a device.
+        pub fn device_status(device_id: &DeviceId) -> Option<DeviceStatus> {
+            Devices::<T>::get(device_id).map(|info| info.status)
+        }
+
+        /// Maximum power in watts of a registered device's model, or
+        /// `DefaultMaxPowerW` when the model is not in `DeviceModels`.
+        pub fn max_power(device_id: &DeviceId) -> Option<u32> {
+            let info = Devices::<T>::get(device_id)?;
+            Some(
+                DeviceModels::<T>::get(&info.metadata.model)
+                    .map_or_else(T::DefaultMaxPowerW::get, |model| model.max_power_w),
+            )
+        }
+
+        /// Whether `attestation_hash` matches the device's anchored
+        /// attestation. Unregistered and unattested devices never match.
+        pub fn verify_attestation(device_id: &DeviceId, attestation_hash: &H256) -> bool {
+            Devices::<T>::get(device_id)
+                .and_then(|info| info.metadata.attestation_hash)
+                .is_some_and(|anchored| anchored == *attestation_hash)
+        }
+
+        /// Account that owns a device.
+        pub fn device_owner(device_id: &DeviceId) -> Option<T::AccountId> {
+            Devices::<T>::get(device_id).map(|info| info.owner)
+        }
+    }
+}
@@ -0,0 +1,1103 @@
+//! Unit tests for the Device Registry pallet.
+
+use crate::{self as pallet_device_registry, *};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{fungible::InspectHold, ConstU32, ConstU64},
+    BoundedVec,
+};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        DeviceRegistry: pallet_device_registry,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+    type RuntimeTask = ();
+    type SingleBlockMigrations = ();
+    type MultiBlockMigrator = ();
+    type PreInherents = ();
+    type PostInherents = ();
+    type PostTransactions = ();
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU64<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type RuntimeFreezeReason = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+}
+
+frame_support::parameter_types! {
+    pub static RequireTransferAcceptance: bool = false;
+    pub static DecommissionFails: bool = false;
+}
+
+/// Reports a fixed sweep, or fails when `DecommissionFails` is set
+pub struct MockDecommission;
+
+impl traits::OnDecommission<DeviceId, u64> for MockDecommission {
+    fn on_decommission(
+        _device: &DeviceId,
+        _owner: &u64,
+    ) -> Result<DecommissionReport, sp_runtime::DispatchError> {
+        if DecommissionFails::get() {
+            return Err(sp_runtime::DispatchError::Other("decommission failed"));
+        }
+        Ok(DecommissionReport {
+            credits_swept: 7,
+            proofs_scheduled: 3,
+        })
+    }
+
+    fn weight() -> frame_support::weights::Weight {
+        frame_support::weights::Weight::zero()
+    }
+}
+
+frame_support::ord_parameter_types! {
+    pub const Manufacturer: u64 = 5;
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type AdminOrigin = frame_system::EnsureRoot<u64>;
+    type ManufacturerOrigin = frame_system::EnsureSignedBy<Manufacturer, u64>;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type NativeBalance = Balances;
+    type RegistrationDeposit = ConstU64<10>;
+    type MaxModelLength = ConstU32<16>;
+    type MaxBatchSize = ConstU32<5>;
+    type MaxMetadataHistory = ConstU32<2>;
+    type MaxModelDescriptionLength = ConstU32<32>;
+    type DefaultMaxPowerW = ConstU32<1_500>;
+    type OnDecommission = MockDecommission;
+    type RequireTransferAcceptance = RequireTransferAcceptance;
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 100), (2, 100), (3, 5)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    pallet_device_registry::GenesisConfig::<Test> {
+        devices: vec![(
+            2,
+            b"genesis-device".to_vec(),
+            b"stove-v1".to_vec(),
+            H256::repeat_byte(1),
+            H256::repeat_byte(2),
+        )],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+fn device_id(id: &str) -> Vec<u8> {
+    id.as_bytes().to_vec()
+}
+
+fn bounded(id: &str) -> DeviceId {
+    device_id(id).try_into().unwrap()
+}
+
+fn metadata(model: &str) -> DeviceMetadata<Test> {
+    DeviceMetadata {
+        model: model.as_bytes().to_vec().try_into().unwrap(),
+        firmware_hash: H256::repeat_byte(3),
+        location_hash: H256::repeat_byte(4),
+        attestation_hash: None,
+        geohash: None,
+    }
+}
+
+#[test]
+fn register_device_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(DeviceRegistry::register_device(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            metadata("stove-v2"),
+        ));
+
+        let info = DeviceRegistry::devices(bounded("device-001")).unwrap();
+        assert_eq!(info.owner, 1);
+        assert_eq!(info.metadata, metadata("stove-v2"));
+        assert_eq!(info.deposit, 10);
+        assert_eq!(info.registered_at, 1);
+        assert_eq!(info.status, DeviceStatus::Registered);
+        assert_eq!(DeviceRegistry::device_count(), 2);
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::RegistrationDeposit.into(), &1),
+            10
+        );
+        System::assert_last_event(
+            Event::DeviceRegistered {
+                device_id: bounded("device-001"),
+                owner: 1,
+                deposit: 10,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn register_device_enforces_uniqueness() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(DeviceRegistry::register_device(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            metadata("stove-v2"),
+        ));
+        assert_noop!(
+            DeviceRegistry::register_device(
+                RuntimeOrigin::signed(2),
+                device_id("device-001"),
+                metadata("stove-v3"),
+            ),
+            Error::<Test>::DeviceAlreadyRegistered
+        );
+        // Genesis devices are registered too
+        assert_noop!(
+            DeviceRegistry::register_device(
+                RuntimeOrigin::signed(1),
+                device_id("genesis-device"),
+                metadata("stove-v2"),
+            ),
+            Error::<Test>::DeviceAlreadyRegistered
+        );
+    });
+}
+
+#[test]
+fn register_device_rejects_bad_input_and_unfunded_callers() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            DeviceRegistry::register_device(
+                RuntimeOrigin::signed(1),
+                vec![b'a'; 65],
+                metadata("stove-v2"),
+            ),
+            Error::<Test>::DeviceIdTooLong
+        );
+        assert_noop!(
+            DeviceRegistry::register_device(
+                RuntimeOrigin::none(),
+                device_id("device-001"),
+                metadata("stove-v2"),
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        // Account 3 cannot cover the deposit
+        assert!(DeviceRegistry::register_device(
+            RuntimeOrigin::signed(3),
+            device_id("device-001"),
+            metadata("stove-v2"),
+        )
+        .is_err());
+        assert!(!DeviceRegistry::is_registered(&bounded("device-001")));
+    });
+}
+
+#[test]
+fn genesis_devices_and_registry_trait() {
+    new_test_ext().execute_with(|| {
+        let info = DeviceRegistry::devices(bounded("genesis-device")).unwrap();
+        assert_eq!(info.owner, 2);
+        assert_eq!(info.deposit, 0);
+        assert_eq!(info.metadata.model.into_inner(), b"stove-v1".to_vec());
+        assert_eq!(DeviceRegistry::device_count(), 1);
+
+        assert!(
+            <DeviceRegistry as traits::DeviceRegistry<_>>::is_registered(&bounded(
+                "genesis-device"
+            ))
+        );
+        let unknown = bounded("device-404");
+        assert!(!<DeviceRegistry as traits::DeviceRegistry<_>>::is_registered(&unknown));
+        assert!(<() as traits::DeviceRegistry<_>>::is_registered(&unknown));
+    });
+}
+
+fn owns(who: u64, id: &str) -> bool {
+    OwnerDevices::<Test>::contains_key(who, bounded(id))
+}
+
+#[test]
+fn transfer_device_moves_ownership_directly() {
+    new_test_ext().execute_with(|| {
+        RequireTransferAcceptance::set(false);
+        assert_ok!(DeviceRegistry::register_device(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            metadata("stove-v2"),
+        ));
+        assert!(owns(1, "device-001"));
+
+        assert_ok!(DeviceRegistry::transfer_device(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            3,
+        ));
+
+        let info = DeviceRegistry::devices(bounded("device-001")).unwrap();
+        assert_eq!(info.owner, 3);
+        // The deposit stays with the account that paid it
+        assert_eq!(info.depositor, 1);
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::RegistrationDeposit.into(), &1),
+            10
+        );
+        assert!(!owns(1, "device-001"));
+        assert!(owns(3, "device-001"));
+        System::assert_last_event(
+            Event::DeviceOwnershipTransferred {
+                device_id: bounded("device-001"),
+                from: 1,
+                to: 3,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn transfer_device_waits_for_acceptance_when_required() {
+    new_test_ext().execute_with(|| {
+        RequireTransferAcceptance::set(true);
+        assert_ok!(DeviceRegistry::transfer_device(
+            RuntimeOrigin::signed(2),
+            device_id("genesis-device"),
+            3,
+        ));
+        System::assert_last_event(
+            Event::DeviceTransferProposed {
+                device_id: bounded("genesis-device"),
+                from: 2,
+                to: 3,
+            }
+            .into(),
+        );
+        assert_eq!(
+            DeviceRegistry::device_owner(&bounded("genesis-device")),
+            Some(2)
+        );
+        assert_eq!(
+            DeviceRegistry::pending_transfer(bounded("genesis-device")),
+            Some(3)
+        );
+
+        // Only the offered account may accept
+        assert_noop!(
+            DeviceRegistry::accept_device(RuntimeOrigin::signed(1), device_id("genesis-device")),
+            Error::<Test>::NoPendingTransfer
+        );
+        assert_ok!(DeviceRegistry::accept_device(
+            RuntimeOrigin::signed(3),
+            device_id("genesis-device"),
+        ));
+
+        assert_eq!(
+            DeviceRegistry::device_owner(&bounded("genesis-device")),
+            Some(3)
+        );
+        assert_eq!(
+            DeviceRegistry::pending_transfer(bounded("genesis-device")),
+            None
+        );
+        assert!(!owns(2, "genesis-device"));
+        assert!(owns(3, "genesis-device"));
+        System::assert_last_event(
+            Event::DeviceOwnershipTransferred {
+                device_id: bounded("genesis-device"),
+                from: 2,
+                to: 3,
+            }
+            .into(),
+        );
+        assert_noop!(
+            DeviceRegistry::accept_device(RuntimeOrigin::signed(3), device_id("genesis-device")),
+            Error::<Test>::NoPendingTransfer
+        );
+    });
+}
+
+#[test]
+fn transfer_device_rejects_invalid_transfers() {
+    new_test_ext().execute_with(|| {
+        RequireTransferAcceptance::set(false);
+        assert_noop!(
+            DeviceRegistry::transfer_device(RuntimeOrigin::signed(1), device_id("device-404"), 3),
+            Error::<Test>::DeviceNotFound
+        );
+        assert_noop!(
+            DeviceRegistry::transfer_device(
+                RuntimeOrigin::signed(1),
+                device_id("genesis-device"),
+                3
+            ),
+            Error::<Test>::NotDeviceOwner
+        );
+        assert_noop!(
+            DeviceRegistry::transfer_device(
+                RuntimeOrigin::signed(2),
+                device_id("genesis-device"),
+                2
+            ),
+            Error::<Test>::TransferToSelf
+        );
+
+        for status in [DeviceStatus::Suspended, DeviceStatus::Retired] {
+            Devices::<Test>::mutate(bounded("genesis-device"), |info| {
+                info.as_mut().unwrap().status = status
+            });
+            assert_noop!(
+                DeviceRegistry::transfer_device(
+                    RuntimeOrigin::signed(2),
+                    device_id("genesis-device"),
+                    3
+                ),
+                Error::<Test>::DeviceNotActive
+            );
+        }
+    });
+}
+
+#[test]
+fn device_status_follows_lifecycle_rules() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(DeviceRegistry::register_device(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            metadata("stove-v2"),
+        ));
+        let status = || DeviceRegistry::device_status(&bounded("device-001")).unwrap();
+        assert_eq!(status(), DeviceStatus::Registered);
+
+        // Only the owner may activate, and may not suspend
+        assert_noop!(
+            DeviceRegistry::set_device_status(
+                RuntimeOrigin::signed(2),
+                device_id("device-001"),
+                DeviceStatus::Active
+            ),
+            Error::<Test>::NotDeviceOwner
+        );
+        assert_ok!(DeviceRegistry::set_device_status(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            DeviceStatus::Active
+        ));
+        System::assert_last_event(
+            Event::DeviceStatusChanged {
+                device_id: bounded("device-001"),
+                from: DeviceStatus::Registered,
+                to: DeviceStatus::Active,
+            }
+            .into(),
+        );
+        assert_noop!(
+            DeviceRegistry::set_device_status(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                DeviceStatus::Suspended
+            ),
+            Error::<Test>::InvalidStatusTransition
+        );
+
+        // Suspension is lifted by the admin only
+        assert_ok!(DeviceRegistry::set_device_status(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            DeviceStatus::Suspended
+        ));
+        assert_noop!(
+            DeviceRegistry::set_device_status(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                DeviceStatus::Active
+            ),
+            Error::<Test>::InvalidStatusTransition
+        );
+        assert_noop!(
+            DeviceRegistry::set_device_status(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                DeviceStatus::Registered
+            ),
+            Error::<Test>::InvalidStatusTransition
+        );
+        assert_ok!(DeviceRegistry::set_device_status(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            DeviceStatus::Active
+        ));
+
+        // Owners may retire, and retirement is final
+        assert_ok!(DeviceRegistry::set_device_status(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            DeviceStatus::Retired
+        ));
+        assert_eq!(status(), DeviceStatus::Retired);
+        assert_noop!(
+            DeviceRegistry::set_device_status(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                DeviceStatus::Active
+            ),
+            Error::<Test>::InvalidStatusTransition
+        );
+    });
+}
+
+#[test]
+fn device_interface_reports_status_and_owner() {
+    new_test_ext().execute_with(|| {
+        let genesis = bounded("genesis-device");
+        let unknown = bounded("device-404");
+
+        assert_eq!(
+            <DeviceRegistry as traits::DeviceInterface<_, u64>>::status(&genesis),
+            Some(DeviceStatus::Active)
+        );
+        assert_eq!(
+            <DeviceRegistry as traits::DeviceInterface<_, u64>>::owner(&genesis),
+            Some(2)
+        );
+        assert_eq!(
+            <DeviceRegistry as traits::DeviceInterface<_, u64>>::status(&unknown),
+            None
+        );
+        assert_eq!(
+            <() as traits::DeviceInterface<_, u64>>::status(&unknown),
+            Some(DeviceStatus::Active)
+        );
+        assert_eq!(
+            <() as traits::DeviceInterface<_, u64>>::owner(&unknown),
+            None
+        );
+    });
+}
+
+#[test]
+fn attestation_is_set_once_and_replaced_by_governance() {
+    new_test_ext().execute_with(|| {
+        let device = bounded("genesis-device");
+        let first = H256::repeat_byte(7);
+        let second = H256::repeat_byte(8);
+
+        assert_noop!(
+            DeviceRegistry::submit_attestation(
+                RuntimeOrigin::signed(2),
+                device_id("genesis-device"),
+                first
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            DeviceRegistry::submit_attestation(
+                RuntimeOrigin::signed(5),
+                device_id("device-404"),
+                first
+            ),
+            Error::<Test>::DeviceNotFound
+        );
+        assert_ok!(DeviceRegistry::submit_attestation(
+            RuntimeOrigin::signed(5),
+            device_id("genesis-device"),
+            first
+        ));
+        System::assert_last_event(
+            Event::AttestationSubmitted {
+                device_id: device.clone(),
+                attestation_hash: first,
+                previous: None,
+            }
+            .into(),
+        );
+
+        // The manufacturer cannot overwrite it
+        assert_noop!(
+            DeviceRegistry::submit_attestation(
+                RuntimeOrigin::signed(5),
+                device_id("genesis-device"),
+                second
+            ),
+            Error::<Test>::AttestationAlreadySet
+        );
+
+        assert_ok!(DeviceRegistry::submit_attestation(
+            RuntimeOrigin::root(),
+            device_id("genesis-device"),
+            second
+        ));
+        System::assert_last_event(
+            Event::AttestationSubmitted {
+                device_id: device.clone(),
+                attestation_hash: second,
+                previous: Some(first),
+            }
+            .into(),
+        );
+        assert!(DeviceRegistry::verify_attestation(&device, &second));
+        assert!(!DeviceRegistry::verify_attestation(&device, &first));
+    });
+}
+
+#[test]
+fn verify_attestation_rejects_wrong_hashes() {
+    new_test_ext().execute_with(|| {
+        let mut registered = metadata("stove-v2");
+        registered.attestation_hash = Some(H256::repeat_byte(7));
+        assert_ok!(DeviceRegistry::register_device(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            registered,
+        ));
+
+        let device = bounded("device-001");
+        assert!(DeviceRegistry::verify_attestation(
+            &device,
+            &H256::repeat_byte(7)
+        ));
+        assert!(!DeviceRegistry::verify_attestation(
+            &device,
+            &H256::repeat_byte(8)
+        ));
+        // Devices without an attestation never verify
+        assert!(!DeviceRegistry::verify_attestation(
+            &bounded("genesis-device"),
+            &H256::zero()
+        ));
+        assert!(!DeviceRegistry::verify_attestation(
+            &bounded("device-404"),
+            &H256::repeat_byte(7)
+        ));
+
+        // Set at registration, so the manufacturer cannot overwrite it
+        assert_noop!(
+            DeviceRegistry::submit_attestation(
+                RuntimeOrigin::signed(5),
+                device_id("device-001"),
+                H256::repeat_byte(8)
+            ),
+            Error::<Test>::AttestationAlreadySet
+        );
+
+        assert_ok!(DeviceRegistry::check_attestation(
+            RuntimeOrigin::signed(3),
+            device_id("device-001"),
+            H256::repeat_byte(8),
+        ));
+        System::assert_last_event(
+            Event::AttestationVerified {
+                device_id: device.clone(),
+                attestation_hash: H256::repeat_byte(8),
+                valid: false,
+            }
+            .into(),
+        );
+        assert_ok!(DeviceRegistry::check_attestation(
+            RuntimeOrigin::signed(3),
+            device_id("device-001"),
+            H256::repeat_byte(7),
+        ));
+        System::assert_last_event(
+            Event::AttestationVerified {
+                device_id: device,
+                attestation_hash: H256::repeat_byte(7),
+                valid: true,
+            }
+            .into(),
+        );
+    });
+}
+
+fn batch_entry(id: &str, owner: u64) -> BatchDeviceEntry<Test> {
+    (device_id(id), metadata("stove-v2"), owner)
+}
+
+#[test]
+fn register_devices_batch_skips_duplicates_and_invalid_ids() {
+    new_test_ext().execute_with(|| {
+        let entries = vec![
+            batch_entry("device-001", 3),
+            batch_entry("genesis-device", 3),
+            (vec![b'a'; 65], metadata("stove-v2"), 3),
+            batch_entry("device-001", 2),
+            batch_entry("device-002", 2),
+        ];
+        assert_ok!(DeviceRegistry::register_devices_batch(
+            RuntimeOrigin::signed(1),
+            entries
+        ));
+
+        for (index, reason) in [
+            (1, BatchRejectReason::AlreadyRegistered),
+            (2, BatchRejectReason::DeviceIdTooLong),
+            (3, BatchRejectReason::AlreadyRegistered),
+        ] {
+            System::assert_has_event(Event::BatchEntryRejected { index, reason }.into());
+        }
+        System::assert_last_event(
+            Event::BatchRegistered {
+                depositor: 1,
+                submitted: 5,
+                accepted: 2,
+                rejected: 3,
+                deposit: 20,
+            }
+            .into(),
+        );
+
+        // The caller pays for both accepted devices, which go to their owners
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::RegistrationDeposit.into(), &1),
+            20
+        );
+        let first = DeviceRegistry::devices(bounded("device-001")).unwrap();
+        assert_eq!((first.owner, first.depositor, first.deposit), (3, 1, 10));
+        assert_eq!(first.status, DeviceStatus::Registered);
+        assert_eq!(
+            DeviceRegistry::device_owner(&bounded("device-002")),
+            Some(2)
+        );
+        assert!(owns(2, "device-002"));
+        assert_eq!(DeviceRegistry::device_count(), 3);
+    });
+}
+
+#[test]
+fn register_devices_batch_bounds_size_and_deposit() {
+    new_test_ext().execute_with(|| {
+        let full: Vec<_> = (0..5)
+            .map(|i| batch_entry(&format!("device-{:03}", i), 3))
+            .collect();
+
+        assert_noop!(
+            DeviceRegistry::register_devices_batch(RuntimeOrigin::signed(1), vec![]),
+            Error::<Test>::EmptyBatch
+        );
+        let mut oversized = full.clone();
+        oversized.push(batch_entry("device-005", 3));
+        assert_noop!(
+            DeviceRegistry::register_devices_batch(RuntimeOrigin::signed(1), oversized),
+            Error::<Test>::BatchTooLarge
+        );
+        // Account 3 cannot cover the batch deposit, so nothing is registered
+        assert!(
+            DeviceRegistry::register_devices_batch(RuntimeOrigin::signed(3), full.clone()).is_err()
+        );
+        assert!(!DeviceRegistry::is_registered(&bounded("device-000")));
+
+        assert_ok!(DeviceRegistry::register_devices_batch(
+            RuntimeOrigin::signed(1),
+            full
+        ));
+        assert_eq!(DeviceRegistry::device_count(), 6);
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::RegistrationDeposit.into(), &1),
+            50
+        );
+        System::assert_last_event(
+            Event::BatchRegistered {
+                depositor: 1,
+                submitted: 5,
+                accepted: 5,
+                rejected: 0,
+                deposit: 50,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn update_device_metadata_versions_and_bounds_history() {
+    new_test_ext().execute_with(|| {
+        use sp_runtime::traits::Hash;
+        let device = bounded("genesis-device");
+        assert_ok!(DeviceRegistry::submit_attestation(
+            RuntimeOrigin::root(),
+            device_id("genesis-device"),
+            H256::repeat_byte(7)
+        ));
+        let original = DeviceRegistry::devices(&device).unwrap().metadata;
+
+        assert_noop!(
+            DeviceRegistry::update_device_metadata(
+                RuntimeOrigin::signed(1),
+                device_id("genesis-device"),
+                metadata("stove-v2")
+            ),
+            Error::<Test>::NotDeviceOwner
+        );
+
+        let mut hashes = vec![BlakeTwo256::hash_of(&original)];
+        for (version, model) in [(1, "stove-v2"), (2, "stove-v3"), (3, "stove-v4")] {
+            assert_ok!(DeviceRegistry::update_device_metadata(
+                RuntimeOrigin::signed(2),
+                device_id("genesis-device"),
+                metadata(model)
+            ));
+            System::assert_last_event(
+                Event::DeviceMetadataUpdated {
+                    device_id: device.clone(),
+                    version,
+                }
+                .into(),
+            );
+            let info = DeviceRegistry::devices(&device).unwrap();
+            assert_eq!(info.metadata_version, version);
+            assert_eq!(info.metadata.model.to_vec(), model.as_bytes().to_vec());
+            // The anchored attestation survives updates
+            assert_eq!(info.metadata.attestation_hash, Some(H256::repeat_byte(7)));
+            hashes.push(BlakeTwo256::hash_of(&info.metadata));
+        }
+
+        // Only the latest two replaced hashes are kept, oldest first
+        assert_eq!(
+            DeviceRegistry::metadata_history(&device).into_inner(),
+            hashes[1..3].to_vec()
+        );
+    });
+}
+
+#[test]
+fn suspended_devices_cannot_update_metadata() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(DeviceRegistry::set_device_status(
+            RuntimeOrigin::root(),
+            device_id("genesis-device"),
+            DeviceStatus::Suspended
+        ));
+        assert_noop!(
+            DeviceRegistry::update_device_metadata(
+                RuntimeOrigin::signed(2),
+                device_id("genesis-device"),
+                metadata("stove-v2")
+            ),
+            Error::<Test>::DeviceNotActive
+        );
+    });
+}
+
+fn located(model: &str, geohash: &str) -> DeviceMetadata<Test> {
+    let mut metadata = metadata(model);
+    metadata.geohash = Some(geohash.as_bytes().to_vec().try_into().unwrap());
+    metadata
+}
+
+fn area(prefix: &str) -> u32 {
+    DeviceRegistry::devices_in_area(AreaPrefix::truncate_from(prefix.as_bytes().to_vec()))
+}
+
+#[test]
+fn devices_by_area_follow_registration_relocation_and_retirement() {
+    new_test_ext().execute_with(|| {
+        // Two devices in Nairobi (kzf0...), one in Kampala (kpzr...)
+        for (id, geohash) in [
+            ("device-001", "kzf0tq6x2c9n"),
+            ("device-002", "kzf0tqbb"),
+            ("device-003", "kpzr4m"),
+        ] {
+            assert_ok!(DeviceRegistry::register_device(
+                RuntimeOrigin::signed(1),
+                device_id(id),
+                located("stove-v2", geohash),
+            ));
+        }
+        assert_eq!(area("kzf0tq"), 2);
+        assert_eq!(area("kpzr4m"), 1);
+
+        // Relocation moves the device between areas
+        assert_ok!(DeviceRegistry::update_device_metadata(
+            RuntimeOrigin::signed(1),
+            device_id("device-002"),
+            located("stove-v2", "kpzr4mzz"),
+        ));
+        assert_eq!(area("kzf0tq"), 1);
+        assert_eq!(area("kpzr4m"), 2);
+
+        // Retired devices are no longer counted
+        assert_ok!(DeviceRegistry::set_device_status(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            DeviceStatus::Retired
+        ));
+        assert_eq!(area("kzf0tq"), 0);
+        assert!(!DevicesByArea::<Test>::contains_key(
+            AreaPrefix::truncate_from(b"kzf0tq".to_vec())
+        ));
+        assert_eq!(area("kpzr4m"), 2);
+    });
+}
+
+#[test]
+fn geohashes_must_use_the_geohash_alphabet() {
+    new_test_ext().execute_with(|| {
+        // 'a', 'i', 'l' and 'o' are not geohash characters
+        for geohash in ["kzf0ta", "KZF0TQ", ""] {
+            assert_noop!(
+                DeviceRegistry::register_device(
+                    RuntimeOrigin::signed(1),
+                    device_id("device-001"),
+                    located("stove-v2", geohash),
+                ),
+                Error::<Test>::InvalidGeohash
+            );
+        }
+        assert_noop!(
+            DeviceRegistry::update_device_metadata(
+                RuntimeOrigin::signed(2),
+                device_id("genesis-device"),
+                located("stove-v2", "kzf0to"),
+            ),
+            Error::<Test>::InvalidGeohash
+        );
+
+        assert_ok!(DeviceRegistry::register_devices_batch(
+            RuntimeOrigin::signed(1),
+            vec![
+                (device_id("device-001"), located("stove-v2", "kzf0tl"), 3),
+                (device_id("device-002"), located("stove-v2", "kzf0tq"), 3),
+            ]
+        ));
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 0,
+                reason: BatchRejectReason::InvalidGeohash,
+            }
+            .into(),
+        );
+        assert_eq!(area("kzf0tq"), 1);
+    });
+}
+
+#[test]
+fn device_models_can_be_created_updated_and_removed() {
+    new_test_ext().execute_with(|| {
+        let model = || -> BoundedVec<u8, ConstU32<16>> { b"stove-v1".to_vec().try_into().unwrap() };
+        let genesis = bounded("genesis-device");
+        // Devices of unknown models use the default rating
+        assert_eq!(DeviceRegistry::max_power(&genesis), Some(1_500));
+        assert_eq!(DeviceRegistry::max_power(&bounded("device-404")), None);
+
+        assert_noop!(
+            DeviceRegistry::create_model(
+                RuntimeOrigin::signed(1),
+                b"stove-v1".to_vec(),
+                800,
+                b"LPG burner".to_vec()
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            DeviceRegistry::create_model(
+                RuntimeOrigin::root(),
+                b"stove-v1".to_vec(),
+                0,
+                b"LPG burner".to_vec()
+            ),
+            Error::<Test>::ZeroPowerRating
+        );
+        assert_noop!(
+            DeviceRegistry::create_model(
+                RuntimeOrigin::root(),
+                b"stove-v1".to_vec(),
+                800,
+                vec![b'x'; 33]
+            ),
+            Error::<Test>::DescriptionTooLong
+        );
+        assert_ok!(DeviceRegistry::create_model(
+            RuntimeOrigin::root(),
+            b"stove-v1".to_vec(),
+            800,
+            b"LPG burner".to_vec()
+        ));
+        System::assert_last_event(
+            Event::ModelCreated {
+                model_id: model(),
+                max_power_w: 800,
+            }
+            .into(),
+        );
+        assert_noop!(
+            DeviceRegistry::create_model(
+                RuntimeOrigin::root(),
+                b"stove-v1".to_vec(),
+                900,
+                b"LPG burner".to_vec()
+            ),
+            Error::<Test>::ModelAlreadyExists
+        );
+        assert_eq!(DeviceRegistry::max_power(&genesis), Some(800));
+
+        assert_ok!(DeviceRegistry::update_model(
+            RuntimeOrigin::root(),
+            b"stove-v1".to_vec(),
+            1_000,
+            b"LPG burner, rev B".to_vec()
+        ));
+        let stored = DeviceRegistry::device_models(model()).unwrap();
+        assert_eq!(stored.max_power_w, 1_000);
+        assert_eq!(stored.description.to_vec(), b"LPG burner, rev B".to_vec());
+        assert_eq!(
+            <DeviceRegistry as traits::DeviceInterface<_, u64>>::max_power(&genesis),
+            Some(1_000)
+        );
+
+        assert_ok!(DeviceRegistry::remove_model(
+            RuntimeOrigin::root(),
+            b"stove-v1".to_vec()
+        ));
+        System::assert_last_event(Event::ModelRemoved { model_id: model() }.into());
+        assert_eq!(DeviceRegistry::max_power(&genesis), Some(1_500));
+        assert_noop!(
+            DeviceRegistry::update_model(
+                RuntimeOrigin::root(),
+                b"stove-v1".to_vec(),
+                1_000,
+                vec![]
+            ),
+            Error::<Test>::ModelNotFound
+        );
+        assert_noop!(
+            DeviceRegistry::remove_model(RuntimeOrigin::root(), b"stove-v1".to_vec()),
+            Error::<Test>::ModelNotFound
+        );
+    });
+}
+
+#[test]
+fn decommission_device_retires_and_reports_hook_results() {
+    new_test_ext().execute_with(|| {
+        RequireTransferAcceptance::set(true);
+        assert_ok!(DeviceRegistry::register_device(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            located("stove-v2", "kzf0tq6x2c9n"),
+        ));
+        assert_ok!(DeviceRegistry::transfer_device(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            3
+        ));
+        assert!(PendingTransfers::<Test>::contains_key(bounded(
+            "device-001"
+        )));
+        assert_eq!(area("kzf0tq"), 1);
+
+        assert_noop!(
+            DeviceRegistry::decommission_device(RuntimeOrigin::signed(2), device_id("device-001")),
+            Error::<Test>::NotDeviceOwner
+        );
+        assert_noop!(
+            DeviceRegistry::decommission_device(RuntimeOrigin::signed(1), device_id("missing")),
+            Error::<Test>::DeviceNotFound
+        );
+
+        // A failing hook leaves the device untouched
+        DecommissionFails::set(true);
+        assert_noop!(
+            DeviceRegistry::decommission_device(RuntimeOrigin::signed(1), device_id("device-001")),
+            sp_runtime::DispatchError::Other("decommission failed")
+        );
+        DecommissionFails::set(false);
+
+        assert_ok!(DeviceRegistry::decommission_device(
+            RuntimeOrigin::signed(1),
+            device_id("device-001")
+        ));
+        System::assert_last_event(
+            Event::DeviceDecommissioned {
+                device_id: bounded("device-001"),
+                owner: 1,
+                from: DeviceStatus::Registered,
+                credits_swept: 7,
+                proofs_scheduled: 3,
+            }
+            .into(),
+        );
+        assert_eq!(
+            DeviceRegistry::devices(bounded("device-001"))
+                .unwrap()
+                .status,
+            DeviceStatus::Retired
+        );
+        assert!(!PendingTransfers::<Test>::contains_key(bounded(
+            "device-001"
+        )));
+        assert_eq!(area("kzf0tq"), 0);
+
+        assert_noop!(
+            DeviceRegistry::decommission_device(RuntimeOrigin::signed(1), device_id("device-001")),
+            Error::<Test>::InvalidStatusTransition
+        );
+
+        // AdminOrigin may decommission any device
+        assert_ok!(DeviceRegistry::decommission_device(
+            RuntimeOrigin::root(),
+            device_id("genesis-device")
+        ));
+        assert_eq!(
+            DeviceRegistry::devices(bounded("genesis-device"))
+                .unwrap()
+                .status,
+            DeviceStatus::Retired
+        );
+    });
+}
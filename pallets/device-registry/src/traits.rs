@@ -0,0 +1,170 @@
+//! Interfaces the Device Registry pallet offers to other pallets.
+
+use super::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::DispatchError, weights::Weight};
+use scale_info::TypeInfo;
+
+/// Registration status of devices.
+///
+/// Pallets that key their storage by device id take this as a Config
+/// associated type to require registration without reading the registry's
+/// storage directly.
+pub trait DeviceRegistry<DeviceId> {
+    /// Whether the device is registered.
+    fn is_registered(device: &DeviceId) -> bool;
+}
+
+/// Lifecycle status and ownership of devices.
+///
+/// Pallets gate their extrinsics on a device's [`DeviceStatus`] through this
+/// trait; unregistered devices have neither status nor owner.
+pub trait DeviceInterface<DeviceId, AccountId> {
+    /// Lifecycle status of the device, if registered.
+    fn status(device: &DeviceId) -> Option<DeviceStatus>;
+
+    /// Owner of the device, if registered.
+    fn owner(device: &DeviceId) -> Option<AccountId>;
+
+    /// Maximum power in watts of the device's model, if registered.
+    fn max_power(device: &DeviceId) -> Option<u32>;
+
+    /// Register `device` as active and owned by `owner`, so benchmarks of
+    /// other pallets can act on it.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn register_active(_device: &DeviceId, _owner: &AccountId) {}
+}
+
+/// What other pallets did when a device was decommissioned.
+#[derive(Clone, Copy, Default, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+pub struct DecommissionReport {
+    /// Carbon credits moved from the device to its owner's account
+    pub credits_swept: u128,
+    /// Retained proofs scheduled for incremental removal
+    pub proofs_scheduled: u64,
+}
+
+impl DecommissionReport {
+    /// Field-wise sum of two reports.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            credits_swept: self.credits_swept.saturating_add(other.credits_swept),
+            proofs_scheduled: self.proofs_scheduled.saturating_add(other.proofs_scheduled),
+        }
+    }
+}
+
+/// Pallets' share of `decommission_device`.
+///
+/// Runs before the registry retires the device, inside the same
+/// transactional call: an error aborts the whole decommissioning.
+pub trait OnDecommission<DeviceId, AccountId> {
+    /// Wind down the device's state, crediting anything left to `owner`.
+    fn on_decommission(
+        device: &DeviceId,
+        owner: &AccountId,
+    ) -> Result<DecommissionReport, DispatchError>;
+
+    /// Upper bound on the weight of `on_decommission`.
+    fn weight() -> Weight;
+}
+
+/// Nothing to wind down.
+impl<DeviceId, AccountId> OnDecommission<DeviceId, AccountId> for () {
+    fn on_decommission(
+        _device: &DeviceId,
+        _owner: &AccountId,
+    ) -> Result<DecommissionReport, DispatchError> {
+        Ok(DecommissionReport::default())
+    }
+
+    fn weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Runs both hooks in order and merges their reports.
+impl<DeviceId, AccountId, A, B> OnDecommission<DeviceId, AccountId> for (A, B)
+where
+    A: OnDecommission<DeviceId, AccountId>,
+    B: OnDecommission<DeviceId, AccountId>,
+{
+    fn on_decommission(
+        device: &DeviceId,
+        owner: &AccountId,
+    ) -> Result<DecommissionReport, DispatchError> {
+        Ok(A::on_decommission(device, owner)?.merge(B::on_decommission(device, owner)?))
+    }
+
+    fn weight() -> Weight {
+        A::weight().saturating_add(B::weight())
+    }
+}
+
+/// No registry: every device counts as registered, so pallets configured
+/// with `()` keep accepting any device id.
+impl<DeviceId> DeviceRegistry<DeviceId> for () {
+    fn is_registered(_device: &DeviceId) -> bool {
+        true
+    }
+}
+
+/// No registry: every device counts as active and has no known owner or
+/// power rating.
+impl<DeviceId, AccountId> DeviceInterface<DeviceId, AccountId> for () {
+    fn status(_device: &DeviceId) -> Option<DeviceStatus> {
+        Some(DeviceStatus::Active)
+    }
+
+    fn owner(_device: &DeviceId) -> Option<AccountId> {
+        None
+    }
+
+    fn max_power(_device: &DeviceId) -> Option<u32> {
+        None
+    }
+}
+
+impl<T: Config> DeviceRegistry<DeviceId> for Pallet<T> {
+    fn is_registered(device: &DeviceId) -> bool {
+        Devices::<T>::contains_key(device)
+    }
+}
+
+impl<T: Config> DeviceInterface<DeviceId, T::AccountId> for Pallet<T> {
+    fn status(device: &DeviceId) -> Option<DeviceStatus> {
+        Pallet::<T>::device_status(device)
+    }
+
+    fn owner(device: &DeviceId) -> Option<T::AccountId> {
+        Pallet::<T>::device_owner(device)
+    }
+
+    fn max_power(device: &DeviceId) -> Option<u32> {
+        Pallet::<T>::max_power(device)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn register_active(device: &DeviceId, owner: &T::AccountId) {
+        Devices::<T>::insert(
+            device,
+            DeviceInfo {
+                owner: owner.clone(),
+                depositor: owner.clone(),
+                metadata: DeviceMetadata {
+                    model: Default::default(),
+                    firmware_hash: Default::default(),
+                    location_hash: Default::default(),
+                    attestation_hash: None,
+                    geohash: None,
+                },
+                deposit: 0u32.into(),
+                registered_at: frame_system::Pallet::<T>::block_number(),
+                status: DeviceStatus::Active,
+                metadata_version: 0,
+            },
+        );
+        OwnerDevices::<T>::insert(owner, device, ());
+        DeviceCount::<T>::mutate(|count| *count = count.saturating_add(1));
+    }
+}
@@ -0,0 +1,226 @@
+//! Weight calculations for the Device Registry pallet.
+//!
+//! These weights ensure proper transaction fee calculation
+//! and prevent denial-of-service attacks.
+
+use frame_support::{traits::Get, weights::Weight};
+
+/// Weight functions for the pallet.
+pub trait WeightInfo {
+    /// Weight for registering a device.
+    fn register_device() -> Weight;
+    /// Weight for registering a batch that stores `n` devices.
+    fn register_devices_batch(n: u32) -> Weight;
+    /// Weight for transferring or offering a device.
+    fn transfer_device() -> Weight;
+    /// Weight for accepting a device transfer.
+    fn accept_device() -> Weight;
+    /// Weight for changing a device's lifecycle status.
+    fn set_device_status() -> Weight;
+    /// Weight for replacing a device's metadata.
+    fn update_device_metadata() -> Weight;
+    /// Weight for anchoring an attestation hash.
+    fn submit_attestation() -> Weight;
+    /// Weight for adding a stove model.
+    fn create_model() -> Weight;
+    /// Weight for changing a stove model.
+    fn update_model() -> Weight;
+    /// Weight for removing a stove model.
+    fn remove_model() -> Weight;
+    /// Weight for checking an attestation hash.
+    fn check_attestation() -> Weight;
+    /// Weight for decommissioning a device, excluding the other pallets' hooks.
+    fn decommission_device() -> Weight;
+}
+
+/// Default weight implementation.
+///
+/// Placeholder weights that should be replaced with benchmark results.
+pub struct SubstrateWeight<T>(core::marker::PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    /// Weight for registering a device.
+    ///
+    /// Operations:
+    /// - Check the device is not registered
+    /// - Hold the registration deposit
+    /// - Write the device and the device count
+    /// - Emit event
+    fn register_device() -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    /// Weight for registering a batch that stores `n` devices.
+    ///
+    /// Operations:
+    /// - Check each device is not registered
+    /// - Write each device, its owner index entry and the device count
+    /// - Hold the combined deposit once
+    /// - Emit events
+    fn register_devices_batch(n: u32) -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(Weight::from_parts(20_000_000 * n as u64, 0))
+            .saturating_add(T::DbWeight::get().reads(2 + 2 * n as u64))
+            .saturating_add(T::DbWeight::get().writes(2 + 3 * n as u64))
+    }
+
+    /// Weight for transferring or offering a device.
+    ///
+    /// Operations:
+    /// - Read the device
+    /// - Write the pending transfer, or move the device between owners
+    /// - Emit event
+    fn transfer_device() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    /// Weight for accepting a device transfer.
+    ///
+    /// Operations:
+    /// - Read the pending transfer and the device
+    /// - Move the device between owners and clear the pending transfer
+    /// - Emit event
+    fn accept_device() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    /// Weight for changing a device's lifecycle status.
+    ///
+    /// Operations:
+    /// - Read the device
+    /// - Write the device and clear any pending transfer
+    /// - Emit event
+    fn set_device_status() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Weight for replacing a device's metadata.
+    ///
+    /// Operations:
+    /// - Read the device and its metadata history
+    /// - Hash the replaced metadata
+    /// - Write the device and its metadata history
+    /// - Emit event
+    fn update_device_metadata() -> Weight {
+        Weight::from_parts(35_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Weight for anchoring an attestation hash.
+    ///
+    /// Operations:
+    /// - Read the device
+    /// - Write the device
+    /// - Emit event
+    fn submit_attestation() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for checking an attestation hash.
+    ///
+    /// Operations:
+    /// - Read the device
+    /// - Emit event
+    fn check_attestation() -> Weight {
+        Weight::from_parts(15_000_000, 0).saturating_add(T::DbWeight::get().reads(1))
+    }
+
+    /// Weight for adding a stove model.
+    ///
+    /// Operations:
+    /// - Check the model is not registered
+    /// - Write the model
+    /// - Emit event
+    fn create_model() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for changing a stove model.
+    ///
+    /// Operations:
+    /// - Check the model is registered
+    /// - Write the model
+    /// - Emit event
+    fn update_model() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for removing a stove model.
+    ///
+    /// Operations:
+    /// - Take the model
+    /// - Emit event
+    fn remove_model() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for decommissioning a device, excluding the other pallets' hooks.
+    ///
+    /// Operations:
+    /// - Read the device
+    /// - Clear any pending transfer and update the area count
+    /// - Write the retired device
+    /// - Emit event
+    fn decommission_device() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+}
+
+/// Unit implementation for testing.
+impl WeightInfo for () {
+    fn register_device() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn register_devices_batch(n: u32) -> Weight {
+        Weight::from_parts(10_000 * n as u64, 0)
+    }
+    fn transfer_device() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn accept_device() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn set_device_status() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn update_device_metadata() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn submit_attestation() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn check_attestation() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn create_model() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn update_model() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn remove_model() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn decommission_device() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+}
@@ -0,0 +1,64 @@
+//! # Governance Origins Pallet
+//!
+//! This pallet declares the origins that token-holder referenda dispatch
+//! approved proposals with.
+//!
+//! ## Overview
+//!
+//! `pallet_referenda` enacts an approved proposal with the origin it was
+//! submitted for, and picks the referendum's track from that origin. A Root
+//! track would let token holders dispatch anything; the origins here give
+//! each track exactly the privileges of one admin role:
+//! - `Methodology` - Carbon methodology parameters, such as the emission factor
+//!
+//! `EnsureMethodology` admits the `Methodology` origin. Runtimes add it to the
+//! `AdminOrigin` of the pallets the methodology track governs.
+//!
+//! The pallet has no storage, dispatchable functions or events.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod tests;
+
+use frame_support::traits::EnsureOrigin;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use frame_support::pallet_prelude::*;
+
+    /// The pallet's configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {}
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Origins of the runtime's referenda tracks
+    #[derive(PartialEq, Eq, Clone, MaxEncodedLen, Encode, Decode, TypeInfo, RuntimeDebug)]
+    #[pallet::origin]
+    pub enum Origin {
+        /// A referendum on carbon methodology parameters was approved
+        Methodology,
+    }
+}
+
+/// Admits the `Methodology` origin of an approved methodology referendum.
+pub struct EnsureMethodology;
+
+impl<O: Into<Result<Origin, O>> + From<Origin>> EnsureOrigin<O> for EnsureMethodology {
+    type Success = ();
+
+    fn try_origin(o: O) -> Result<Self::Success, O> {
+        o.into().map(|origin| match origin {
+            Origin::Methodology => (),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<O, ()> {
+        Ok(O::from(Origin::Methodology))
+    }
+}
@@ -0,0 +1,58 @@
+//! Unit tests for the Governance Origins pallet.
+
+use crate::{self as pallet_governance_origins, *};
+use frame_support::traits::{ConstU32, ConstU64};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        GovernanceOrigins: pallet_governance_origins,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+    type RuntimeTask = ();
+    type SingleBlockMigrations = ();
+    type MultiBlockMigrator = ();
+    type PreInherents = ();
+    type PostInherents = ();
+    type PostTransactions = ();
+}
+
+impl Config for Test {}
+
+#[test]
+fn ensure_methodology_admits_only_the_methodology_origin() {
+    assert!(EnsureMethodology::try_origin(RuntimeOrigin::from(Origin::Methodology)).is_ok());
+
+    // Neither Root nor a signed account stands in for a referendum
+    assert!(EnsureMethodology::try_origin(RuntimeOrigin::root()).is_err());
+    assert!(EnsureMethodology::try_origin(RuntimeOrigin::signed(1)).is_err());
+}
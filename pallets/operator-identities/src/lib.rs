@@ -0,0 +1,131 @@
+//! # Operator Identities Pallet
+//!
+//! This pallet seeds `pallet-identity` at genesis, so explorers show names
+//! for the bridge, committee and validator accounts from the first block.
+//!
+//! ## Overview
+//!
+//! `pallet-identity` has no genesis configuration. This pallet's genesis:
+//! - Adds `registrars` as identity registrars, in registrar index order
+//! - Sets a display-name identity for each account in `identities`
+//! - Has the first registrar judge each of those identities `Reasonable`
+//!
+//! It has no storage or dispatchable functions of its own. After genesis,
+//! registrars and identities are managed through `pallet-identity`.
+//!
+//! ## Requirements
+//!
+//! The pallet must come after `pallet-balances` and `pallet-identity` in
+//! `construct_runtime!`, so their genesis is built first. Registrars are
+//! added with a Root origin, which `RegistrarOrigin` must accept. Every
+//! account given an identity must hold the identity deposit on top of the
+//! existential deposit, and genesis fails otherwise.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use frame_support::traits::Get;
+use pallet_identity::{legacy::IdentityInfo, Data};
+use sp_runtime::traits::Convert;
+
+/// Builds a legacy `IdentityInfo` holding only a display name.
+///
+/// Returns `None` for names longer than the 32 bytes `Data::Raw` holds.
+pub struct DisplayName<FieldLimit>(PhantomData<FieldLimit>);
+
+impl<FieldLimit: Get<u32>> Convert<Vec<u8>, Option<IdentityInfo<FieldLimit>>>
+    for DisplayName<FieldLimit>
+{
+    fn convert(name: Vec<u8>) -> Option<IdentityInfo<FieldLimit>> {
+        Some(IdentityInfo {
+            additional: Default::default(),
+            display: Data::Raw(name.try_into().ok()?),
+            legal: Data::None,
+            web: Data::None,
+            riot: Data::None,
+            email: Data::None,
+            pgp_fingerprint: None,
+            image: Data::None,
+            twitter: Data::None,
+        })
+    }
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use alloc::boxed::Box;
+    use frame_support::pallet_prelude::*;
+    use frame_system::RawOrigin;
+    use pallet_identity::Judgement;
+    use sp_runtime::traits::{Hash, StaticLookup, Zero};
+
+    /// The pallet's configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_identity::Config {
+        /// Builds the identity of an account from its display name
+        type IdentityFromName: Convert<Vec<u8>, Option<Self::IdentityInformation>>;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Identity registrars, in registrar index order
+        pub registrars: Vec<T::AccountId>,
+        /// Accounts given an identity, with their display names
+        pub identities: Vec<(T::AccountId, Vec<u8>)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for registrar in &self.registrars {
+                pallet_identity::Pallet::<T>::add_registrar(
+                    RawOrigin::Root.into(),
+                    T::Lookup::unlookup(registrar.clone()),
+                )
+                .expect("genesis registrar rejected; RegistrarOrigin must accept Root");
+            }
+
+            for (who, name) in &self.identities {
+                let info = T::IdentityFromName::convert(name.clone())
+                    .expect("genesis display name too long");
+                let info_hash = T::Hashing::hash_of(&info);
+                pallet_identity::Pallet::<T>::set_identity(
+                    RawOrigin::Signed(who.clone()).into(),
+                    Box::new(info),
+                )
+                .expect("genesis identity account cannot pay the identity deposit");
+
+                let Some(registrar) = self.registrars.first() else {
+                    continue;
+                };
+                pallet_identity::Pallet::<T>::request_judgement(
+                    RawOrigin::Signed(who.clone()).into(),
+                    0,
+                    Zero::zero(),
+                )
+                .expect("first genesis registrar charges no fee");
+                pallet_identity::Pallet::<T>::provide_judgement(
+                    RawOrigin::Signed(registrar.clone()).into(),
+                    0,
+                    T::Lookup::unlookup(who.clone()),
+                    Judgement::Reasonable,
+                    info_hash,
+                )
+                .expect("judgement was requested from the first registrar");
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Unit tests for the Operator Identities pallet.
+
+use crate::{self as pallet_operator_identities, *};
+use frame_support::traits::{ConstU32, ConstU64};
+use pallet_identity::Judgement;
+use sp_core::H256;
+use sp_runtime::{
+    testing::{TestSignature, UintAuthorityId},
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Identity: pallet_identity,
+        OperatorIdentities: pallet_operator_identities,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+    type RuntimeTask = ();
+    type SingleBlockMigrations = ();
+    type MultiBlockMigrator = ();
+    type PreInherents = ();
+    type PostInherents = ();
+    type PostTransactions = ();
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU64<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type RuntimeHoldReason = ();
+    type RuntimeFreezeReason = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+}
+
+type MaxAdditionalFields = ConstU32<2>;
+
+impl pallet_identity::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type BasicDeposit = ConstU64<10>;
+    type ByteDeposit = ConstU64<1>;
+    type SubAccountDeposit = ConstU64<10>;
+    type MaxSubAccounts = ConstU32<2>;
+    type IdentityInformation = pallet_identity::legacy::IdentityInfo<MaxAdditionalFields>;
+    type MaxRegistrars = ConstU32<4>;
+    type Slashed = ();
+    type ForceOrigin = frame_system::EnsureRoot<u64>;
+    type RegistrarOrigin = frame_system::EnsureRoot<u64>;
+    type OffchainSignature = TestSignature;
+    type SigningPublicKey = UintAuthorityId;
+    type UsernameAuthorityOrigin = frame_system::EnsureRoot<u64>;
+    type PendingUsernameExpiration = ConstU64<100>;
+    type MaxSuffixLength = ConstU32<7>;
+    type MaxUsernameLength = ConstU32<32>;
+    type WeightInfo = ();
+}
+
+impl Config for Test {
+    type IdentityFromName = DisplayName<MaxAdditionalFields>;
+}
+
+const REGISTRAR: u64 = 1;
+const BRIDGE: u64 = 2;
+const VALIDATOR: u64 = 3;
+
+fn build_ext(identities: Vec<(u64, Vec<u8>)>) -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(REGISTRAR, 100), (BRIDGE, 100), (VALIDATOR, 1)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    pallet_operator_identities::GenesisConfig::<Test> {
+        registrars: vec![REGISTRAR],
+        identities,
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    t.into()
+}
+
+#[test]
+fn genesis_identities_are_set_and_judged() {
+    build_ext(vec![(BRIDGE, b"ARED Bridge".to_vec())]).execute_with(|| {
+        assert_eq!(pallet_identity::Registrars::<Test>::get().len(), 1);
+
+        let (registration, _username) = pallet_identity::IdentityOf::<Test>::get(BRIDGE).unwrap();
+        assert_eq!(
+            registration.info.display,
+            Data::Raw(b"ARED Bridge".to_vec().try_into().unwrap())
+        );
+        assert_eq!(
+            registration.judgements.into_inner(),
+            vec![(0, Judgement::Reasonable)]
+        );
+
+        // The deposit is reserved; the account keeps the rest as free balance
+        assert_eq!(Balances::reserved_balance(BRIDGE), registration.deposit);
+        assert_eq!(Balances::free_balance(BRIDGE), 100 - registration.deposit);
+    });
+}
+
+#[test]
+#[should_panic(expected = "cannot pay the identity deposit")]
+fn genesis_identity_needs_the_deposit() {
+    // VALIDATOR only holds the existential deposit
+    build_ext(vec![(VALIDATOR, b"Validator".to_vec())]);
+}
+
+#[test]
+fn display_name_is_bounded_by_raw_data() {
+    let name_of = |len: usize| DisplayName::<MaxAdditionalFields>::convert(vec![b'a'; len]);
+
+    assert!(name_of(32).is_some());
+    assert!(name_of(33).is_none());
+}
@@ -0,0 +1,27 @@
+//! Benchmarking setup for pallet-production-mode
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn enable() -> Result<(), BenchmarkError> {
+        let origin =
+            T::EnableOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        Enabled::<T>::kill();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
+
+        assert!(Enabled::<T>::get());
+        Ok(())
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Test);
+}
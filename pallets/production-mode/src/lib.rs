@@ -0,0 +1,126 @@
+//! # Production Mode Pallet
+//!
+//! This pallet records whether the chain runs in production mode, in which
+//! the runtime's call filter blocks calls that do not belong on a
+//! permissioned chain, such as sudo and plain balance transfers.
+//!
+//! ## Overview
+//!
+//! Production mode is on when either:
+//! - `ForceEnabled` is set, e.g. because the runtime was built for production
+//! - `Enabled` is set, at genesis or later through `EnableOrigin`
+//!
+//! Once on, production mode cannot be switched off again.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `enable` - Switch production mode on for good (EnableOrigin)
+//!
+//! ## Usage
+//!
+//! The pallet only keeps the flag. The runtime's `BaseCallFilter` reads
+//! [`Pallet::is_enabled`] to decide which calls to block. Clients query the
+//! `Enabled` storage item and the `ForceEnabled` constant.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    /// The pallet's configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Weight information for extrinsics.
+        type WeightInfo: WeightInfo;
+
+        /// Origin allowed to switch production mode on
+        type EnableOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Production mode regardless of `Enabled`
+        #[pallet::constant]
+        type ForceEnabled: Get<bool>;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Whether production mode was switched on at genesis or by `EnableOrigin`
+    #[pallet::storage]
+    pub type Enabled<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Start the chain in production mode
+        pub enabled: bool,
+        #[serde(skip)]
+        pub _config: core::marker::PhantomData<T>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            Enabled::<T>::put(self.enabled);
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Production mode was switched on
+        ProductionModeEnabled,
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Production mode is already on
+        AlreadyEnabled,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Switch production mode on. It cannot be switched off again.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - EnableOrigin required
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::enable())]
+        pub fn enable(origin: OriginFor<T>) -> DispatchResult {
+            T::EnableOrigin::ensure_origin(origin)?;
+            ensure!(!Self::is_enabled(), Error::<T>::AlreadyEnabled);
+
+            Enabled::<T>::put(true);
+
+            Self::deposit_event(Event::ProductionModeEnabled);
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Whether production mode is on, forced or switched on
+        pub fn is_enabled() -> bool {
+            T::ForceEnabled::get() || Enabled::<T>::get()
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Unit tests for the Production Mode pallet.
+
+use crate::{self as pallet_production_mode, *};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{ConstU32, ConstU64},
+};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        ProductionMode: pallet_production_mode,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+    type RuntimeTask = ();
+    type SingleBlockMigrations = ();
+    type MultiBlockMigrator = ();
+    type PreInherents = ();
+    type PostInherents = ();
+    type PostTransactions = ();
+}
+
+frame_support::parameter_types! {
+    pub static Forced: bool = false;
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type EnableOrigin = frame_system::EnsureRoot<u64>;
+    type ForceEnabled = Forced;
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+#[test]
+fn enable_is_governed_and_one_way() {
+    new_test_ext().execute_with(|| {
+        assert!(!ProductionMode::is_enabled());
+
+        assert_noop!(
+            ProductionMode::enable(RuntimeOrigin::signed(1)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(ProductionMode::enable(RuntimeOrigin::root()));
+        System::assert_last_event(Event::ProductionModeEnabled.into());
+        assert!(ProductionMode::is_enabled());
+
+        assert_noop!(
+            ProductionMode::enable(RuntimeOrigin::root()),
+            Error::<Test>::AlreadyEnabled
+        );
+    });
+}
+
+#[test]
+fn force_enabled_overrides_storage() {
+    new_test_ext().execute_with(|| {
+        Forced::set(true);
+        assert!(!Enabled::<Test>::get());
+        assert!(ProductionMode::is_enabled());
+        assert_noop!(
+            ProductionMode::enable(RuntimeOrigin::root()),
+            Error::<Test>::AlreadyEnabled
+        );
+        Forced::set(false);
+    });
+}
+
+#[test]
+fn genesis_can_start_in_production_mode() {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    pallet_production_mode::GenesisConfig::<Test> {
+        enabled: true,
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    sp_io::TestExternalities::from(t).execute_with(|| {
+        assert!(ProductionMode::is_enabled());
+    });
+}
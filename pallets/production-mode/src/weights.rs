@@ -0,0 +1,37 @@
+//! Weight calculations for the Production Mode pallet.
+//!
+//! These weights ensure proper transaction fee calculation
+//! and prevent denial-of-service attacks.
+
+use frame_support::{traits::Get, weights::Weight};
+
+/// Weight functions for the pallet.
+pub trait WeightInfo {
+    /// Weight for enabling production mode.
+    fn enable() -> Weight;
+}
+
+/// Default weight implementation.
+///
+/// Placeholder weights that should be replaced with benchmark results.
+pub struct SubstrateWeight<T>(core::marker::PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    /// Weight for enabling production mode.
+    ///
+    /// Operations:
+    /// - Read and write the production mode flag
+    /// - Emit event
+    fn enable() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+}
+
+/// Unit implementation for testing.
+impl WeightInfo for () {
+    fn enable() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+}
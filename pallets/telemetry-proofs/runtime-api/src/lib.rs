@@ -0,0 +1,69 @@
+//! # Telemetry Proofs Runtime API
+//!
+//! Lets indexers and RPC clients query stored telemetry proofs without
+//! decoding raw storage keys. Implemented by the runtime on top of the
+//! public helpers of `pallet-telemetry-proofs`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+    /// Read access to telemetry proofs.
+    pub trait TelemetryProofsApi<ProofMetadata, BlockNumber>
+    where
+        ProofMetadata: Codec,
+        BlockNumber: Codec,
+    {
+        /// Lifetime number of proofs submitted for a device.
+        fn proof_count(device_id: Vec<u8>) -> u64;
+
+        /// Block of the device's latest proof, if it has any retained proofs.
+        fn latest_proof_block(device_id: Vec<u8>) -> Option<BlockNumber>;
+
+        /// Unrevoked proofs whose telemetry window overlaps `[start, end]`.
+        fn proofs_in_window(device_id: Vec<u8>, start: u64, end: u64) -> Vec<ProofMetadata>;
+
+        /// Up to `count` retained proofs from `start_index`, capped by the
+        /// pallet's `MaxPageSize`, and the index of the next page (`None` when
+        /// the last proof has been returned).
+        fn proofs_paged(
+            device_id: Vec<u8>,
+            start_index: u64,
+            count: u32,
+        ) -> (Vec<ProofMetadata>, Option<u64>);
+
+        /// Same page as `proofs_paged`, with each proof flagged `true` once it
+        /// is older than the pallet's `ProofValidityBlocks`.
+        fn proof_history(
+            device_id: Vec<u8>,
+            start_index: u64,
+            count: u32,
+        ) -> (Vec<(ProofMetadata, bool)>, Option<u64>);
+
+        /// The device's latest `n` unrevoked proofs, newest first, capped by
+        /// the pallet's `MaxPageSize`.
+        fn recent_proofs(device_id: Vec<u8>, n: u32) -> Vec<ProofMetadata>;
+
+        /// Whether a valid, unexpired proof with this hash (32 raw bytes or 64 hex
+        /// characters) exists for the device.
+        fn proof_exists(device_id: Vec<u8>, proof_hash: Vec<u8>) -> bool;
+
+        /// Records the device's proofs reported for `day` (`window_end / 86400`).
+        fn records_on_day(device_id: Vec<u8>, day: u32) -> u32;
+
+        /// Device id, proof index and metadata of the retained proof with
+        /// this proof id.
+        fn lookup_proof(proof_id: [u8; 32]) -> Option<(Vec<u8>, u64, ProofMetadata)>;
+
+        /// Lifetime telemetry records of the device's accepted, unrevoked
+        /// proofs, including pruned ones.
+        fn total_records(device_id: Vec<u8>) -> u64;
+
+        /// Nonce the device's next submission must carry.
+        fn submission_nonce(device_id: Vec<u8>) -> u64;
+    }
+}
@@ -3,53 +3,233 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
+use alloc::{vec, vec::Vec};
 use frame_benchmarking::v2::*;
+use frame_support::traits::{fungible::Mutate, EnsureOrigin};
 use frame_system::RawOrigin;
 
 #[benchmarks]
 mod benchmarks {
     use super::*;
 
+    /// Device id of maximum length and a hex-encoded proof hash (the costlier
+    /// encoding to normalize), distinct per `seed`.
+    fn max_len_ids<T: Config>(seed: u32) -> (Vec<u8>, Vec<u8>) {
+        let mut device_id = vec![0u8; ared_primitives::MAX_DEVICE_ID_LENGTH as usize];
+        let mut proof_hash = vec![b'0'; 64];
+        device_id[..4].copy_from_slice(&seed.to_le_bytes());
+        for (i, byte) in seed.to_le_bytes().iter().enumerate() {
+            proof_hash[2 * i] = b"0123456789abcdef"[(byte >> 4) as usize];
+            proof_hash[2 * i + 1] = b"0123456789abcdef"[(byte & 0x0f) as usize];
+        }
+        (device_id, proof_hash)
+    }
+
     #[benchmark]
-    fn submit_proof() {
-        let caller: T::AccountId = whitelisted_caller();
-        let device_id = vec![0u8; 36];
-        let proof_hash = vec![0u8; 32];
-        let record_count = 10u32;
-        let window_start = 0u64;
-        let window_end = 3600u64;
+    fn submit_proof() -> Result<(), BenchmarkError> {
+        let origin =
+            T::SubmitterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let caller = T::SubmitterOrigin::ensure_origin(origin.clone())
+            .map_err(|_| BenchmarkError::Weightless)?;
+        // Benchmark state has no meaningful on-chain time
+        BackfillMode::<T>::put(true);
+        let deposit = T::ProofDeposit::get();
+        T::NativeBalance::set_balance(&caller, deposit + deposit + deposit);
+        let (device_id, first_hash) = max_len_ids::<T>(0);
+        let (_, proof_hash) = max_len_ids::<T>(1);
+
+        // Worst case: the chain link is checked and the oldest proof is evicted
+        Pallet::<T>::submit_proof(
+            origin.clone(),
+            device_id.clone(),
+            first_hash,
+            T::MaxRecordsPerProof::get(),
+            0,
+            3600,
+            u64::MAX,
+            1,
+            ProofCategory::EnergyUsage,
+            0,
+            None,
+            None,
+            None,
+        )
+        .expect("seed proof is valid");
+        // Submit the measured proof once the minimum interval has passed
+        frame_system::Pallet::<T>::set_block_number(
+            frame_system::Pallet::<T>::block_number() + Pallet::<T>::min_proof_interval(),
+        );
+        let bounded_device_id: DeviceId = device_id.clone().try_into().unwrap();
+        let prev_proof_hash =
+            Pallet::<T>::latest_proof_hash(&bounded_device_id).map(|h| h.as_bytes().to_vec());
+        if T::PruneOldestProofs::get() {
+            StoredProofCount::<T>::insert(&bounded_device_id, T::MaxProofsPerDevice::get() as u64);
+        }
 
         #[extrinsic_call]
         _(
-            RawOrigin::Signed(caller),
+            origin as T::RuntimeOrigin,
             device_id,
             proof_hash,
-            record_count,
-            window_start,
-            window_end,
+            T::MaxRecordsPerProof::get(),
+            3600,
+            7200,
+            u64::MAX,
+            1,
+            ProofCategory::EnergyUsage,
+            1,
+            prev_proof_hash,
+            None,
+            Some(vec![b'b'; T::MaxCidLength::get() as usize]),
         );
+
+        assert_eq!(ProofCount::<T>::get(&bounded_device_id), 2);
+        Ok(())
     }
 
+    /// `n` counts accepted entries: every entry is valid, so the result also
+    /// prices the post-dispatch refund for skipped ones.
     #[benchmark]
-    fn submit_batch_proofs(n: Linear<1, 100>) {
-        let caller: T::AccountId = whitelisted_caller();
+    fn submit_batch_proofs(n: Linear<1, { T::MaxBatchSize::get() }>) -> Result<(), BenchmarkError> {
+        BackfillMode::<T>::put(true);
+        let origin =
+            T::SubmitterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let caller = T::SubmitterOrigin::ensure_origin(origin.clone())
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let deposit = T::ProofDeposit::get();
+        T::NativeBalance::set_balance(&caller, deposit * (n + 1).into());
         let proofs: Vec<pallet::BatchProofEntry> = (0..n)
-            .map(|i| (vec![i as u8; 36], vec![i as u8; 32], 10u32, 0u64, 3600u64))
+            .map(|i| {
+                let (device_id, proof_hash) = max_len_ids::<T>(i);
+                (
+                    device_id,
+                    proof_hash,
+                    T::MaxRecordsPerProof::get(),
+                    0u64,
+                    3600u64,
+                    u64::MAX,
+                    1u16,
+                    ProofCategory::EnergyUsage,
+                    0u64,
+                )
+            })
             .collect();
 
         #[extrinsic_call]
-        _(RawOrigin::Signed(caller), proofs);
+        _(origin as T::RuntimeOrigin, proofs);
+
+        assert_eq!(TotalProofs::<T>::get(), n as u64);
+        Ok(())
     }
 
     #[benchmark]
     fn verify_proof() {
+        BackfillMode::<T>::put(true);
         let caller: T::AccountId = whitelisted_caller();
-        let device_id = vec![0u8; 36];
-        let proof_hash = vec![0u8; 32];
+        let (device_id, proof_hash) = max_len_ids::<T>(0);
+        Pallet::<T>::do_submit_proof(
+            None,
+            device_id.clone(),
+            proof_hash.clone(),
+            T::MaxRecordsPerProof::get(),
+            0,
+            3600,
+            u64::MAX,
+            1,
+            ProofCategory::EnergyUsage,
+            0,
+            None,
+            None,
+        )
+        .expect("seed proof is valid");
 
         #[extrinsic_call]
         _(RawOrigin::Signed(caller), device_id, proof_hash);
     }
 
+    #[benchmark]
+    fn verify_proofs(n: Linear<1, { T::MaxBatchSize::get() }>) {
+        BackfillMode::<T>::put(true);
+        let caller: T::AccountId = whitelisted_caller();
+        let mut queries = Vec::new();
+        for i in 0..n {
+            let (device_id, proof_hash) = max_len_ids::<T>(i);
+            Pallet::<T>::do_submit_proof(
+                None,
+                device_id.clone(),
+                proof_hash.clone(),
+                T::MaxRecordsPerProof::get(),
+                0,
+                3600,
+                u64::MAX,
+                1,
+                ProofCategory::EnergyUsage,
+                0,
+                None,
+                None,
+            )
+            .expect("seed proof is valid");
+            queries.push((device_id, proof_hash));
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), queries);
+    }
+
+    /// `n` proofs with windows far from the queried one: the reads stay
+    /// constant because only the buckets covering the query are touched.
+    #[benchmark]
+    fn get_proofs_in_window(n: Linear<1, 500>) {
+        BackfillMode::<T>::put(true);
+        let (device_id, _) = max_len_ids::<T>(0);
+        let bucket_seconds = T::WindowBucketSeconds::get().max(1);
+        for i in 0..=n {
+            let (_, proof_hash) = max_len_ids::<T>(i);
+            frame_system::Pallet::<T>::set_block_number(
+                frame_system::Pallet::<T>::block_number() + Pallet::<T>::min_proof_interval(),
+            );
+            // Proof 0 lies in the queried window, every other one buckets away
+            let window_start = (i as u64) * 2 * bucket_seconds;
+            Pallet::<T>::do_submit_proof(
+                None,
+                device_id.clone(),
+                proof_hash,
+                1,
+                window_start,
+                window_start + 1,
+                0,
+                1,
+                ProofCategory::EnergyUsage,
+                i as u64,
+                None,
+                None,
+            )
+            .expect("seed proof is valid");
+        }
+        let bounded_device_id: DeviceId = device_id.try_into().unwrap();
+
+        let proofs;
+        #[block]
+        {
+            proofs = Pallet::<T>::get_proofs_in_window(&bounded_device_id, 0, 1, None);
+        }
+
+        assert_eq!(proofs.len(), 1);
+    }
+
+    #[benchmark]
+    fn heartbeat() -> Result<(), BenchmarkError> {
+        let origin =
+            T::SubmitterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let caller = T::SubmitterOrigin::ensure_origin(origin.clone())
+            .map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
+
+        assert!(Pallet::<T>::last_heartbeat(&caller).is_some());
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Test);
 }
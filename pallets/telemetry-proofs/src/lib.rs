@@ -25,8 +25,283 @@
 //! ### Dispatchable Functions
 //!
 //! - `submit_proof` - Submit a new telemetry proof for a device
+//! - `submit_proof_with_energy` - Submit a proof and report the energy it covers in one call
 //! - `submit_batch_proofs` - Submit multiple proofs in a single transaction
 //! - `verify_proof` - Verify a telemetry proof exists on-chain
+//! - `verify_proofs` - Verify many proofs at once, reporting a result bitmap
+//! - `prune_proofs_before` - Remove a bounded number of a device's oldest proofs (admin or device owner)
+//! - `register_device_key` - Register a device's sr25519 signing key (admin)
+//! - `rotate_device_key` - Replace a device's signing key from the current block on (admin or device owner)
+//! - `mark_key_compromised` - Invalidate proofs signed with a device key from a given block on (admin)
+//! - `revoke_compromised_proofs` - Revoke a bounded number of proofs signed with a compromised key
+//! - `revoke_proof` - Mark a proof as revoked with a reason code (submitter or admin)
+//! - `challenge_proof` - Contest a proof, holding a deposit from the challenger
+//! - `resolve_challenge` - Uphold (revoke) or dismiss a challenge (dispute origin)
+//! - `expire_challenge` - Close a challenge left unresolved past `ChallengePeriod`
+//! - `verify_merkle_inclusion` - Prove a telemetry record is included in a stored batch root
+//! - `add_trusted_submitter` / `remove_trusted_submitter` - Manage the submitter whitelist (admin)
+//! - `set_open_submission` - Toggle whether any signed account may submit proofs (admin)
+//! - `add_supported_schema` / `remove_supported_schema` - Manage accepted aggregation schema versions (admin)
+//! - `set_retention_blocks` - Set or clear the automatic proof retention period (admin)
+//! - `set_anchor` - Record where a proof was checkpointed on an external chain (anchor origin)
+//! - `set_backfill_mode` - Skip window time checks while loading historical telemetry (admin)
+//! - `freeze_device` / `unfreeze_device` - Block or allow new proofs for a decommissioned device (admin)
+//! - `set_content_cid` - Attach the IPFS CID of a proof's telemetry batch (submitter, once)
+//! - `set_max_total_devices` - Set or clear the cap on distinct devices with proofs (admin)
+//! - `set_min_proof_interval` - Override or restore the minimum blocks between a device's proofs (admin)
+//! - `set_proof_validity_blocks` - Set or clear how long proofs count as evidence (admin)
+//! - `remove_device_proofs` - Remove a retired device's proofs in bounded steps (admin)
+//! - `heartbeat` - Record that a submitter's offchain worker is alive (submitter)
+//!
+//! ## Window Time Checks
+//!
+//! Outside backfill mode, a proof's `window_end` may lie at most
+//! `MaxClockDrift` seconds ahead of `TimeProvider`, and when `MaxWindowAge`
+//! is set its `window_start` may be at most that many seconds in the past.
+//!
+//! ## Window Index
+//!
+//! `ProofsByWindowEnd` lists a device's unrevoked proofs by
+//! `window_end / WindowBucketSeconds`, and `WindowIndexBounds` keeps the
+//! device's first and last bucket and its longest window. A window query only
+//! reads the buckets in which an overlapping window can end, so its cost
+//! follows the queried range rather than the number of stored proofs. A proof
+//! whose bucket already holds `MaxProofsPerWindowBucket` proofs is rejected
+//! with `WindowBucketFull`.
+//!
+//! ## Proof Interval
+//!
+//! Telemetry is aggregated over fixed windows, so a device's proofs arrive
+//! blocks apart. A proof submitted fewer than `MinProofInterval` blocks after
+//! the device's `LatestProofBlock` is rejected with `ProofTooFrequent`;
+//! `AdminOrigin` may override the interval in storage. With
+//! `ExemptTrustedFromProofInterval` set, submitters in `ExemptSubmitters`
+//! skip the check.
+//!
+//! ## Proof Expiry
+//!
+//! When `ProofValidityBlocks` is set, a proof submitted more than that many
+//! blocks ago is expired: `proof_exists`, `verify_proof`, `verify_proofs` and
+//! [`ProofProvider::proof_exists`] no longer accept it, and `verify_proof`
+//! reports it as found but `expired`. Expiry is computed on read, so expired
+//! proofs stay stored and `get_proof_history` returns them flagged.
+//!
+//! ## Automatic Retention
+//!
+//! When `RetentionBlocks` is set, the `on_idle` hook removes proofs submitted
+//! more than `RetentionBlocks` blocks ago. Devices are walked in storage
+//! order from `RetentionCursor`, so cleanup resumes where the previous block
+//! ran out of idle weight.
+//!
+//! ## Proof Hashes
+//!
+//! Proof hashes are stored as `H256`. Submissions may pass the hash as 32 raw
+//! bytes or as a 64-character hex string (see
+//! [`Pallet::normalize_proof_hash`]); anything else is rejected with
+//! `InvalidProofHash`.
+//!
+//! ## Proof Categories
+//!
+//! Each proof carries a [`ProofCategory`] naming the kind of telemetry it
+//! covers. Window queries can filter on it, `CategoryProofCount` tracks
+//! lifetime counts per device and category, and only `EnergyUsage` proofs
+//! satisfy [`ProofProvider::proof_exists`].
+//!
+//! ## Submission Nonces
+//!
+//! Every submission carries the device's expected `SubmissionNonce`, which
+//! advances by one with each stored proof. A nonce that is repeated or skips
+//! ahead is rejected with `BadSubmissionNonce`, so a replayed extrinsic
+//! payload cannot be stored again. The bridge resyncs after a restart by
+//! reading the nonce back.
+//!
+//! ## Content CIDs
+//!
+//! Raw telemetry batches are archived to IPFS. A proof may carry the batch's
+//! content CID, checked only against `MaxCidLength`, either passed to
+//! `submit_proof` or attached later by the submitter with `set_content_cid`.
+//! Once set, a proof's CID cannot be replaced.
+//!
+//! ## Device Limit
+//!
+//! Each new device id opens a new storage subtree. `KnownDeviceCount` counts
+//! the devices that have ever stored a proof, and while `MaxTotalDevices` is
+//! set a device's first proof is rejected with `DeviceLimitReached` once the
+//! count has reached it. Devices that already have proofs keep submitting.
+//!
+//! ## Retired Devices
+//!
+//! Proofs from devices the `Devices` interface reports as `Retired` are
+//! rejected with `DeviceRetired`. Unregistered devices are not affected, so
+//! proofs can be anchored before a device is registered.
+//!
+//! ## Proofs With Energy
+//!
+//! `submit_proof_with_energy` stores a proof exactly like `submit_proof` and
+//! then hands the new proof index and the reported energy to
+//! `OnProofAccepted` (the carbon credits pallet in the runtime). Both halves
+//! succeed or fail together: if the hook rejects the energy, the proof is not
+//! stored either.
+//!
+//! ## Proof Notifications
+//!
+//! Every accepted proof, whether submitted alone, in a batch or unsigned, is
+//! passed to `OnProofSubmitted` with its index, record count and window once
+//! it is stored. Rejected submissions and batch entries are not reported.
+//! Handlers cannot fail the submission; their weight is charged per proof.
+//!
+//! ## Decommissioning
+//!
+//! When the device registry decommissions a device, this pallet's
+//! `OnDecommission` hook freezes the device and queues its retained proofs in
+//! `PendingProofRemovals`. The `on_idle` hook then removes them oldest first,
+//! after retention pruning, emitting
+//! `DeviceProofsRemoved` for each step until the device's proof state is
+//! cleared.
+//!
+//! ## Event Topics
+//!
+//! `ProofSubmitted` is indexed under the device's topic,
+//! `blake2_256(device_id)` over the raw id bytes (see `DeviceId::topic`), so
+//! light clients can follow a device through `System::event_topics` without
+//! decoding every block. Other events are deposited without topics.
+//!
+//! ## Proof Chaining
+//!
+//! Every stored proof records the hash of the device's previous proof.
+//! `submit_proof` callers must pass that hash as `prev_proof_hash` (or `None`
+//! for a device's first proof), so a batch cannot be silently dropped between
+//! two anchored ones. Batch and unsigned submissions are linked to the latest
+//! stored proof automatically. [`Pallet::verify_chain`] walks the links.
+//!
+//! ## Offchain Index
+//!
+//! With `OffchainIndexing` enabled every accepted proof is written to the
+//! offchain database under [`OFFCHAIN_PROOF_PREFIX`] followed by the
+//! blake2-256 hash of the proof hash (see [`Pallet::offchain_proof_key`]).
+//! The value is the SCALE encoding of `(device_id, block_number, proof_index)`,
+//! so explorers can resolve a proof hash without running their own indexer.
+//!
+//! ## Merkle Inclusion
+//!
+//! Proof hashes are 32-byte Merkle roots over the individual telemetry
+//! records of a batch. An inclusion path lists sibling hashes from the leaf
+//! upwards; the flag is `true` when the sibling is the left child, so each
+//! step hashes `blake2_256(left ++ right)`.
+//!
+//! ## Block Proof Roots
+//!
+//! Every accepted proof contributes the leaf
+//! `blake2_256(SCALE(device_id, proof_index, proof_hash))` (see
+//! [`Pallet::block_proof_leaf`]) to a per-block tree built the same way, with
+//! an unpaired node carried up unchanged. In `on_finalize` the root is stored
+//! in `BlockProofRoots` for `ProofRootRetention` blocks and deposited as a
+//! consensus digest log under [`PROOF_ROOT_ENGINE_ID`], so light clients can
+//! check inclusion from headers alone. [`Pallet::block_proof_inclusion_path`]
+//! builds paths for the current block's proofs.
+//!
+//! ## Challenges
+//!
+//! Challenging a proof holds `ChallengeDeposit` from the challenger
+//! (`HoldReason::ChallengeDeposit`). A challenged proof is not valid until
+//! its challenge is closed:
+//!
+//! - Upheld: the proof is revoked and the challenger's deposit is returned.
+//! - Dismissed: the deposit goes to the proof's submitter, or to
+//!   `TreasuryAccount` for unsigned proofs.
+//! - Expired: after `ChallengePeriod` blocks without resolution anyone may
+//!   close the challenge; the proof is restored and the deposit returned.
+//!
+//! ## Submitter Origin
+//!
+//! The signed submission calls accept whatever `SubmitterOrigin` admits and
+//! reject other origins with `BadOrigin`. The runtime shares one bridge
+//! membership origin between this pallet and the carbon credits pallet.
+//! [`EnsureAuthorizedSubmitter`] keeps the pallet's own whitelist for chains
+//! without one: while `OpenSubmission` is false it only admits accounts in
+//! `TrustedSubmitters`. Both are seeded from the genesis config, and such
+//! chains set `ExemptSubmitters` to [`TrustedSubmitterList`] so the same
+//! accounts are trusted. Runtimes with a shared origin leave both empty and
+//! trust a `Contains` of their own.
+//!
+//! ## Feeless Submissions
+//!
+//! `submit_proof`, `submit_batch_proofs`, `submit_proof_with_energy` and
+//! `heartbeat` are marked `feeless_if` the origin passes `SubmitterOrigin` and its account is
+//! in `FeelessSubmitters`. Runtimes that wrap `ChargeTransactionPayment` in
+//! `pallet_skip_feeless_payment::SkipCheckIfFeeless` then charge those
+//! accounts no fee; every other caller pays as usual.
+//!
+//! ## Operational Dispatch
+//!
+//! `submit_proof`, `submit_batch_proofs` and `submit_proof_with_energy` are
+//! `DispatchClass::Operational`, so they use block space a runtime reserves
+//! for operational calls and still fit when normal transactions fill their
+//! share of the block. Only `SubmitterOrigin` may dispatch them; any other
+//! signer pays the full fee for a call that fails with `BadOrigin`.
+//!
+//! ## Proof Deposits
+//!
+//! Signed submitters outside `ExemptSubmitters` have `ProofDeposit` held
+//! (`HoldReason::ProofDeposit`) for every stored proof. The hold is released
+//! when the proof is pruned, evicted or revoked.
+//!
+//! ## Device Signatures
+//!
+//! Once a device has a registered key, `submit_proof` requires a signature by
+//! that key over the SCALE encoding of
+//! `(device_id, proof_hash, window_start, window_end)` (see
+//! [`Pallet::signing_payload`]). Batch and unsigned submissions carry no
+//! signature and are rejected for devices with a registered key.
+//!
+//! Keys are kept as a bounded history of `(key, valid_from_block)` entries.
+//! A submission is checked against the key valid at the current block, and
+//! [`Pallet::verify_device_signature_at`] checks historical signatures against
+//! the key that was valid when the proof was submitted.
+//!
+//! ## Compromised Keys
+//!
+//! `mark_key_compromised` records that a device key leaked from
+//! `compromised_from_block` on. Device-signed proofs submitted in that window
+//! and signed with that key immediately stop counting as valid in
+//! `proof_exists`, `verify_proof` and `verify_proofs`, even before they are
+//! revoked. `revoke_compromised_proofs` then walks the device's retained
+//! proofs from a per-device cursor in bounded steps, revoking each affected
+//! proof with `RevocationReason::DeviceCompromised` so its deposit, hash and
+//! window index entries are released like any other revocation.
+//!
+//! ## Offchain Worker Heartbeats
+//!
+//! Every `HeartbeatInterval` blocks the offchain worker submits a signed
+//! `heartbeat` for each local key of type [`KEY_TYPE`] (`ared`), signing with
+//! the application crypto in [`crypto`]. The call records the block in
+//! `LastHeartbeat` for accounts admitted by `SubmitterOrigin`, so operators
+//! can see that a bridge node is up even while no telemetry flows. Nodes
+//! without such a key submit nothing, and a zero interval disables the
+//! heartbeats.
+//!
+//! ## Unsigned Proofs
+//!
+//! `submit_proof_unsigned` takes a [`ProofPayload`] and a signature over its
+//! SCALE encoding by a key of the `AuthorityId` crypto, so a bridge can
+//! submit proofs without a funded account paying fees. `ValidateUnsigned`
+//! checks, before the transaction enters the pool and again when it is
+//! included, that:
+//!
+//! - The signature matches the payload's `public` key
+//! - The payload's `genesis_hash` is this chain's, so it cannot be replayed
+//!   on another chain
+//! - The key's account is admitted by `SubmitterOrigin`
+//! - The payload's `nonce` is at most `UnsignedNonceLookahead` ahead of the
+//!   device's current `SubmissionNonce`
+//!
+//! A nonce already used is `Stale`, so an included payload cannot be
+//! replayed, and one further ahead is `Future`. The transaction provides the
+//! tag `(device_id, nonce)` and, ahead of the current nonce, requires
+//! `(device_id, nonce - 1)`, so the pool keeps one payload per device and
+//! nonce and includes them in order. Unsigned proofs have no submitter: they
+//! hold no deposit and are not exempt from `MinProofInterval`. Batches are
+//! only accepted signed, through `submit_batch_proofs`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -34,36 +309,294 @@ extern crate alloc;
 
 pub use pallet::*;
 
+/// Prefix of the offchain index keys for proof hashes
+pub const OFFCHAIN_PROOF_PREFIX: &[u8] = b"telemetry-proofs::proof::";
+
+/// Engine id of the digest log carrying a block's proof root
+pub const PROOF_ROOT_ENGINE_ID: sp_runtime::ConsensusEngineId = *b"tprf";
+
+/// Log target for the pallet
+pub const LOG_TARGET: &str = "runtime::telemetry-proofs";
+
+/// Length of a `DailyRecordCounts` day in seconds
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Key type of the offchain worker keys signing heartbeats
+pub const KEY_TYPE: sp_core::crypto::KeyTypeId = sp_core::crypto::KeyTypeId(*b"ared");
+
+/// Sr25519 application crypto of the offchain worker keys.
+pub mod crypto {
+    use super::KEY_TYPE;
+    use sp_core::sr25519::{Public as Sr25519Public, Signature as Sr25519Signature};
+    use sp_runtime::{
+        app_crypto::{app_crypto, sr25519},
+        MultiSignature, MultiSigner,
+    };
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    /// Signs offchain worker transactions of runtimes using `MultiSignature`
+    pub struct OffchainAuthId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OffchainAuthId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = Sr25519Signature;
+        type GenericPublic = Sr25519Public;
+    }
+}
+
 #[cfg(test)]
 mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod traits;
 pub mod weights;
+pub use ared_primitives::DeviceId;
+pub use traits::{
+    EnsureAuthorizedSubmitter, OnProofAccepted, OnProofSubmitted, ProofProvider,
+    TrustedSubmitterList,
+};
 pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use alloc::vec::Vec;
-    use frame_support::pallet_prelude::*;
-    use frame_system::pallet_prelude::*;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{
+            fungible::{self, MutateHold},
+            tokens::{Fortitude, Precision, Restriction},
+            Contains, UnixTime,
+        },
+    };
+    use frame_system::{
+        offchain::{
+            AppCrypto, CreateSignedTransaction, SendSignedTransaction, SignedPayload, Signer,
+            SigningTypes,
+        },
+        pallet_prelude::*,
+    };
+    use pallet_device_registry::{DeviceInterface, DeviceStatus};
+    use sp_core::{sr25519, H256};
     use sp_runtime::transaction_validity::{
-        InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction,
+        InvalidTransaction, TransactionSource, TransactionValidity, TransactionValidityError,
+        ValidTransaction,
     };
+    use sp_runtime::{
+        traits::{CheckedSub, IdentifyAccount, Zero},
+        DigestItem, Percent,
+    };
+
+    /// Batch proof entry: (device_id, proof_hash, record_count, window_start, window_end,
+    /// data_size_bytes, schema_version, category, nonce)
+    pub type BatchProofEntry = (
+        Vec<u8>,
+        Vec<u8>,
+        u32,
+        u64,
+        u64,
+        u64,
+        u16,
+        ProofCategory,
+        u64,
+    );
+
+    /// Proof submitted by `submit_proof_unsigned`, signed by `public`
+    #[derive(Clone, Encode, Decode, TypeInfo, Debug, PartialEq)]
+    pub struct ProofPayload<Public, Hash> {
+        /// The device identifier
+        pub device_id: Vec<u8>,
+        /// Hash of the telemetry batch, 32 raw bytes or 64 hex characters
+        pub proof_hash: Vec<u8>,
+        /// Number of telemetry records in the batch
+        pub record_count: u32,
+        /// Start timestamp of the telemetry window
+        pub window_start: u64,
+        /// End timestamp of the telemetry window
+        pub window_end: u64,
+        /// Size in bytes of the raw telemetry covered
+        pub data_size_bytes: u64,
+        /// Aggregation schema that produced the proof hash
+        pub schema_version: u16,
+        /// Kind of telemetry the proof covers
+        pub category: ProofCategory,
+        /// The device's `SubmissionNonce` the proof is stored under
+        pub nonce: u64,
+        /// Hash of the chain's genesis block, so the signature is only valid
+        /// on this chain
+        pub genesis_hash: Hash,
+        /// Key that signed the payload; its account must pass `SubmitterOrigin`
+        pub public: Public,
+    }
+
+    impl<T: SigningTypes, Hash: Encode> SignedPayload<T> for ProofPayload<T::Public, Hash> {
+        fn public(&self) -> T::Public {
+            self.public.clone()
+        }
+    }
+
+    /// Balance type of the currency holding proof and challenge deposits
+    pub type DepositBalanceOf<T> = <<T as Config>::NativeBalance as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    /// Kind of telemetry a proof covers
+    #[derive(
+        Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq, Default,
+    )]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub enum ProofCategory {
+        /// Energy usage readings; the only category carbon-credits accepts
+        #[default]
+        EnergyUsage,
+        /// Temperature logs
+        Temperature,
+        /// Uptime heartbeats
+        Uptime,
+        /// Any other kind, identified by an off-chain code
+        Other(u8),
+    }
+
+    /// Why a proof was revoked
+    #[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub enum RevocationReason {
+        /// The anchored telemetry batch was found to be corrupted
+        CorruptedData,
+        /// The batch was anchored more than once
+        DuplicateSubmission,
+        /// The device or its signing key was compromised
+        DeviceCompromised,
+        /// The ingest service anchored the batch in error
+        IngestError,
+        /// Any other reason, documented off-chain
+        Other,
+        /// A challenge against the proof was upheld
+        ChallengeUpheld,
+    }
+
+    /// Why an entry of a signed batch was not stored
+    #[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+    pub enum BatchRejectReason {
+        /// `window_start` was not before `window_end`
+        InvalidWindow,
+        /// The device id exceeded `MaxDeviceIdLength`
+        DeviceIdTooLong,
+        /// The proof hash was neither 32 raw bytes nor 64 hex characters
+        InvalidProofHash,
+        /// The proof hash is already anchored by a live proof
+        Duplicate,
+        /// The aggregation schema version is not in `SupportedSchemas`
+        UnsupportedSchema,
+        /// The device is at `MaxProofsPerDevice` and pruning is disabled
+        TooManyProofs,
+        /// `window_end` lies beyond `MaxClockDrift` of on-chain time
+        WindowInFuture,
+        /// `window_start` is older than `MaxWindowAge`
+        WindowTooOld,
+        /// The device is frozen
+        DeviceFrozen,
+        /// The entry's nonce was not the device's expected submission nonce
+        BadSubmissionNonce,
+        /// `record_count` was zero
+        EmptyProof,
+        /// `record_count` exceeded `MaxRecordsPerProof`
+        TooManyRecords,
+        /// The device has no proofs and `MaxTotalDevices` has been reached
+        DeviceLimitReached,
+        /// The device's previous proof is less than the minimum interval old
+        ProofTooFrequent,
+        /// The device's window index bucket for `window_end` is full
+        WindowBucketFull,
+        /// Any other submission error
+        Other,
+        /// The device is retired in the device registry
+        DeviceRetired,
+    }
+
+    /// Submission accounting per signed submitter
+    #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq, Default)]
+    pub struct SubmitterInfo<BlockNumber> {
+        /// Proofs accepted from this submitter
+        pub proofs: u64,
+        /// Batch submissions made by this submitter
+        pub batches: u64,
+        /// Telemetry records covered by the accepted proofs
+        pub records: u64,
+        /// Block of the submitter's latest accepted proof or batch
+        pub last_block: BlockNumber,
+    }
 
-    /// Batch proof entry: (device_id, proof_hash, record_count, window_start, window_end)
-    pub type BatchProofEntry = (Vec<u8>, Vec<u8>, u32, u64, u64);
+    /// Running totals of the quality scores set for a device's proofs
+    #[derive(
+        Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq, Default,
+    )]
+    pub struct QualitySummary {
+        /// Sum of the current score of every scored proof
+        pub total: u64,
+        /// Number of scored proofs
+        pub scored: u32,
+    }
+
+    impl QualitySummary {
+        /// Average score over the scored proofs, rounded down.
+        pub fn average(&self) -> Option<u8> {
+            (self.scored > 0).then(|| (self.total / self.scored as u64) as u8)
+        }
+    }
+
+    /// Extent of a device's entries in `ProofsByWindowEnd`
+    #[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+    pub struct WindowBounds {
+        /// Lowest bucket that received a proof
+        pub first_bucket: u64,
+        /// Highest bucket that received a proof
+        pub last_bucket: u64,
+        /// Longest `window_end - window_start` of the device's proofs
+        pub longest_window: u64,
+    }
+
+    /// Reference to an external chain transaction checkpointing a proof
+    #[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq)]
+    pub struct AnchorRef {
+        /// Identifier of the external chain
+        pub chain_id: u32,
+        /// Hash of the anchoring transaction
+        pub tx_hash: H256,
+        /// Time the anchor was made (UNIX timestamp)
+        pub anchored_at: u64,
+    }
+
+    /// An open challenge against a proof
+    #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq)]
+    #[scale_info(skip_type_params(T))]
+    pub struct ProofChallenge<T: Config> {
+        /// Account that raised the challenge
+        pub challenger: T::AccountId,
+        /// Deposit held from the challenger
+        pub deposit: DepositBalanceOf<T>,
+        /// Hash of the off-chain evidence backing the challenge
+        pub evidence_hash: T::Hash,
+        /// Block at which the challenge was raised
+        pub challenged_at: BlockNumberFor<T>,
+    }
 
     /// Proof metadata stored alongside the hash
     #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, Debug, PartialEq)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(bound = ""))]
     #[scale_info(skip_type_params(T))]
     pub struct ProofMetadata<T: Config> {
         /// The proof hash (SHA-256 of telemetry batch)
-        pub proof_hash: BoundedVec<u8, T::MaxProofLength>,
+        pub proof_hash: H256,
         /// Block number when proof was submitted
-        pub block_number: BlockNumberFor<T>,
+        pub submitted_block: BlockNumberFor<T>,
+        /// Account that submitted the proof (`None` for unsigned submissions)
+        pub submitter: Option<T::AccountId>,
         /// Timestamp of submission (from pallet_timestamp if available)
         pub timestamp: u64,
         /// Number of telemetry records in this batch
@@ -72,35 +605,211 @@ pub mod pallet {
         pub window_start: u64,
         /// End time of the telemetry window (UNIX timestamp)
         pub window_end: u64,
+        /// Size in bytes of the raw telemetry covered by the proof
+        pub data_size_bytes: u64,
+        /// Version of the aggregation schema that produced the proof hash
+        pub schema_version: u16,
+        /// Kind of telemetry the proof covers
+        pub category: ProofCategory,
+        /// Whether the proof carried a valid signature by the device key
+        pub device_signed: bool,
+        /// Hash of the device's previous stored proof (`None` for the first)
+        pub prev_proof_hash: Option<H256>,
+        /// Revocation reason, set once the proof has been revoked
+        pub revoked: Option<RevocationReason>,
+        /// Whether the proof has an open challenge
+        pub challenged: bool,
+        /// Anomaly score set by the oracle (`None` until scored)
+        pub quality_score: Option<u8>,
+        /// IPFS CID of the archived telemetry batch, if provided
+        pub content_cid: Option<BoundedVec<u8, T::MaxCidLength>>,
+    }
+
+    impl<T: Config> ProofMetadata<T> {
+        /// Whether the proof still counts as valid evidence.
+        pub fn is_valid(&self) -> bool {
+            self.revoked.is_none() && !self.challenged
+        }
     }
 
     /// The pallet's configuration trait.
     #[pallet::config]
-    pub trait Config: frame_system::Config + pallet_timestamp::Config {
+    pub trait Config:
+        CreateSignedTransaction<Call<Self>> + frame_system::Config + pallet_timestamp::Config
+    {
         /// The overarching event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
 
-        /// Maximum length of device ID (typically UUID = 36 bytes)
+        /// Origin allowed to perform administrative actions such as pruning
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to submit proofs through the signed submission calls
+        type SubmitterOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+        /// Submitters whose signed submissions are feeless
+        type FeelessSubmitters: Contains<Self::AccountId>;
+
+        /// Trusted submitters: no `ProofDeposit` is held from them, and with
+        /// `ExemptTrustedFromProofInterval` they skip `MinProofInterval`
+        type ExemptSubmitters: Contains<Self::AccountId>;
+
+        /// The overarching hold reason.
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// Currency used to hold proof and challenge deposits
+        type NativeBalance: fungible::Mutate<Self::AccountId>
+            + fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// Deposit held from a non-trusted submitter for every stored proof
+        #[pallet::constant]
+        type ProofDeposit: Get<DepositBalanceOf<Self>>;
+
+        /// Origin allowed to resolve proof challenges
+        type DisputeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to record external chain anchors for proofs
+        type AnchorOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to set proof quality scores
+        type OracleOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Deposit held from a challenger until the challenge is closed
+        #[pallet::constant]
+        type ChallengeDeposit: Get<DepositBalanceOf<Self>>;
+
+        /// Blocks after which an unresolved challenge may be expired
         #[pallet::constant]
-        type MaxDeviceIdLength: Get<u32>;
+        type ChallengePeriod: Get<BlockNumberFor<Self>>;
 
-        /// Maximum length of proof hash (SHA-256 = 32 bytes, hex = 64 bytes)
+        /// Receives dismissed challenge deposits for proofs without a submitter
+        type TreasuryAccount: Get<Self::AccountId>;
+
+        /// Whether a device may resubmit a proof hash it already anchored.
+        /// Hashes anchored by a different device are always rejected.
+        #[pallet::constant]
+        type AllowSameDeviceResubmission: Get<bool>;
+
+        /// Whether accepted proofs are written to the offchain index.
+        /// Only useful on chains whose nodes run with offchain indexing enabled.
         #[pallet::constant]
-        type MaxProofLength: Get<u32>;
+        type OffchainIndexing: Get<bool>;
 
         /// Maximum number of proofs in a batch submission
         #[pallet::constant]
         type MaxBatchSize: Get<u32>;
 
+        /// How far ahead of a device's `SubmissionNonce` an unsigned proof
+        /// may be and still wait in the transaction pool for its predecessors
+        #[pallet::constant]
+        type UnsignedNonceLookahead: Get<u64>;
+
+        /// Maximum number of telemetry records a single proof may cover
+        #[pallet::constant]
+        type MaxRecordsPerProof: Get<u32>;
+
+        /// Maximum length of a proof's IPFS content CID
+        #[pallet::constant]
+        type MaxCidLength: Get<u32>;
+
         /// Maximum number of proofs stored per device
         #[pallet::constant]
         type MaxProofsPerDevice: Get<u32>;
+
+        /// Whether a device at `MaxProofsPerDevice` evicts its oldest proof
+        /// (ring buffer) instead of rejecting new submissions
+        #[pallet::constant]
+        type PruneOldestProofs: Get<bool>;
+
+        /// Share of `MaxProofsPerDevice` at which `ProofStorageNearlyFull` is
+        /// emitted for a device
+        #[pallet::constant]
+        type ProofStorageWarningPercent: Get<Percent>;
+
+        /// Maximum number of signing keys remembered per device
+        #[pallet::constant]
+        type MaxKeyHistory: Get<u32>;
+
+        /// Maximum number of proofs returned by a single query helper
+        #[pallet::constant]
+        type MaxQueryResults: Get<u32>;
+
+        /// Maximum number of proofs returned by one page of `get_proofs_paged`
+        #[pallet::constant]
+        type MaxPageSize: Get<u32>;
+
+        /// Maximum number of siblings in a Merkle inclusion path
+        #[pallet::constant]
+        type MaxMerkleDepth: Get<u32>;
+
+        /// Maximum number of proofs accepted in a single block
+        #[pallet::constant]
+        type MaxProofsPerBlock: Get<u32>;
+
+        /// Source of on-chain time for checking proof windows
+        type TimeProvider: UnixTime;
+
+        /// Device registry; retired devices may not submit proofs
+        type Devices: DeviceInterface<DeviceId, Self::AccountId>;
+
+        /// Receives the energy reported by `submit_proof_with_energy`
+        type OnProofAccepted: OnProofAccepted<DeviceId>;
+
+        /// Notified after every accepted proof; `()` when nothing listens
+        type OnProofSubmitted: OnProofSubmitted<DeviceId, BlockNumberFor<Self>>;
+
+        /// Seconds a proof's `window_end` may lie ahead of on-chain time
+        #[pallet::constant]
+        type MaxClockDrift: Get<u64>;
+
+        /// Maximum age in seconds of a proof's `window_start`, if limited
+        #[pallet::constant]
+        type MaxWindowAge: Get<Option<u64>>;
+
+        /// Minimum number of blocks between two proofs of the same device,
+        /// unless overridden by `MinProofIntervalOverride`
+        #[pallet::constant]
+        type MinProofInterval: Get<BlockNumberFor<Self>>;
+
+        /// Whether `ExemptSubmitters` skip the minimum proof interval
+        #[pallet::constant]
+        type ExemptTrustedFromProofInterval: Get<bool>;
+
+        /// Width in seconds of the `window_end` buckets of `ProofsByWindowEnd`
+        #[pallet::constant]
+        type WindowBucketSeconds: Get<u64>;
+
+        /// Maximum number of a device's proofs whose windows end in one bucket
+        #[pallet::constant]
+        type MaxProofsPerWindowBucket: Get<u32>;
+
+        /// Number of days of per-device record counts kept in `DailyRecordCounts`
+        #[pallet::constant]
+        type MaxDaysRetained: Get<u32>;
+
+        /// Number of blocks for which per-block proof roots are kept
+        #[pallet::constant]
+        type ProofRootRetention: Get<BlockNumberFor<Self>>;
+
+        /// Key the offchain worker signs heartbeats with, and the crypto of
+        /// the keys signing `submit_proof_unsigned` payloads
+        type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+        /// Blocks between two offchain worker heartbeats; zero disables them
+        #[pallet::constant]
+        type HeartbeatInterval: Get<BlockNumberFor<Self>>;
     }
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(10);
+
+    /// Raw storage key from which an `on_idle` sweep resumes
+    pub type BackfillCursor = BoundedVec<u8, ConstU32<256>>;
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Telemetry proofs indexed by device and proof index
@@ -109,174 +818,762 @@ pub mod pallet {
     pub type Proofs<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
-        BoundedVec<u8, T::MaxDeviceIdLength>, // device_id
+        DeviceId, // device_id
         Blake2_128Concat,
         u64, // proof index
         ProofMetadata<T>,
         OptionQuery,
     >;
 
-    /// Proofs indexed by block number for time-range queries
+    /// Proof indexes submitted per block and device, for historical queries
     #[pallet::storage]
-    #[pallet::getter(fn proofs_by_block)]
-    pub type ProofsByBlock<T: Config> = StorageDoubleMap<
+    #[pallet::getter(fn proof_indexes_by_block)]
+    pub type ProofIndexesByBlock<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
         BlockNumberFor<T>, // block number
         Blake2_128Concat,
-        BoundedVec<u8, T::MaxDeviceIdLength>, // device_id
-        BoundedVec<u8, T::MaxProofLength>,    // proof hash
-        OptionQuery,
+        DeviceId,                              // device_id
+        BoundedVec<u64, T::MaxProofsPerBlock>, // proof indexes
+        ValueQuery,
     >;
 
-    /// Proof count per device (also serves as next proof index)
+    /// Indexes of a device's unrevoked proofs by `window_end / WindowBucketSeconds`
     #[pallet::storage]
-    #[pallet::getter(fn proof_count)]
-    pub type ProofCount<T: Config> =
-        StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxDeviceIdLength>, u64, ValueQuery>;
+    pub type ProofsByWindowEnd<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeviceId,
+        Twox64Concat,
+        u64,
+        BoundedVec<u64, T::MaxProofsPerWindowBucket>,
+        ValueQuery,
+    >;
 
-    /// Total proofs submitted across all devices
+    /// Buckets and longest window of a device's `ProofsByWindowEnd` entries
     #[pallet::storage]
-    #[pallet::getter(fn total_proofs)]
-    pub type TotalProofs<T: Config> = StorageValue<_, u64, ValueQuery>;
+    #[pallet::getter(fn window_index_bounds)]
+    pub type WindowIndexBounds<T: Config> = StorageMap<_, Blake2_128Concat, DeviceId, WindowBounds>;
 
-    /// Latest proof block per device for quick lookup
+    /// Lifetime proof count per device (also serves as next proof index)
     #[pallet::storage]
-    #[pallet::getter(fn latest_proof_block)]
-    pub type LatestProofBlock<T: Config> = StorageMap<
+    #[pallet::getter(fn proof_count)]
+    pub type ProofCount<T: Config> = StorageMap<_, Blake2_128Concat, DeviceId, u64, ValueQuery>;
+
+    /// Lifetime proof count per device and category
+    #[pallet::storage]
+    #[pallet::getter(fn category_proof_count)]
+    pub type CategoryProofCount<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
-        BoundedVec<u8, T::MaxDeviceIdLength>,
-        BlockNumberFor<T>,
-        OptionQuery,
+        DeviceId,
+        Twox64Concat,
+        ProofCategory,
+        u64,
+        ValueQuery,
     >;
 
-    #[pallet::event]
-    #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    pub enum Event<T: Config> {
-        /// A telemetry proof was submitted
-        ProofSubmitted {
-            device_id: BoundedVec<u8, T::MaxDeviceIdLength>,
-            proof_hash: BoundedVec<u8, T::MaxProofLength>,
-            block_number: BlockNumberFor<T>,
-            proof_index: u64,
-        },
-        /// A batch of proofs was submitted
-        BatchProofsSubmitted {
-            submitter: T::AccountId,
-            proof_count: u32,
-            block_number: BlockNumberFor<T>,
-        },
-        /// A batch of unsigned proofs was submitted
-        UnsignedBatchProofsSubmitted {
-            proof_count: u32,
-            block_number: BlockNumberFor<T>,
-        },
-        /// A proof was verified
-        ProofVerified {
-            device_id: BoundedVec<u8, T::MaxDeviceIdLength>,
-            proof_hash: BoundedVec<u8, T::MaxProofLength>,
-            exists: bool,
-        },
-    }
+    /// Lifetime telemetry records per device, summed over accepted proofs.
+    ///
+    /// Revocation subtracts the proof's records; pruning keeps them.
+    #[pallet::storage]
+    #[pallet::getter(fn total_records)]
+    pub type TotalRecords<T: Config> = StorageMap<_, Blake2_128Concat, DeviceId, u64, ValueQuery>;
 
-    #[pallet::error]
-    pub enum Error<T> {
-        /// Device ID exceeds maximum length
-        DeviceIdTooLong,
-        /// Proof hash exceeds maximum length
-        ProofTooLong,
-        /// Proof already exists for this device at this block
-        ProofAlreadyExists,
-        /// Batch size exceeds maximum allowed
-        BatchTooLarge,
-        /// Empty batch submitted
-        EmptyBatch,
-        /// Maximum proofs per device exceeded
-        MaxProofsExceeded,
-        /// Proof not found
-        ProofNotFound,
-        /// Invalid time window (start >= end)
-        InvalidTimeWindow,
-    }
+    /// Nonce the device's next submission must carry
+    #[pallet::storage]
+    #[pallet::getter(fn submission_nonce)]
+    pub type SubmissionNonce<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeviceId, u64, ValueQuery>;
 
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// Submit a telemetry proof for a device.
-        ///
-        /// This extrinsic stores a cryptographic commitment of a telemetry batch.
-        /// The proof_hash is typically a SHA-256 hash of the aggregated telemetry.
-        ///
-        /// # Arguments
-        ///
-        /// - `origin` - The transaction origin (must be signed by bridge account)
-        /// - `device_id` - The device identifier (UUID format)
-        /// - `proof_hash` - The cryptographic hash of the telemetry batch
-        /// - `record_count` - Number of telemetry records in this batch
-        /// - `window_start` - Start timestamp of the telemetry window
-        /// - `window_end` - End timestamp of the telemetry window
-        #[pallet::call_index(0)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_proof())]
-        pub fn submit_proof(
-            origin: OriginFor<T>,
-            device_id: Vec<u8>,
-            proof_hash: Vec<u8>,
-            record_count: u32,
-            window_start: u64,
-            window_end: u64,
-        ) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
+    /// Number of proofs currently retained per device
+    #[pallet::storage]
+    #[pallet::getter(fn stored_proof_count)]
+    pub type StoredProofCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeviceId, u64, ValueQuery>;
 
-            // Validate time window
-            ensure!(window_start < window_end, Error::<T>::InvalidTimeWindow);
+    /// Number of distinct devices that have ever stored a proof
+    #[pallet::storage]
+    #[pallet::getter(fn known_device_count)]
+    pub type KnownDeviceCount<T: Config> = StorageValue<_, u32, ValueQuery>;
 
-            let bounded_device_id: BoundedVec<u8, T::MaxDeviceIdLength> = device_id
-                .try_into()
-                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+    /// Cap on `KnownDeviceCount`, checked on a device's first proof. `None`
+    /// allows any number of devices.
+    #[pallet::storage]
+    #[pallet::getter(fn max_total_devices)]
+    pub type MaxTotalDevices<T: Config> = StorageValue<_, u32, OptionQuery>;
 
-            let bounded_proof: BoundedVec<u8, T::MaxProofLength> = proof_hash
-                .try_into()
-                .map_err(|_| Error::<T>::ProofTooLong)?;
+    /// Skips the window time checks while historical telemetry is backfilled
+    #[pallet::storage]
+    #[pallet::getter(fn backfill_mode)]
+    pub type BackfillMode<T: Config> = StorageValue<_, bool, ValueQuery>;
 
-            let current_block = <frame_system::Pallet<T>>::block_number();
+    /// Proofs submitted more than this many blocks ago are removed in
+    /// `on_idle`. `None` keeps proofs forever.
+    #[pallet::storage]
+    #[pallet::getter(fn retention_blocks)]
+    pub type RetentionBlocks<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
 
-            // Check max proofs per device
-            let current_count = ProofCount::<T>::get(&bounded_device_id);
-            ensure!(
-                current_count < T::MaxProofsPerDevice::get() as u64,
-                Error::<T>::MaxProofsExceeded
-            );
+    /// Proofs submitted more than this many blocks ago no longer count as
+    /// evidence. `None` keeps proofs valid indefinitely.
+    #[pallet::storage]
+    #[pallet::getter(fn proof_validity_blocks)]
+    pub type ProofValidityBlocks<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
 
-            // Check for duplicate at same block
-            ensure!(
-                !ProofsByBlock::<T>::contains_key(current_block, &bounded_device_id),
-                Error::<T>::ProofAlreadyExists
-            );
+    /// Replaces `MinProofInterval` while set
+    #[pallet::storage]
+    #[pallet::getter(fn min_proof_interval_override)]
+    pub type MinProofIntervalOverride<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
 
-            // Create proof metadata
-            let metadata = ProofMetadata::<T> {
-                proof_hash: bounded_proof.clone(),
-                block_number: current_block,
-                timestamp: Self::current_timestamp(),
+    /// Raw `StoredProofCount` key of the last device fully checked by the
+    /// retention sweep. `None` starts the next sweep at the first device.
+    #[pallet::storage]
+    pub type RetentionCursor<T: Config> = StorageValue<_, BackfillCursor, OptionQuery>;
+
+    /// Index of the oldest retained proof per device.
+    ///
+    /// Retained proofs always occupy the contiguous range
+    /// `[OldestProofIndex, ProofCount)`.
+    #[pallet::storage]
+    #[pallet::getter(fn oldest_proof_index)]
+    pub type OldestProofIndex<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeviceId, u64, ValueQuery>;
+
+    /// Total unrevoked proofs currently retained across all devices.
+    ///
+    /// Incremented for every accepted proof (single and batch paths) and
+    /// decremented when a proof is revoked, or when an unrevoked proof is
+    /// evicted or pruned. Revoked proofs stay in `StoredProofCount` until
+    /// removed.
+    #[pallet::storage]
+    #[pallet::getter(fn total_proofs)]
+    pub type TotalProofs<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Latest proof block per device for quick lookup.
+    ///
+    /// Used by operators to alert on devices that have stopped anchoring
+    /// telemetry. Removed once all of a device's proofs have been pruned.
+    #[pallet::storage]
+    #[pallet::getter(fn latest_proof_block)]
+    pub type LatestProofBlock<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeviceId, BlockNumberFor<T>, OptionQuery>;
+
+    /// Records per device and day, the day being `window_end / 86400`.
+    ///
+    /// Summed over accepted proofs and kept for the `MaxDaysRetained` days
+    /// up to the device's latest day; revocation and pruning do not change it.
+    #[pallet::storage]
+    #[pallet::getter(fn records_on_day)]
+    pub type DailyRecordCounts<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeviceId,
+        Twox64Concat,
+        u32, // day index
+        u32,
+        ValueQuery,
+    >;
+
+    /// Latest day with a `DailyRecordCounts` bucket per device
+    #[pallet::storage]
+    #[pallet::getter(fn latest_record_day)]
+    pub type LatestRecordDay<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeviceId, u32, OptionQuery>;
+
+    /// Proof index per device and `blake2_256(proof_hash)` for O(1) lookups.
+    ///
+    /// Points at the newest stored proof with that hash. Revoked proofs keep
+    /// their entry so they remain auditable; pruned proofs lose it.
+    #[pallet::storage]
+    #[pallet::getter(fn proof_lookup)]
+    pub type ProofLookup<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, DeviceId, Identity, [u8; 32], u64, OptionQuery>;
+
+    /// Device and index of each retained proof, keyed by its proof id.
+    ///
+    /// See [`Pallet::proof_id`]. Entries are removed with the proof.
+    #[pallet::storage]
+    #[pallet::getter(fn proof_id_lookup)]
+    pub type ProofIdLookup<T: Config> = StorageMap<_, Identity, H256, (DeviceId, u64), OptionQuery>;
+
+    /// Owner of each live proof hash, keyed by `blake2_256(proof_hash)`.
+    ///
+    /// Entries are released when the proof is pruned, evicted or revoked.
+    #[pallet::storage]
+    #[pallet::getter(fn proof_hash_index)]
+    pub type ProofHashIndex<T: Config> =
+        StorageMap<_, Identity, [u8; 32], (DeviceId, u64), OptionQuery>;
+
+    /// Open challenges by device and proof index
+    #[pallet::storage]
+    #[pallet::getter(fn challenges)]
+    pub type Challenges<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeviceId,
+        Blake2_128Concat,
+        u64,
+        ProofChallenge<T>,
+        OptionQuery,
+    >;
+
+    /// Proof storage deposits by device and proof index: (depositor, amount)
+    #[pallet::storage]
+    #[pallet::getter(fn proof_deposits)]
+    pub type ProofDeposits<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeviceId,
+        Blake2_128Concat,
+        u64,
+        (T::AccountId, DepositBalanceOf<T>),
+        OptionQuery,
+    >;
+
+    /// Leaves of the proofs accepted in the current block, cleared in `on_finalize`
+    #[pallet::storage]
+    pub type BlockProofLeaves<T: Config> =
+        StorageValue<_, BoundedVec<H256, T::MaxProofsPerBlock>, ValueQuery>;
+
+    /// Merkle root of the proofs accepted in each recent block
+    #[pallet::storage]
+    #[pallet::getter(fn block_proof_root)]
+    pub type BlockProofRoots<T: Config> =
+        StorageMap<_, Twox64Concat, BlockNumberFor<T>, H256, OptionQuery>;
+
+    /// External chain anchors by device and proof index
+    #[pallet::storage]
+    #[pallet::getter(fn proof_anchor)]
+    pub type ProofAnchors<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeviceId,
+        Blake2_128Concat,
+        u64,
+        AnchorRef,
+        OptionQuery,
+    >;
+
+    /// Quality score totals per device, adjusted as scores are set or replaced
+    #[pallet::storage]
+    #[pallet::getter(fn device_quality)]
+    pub type DeviceQuality<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeviceId, QualitySummary, ValueQuery>;
+
+    /// Submission statistics per signed submitter
+    #[pallet::storage]
+    #[pallet::getter(fn submitter_stats)]
+    pub type SubmitterStats<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, SubmitterInfo<BlockNumberFor<T>>, ValueQuery>;
+
+    /// Aggregation schema versions accepted for new proofs
+    #[pallet::storage]
+    #[pallet::getter(fn supported_schemas)]
+    pub type SupportedSchemas<T: Config> = StorageMap<_, Twox64Concat, u16, (), OptionQuery>;
+
+    /// Devices that may no longer submit proofs, e.g. after decommissioning
+    #[pallet::storage]
+    #[pallet::getter(fn frozen_devices)]
+    pub type FrozenDevices<T: Config> = StorageMap<_, Blake2_128Concat, DeviceId, (), OptionQuery>;
+
+    /// Decommissioned devices whose proofs `on_idle` still has to remove
+    #[pallet::storage]
+    #[pallet::getter(fn pending_proof_removals)]
+    pub type PendingProofRemovals<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeviceId, (), OptionQuery>;
+
+    /// Devices warned by `ProofStorageNearlyFull` since last dropping below
+    /// the warning threshold
+    #[pallet::storage]
+    #[pallet::getter(fn storage_warned)]
+    pub type StorageWarned<T: Config> = StorageMap<_, Blake2_128Concat, DeviceId, (), OptionQuery>;
+
+    /// Default for `OpenSubmission`: chains without genesis config stay open
+    #[pallet::type_value]
+    pub fn DefaultOpenSubmission() -> bool {
+        true
+    }
+
+    /// Whether any signed account may submit proofs
+    #[pallet::storage]
+    #[pallet::getter(fn open_submission)]
+    pub type OpenSubmission<T: Config> = StorageValue<_, bool, ValueQuery, DefaultOpenSubmission>;
+
+    /// Accounts allowed to submit proofs while submission is closed
+    #[pallet::storage]
+    #[pallet::getter(fn trusted_submitters)]
+    pub type TrustedSubmitters<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Block of each submitter's latest offchain worker heartbeat
+    #[pallet::storage]
+    #[pallet::getter(fn last_heartbeat)]
+    pub type LastHeartbeat<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+    /// Signing key history per device as `(key, valid_from_block)`, oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn device_key_history)]
+    pub type DeviceKeys<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        DeviceId,
+        BoundedVec<(sr25519::Public, BlockNumberFor<T>), T::MaxKeyHistory>,
+        ValueQuery,
+    >;
+
+    /// Compromised signing keys per device as `(key, compromised_from_block)`
+    #[pallet::storage]
+    #[pallet::getter(fn compromised_keys)]
+    pub type CompromisedKeys<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        DeviceId,
+        BoundedVec<(sr25519::Public, BlockNumberFor<T>), T::MaxKeyHistory>,
+        ValueQuery,
+    >;
+
+    /// Next proof index `revoke_compromised_proofs` checks per device
+    #[pallet::storage]
+    #[pallet::getter(fn compromise_sweep_cursor)]
+    pub type CompromiseSweepCursor<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeviceId, u64, OptionQuery>;
+
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        /// Accounts trusted to submit proofs from genesis
+        pub trusted_submitters: Vec<T::AccountId>,
+        /// Whether any signed account may submit proofs
+        pub open_submission: bool,
+        /// Aggregation schema versions supported from genesis
+        pub supported_schemas: Vec<u16>,
+    }
+
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            Self {
+                trusted_submitters: Vec::new(),
+                open_submission: DefaultOpenSubmission::get(),
+                supported_schemas: alloc::vec![1],
+            }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            OpenSubmission::<T>::put(self.open_submission);
+            for who in &self.trusted_submitters {
+                TrustedSubmitters::<T>::insert(who, ());
+            }
+            for version in &self.supported_schemas {
+                SupportedSchemas::<T>::insert(version, ());
+            }
+        }
+    }
+
+    /// Reasons for the pallet placing holds on funds
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// Storage deposit for a stored proof
+        ProofDeposit,
+        /// Deposit of an open proof challenge
+        ChallengeDeposit,
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A telemetry proof was submitted.
+        ///
+        /// `proof_index` is the device's sequence index for the proof, the
+        /// value carbon-credits' `record_energy` expects as `proof_index`.
+        ProofSubmitted {
+            device_id: DeviceId,
+            proof_hash: H256,
+            block_number: BlockNumberFor<T>,
+            proof_index: u64,
+            proof_id: H256,
+            window_start: u64,
+            window_end: u64,
+            data_size_bytes: u64,
+            schema_version: u16,
+            category: ProofCategory,
+            content_cid: Option<BoundedVec<u8, T::MaxCidLength>>,
+        },
+        /// A batch of proofs was submitted
+        BatchProofsSubmitted {
+            submitter: T::AccountId,
+            proof_count: u32,
+            block_number: BlockNumberFor<T>,
+        },
+        /// Outcome summary of a signed batch
+        BatchProcessed {
+            submitted: u32,
+            accepted: u32,
+            rejected: u32,
+        },
+        /// An entry of a signed batch was not stored
+        BatchEntryRejected {
+            index: u32,
+            reason: BatchRejectReason,
+        },
+        /// A device signing key was registered
+        DeviceKeyRegistered {
+            device_id: DeviceId,
+            public_key: sr25519::Public,
+        },
+        /// A device signing key was rotated
+        DeviceKeyRotated {
+            device_id: DeviceId,
+            old_key: sr25519::Public,
+            new_key: sr25519::Public,
+            valid_from: BlockNumberFor<T>,
+        },
+        /// A proof was challenged
+        ProofChallenged {
+            device_id: DeviceId,
+            proof_index: u64,
+            challenger: T::AccountId,
+            evidence_hash: T::Hash,
+        },
+        /// A challenge was upheld and the proof revoked
+        ChallengeUpheld {
+            device_id: DeviceId,
+            proof_index: u64,
+            challenger: T::AccountId,
+        },
+        /// A challenge was dismissed and its deposit paid to `beneficiary`
+        ChallengeDismissed {
+            device_id: DeviceId,
+            proof_index: u64,
+            challenger: T::AccountId,
+            beneficiary: T::AccountId,
+        },
+        /// An unresolved challenge expired and its deposit was returned
+        ChallengeExpired {
+            device_id: DeviceId,
+            proof_index: u64,
+            challenger: T::AccountId,
+        },
+        /// A proof was anchored on an external chain
+        ProofAnchored {
+            device_id: DeviceId,
+            proof_index: u64,
+            anchor: AnchorRef,
+        },
+        /// Result of a Merkle inclusion check against a stored proof
+        InclusionVerified {
+            device_id: DeviceId,
+            proof_index: u64,
+            leaf_hash: H256,
+            valid: bool,
+        },
+        /// An account was added to the trusted submitters
+        TrustedSubmitterAdded { who: T::AccountId },
+        /// An account was removed from the trusted submitters
+        TrustedSubmitterRemoved { who: T::AccountId },
+        /// Open submission mode was changed
+        OpenSubmissionSet { open: bool },
+        /// An aggregation schema version was added to the supported set
+        SchemaAdded { version: u16 },
+        /// An aggregation schema version was removed from the supported set
+        SchemaRemoved { version: u16 },
+        /// The automatic retention period was set or cleared
+        RetentionBlocksSet {
+            retention: Option<BlockNumberFor<T>>,
+        },
+        /// The cap on distinct devices with proofs was set or cleared
+        MaxTotalDevicesSet { max: Option<u32> },
+        /// The minimum proof interval was overridden, or restored when `None`
+        MinProofIntervalSet { interval: Option<BlockNumberFor<T>> },
+        /// The proof validity period was set or cleared
+        ProofValidityBlocksSet { validity: Option<BlockNumberFor<T>> },
+        /// Backfill mode was switched on or off
+        BackfillModeSet { enabled: bool },
+        /// A device was frozen and can no longer submit proofs
+        DeviceFrozen { device_id: DeviceId },
+        /// A frozen device may submit proofs again
+        DeviceUnfrozen { device_id: DeviceId },
+        /// The oracle set a proof's quality score
+        ProofQualityScored {
+            device_id: DeviceId,
+            proof_index: u64,
+            score: u8,
+        },
+        /// The submitter attached a content CID to a proof
+        ContentCidSet {
+            device_id: DeviceId,
+            proof_index: u64,
+            cid: BoundedVec<u8, T::MaxCidLength>,
+        },
+        /// Old proofs were pruned for a device
+        ProofsPruned { device_id: DeviceId, removed: u32 },
+        /// A device's retained proofs reached `ProofStorageWarningPercent` of
+        /// `MaxProofsPerDevice`; emitted once until pruning drops below it
+        ProofStorageNearlyFull {
+            device_id: DeviceId,
+            retained: u64,
+            max: u32,
+        },
+        /// Proofs of a retired device were removed; `complete` once none are left
+        DeviceProofsRemoved {
+            device_id: DeviceId,
+            removed: u32,
+            complete: bool,
+        },
+        /// Result of an on-chain proof verification request
+        ProofVerificationResult {
+            device_id: DeviceId,
+            proof_hash: H256,
+            found: bool,
+            proof_index: Option<u64>,
+            /// Whether the found proof still counts as evidence (not revoked,
+            /// challenged or expired)
+            valid: bool,
+            /// Whether the found proof is older than `ProofValidityBlocks`
+            expired: bool,
+        },
+        /// Result of a bulk verification request.
+        ///
+        /// Bit `i % 8` of byte `i / 8` is set when query `i` matched a stored,
+        /// still valid proof.
+        BulkVerificationResult {
+            count: u32,
+            results: BoundedVec<u8, T::MaxBatchSize>,
+        },
+        /// A proof was revoked
+        ProofRevoked {
+            device_id: DeviceId,
+            proof_index: u64,
+            reason: RevocationReason,
+        },
+        /// A device signing key was marked compromised
+        DeviceKeyCompromised {
+            device_id: DeviceId,
+            key: sr25519::Public,
+            compromised_from_block: BlockNumberFor<T>,
+        },
+        /// A submitter's offchain worker sent a heartbeat
+        Heartbeat { who: T::AccountId },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Device ID exceeds maximum length
+        DeviceIdTooLong,
+        /// Proof hash is neither 32 raw bytes nor a 64-character hex string
+        InvalidProofHash,
+        /// Batch size exceeds maximum allowed
+        BatchTooLarge,
+        /// Empty batch submitted
+        EmptyBatch,
+        /// Maximum proofs per device exceeded
+        MaxProofsExceeded,
+        /// Proof not found
+        ProofNotFound,
+        /// Invalid time window (start >= end)
+        InvalidTimeWindow,
+        /// The aggregation schema version is not supported
+        UnsupportedSchema,
+        /// The schema version is already supported
+        SchemaAlreadySupported,
+        /// The schema version is not in the supported set
+        SchemaNotSupported,
+        /// Device has a registered key and the signature is missing or invalid
+        InvalidDeviceSignature,
+        /// Device already has a registered signing key
+        DeviceKeyAlreadyRegistered,
+        /// Device has no registered signing key
+        DeviceKeyNotRegistered,
+        /// New key is identical to the current device key
+        SameDeviceKey,
+        /// Account is already a trusted submitter
+        AlreadyTrustedSubmitter,
+        /// Account is not a trusted submitter
+        NotTrustedSubmitter,
+        /// Proof has already been revoked
+        ProofAlreadyRevoked,
+        /// Caller is neither the proof's submitter nor the admin origin
+        NotProofSubmitter,
+        /// Proof already has an open challenge
+        ProofAlreadyChallenged,
+        /// No open challenge exists for this proof
+        ChallengeNotFound,
+        /// Challenge period has not elapsed yet
+        ChallengeNotExpired,
+        /// Proof hash is already anchored by a live proof
+        DuplicateProofHash,
+        /// A retained proof already has the same proof id
+        DuplicateProofId,
+        /// `prev_proof_hash` does not match the device's latest stored proof
+        BrokenProofChain,
+        /// Merkle inclusion path exceeds `MaxMerkleDepth`
+        MerklePathTooLong,
+        /// The proof already has an anchor
+        ProofAlreadyAnchored,
+        /// The block already holds `MaxProofsPerBlock` proofs
+        BlockProofLimitReached,
+        /// The window ends further in the future than `MaxClockDrift` allows
+        WindowInFuture,
+        /// The window starts earlier than `MaxWindowAge` allows
+        WindowTooOld,
+        /// The device is frozen and cannot submit proofs
+        DeviceFrozen,
+        /// The device is already frozen
+        DeviceAlreadyFrozen,
+        /// The device is not frozen
+        DeviceNotFrozen,
+        /// The device is neither frozen nor retired in the device registry
+        DeviceNotRetired,
+        /// The submission nonce was not the device's expected `SubmissionNonce`
+        BadSubmissionNonce,
+        /// The proof covers no telemetry records
+        EmptyProof,
+        /// The proof covers more than `MaxRecordsPerProof` records
+        TooManyRecords,
+        /// Content CID exceeds `MaxCidLength`
+        CidTooLong,
+        /// The proof already has a content CID
+        ContentCidAlreadySet,
+        /// The device has no proofs and `MaxTotalDevices` devices already do
+        DeviceLimitReached,
+        /// The device's latest proof is fewer than the minimum interval blocks old
+        ProofTooFrequent,
+        /// The device already has `MaxProofsPerWindowBucket` proofs ending in this bucket
+        WindowBucketFull,
+        /// The device is retired in the device registry
+        DeviceRetired,
+        /// Key is not in the device's signing key history
+        UnknownDeviceKey,
+        /// Key is already marked compromised for the device
+        KeyAlreadyCompromised,
+        /// Device has no compromised proofs left to sweep
+        NoCompromiseSweep,
+        /// The caller does not own the device
+        NotDeviceOwner,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            // Reserve the worst case of `on_finalize`
+            T::WeightInfo::finalize_block_proof_root(T::MaxProofsPerBlock::get())
+        }
+
+        fn on_finalize(n: BlockNumberFor<T>) {
+            if let Some(expired) = n.checked_sub(&T::ProofRootRetention::get()) {
+                BlockProofRoots::<T>::remove(expired);
+            }
+            let leaves = BlockProofLeaves::<T>::take();
+            if leaves.is_empty() {
+                return;
+            }
+            let root = Self::merkle_root_of(&leaves);
+            BlockProofRoots::<T>::insert(n, root);
+            <frame_system::Pallet<T>>::deposit_log(DigestItem::Consensus(
+                PROOF_ROOT_ENGINE_ID,
+                root.encode(),
+            ));
+        }
+
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let used = Self::prune_expired_proofs(remaining_weight);
+            used.saturating_add(Self::prune_decommissioned(
+                remaining_weight.saturating_sub(used),
+            ))
+        }
+
+        fn offchain_worker(n: BlockNumberFor<T>) {
+            let interval = T::HeartbeatInterval::get();
+            if interval.is_zero() || !(n % interval).is_zero() {
+                return;
+            }
+            Self::send_heartbeats();
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Submit a telemetry proof for a device.
+        ///
+        /// This extrinsic stores a cryptographic commitment of a telemetry batch.
+        /// The proof_hash is typically a SHA-256 hash of the aggregated telemetry.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - The transaction origin, admitted by `SubmitterOrigin`
+        /// - `device_id` - The device identifier (UUID format)
+        /// - `proof_hash` - The cryptographic hash of the telemetry batch, as 32
+        ///   raw bytes or 64 hex characters
+        /// - `record_count` - Number of telemetry records in this batch
+        /// - `window_start` - Start timestamp of the telemetry window
+        /// - `window_end` - End timestamp of the telemetry window
+        /// - `data_size_bytes` - Size in bytes of the raw telemetry covered
+        /// - `schema_version` - Aggregation schema that produced the proof hash
+        /// - `category` - Kind of telemetry the proof covers
+        /// - `nonce` - The device's current `SubmissionNonce`
+        /// - `prev_proof_hash` - Hash of the device's latest stored proof (`None` for the first),
+        ///   in either encoding
+        /// - `signature` - Device signature, mandatory once a device key is registered
+        /// - `content_cid` - IPFS CID of the archived telemetry batch, if already known
+        #[pallet::call_index(0)]
+        #[pallet::weight((
+            <T as pallet::Config>::WeightInfo::submit_proof()
+                .saturating_add(T::OnProofSubmitted::weight()),
+            DispatchClass::Operational
+        ))]
+        #[pallet::feeless_if(|
+            origin: &OriginFor<T>,
+            _device_id: &Vec<u8>,
+            _proof_hash: &Vec<u8>,
+            _record_count: &u32,
+            _window_start: &u64,
+            _window_end: &u64,
+            _data_size_bytes: &u64,
+            _schema_version: &u16,
+            _category: &ProofCategory,
+            _nonce: &u64,
+            _prev_proof_hash: &Option<Vec<u8>>,
+            _signature: &Option<Vec<u8>>,
+            _content_cid: &Option<Vec<u8>>,
+        | -> bool { Pallet::<T>::is_feeless_submitter(origin) })]
+        pub fn submit_proof(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            proof_hash: Vec<u8>,
+            record_count: u32,
+            window_start: u64,
+            window_end: u64,
+            data_size_bytes: u64,
+            schema_version: u16,
+            category: ProofCategory,
+            nonce: u64,
+            prev_proof_hash: Option<Vec<u8>>,
+            signature: Option<Vec<u8>>,
+            content_cid: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            let who = T::SubmitterOrigin::ensure_origin(origin)?;
+            Self::do_submit_linked_proof(
+                who,
+                device_id,
+                proof_hash,
                 record_count,
                 window_start,
                 window_end,
-            };
-
-            // Store proof with index
-            let proof_index = current_count;
-            Proofs::<T>::insert(&bounded_device_id, proof_index, metadata);
-            ProofsByBlock::<T>::insert(current_block, &bounded_device_id, bounded_proof.clone());
-            ProofCount::<T>::mutate(&bounded_device_id, |count| *count += 1);
-            TotalProofs::<T>::mutate(|total| *total += 1);
-            LatestProofBlock::<T>::insert(&bounded_device_id, current_block);
-
-            Self::deposit_event(Event::ProofSubmitted {
-                device_id: bounded_device_id,
-                proof_hash: bounded_proof,
-                block_number: current_block,
-                proof_index,
-            });
+                data_size_bytes,
+                schema_version,
+                category,
+                nonce,
+                prev_proof_hash,
+                signature,
+                content_cid,
+            )?;
 
             Ok(())
         }
@@ -284,97 +1581,103 @@ pub mod pallet {
         /// Submit multiple proofs in a single transaction.
         ///
         /// This is more efficient than multiple individual submissions as it
-        /// amortizes the transaction overhead across all proofs.
+        /// amortizes the transaction overhead across all proofs. Invalid entries
+        /// are skipped and reported with `BatchEntryRejected`, followed by a
+        /// `BatchProcessed` summary.
+        ///
+        /// The weight is charged for the full batch up front and refunded down
+        /// to the entries actually stored.
         ///
         /// # Arguments
         ///
-        /// - `origin` - The transaction origin (must be signed by bridge account)
-        /// - `proofs` - Vector of (device_id, proof_hash, record_count, window_start, window_end)
+        /// - `origin` - The transaction origin, admitted by `SubmitterOrigin`
+        /// - `proofs` - Vector of (device_id, proof_hash, record_count, window_start, window_end,
+        ///   data_size_bytes, schema_version, category, nonce)
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_batch_proofs(proofs.len() as u32))]
+        #[pallet::weight((Pallet::<T>::batch_weight(proofs.len()), DispatchClass::Operational))]
+        #[pallet::feeless_if(|origin: &OriginFor<T>, _proofs: &Vec<BatchProofEntry>| -> bool {
+            Pallet::<T>::is_feeless_submitter(origin)
+        })]
         pub fn submit_batch_proofs(
             origin: OriginFor<T>,
             proofs: Vec<BatchProofEntry>,
-        ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+        ) -> DispatchResultWithPostInfo {
+            let who = T::SubmitterOrigin::ensure_origin(origin)?;
 
-            let batch_len = proofs.len() as u32;
-            ensure!(batch_len > 0, Error::<T>::EmptyBatch);
+            // Compare lengths before narrowing so oversized batches cannot wrap
             ensure!(
-                batch_len <= T::MaxBatchSize::get(),
+                proofs.len() <= T::MaxBatchSize::get() as usize,
                 Error::<T>::BatchTooLarge
             );
+            ensure!(!proofs.is_empty(), Error::<T>::EmptyBatch);
 
+            let batch_len = proofs.len() as u32;
             let current_block = <frame_system::Pallet<T>>::block_number();
 
-            for (device_id, proof_hash, record_count, window_start, window_end) in proofs {
-                // Validate time window
-                if window_start >= window_end {
-                    continue; // Skip invalid entries rather than fail entire batch
-                }
-
-                let bounded_device_id: BoundedVec<u8, T::MaxDeviceIdLength> =
-                    match device_id.try_into() {
-                        Ok(id) => id,
-                        Err(_) => continue, // Skip invalid device IDs
-                    };
-
-                let bounded_proof: BoundedVec<u8, T::MaxProofLength> = match proof_hash.try_into() {
-                    Ok(hash) => hash,
-                    Err(_) => continue, // Skip invalid proofs
-                };
-
-                // Check limits and duplicates
-                let current_count = ProofCount::<T>::get(&bounded_device_id);
-                if current_count >= T::MaxProofsPerDevice::get() as u64 {
-                    continue;
-                }
-                if ProofsByBlock::<T>::contains_key(current_block, &bounded_device_id) {
-                    continue;
-                }
-
-                // Create and store proof
-                let metadata = ProofMetadata::<T> {
-                    proof_hash: bounded_proof.clone(),
-                    block_number: current_block,
-                    timestamp: Self::current_timestamp(),
+            let mut accepted: u32 = 0;
+            for (
+                index,
+                (
+                    device_id,
+                    proof_hash,
                     record_count,
                     window_start,
                     window_end,
-                };
-
-                let proof_index = current_count;
-                Proofs::<T>::insert(&bounded_device_id, proof_index, metadata);
-                ProofsByBlock::<T>::insert(
-                    current_block,
-                    &bounded_device_id,
-                    bounded_proof.clone(),
-                );
-                ProofCount::<T>::mutate(&bounded_device_id, |count| *count += 1);
-                TotalProofs::<T>::mutate(|total| *total += 1);
-                LatestProofBlock::<T>::insert(&bounded_device_id, current_block);
-
-                Self::deposit_event(Event::ProofSubmitted {
-                    device_id: bounded_device_id,
-                    proof_hash: bounded_proof,
-                    block_number: current_block,
-                    proof_index,
-                });
+                    data_size_bytes,
+                    schema_version,
+                    category,
+                    nonce,
+                ),
+            ) in proofs.into_iter().enumerate()
+            {
+                // Reject invalid entries individually rather than fail the entire batch
+                match Self::do_submit_proof(
+                    Some(who.clone()),
+                    device_id,
+                    proof_hash,
+                    record_count,
+                    window_start,
+                    window_end,
+                    data_size_bytes,
+                    schema_version,
+                    category,
+                    nonce,
+                    None,
+                    None,
+                ) {
+                    Ok(_) => accepted = accepted.saturating_add(1),
+                    Err(error) => Self::deposit_event(Event::BatchEntryRejected {
+                        index: index as u32,
+                        reason: Self::batch_reject_reason(error),
+                    }),
+                }
             }
 
+            SubmitterStats::<T>::mutate(&who, |stats| {
+                stats.batches = stats.batches.saturating_add(1);
+                stats.last_block = current_block;
+            });
+
             Self::deposit_event(Event::BatchProofsSubmitted {
                 submitter: who,
                 proof_count: batch_len,
                 block_number: current_block,
             });
+            Self::deposit_event(Event::BatchProcessed {
+                submitted: batch_len,
+                accepted,
+                rejected: batch_len.saturating_sub(accepted),
+            });
 
-            Ok(())
+            Ok(Some(Self::batch_weight(accepted as usize)).into())
         }
 
         /// Verify that a proof exists for a device.
         ///
-        /// This is primarily for debugging and verification purposes.
-        /// Returns an event indicating whether the proof exists.
+        /// Gives downstream consumers an on-chain attestation of a proof lookup.
+        /// A missing proof is not an error: the outcome, including the matching
+        /// proof index and whether the proof is still valid, is reported in
+        /// `ProofVerificationResult`.
         ///
         /// # Arguments
         ///
@@ -390,258 +1693,2613 @@ pub mod pallet {
         ) -> DispatchResult {
             let _who = ensure_signed(origin)?;
 
-            let bounded_device_id: BoundedVec<u8, T::MaxDeviceIdLength> = device_id
+            let bounded_device_id: DeviceId = device_id
                 .try_into()
                 .map_err(|_| Error::<T>::DeviceIdTooLong)?;
 
-            let bounded_proof: BoundedVec<u8, T::MaxProofLength> = proof_hash
-                .try_into()
-                .map_err(|_| Error::<T>::ProofTooLong)?;
+            let proof_hash =
+                Self::normalize_proof_hash(&proof_hash).ok_or(Error::<T>::InvalidProofHash)?;
+
+            // Search for the proof in device's proof history
+            let proof_index = Self::find_proof_index(&bounded_device_id, &proof_hash);
+            let metadata =
+                proof_index.and_then(|index| Proofs::<T>::get(&bounded_device_id, index));
+            let expired = metadata.as_ref().is_some_and(Self::is_expired);
+            let valid = metadata.is_some_and(|metadata| {
+                metadata.is_valid() && !Self::is_compromised(&bounded_device_id, &metadata)
+            }) && !expired;
+
+            Self::deposit_event(Event::ProofVerificationResult {
+                device_id: bounded_device_id,
+                proof_hash,
+                found: proof_index.is_some(),
+                proof_index,
+                valid,
+                expired,
+            });
+
+            Ok(())
+        }
+
+        /// Verify many proofs at once.
+        ///
+        /// Each `(device_id, proof_hash)` query is checked against the lookup
+        /// index; malformed queries count as not verified. The results are
+        /// reported as a bitmap in input order in `BulkVerificationResult`.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - The transaction origin (can be any signed account)
+        /// - `queries` - Device identifier and proof hash pairs to verify
+        #[pallet::call_index(27)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::verify_proofs(queries.len() as u32))]
+        pub fn verify_proofs(
+            origin: OriginFor<T>,
+            queries: Vec<(Vec<u8>, Vec<u8>)>,
+        ) -> DispatchResult {
+            let _who = ensure_signed(origin)?;
+
+            ensure!(
+                queries.len() <= T::MaxBatchSize::get() as usize,
+                Error::<T>::BatchTooLarge
+            );
+            ensure!(!queries.is_empty(), Error::<T>::EmptyBatch);
+
+            let mut bitmap = alloc::vec![0u8; queries.len().div_ceil(8)];
+            for (i, (device_id, proof_hash)) in queries.iter().enumerate() {
+                let Ok(bounded_device_id) = DeviceId::try_from(device_id.clone()) else {
+                    continue;
+                };
+                let Some(proof_hash) = Self::normalize_proof_hash(proof_hash) else {
+                    continue;
+                };
+                if Self::proof_exists(&bounded_device_id, &proof_hash) {
+                    bitmap[i / 8] |= 1 << (i % 8);
+                }
+            }
+
+            Self::deposit_event(Event::BulkVerificationResult {
+                count: queries.len() as u32,
+                // At most one byte per query, within `MaxBatchSize`
+                results: bitmap.try_into().map_err(|_| Error::<T>::BatchTooLarge)?,
+            });
+
+            Ok(())
+        }
+
+        /// Submit a telemetry proof without requiring a signed transaction.
+        ///
+        /// The payload is signed by a bridge key instead, so the bridge needs
+        /// no funded account. `ValidateUnsigned` checks the signature, that
+        /// the key's account passes `SubmitterOrigin` and that the payload
+        /// carries the device's current nonce.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Must be none (unsigned)
+        /// - `payload` - The proof, with the device's current `SubmissionNonce`
+        ///   and the signing key
+        /// - `signature` - Signature of the encoded payload by `payload.public`
+        #[pallet::call_index(3)]
+        #[pallet::weight(
+            <T as pallet::Config>::WeightInfo::submit_proof()
+                .saturating_add(T::OnProofSubmitted::weight())
+        )]
+        pub fn submit_proof_unsigned(
+            origin: OriginFor<T>,
+            payload: ProofPayload<T::Public, T::Hash>,
+            _signature: T::Signature,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+
+            Self::do_submit_proof(
+                None,
+                payload.device_id,
+                payload.proof_hash,
+                payload.record_count,
+                payload.window_start,
+                payload.window_end,
+                payload.data_size_bytes,
+                payload.schema_version,
+                payload.category,
+                payload.nonce,
+                None,
+                None,
+            )?;
+
+            Ok(())
+        }
+
+        /// Prune a device's oldest proofs.
+        ///
+        /// Removes up to `limit` proofs with an index below `before_index`,
+        /// oldest first, together with their secondary index entries. The
+        /// `limit` keeps the call bounded; repeat the call to finish pruning.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin or the device's owner
+        /// - `device_id` - The device identifier
+        /// - `before_index` - Proofs with a lower index are eligible for pruning
+        /// - `limit` - Maximum number of proofs to remove in this call
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::prune_proofs_before(*limit))]
+        pub fn prune_proofs_before(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            before_index: u64,
+            limit: u32,
+        ) -> DispatchResult {
+            let caller = match T::AdminOrigin::try_origin(origin) {
+                Ok(_) => None,
+                Err(origin) => Some(ensure_signed(origin)?),
+            };
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            if let Some(who) = caller {
+                ensure!(
+                    T::Devices::owner(&bounded_device_id) == Some(who),
+                    Error::<T>::NotDeviceOwner
+                );
+            }
+
+            let mut removed = 0u32;
+            while removed < limit {
+                let oldest = OldestProofIndex::<T>::get(&bounded_device_id);
+                if oldest >= before_index || !Self::remove_proof(&bounded_device_id, oldest) {
+                    break;
+                }
+                removed += 1;
+            }
+            Self::release_storage_warning(&bounded_device_id);
+
+            Self::deposit_event(Event::ProofsPruned {
+                device_id: bounded_device_id,
+                removed,
+            });
+
+            Ok(())
+        }
+
+        /// Register the sr25519 key a device uses to sign its proofs.
+        ///
+        /// Once registered, `submit_proof` for the device must carry a valid
+        /// signature by this key.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `device_id` - The device identifier
+        /// - `public_key` - The device's sr25519 public key
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::register_device_key())]
+        pub fn register_device_key(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            public_key: sr25519::Public,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            ensure!(
+                !DeviceKeys::<T>::contains_key(&bounded_device_id),
+                Error::<T>::DeviceKeyAlreadyRegistered
+            );
+            let current_block = <frame_system::Pallet<T>>::block_number();
+            let history = BoundedVec::truncate_from(alloc::vec![(public_key, current_block)]);
+            DeviceKeys::<T>::insert(&bounded_device_id, history);
+
+            Self::deposit_event(Event::DeviceKeyRegistered {
+                device_id: bounded_device_id,
+                public_key,
+            });
+
+            Ok(())
+        }
+
+        /// Rotate a device's signing key.
+        ///
+        /// The new key is valid from the current block. Earlier entries stay
+        /// in the bounded key history so proofs submitted before the rotation
+        /// can still be checked against the key that signed them; once the
+        /// history is full the oldest entry is dropped.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin or the device's owner
+        /// - `device_id` - The device identifier
+        /// - `new_key` - The device's new sr25519 public key
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::rotate_device_key())]
+        pub fn rotate_device_key(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            new_key: sr25519::Public,
+        ) -> DispatchResult {
+            let caller = match T::AdminOrigin::try_origin(origin) {
+                Ok(_) => None,
+                Err(origin) => Some(ensure_signed(origin)?),
+            };
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            if let Some(who) = caller {
+                ensure!(
+                    T::Devices::owner(&bounded_device_id) == Some(who),
+                    Error::<T>::NotDeviceOwner
+                );
+            }
+
+            let valid_from = <frame_system::Pallet<T>>::block_number();
+            let old_key = DeviceKeys::<T>::try_mutate(
+                &bounded_device_id,
+                |history| -> Result<sr25519::Public, DispatchError> {
+                    let (old_key, _) = *history.last().ok_or(Error::<T>::DeviceKeyNotRegistered)?;
+                    ensure!(old_key != new_key, Error::<T>::SameDeviceKey);
+
+                    if history.is_full() {
+                        history.remove(0);
+                    }
+                    history
+                        .try_push((new_key, valid_from))
+                        .map_err(|_| Error::<T>::DeviceKeyNotRegistered)?;
+
+                    Ok(old_key)
+                },
+            )?;
+
+            Self::deposit_event(Event::DeviceKeyRotated {
+                device_id: bounded_device_id,
+                old_key,
+                new_key,
+                valid_from,
+            });
+
+            Ok(())
+        }
+
+        /// Revoke a stored proof.
+        ///
+        /// The proof stays readable for audit but no longer counts as valid in
+        /// `proof_exists` and `verify_proof`. Revocation is permanent; an open
+        /// challenge against the proof must still be resolved or expired.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - The proof's original submitter or the admin origin
+        /// - `device_id` - The device identifier
+        /// - `proof_index` - Index of the proof to revoke
+        /// - `reason` - Why the proof is being revoked
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::revoke_proof())]
+        pub fn revoke_proof(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            proof_index: u64,
+            reason: RevocationReason,
+        ) -> DispatchResult {
+            let caller = match T::AdminOrigin::try_origin(origin) {
+                Ok(_) => None,
+                Err(origin) => Some(ensure_signed(origin)?),
+            };
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            Proofs::<T>::try_mutate(&bounded_device_id, proof_index, |maybe_metadata| {
+                let metadata = maybe_metadata.as_mut().ok_or(Error::<T>::ProofNotFound)?;
+                if let Some(who) = &caller {
+                    ensure!(
+                        metadata.submitter.as_ref() == Some(who),
+                        Error::<T>::NotProofSubmitter
+                    );
+                }
+                ensure!(metadata.revoked.is_none(), Error::<T>::ProofAlreadyRevoked);
+
+                Self::mark_revoked(&bounded_device_id, proof_index, metadata, reason);
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::ProofRevoked {
+                device_id: bounded_device_id,
+                proof_index,
+                reason,
+            });
+
+            Ok(())
+        }
+
+        /// Challenge a stored proof.
+        ///
+        /// Holds `ChallengeDeposit` from the caller and marks the proof as
+        /// challenged, which makes it invalid until the challenge is closed.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Any signed account
+        /// - `device_id` - The device identifier
+        /// - `proof_index` - Index of the proof to challenge
+        /// - `evidence_hash` - Hash of the off-chain evidence
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::challenge_proof())]
+        pub fn challenge_proof(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            proof_index: u64,
+            evidence_hash: T::Hash,
+        ) -> DispatchResult {
+            let challenger = ensure_signed(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            Proofs::<T>::try_mutate(&bounded_device_id, proof_index, |maybe_metadata| {
+                let metadata = maybe_metadata.as_mut().ok_or(Error::<T>::ProofNotFound)?;
+                ensure!(metadata.revoked.is_none(), Error::<T>::ProofAlreadyRevoked);
+                ensure!(!metadata.challenged, Error::<T>::ProofAlreadyChallenged);
+
+                let deposit = T::ChallengeDeposit::get();
+                T::NativeBalance::hold(&HoldReason::ChallengeDeposit.into(), &challenger, deposit)?;
+
+                metadata.challenged = true;
+                Challenges::<T>::insert(
+                    &bounded_device_id,
+                    proof_index,
+                    ProofChallenge {
+                        challenger: challenger.clone(),
+                        deposit,
+                        evidence_hash,
+                        challenged_at: <frame_system::Pallet<T>>::block_number(),
+                    },
+                );
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::ProofChallenged {
+                device_id: bounded_device_id,
+                proof_index,
+                challenger,
+                evidence_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Resolve an open challenge.
+        ///
+        /// Upholding revokes the proof and returns the challenger's deposit.
+        /// A proof already revoked while challenged keeps its original reason
+        /// and is not torn down again. Dismissing restores the proof and pays
+        /// the deposit to the proof's
+        /// submitter, or to `TreasuryAccount` for unsigned proofs.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Dispute origin required
+        /// - `device_id` - The device identifier
+        /// - `proof_index` - Index of the challenged proof
+        /// - `uphold` - Whether the challenge is upheld
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::resolve_challenge())]
+        pub fn resolve_challenge(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            proof_index: u64,
+            uphold: bool,
+        ) -> DispatchResult {
+            T::DisputeOrigin::ensure_origin(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            let challenge = Challenges::<T>::take(&bounded_device_id, proof_index)
+                .ok_or(Error::<T>::ChallengeNotFound)?;
+            let mut metadata = Proofs::<T>::get(&bounded_device_id, proof_index)
+                .ok_or(Error::<T>::ProofNotFound)?;
+            metadata.challenged = false;
+
+            if uphold {
+                if metadata.revoked.is_none() {
+                    Self::mark_revoked(
+                        &bounded_device_id,
+                        proof_index,
+                        &mut metadata,
+                        RevocationReason::ChallengeUpheld,
+                    );
+                }
+                Self::release_challenge_deposit(&challenge);
+
+                Self::deposit_event(Event::ChallengeUpheld {
+                    device_id: bounded_device_id.clone(),
+                    proof_index,
+                    challenger: challenge.challenger,
+                });
+            } else {
+                let beneficiary = metadata
+                    .submitter
+                    .clone()
+                    .unwrap_or_else(T::TreasuryAccount::get);
+                T::NativeBalance::transfer_on_hold(
+                    &HoldReason::ChallengeDeposit.into(),
+                    &challenge.challenger,
+                    &beneficiary,
+                    challenge.deposit,
+                    Precision::BestEffort,
+                    Restriction::Free,
+                    Fortitude::Polite,
+                )?;
+
+                Self::deposit_event(Event::ChallengeDismissed {
+                    device_id: bounded_device_id.clone(),
+                    proof_index,
+                    challenger: challenge.challenger,
+                    beneficiary,
+                });
+            }
+
+            Proofs::<T>::insert(&bounded_device_id, proof_index, metadata);
+
+            Ok(())
+        }
+
+        /// Close a challenge that was not resolved within `ChallengePeriod`.
+        ///
+        /// The proof is restored and the challenger's deposit returned.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Any signed account
+        /// - `device_id` - The device identifier
+        /// - `proof_index` - Index of the challenged proof
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::expire_challenge())]
+        pub fn expire_challenge(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            proof_index: u64,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            let challenge = Challenges::<T>::get(&bounded_device_id, proof_index)
+                .ok_or(Error::<T>::ChallengeNotFound)?;
+            let current_block = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                current_block
+                    >= challenge
+                        .challenged_at
+                        .saturating_add(T::ChallengePeriod::get()),
+                Error::<T>::ChallengeNotExpired
+            );
+
+            Challenges::<T>::remove(&bounded_device_id, proof_index);
+            Proofs::<T>::mutate(&bounded_device_id, proof_index, |maybe_metadata| {
+                if let Some(metadata) = maybe_metadata {
+                    metadata.challenged = false;
+                }
+            });
+            Self::release_challenge_deposit(&challenge);
+
+            Self::deposit_event(Event::ChallengeExpired {
+                device_id: bounded_device_id,
+                proof_index,
+                challenger: challenge.challenger,
+            });
+
+            Ok(())
+        }
+
+        /// Verify that a telemetry record is included in a stored proof.
+        ///
+        /// Recomputes the batch root from `leaf_hash` and the sibling `path`
+        /// and compares it with the stored proof hash. A mismatch is not an
+        /// error: the outcome is reported in `InclusionVerified`, and proofs
+        /// that are revoked or challenged never verify.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Any signed account
+        /// - `device_id` - The device identifier
+        /// - `proof_index` - Index of the proof holding the batch root
+        /// - `leaf_hash` - Hash of the telemetry record
+        /// - `path` - Sibling hashes from the leaf upwards, `true` if the sibling is on the left
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::verify_merkle_inclusion(path.len() as u32))]
+        pub fn verify_merkle_inclusion(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            proof_index: u64,
+            leaf_hash: H256,
+            path: Vec<(H256, bool)>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            ensure!(
+                path.len() <= T::MaxMerkleDepth::get() as usize,
+                Error::<T>::MerklePathTooLong
+            );
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            ensure!(
+                Proofs::<T>::contains_key(&bounded_device_id, proof_index),
+                Error::<T>::ProofNotFound
+            );
+            let valid =
+                Self::check_merkle_inclusion(&bounded_device_id, proof_index, leaf_hash, &path);
+
+            Self::deposit_event(Event::InclusionVerified {
+                device_id: bounded_device_id,
+                proof_index,
+                leaf_hash,
+                valid,
+            });
+
+            Ok(())
+        }
+
+        /// Add an account to the trusted submitters.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `who` - The account to trust
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::add_trusted_submitter())]
+        pub fn add_trusted_submitter(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !TrustedSubmitters::<T>::contains_key(&who),
+                Error::<T>::AlreadyTrustedSubmitter
+            );
+            TrustedSubmitters::<T>::insert(&who, ());
+
+            Self::deposit_event(Event::TrustedSubmitterAdded { who });
+
+            Ok(())
+        }
+
+        /// Remove an account from the trusted submitters.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `who` - The account to remove
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_trusted_submitter())]
+        pub fn remove_trusted_submitter(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                TrustedSubmitters::<T>::contains_key(&who),
+                Error::<T>::NotTrustedSubmitter
+            );
+            TrustedSubmitters::<T>::remove(&who);
+
+            Self::deposit_event(Event::TrustedSubmitterRemoved { who });
+
+            Ok(())
+        }
+
+        /// Open or close proof submission to arbitrary signed accounts.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `open` - Whether any signed account may submit proofs
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_open_submission())]
+        pub fn set_open_submission(origin: OriginFor<T>, open: bool) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            OpenSubmission::<T>::put(open);
+
+            Self::deposit_event(Event::OpenSubmissionSet { open });
+
+            Ok(())
+        }
+
+        /// Add an aggregation schema version to the supported set.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `version` - The schema version to accept
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::add_supported_schema())]
+        pub fn add_supported_schema(origin: OriginFor<T>, version: u16) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !SupportedSchemas::<T>::contains_key(version),
+                Error::<T>::SchemaAlreadySupported
+            );
+            SupportedSchemas::<T>::insert(version, ());
+
+            Self::deposit_event(Event::SchemaAdded { version });
+
+            Ok(())
+        }
+
+        /// Remove an aggregation schema version from the supported set.
+        ///
+        /// Stored proofs keep their schema version; only new submissions
+        /// are rejected.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `version` - The schema version to stop accepting
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_supported_schema())]
+        pub fn remove_supported_schema(origin: OriginFor<T>, version: u16) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                SupportedSchemas::<T>::contains_key(version),
+                Error::<T>::SchemaNotSupported
+            );
+            SupportedSchemas::<T>::remove(version);
+
+            Self::deposit_event(Event::SchemaRemoved { version });
+
+            Ok(())
+        }
+
+        /// Set or clear the automatic retention period.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `retention` - Age in blocks after which proofs are removed, or
+        ///   `None` to keep proofs forever
+        #[pallet::call_index(18)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_retention_blocks())]
+        pub fn set_retention_blocks(
+            origin: OriginFor<T>,
+            retention: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            RetentionBlocks::<T>::set(retention);
+
+            Self::deposit_event(Event::RetentionBlocksSet { retention });
+
+            Ok(())
+        }
+
+        /// Record the external chain transaction that checkpointed a proof.
+        ///
+        /// Each proof can be anchored once; revoked proofs cannot be anchored.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Anchor origin required
+        /// - `device_id` - The device identifier
+        /// - `proof_index` - Index of the anchored proof
+        /// - `anchor` - The external chain, transaction hash and anchoring time
+        #[pallet::call_index(19)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_anchor())]
+        pub fn set_anchor(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            proof_index: u64,
+            anchor: AnchorRef,
+        ) -> DispatchResult {
+            T::AnchorOrigin::ensure_origin(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            let metadata = Proofs::<T>::get(&bounded_device_id, proof_index)
+                .ok_or(Error::<T>::ProofNotFound)?;
+            ensure!(metadata.revoked.is_none(), Error::<T>::ProofAlreadyRevoked);
+            ensure!(
+                !ProofAnchors::<T>::contains_key(&bounded_device_id, proof_index),
+                Error::<T>::ProofAlreadyAnchored
+            );
+
+            ProofAnchors::<T>::insert(&bounded_device_id, proof_index, anchor);
+
+            Self::deposit_event(Event::ProofAnchored {
+                device_id: bounded_device_id,
+                proof_index,
+                anchor,
+            });
+
+            Ok(())
+        }
+
+        /// Switch backfill mode, which skips the window time checks.
+        ///
+        /// Intended for loading historical telemetry when a network starts.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `enabled` - Whether window time checks are skipped
+        #[pallet::call_index(20)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_backfill_mode())]
+        pub fn set_backfill_mode(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            BackfillMode::<T>::put(enabled);
+
+            Self::deposit_event(Event::BackfillModeSet { enabled });
+
+            Ok(())
+        }
+
+        /// Freeze a device so it can no longer submit proofs.
+        ///
+        /// Existing proofs stay queryable and verifiable.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `device_id` - The device to freeze
+        #[pallet::call_index(21)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::freeze_device())]
+        pub fn freeze_device(origin: OriginFor<T>, device_id: Vec<u8>) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            ensure!(
+                !FrozenDevices::<T>::contains_key(&bounded_device_id),
+                Error::<T>::DeviceAlreadyFrozen
+            );
+            FrozenDevices::<T>::insert(&bounded_device_id, ());
+
+            Self::deposit_event(Event::DeviceFrozen {
+                device_id: bounded_device_id,
+            });
+
+            Ok(())
+        }
+
+        /// Allow a frozen device to submit proofs again.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `device_id` - The device to unfreeze
+        #[pallet::call_index(22)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::unfreeze_device())]
+        pub fn unfreeze_device(origin: OriginFor<T>, device_id: Vec<u8>) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            ensure!(
+                FrozenDevices::<T>::contains_key(&bounded_device_id),
+                Error::<T>::DeviceNotFrozen
+            );
+            FrozenDevices::<T>::remove(&bounded_device_id);
+
+            Self::deposit_event(Event::DeviceUnfrozen {
+                device_id: bounded_device_id,
+            });
+
+            Ok(())
+        }
+
+        /// Set the anomaly score of a proof.
+        ///
+        /// A later score replaces the earlier one, in the proof metadata and
+        /// in the device's `DeviceQuality` totals. Revoked proofs cannot be
+        /// scored.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Oracle origin required
+        /// - `device_id` - The device identifier
+        /// - `proof_index` - Index of the scored proof
+        /// - `score` - Quality score from the anomaly pipeline
+        #[pallet::call_index(23)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_proof_quality())]
+        pub fn set_proof_quality(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            proof_index: u64,
+            score: u8,
+        ) -> DispatchResult {
+            T::OracleOrigin::ensure_origin(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            let previous = Proofs::<T>::try_mutate(&bounded_device_id, proof_index, |maybe| {
+                let metadata = maybe.as_mut().ok_or(Error::<T>::ProofNotFound)?;
+                ensure!(metadata.revoked.is_none(), Error::<T>::ProofAlreadyRevoked);
+                Ok::<_, DispatchError>(metadata.quality_score.replace(score))
+            })?;
+
+            DeviceQuality::<T>::mutate(&bounded_device_id, |summary| {
+                match previous {
+                    Some(old) => summary.total = summary.total.saturating_sub(old as u64),
+                    None => summary.scored = summary.scored.saturating_add(1),
+                }
+                summary.total = summary.total.saturating_add(score as u64);
+            });
+
+            Self::deposit_event(Event::ProofQualityScored {
+                device_id: bounded_device_id,
+                proof_index,
+                score,
+            });
+
+            Ok(())
+        }
+
+        /// Attach the IPFS CID of a proof's archived telemetry batch.
+        ///
+        /// For proofs stored without a CID; an existing CID cannot be replaced.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - The proof's original submitter
+        /// - `device_id` - The device identifier
+        /// - `proof_index` - Index of the proof
+        /// - `cid` - IPFS content identifier, at most `MaxCidLength` bytes
+        #[pallet::call_index(24)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_content_cid())]
+        pub fn set_content_cid(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            proof_index: u64,
+            cid: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            let cid: BoundedVec<u8, T::MaxCidLength> =
+                cid.try_into().map_err(|_| Error::<T>::CidTooLong)?;
+
+            Proofs::<T>::try_mutate(&bounded_device_id, proof_index, |maybe| {
+                let metadata = maybe.as_mut().ok_or(Error::<T>::ProofNotFound)?;
+                ensure!(
+                    metadata.submitter.as_ref() == Some(&who),
+                    Error::<T>::NotProofSubmitter
+                );
+                ensure!(
+                    metadata.content_cid.is_none(),
+                    Error::<T>::ContentCidAlreadySet
+                );
+                metadata.content_cid = Some(cid.clone());
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::ContentCidSet {
+                device_id: bounded_device_id,
+                proof_index,
+                cid,
+            });
+
+            Ok(())
+        }
+
+        /// Set or clear the cap on distinct devices with proofs.
+        ///
+        /// A cap at or below `KnownDeviceCount` only stops new devices; devices
+        /// that already have proofs are unaffected.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `max` - Maximum number of devices, or `None` for no limit
+        #[pallet::call_index(25)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_max_total_devices())]
+        pub fn set_max_total_devices(origin: OriginFor<T>, max: Option<u32>) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            MaxTotalDevices::<T>::set(max);
+
+            Self::deposit_event(Event::MaxTotalDevicesSet { max });
+
+            Ok(())
+        }
+
+        /// Override the minimum number of blocks between a device's proofs.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `interval` - Blocks required between proofs, or `None` to restore
+        ///   the configured `MinProofInterval`
+        #[pallet::call_index(26)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_min_proof_interval())]
+        pub fn set_min_proof_interval(
+            origin: OriginFor<T>,
+            interval: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            MinProofIntervalOverride::<T>::set(interval);
+
+            Self::deposit_event(Event::MinProofIntervalSet { interval });
+
+            Ok(())
+        }
+
+        /// Set or clear the period for which proofs count as evidence.
+        ///
+        /// Expiry is computed on read, so changing the period applies to
+        /// already stored proofs without touching them.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `validity` - Blocks a proof stays valid after submission, or
+        ///   `None` to keep proofs valid indefinitely
+        #[pallet::call_index(28)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_proof_validity_blocks())]
+        pub fn set_proof_validity_blocks(
+            origin: OriginFor<T>,
+            validity: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            ProofValidityBlocks::<T>::set(validity);
+
+            Self::deposit_event(Event::ProofValidityBlocksSet { validity });
+
+            Ok(())
+        }
+
+        /// Remove all proofs of a permanently retired device.
+        ///
+        /// The device must be frozen here or retired in the device registry,
+        /// so no proof can arrive while its state is removed.
+        /// Removes up to `limit` proofs, oldest first, with their secondary
+        /// index entries; repeat the call until the event reports `complete`.
+        /// The call that removes the last proof also clears the device's proof
+        /// counters, daily record counts and quality totals. The submission
+        /// nonce and device key are kept so old payloads cannot be replayed.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `device_id` - The device identifier
+        /// - `limit` - Maximum number of proofs to remove in this call
+        #[pallet::call_index(29)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_device_proofs(
+            *limit,
+            T::MaxDaysRetained::get(),
+        ))]
+        pub fn remove_device_proofs(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            limit: u32,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            ensure!(
+                FrozenDevices::<T>::contains_key(&bounded_device_id)
+                    || T::Devices::status(&bounded_device_id) == Some(DeviceStatus::Retired),
+                Error::<T>::DeviceNotRetired
+            );
+            ensure!(
+                ProofCount::<T>::contains_key(&bounded_device_id),
+                Error::<T>::ProofNotFound
+            );
+
+            let mut removed = 0u32;
+            while removed < limit {
+                let oldest = OldestProofIndex::<T>::get(&bounded_device_id);
+                if !Self::remove_proof(&bounded_device_id, oldest) {
+                    break;
+                }
+                removed += 1;
+            }
+            Self::release_storage_warning(&bounded_device_id);
+
+            let complete = StoredProofCount::<T>::get(&bounded_device_id) == 0;
+            if complete {
+                Self::clear_device_state(&bounded_device_id);
+            }
+
+            Self::deposit_event(Event::DeviceProofsRemoved {
+                device_id: bounded_device_id,
+                removed,
+                complete,
+            });
+
+            Ok(())
+        }
+
+        /// Submit a telemetry proof and the energy it covers in one call.
+        ///
+        /// Stores the proof exactly like `submit_proof`, then passes the new
+        /// proof index and `energy_wh` to `T::OnProofAccepted`. If either
+        /// step fails, nothing is stored.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - The transaction origin, as for `submit_proof`
+        /// - `device_id` .. `content_cid` - As for `submit_proof`
+        /// - `energy_wh` - Energy in watt-hours measured over the proof window
+        #[pallet::call_index(30)]
+        #[pallet::weight((
+            <T as pallet::Config>::WeightInfo::submit_proof()
+                .saturating_add(T::OnProofSubmitted::weight())
+                .saturating_add(T::OnProofAccepted::weight()),
+            DispatchClass::Operational
+        ))]
+        #[pallet::feeless_if(|
+            origin: &OriginFor<T>,
+            _device_id: &Vec<u8>,
+            _proof_hash: &Vec<u8>,
+            _record_count: &u32,
+            _window_start: &u64,
+            _window_end: &u64,
+            _data_size_bytes: &u64,
+            _schema_version: &u16,
+            _category: &ProofCategory,
+            _nonce: &u64,
+            _prev_proof_hash: &Option<Vec<u8>>,
+            _signature: &Option<Vec<u8>>,
+            _content_cid: &Option<Vec<u8>>,
+            _energy_wh: &u128,
+        | -> bool { Pallet::<T>::is_feeless_submitter(origin) })]
+        pub fn submit_proof_with_energy(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            proof_hash: Vec<u8>,
+            record_count: u32,
+            window_start: u64,
+            window_end: u64,
+            data_size_bytes: u64,
+            schema_version: u16,
+            category: ProofCategory,
+            nonce: u64,
+            prev_proof_hash: Option<Vec<u8>>,
+            signature: Option<Vec<u8>>,
+            content_cid: Option<Vec<u8>>,
+            energy_wh: u128,
+        ) -> DispatchResult {
+            let who = T::SubmitterOrigin::ensure_origin(origin)?;
+            let bounded_device_id: DeviceId = device_id
+                .clone()
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            let proof_index = Self::do_submit_linked_proof(
+                who,
+                device_id,
+                proof_hash,
+                record_count,
+                window_start,
+                window_end,
+                data_size_bytes,
+                schema_version,
+                category,
+                nonce,
+                prev_proof_hash,
+                signature,
+                content_cid,
+            )?;
+            T::OnProofAccepted::on_proof_accepted(&bounded_device_id, proof_index, energy_wh)
+        }
+
+        /// Mark a device signing key as compromised.
+        ///
+        /// Device-signed proofs submitted at or after `compromised_from_block`
+        /// with `key` no longer count as valid. Also starts a sweep that
+        /// `revoke_compromised_proofs` advances to revoke them.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Admin origin required
+        /// - `device_id` - The device identifier
+        /// - `key` - A key from the device's signing key history
+        /// - `compromised_from_block` - First block the key is no longer trusted
+        #[pallet::call_index(31)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::mark_key_compromised())]
+        pub fn mark_key_compromised(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            key: sr25519::Public,
+            compromised_from_block: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            ensure!(
+                DeviceKeys::<T>::get(&bounded_device_id)
+                    .iter()
+                    .any(|(known, _)| *known == key),
+                Error::<T>::UnknownDeviceKey
+            );
+            CompromisedKeys::<T>::try_mutate(&bounded_device_id, |compromised| {
+                ensure!(
+                    !compromised.iter().any(|(known, _)| *known == key),
+                    Error::<T>::KeyAlreadyCompromised
+                );
+                if compromised.is_full() {
+                    compromised.remove(0);
+                }
+                // Cannot fail: a slot was freed above if the list was full
+                let _ = compromised.try_push((key, compromised_from_block));
+                Ok::<_, DispatchError>(())
+            })?;
+            CompromiseSweepCursor::<T>::insert(
+                &bounded_device_id,
+                OldestProofIndex::<T>::get(&bounded_device_id),
+            );
+
+            Self::deposit_event(Event::DeviceKeyCompromised {
+                device_id: bounded_device_id,
+                key,
+                compromised_from_block,
+            });
+
+            Ok(())
+        }
+
+        /// Revoke proofs signed with a compromised device key.
+        ///
+        /// Checks up to `limit` proofs from the device's sweep cursor and
+        /// revokes each one signed with a compromised key inside its window,
+        /// emitting `ProofRevoked` per proof. The sweep ends once the cursor
+        /// reaches the device's latest proof.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - Any signed account
+        /// - `device_id` - The device identifier
+        /// - `limit` - Maximum number of proofs to check, up to `MaxBatchSize`
+        #[pallet::call_index(32)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::revoke_compromised_proofs(*limit))]
+        pub fn revoke_compromised_proofs(
+            origin: OriginFor<T>,
+            device_id: Vec<u8>,
+            limit: u32,
+        ) -> DispatchResultWithPostInfo {
+            let _who = ensure_signed(origin)?;
+
+            ensure!(limit <= T::MaxBatchSize::get(), Error::<T>::BatchTooLarge);
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+
+            let cursor = CompromiseSweepCursor::<T>::get(&bounded_device_id)
+                .ok_or(Error::<T>::NoCompromiseSweep)?
+                .max(OldestProofIndex::<T>::get(&bounded_device_id));
+            let end = ProofCount::<T>::get(&bounded_device_id);
+            let stop = end.min(cursor.saturating_add(limit.into()));
+
+            let history = DeviceKeys::<T>::get(&bounded_device_id);
+            let compromised = CompromisedKeys::<T>::get(&bounded_device_id);
+            for proof_index in cursor..stop {
+                let revoked =
+                    Proofs::<T>::mutate(&bounded_device_id, proof_index, |maybe_metadata| {
+                        let Some(metadata) = maybe_metadata.as_mut() else {
+                            return false;
+                        };
+                        if metadata.revoked.is_some()
+                            || !Self::signed_with_compromised_key(&history, &compromised, metadata)
+                        {
+                            return false;
+                        }
+                        Self::mark_revoked(
+                            &bounded_device_id,
+                            proof_index,
+                            metadata,
+                            RevocationReason::DeviceCompromised,
+                        );
+                        true
+                    });
+                if revoked {
+                    Self::deposit_event(Event::ProofRevoked {
+                        device_id: bounded_device_id.clone(),
+                        proof_index,
+                        reason: RevocationReason::DeviceCompromised,
+                    });
+                }
+            }
+
+            if stop >= end {
+                CompromiseSweepCursor::<T>::remove(&bounded_device_id);
+            } else {
+                CompromiseSweepCursor::<T>::insert(&bounded_device_id, stop);
+            }
+
+            // At most `limit` proofs were checked
+            let checked = stop.saturating_sub(cursor) as u32;
+            Ok(Some(<T as pallet::Config>::WeightInfo::revoke_compromised_proofs(checked)).into())
+        }
+
+        /// Record that the caller's offchain worker is alive.
+        ///
+        /// Submitted by the offchain worker every `HeartbeatInterval` blocks,
+        /// signed with the node's `AuthorityId` key.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - The transaction origin, admitted by `SubmitterOrigin`
+        #[pallet::call_index(33)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::heartbeat())]
+        #[pallet::feeless_if(|origin: &OriginFor<T>| -> bool {
+            Pallet::<T>::is_feeless_submitter(origin)
+        })]
+        pub fn heartbeat(origin: OriginFor<T>) -> DispatchResult {
+            let who = T::SubmitterOrigin::ensure_origin(origin)?;
+
+            LastHeartbeat::<T>::insert(&who, frame_system::Pallet::<T>::block_number());
+
+            Self::deposit_event(Event::Heartbeat { who });
+
+            Ok(())
+        }
+    }
+
+    // Public query functions for runtime APIs
+    impl<T: Config> Pallet<T> {
+        /// Submit a signed `heartbeat` for every local `AuthorityId` key.
+        ///
+        /// Returns the number of heartbeats handed to the transaction pool.
+        pub fn send_heartbeats() -> u32 {
+            let signer = Signer::<T, T::AuthorityId>::all_accounts();
+            if !signer.can_sign() {
+                log::debug!(target: LOG_TARGET, "No offchain worker key, skipping heartbeat");
+                return 0;
+            }
+
+            let mut sent = 0;
+            for (account, result) in signer.send_signed_transaction(|_| Call::heartbeat {}) {
+                match result {
+                    Ok(()) => sent += 1,
+                    Err(()) => log::warn!(
+                        target: LOG_TARGET,
+                        "Failed to submit heartbeat for {:?}",
+                        account.id
+                    ),
+                }
+            }
+            sent
+        }
+
+        /// Weight of a batch of `len` proofs, including one `OnProofSubmitted`
+        /// notification per proof.
+        fn batch_weight(len: usize) -> Weight {
+            let len: u32 = len.try_into().unwrap_or(u32::MAX);
+            <T as pallet::Config>::WeightInfo::submit_batch_proofs(len)
+                .saturating_add(T::OnProofSubmitted::weight().saturating_mul(len.into()))
+        }
+
+        /// Whether `origin` is an authorized submitter listed in
+        /// `FeelessSubmitters`, so its submissions pay no fee.
+        fn is_feeless_submitter(origin: &OriginFor<T>) -> bool {
+            T::SubmitterOrigin::try_origin(origin.clone())
+                .is_ok_and(|who| T::FeelessSubmitters::contains(&who))
+        }
+
+        /// Check a signed submission's chain link and CID, then store the
+        /// proof. Shared by `submit_proof` and `submit_proof_with_energy`.
+        fn do_submit_linked_proof(
+            who: T::AccountId,
+            device_id: Vec<u8>,
+            proof_hash: Vec<u8>,
+            record_count: u32,
+            window_start: u64,
+            window_end: u64,
+            data_size_bytes: u64,
+            schema_version: u16,
+            category: ProofCategory,
+            nonce: u64,
+            prev_proof_hash: Option<Vec<u8>>,
+            signature: Option<Vec<u8>>,
+            content_cid: Option<Vec<u8>>,
+        ) -> Result<u64, DispatchError> {
+            let prev_proof_hash = prev_proof_hash
+                .map(|hash| Self::normalize_proof_hash(&hash).ok_or(Error::<T>::InvalidProofHash))
+                .transpose()?;
+            Self::ensure_chain_link(&device_id, prev_proof_hash)?;
+            let content_cid = content_cid
+                .map(|cid| cid.try_into().map_err(|_| Error::<T>::CidTooLong))
+                .transpose()?;
+
+            Self::do_submit_proof(
+                Some(who),
+                device_id,
+                proof_hash,
+                record_count,
+                window_start,
+                window_end,
+                data_size_bytes,
+                schema_version,
+                category,
+                nonce,
+                signature,
+                content_cid,
+            )
+        }
+
+        /// Validate and store a single proof, shared by every submission path.
+        ///
+        /// All checks run before any storage is written, so batch callers can
+        /// skip a failed entry without leaving partial state behind.
+        /// Returns the per-device index assigned to the proof.
+        pub(crate) fn do_submit_proof(
+            submitter: Option<T::AccountId>,
+            device_id: Vec<u8>,
+            proof_hash: Vec<u8>,
+            record_count: u32,
+            window_start: u64,
+            window_end: u64,
+            data_size_bytes: u64,
+            schema_version: u16,
+            category: ProofCategory,
+            nonce: u64,
+            signature: Option<Vec<u8>>,
+            content_cid: Option<BoundedVec<u8, T::MaxCidLength>>,
+        ) -> Result<u64, DispatchError> {
+            ensure!(record_count > 0, Error::<T>::EmptyProof);
+            ensure!(
+                record_count <= T::MaxRecordsPerProof::get(),
+                Error::<T>::TooManyRecords
+            );
+
+            // Validate time window
+            ensure!(window_start < window_end, Error::<T>::InvalidTimeWindow);
+            Self::ensure_window_in_time(window_start, window_end)?;
+            ensure!(
+                SupportedSchemas::<T>::contains_key(schema_version),
+                Error::<T>::UnsupportedSchema
+            );
+            let proof_hash =
+                Self::normalize_proof_hash(&proof_hash).ok_or(Error::<T>::InvalidProofHash)?;
+
+            let device_signed = Self::check_device_signature(
+                &device_id,
+                &proof_hash,
+                window_start,
+                window_end,
+                signature.as_deref(),
+            )?;
+
+            let bounded_device_id: DeviceId = device_id
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            ensure!(
+                !FrozenDevices::<T>::contains_key(&bounded_device_id),
+                Error::<T>::DeviceFrozen
+            );
+            ensure!(
+                T::Devices::status(&bounded_device_id) != Some(DeviceStatus::Retired),
+                Error::<T>::DeviceRetired
+            );
+            ensure!(
+                nonce == SubmissionNonce::<T>::get(&bounded_device_id),
+                Error::<T>::BadSubmissionNonce
+            );
+
+            // A device's first proof adds a new device to proof storage
+            let new_device = !ProofCount::<T>::contains_key(&bounded_device_id);
+            if new_device {
+                if let Some(max) = MaxTotalDevices::<T>::get() {
+                    ensure!(
+                        KnownDeviceCount::<T>::get() < max,
+                        Error::<T>::DeviceLimitReached
+                    );
+                }
+            }
+
+            let current_block = <frame_system::Pallet<T>>::block_number();
+
+            if let Some(latest) = LatestProofBlock::<T>::get(&bounded_device_id) {
+                let exempt = T::ExemptTrustedFromProofInterval::get()
+                    && submitter
+                        .as_ref()
+                        .is_some_and(|who| T::ExemptSubmitters::contains(who));
+                ensure!(
+                    exempt || current_block >= latest.saturating_add(Self::min_proof_interval()),
+                    Error::<T>::ProofTooFrequent
+                );
+            }
+
+            // Check max proofs per device
+            let at_capacity = StoredProofCount::<T>::get(&bounded_device_id)
+                >= T::MaxProofsPerDevice::get() as u64;
+            ensure!(
+                !at_capacity || T::PruneOldestProofs::get(),
+                Error::<T>::MaxProofsExceeded
+            );
+
+            // Check for a live proof anchoring the same hash
+            let hash_key = sp_io::hashing::blake2_256(proof_hash.as_bytes());
+            if let Some((owner, _)) = ProofHashIndex::<T>::get(hash_key) {
+                ensure!(
+                    T::AllowSameDeviceResubmission::get() && owner == bounded_device_id,
+                    Error::<T>::DuplicateProofHash
+                );
+            }
+
+            let block_leaves = BlockProofLeaves::<T>::decode_len().unwrap_or(0);
+            ensure!(
+                block_leaves < T::MaxProofsPerBlock::get() as usize,
+                Error::<T>::BlockProofLimitReached
+            );
+            let window_bucket = Self::window_bucket(window_end);
+            ensure!(
+                ProofsByWindowEnd::<T>::decode_len(&bounded_device_id, window_bucket).unwrap_or(0)
+                    < T::MaxProofsPerWindowBucket::get() as usize,
+                Error::<T>::WindowBucketFull
+            );
+
+            let proof_id = Self::proof_id(
+                &bounded_device_id,
+                proof_hash.as_bytes(),
+                window_start,
+                window_end,
+            );
+            ensure!(
+                !ProofIdLookup::<T>::contains_key(proof_id),
+                Error::<T>::DuplicateProofId
+            );
+
+            // Hold the storage deposit; trusted submitters are exempt
+            let deposit = match &submitter {
+                Some(who)
+                    if !T::ExemptSubmitters::contains(who) && !T::ProofDeposit::get().is_zero() =>
+                {
+                    let amount = T::ProofDeposit::get();
+                    T::NativeBalance::hold(&HoldReason::ProofDeposit.into(), who, amount)?;
+                    Some((who.clone(), amount))
+                }
+                _ => None,
+            };
+
+            // Link to the latest stored proof before eviction can remove it
+            let prev_proof_hash = Self::latest_proof_hash(&bounded_device_id);
+
+            // Make room by evicting the oldest retained proof
+            if at_capacity {
+                let oldest = OldestProofIndex::<T>::get(&bounded_device_id);
+                Self::remove_proof(&bounded_device_id, oldest);
+            }
+
+            let current_count = ProofCount::<T>::get(&bounded_device_id);
+
+            if let Some(who) = &submitter {
+                SubmitterStats::<T>::mutate(who, |stats| {
+                    stats.proofs = stats.proofs.saturating_add(1);
+                    stats.records = stats.records.saturating_add(record_count as u64);
+                    stats.last_block = current_block;
+                });
+            }
+
+            // Create proof metadata
+            let metadata = ProofMetadata::<T> {
+                proof_hash,
+                submitted_block: current_block,
+                submitter,
+                timestamp: Self::current_timestamp(),
+                record_count,
+                window_start,
+                window_end,
+                data_size_bytes,
+                schema_version,
+                category,
+                device_signed,
+                prev_proof_hash,
+                revoked: None,
+                challenged: false,
+                quality_score: None,
+                content_cid: content_cid.clone(),
+            };
+
+            // Store proof with index
+            let proof_index = current_count;
+            if let Some(deposit) = deposit {
+                ProofDeposits::<T>::insert(&bounded_device_id, proof_index, deposit);
+            }
+            Proofs::<T>::insert(&bounded_device_id, proof_index, metadata);
+            // Bounded by MaxProofsPerBlock, which BlockProofLeaves enforces above
+            let _ = ProofIndexesByBlock::<T>::try_mutate(
+                current_block,
+                &bounded_device_id,
+                |indexes| indexes.try_push(proof_index),
+            );
+            // Capacity was checked above
+            let _ =
+                ProofsByWindowEnd::<T>::try_append(&bounded_device_id, window_bucket, proof_index);
+            Self::extend_window_bounds(
+                &bounded_device_id,
+                window_bucket,
+                window_end - window_start,
+            );
+            ProofHashIndex::<T>::insert(hash_key, (bounded_device_id.clone(), proof_index));
+            ProofIdLookup::<T>::insert(proof_id, (bounded_device_id.clone(), proof_index));
+            ProofLookup::<T>::insert(&bounded_device_id, hash_key, proof_index);
+            let leaf = Self::block_proof_leaf(&bounded_device_id, proof_index, &proof_hash);
+            // Capacity was checked above
+            let _ = BlockProofLeaves::<T>::try_append(leaf);
+            ProofCount::<T>::mutate(&bounded_device_id, |count| {
+                *count = count.saturating_add(1);
+            });
+            if new_device {
+                KnownDeviceCount::<T>::mutate(|count| *count = count.saturating_add(1));
+            }
+            CategoryProofCount::<T>::mutate(&bounded_device_id, category, |count| {
+                *count = count.saturating_add(1);
+            });
+            TotalRecords::<T>::mutate(&bounded_device_id, |total| {
+                *total = total.saturating_add(record_count as u64);
+            });
+            SubmissionNonce::<T>::insert(&bounded_device_id, nonce.saturating_add(1));
+            let retained = StoredProofCount::<T>::mutate(&bounded_device_id, |count| {
+                *count = count.saturating_add(1);
+                *count
+            });
+            TotalProofs::<T>::mutate(|total| {
+                *total = total.saturating_add(1);
+            });
+            LatestProofBlock::<T>::insert(&bounded_device_id, current_block);
+            Self::record_daily_count(&bounded_device_id, window_end, record_count);
+
+            if T::OffchainIndexing::get() {
+                sp_io::offchain_index::set(
+                    &Self::offchain_proof_key(proof_hash.as_bytes()),
+                    &(&bounded_device_id, current_block, proof_index).encode(),
+                );
+            }
+
+            Self::deposit_device_event(
+                &bounded_device_id,
+                Event::ProofSubmitted {
+                    device_id: bounded_device_id.clone(),
+                    proof_hash,
+                    block_number: current_block,
+                    proof_index,
+                    proof_id,
+                    window_start,
+                    window_end,
+                    data_size_bytes,
+                    schema_version,
+                    category,
+                    content_cid,
+                },
+            );
+
+            if retained >= Self::storage_warning_threshold()
+                && !StorageWarned::<T>::contains_key(&bounded_device_id)
+            {
+                StorageWarned::<T>::insert(&bounded_device_id, ());
+                Self::deposit_event(Event::ProofStorageNearlyFull {
+                    device_id: bounded_device_id.clone(),
+                    retained,
+                    max: T::MaxProofsPerDevice::get(),
+                });
+            }
+
+            T::OnProofSubmitted::on_proof_submitted(
+                &bounded_device_id,
+                proof_index,
+                record_count,
+                window_start,
+                window_end,
+                current_block,
+            );
+
+            Ok(proof_index)
+        }
+
+        /// Add `record_count` to the device's bucket for the day of `window_end`.
+        ///
+        /// Days that fall out of the `MaxDaysRetained` window as the latest day
+        /// advances are removed; counts for days already outside it are dropped.
+        fn record_daily_count(device_id: &DeviceId, window_end: u64, record_count: u32) {
+            let day = (window_end / SECONDS_PER_DAY) as u32;
+            let retained = T::MaxDaysRetained::get();
+            match LatestRecordDay::<T>::get(device_id) {
+                Some(latest) if day < latest.saturating_add(1).saturating_sub(retained) => return,
+                Some(latest) if day > latest => {
+                    // At most `retained` buckets exist, all within the old window
+                    let old_kept_from = latest.saturating_add(1).saturating_sub(retained);
+                    let kept_from = day.saturating_add(1).saturating_sub(retained);
+                    for expired in old_kept_from..kept_from.min(latest.saturating_add(1)) {
+                        DailyRecordCounts::<T>::remove(device_id, expired);
+                    }
+                    LatestRecordDay::<T>::insert(device_id, day);
+                }
+                Some(_) => {}
+                None => LatestRecordDay::<T>::insert(device_id, day),
+            }
+            DailyRecordCounts::<T>::mutate(device_id, day, |count| {
+                *count = count.saturating_add(record_count);
+            });
+        }
+
+        /// Map a `do_submit_proof` error to the reason reported for a batch entry
+        fn batch_reject_reason(error: DispatchError) -> BatchRejectReason {
+            if error == Error::<T>::InvalidTimeWindow.into() {
+                BatchRejectReason::InvalidWindow
+            } else if error == Error::<T>::DeviceIdTooLong.into() {
+                BatchRejectReason::DeviceIdTooLong
+            } else if error == Error::<T>::InvalidProofHash.into() {
+                BatchRejectReason::InvalidProofHash
+            } else if error == Error::<T>::DuplicateProofHash.into()
+                || error == Error::<T>::DuplicateProofId.into()
+            {
+                BatchRejectReason::Duplicate
+            } else if error == Error::<T>::UnsupportedSchema.into() {
+                BatchRejectReason::UnsupportedSchema
+            } else if error == Error::<T>::MaxProofsExceeded.into() {
+                BatchRejectReason::TooManyProofs
+            } else if error == Error::<T>::WindowInFuture.into() {
+                BatchRejectReason::WindowInFuture
+            } else if error == Error::<T>::WindowTooOld.into() {
+                BatchRejectReason::WindowTooOld
+            } else if error == Error::<T>::DeviceFrozen.into() {
+                BatchRejectReason::DeviceFrozen
+            } else if error == Error::<T>::DeviceRetired.into() {
+                BatchRejectReason::DeviceRetired
+            } else if error == Error::<T>::BadSubmissionNonce.into() {
+                BatchRejectReason::BadSubmissionNonce
+            } else if error == Error::<T>::EmptyProof.into() {
+                BatchRejectReason::EmptyProof
+            } else if error == Error::<T>::TooManyRecords.into() {
+                BatchRejectReason::TooManyRecords
+            } else if error == Error::<T>::DeviceLimitReached.into() {
+                BatchRejectReason::DeviceLimitReached
+            } else if error == Error::<T>::ProofTooFrequent.into() {
+                BatchRejectReason::ProofTooFrequent
+            } else if error == Error::<T>::WindowBucketFull.into() {
+                BatchRejectReason::WindowBucketFull
+            } else {
+                BatchRejectReason::Other
+            }
+        }
+
+        /// Remove the hash index entry for a proof if it still points at it.
+        fn release_proof_hash(device_id: &DeviceId, index: u64, proof_hash: &H256) {
+            let hash_key = sp_io::hashing::blake2_256(proof_hash.as_bytes());
+            if ProofHashIndex::<T>::get(hash_key)
+                .is_some_and(|(owner, owner_index)| &owner == device_id && owner_index == index)
+            {
+                ProofHashIndex::<T>::remove(hash_key);
+            }
+        }
+
+        /// Check an unsigned proof's signature, signer, chain and fields.
+        /// Returns the device and its current `SubmissionNonce`; a payload
+        /// below that nonce is `Stale`.
+        fn validate_proof_payload(
+            payload: &ProofPayload<T::Public, T::Hash>,
+            signature: &T::Signature,
+        ) -> Result<(DeviceId, u64), TransactionValidityError> {
+            if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+                return Err(InvalidTransaction::BadProof.into());
+            }
+            if payload.genesis_hash
+                != <frame_system::Pallet<T>>::block_hash(BlockNumberFor::<T>::zero())
+            {
+                return Err(InvalidTransaction::BadProof.into());
+            }
+            let signer = frame_system::RawOrigin::Signed(payload.public.clone().into_account());
+            if T::SubmitterOrigin::try_origin(signer.into()).is_err() {
+                return Err(InvalidTransaction::BadSigner.into());
+            }
+
+            let Ok(device_id) = DeviceId::try_from(payload.device_id.clone()) else {
+                return Err(InvalidTransaction::Custom(1).into());
+            };
+            if Self::normalize_proof_hash(&payload.proof_hash).is_none() {
+                return Err(InvalidTransaction::Custom(2).into());
+            }
+            if payload.window_start >= payload.window_end {
+                return Err(InvalidTransaction::Custom(3).into());
+            }
+            let expected_nonce = SubmissionNonce::<T>::get(&device_id);
+            if payload.nonce < expected_nonce {
+                return Err(InvalidTransaction::Stale.into());
+            }
+
+            Ok((device_id, expected_nonce))
+        }
+
+        /// Parse a submitted proof hash given as 32 raw bytes or as 64 hex
+        /// characters (either case). Returns `None` for any other input.
+        pub fn normalize_proof_hash(raw: &[u8]) -> Option<H256> {
+            match raw.len() {
+                32 => Some(H256::from_slice(raw)),
+                64 => {
+                    let mut bytes = [0u8; 32];
+                    for (byte, pair) in bytes.iter_mut().zip(raw.chunks_exact(2)) {
+                        *byte = (Self::hex_digit(pair[0])? << 4) | Self::hex_digit(pair[1])?;
+                    }
+                    Some(H256(bytes))
+                }
+                _ => None,
+            }
+        }
+
+        fn hex_digit(c: u8) -> Option<u8> {
+            match c {
+                b'0'..=b'9' => Some(c - b'0'),
+                b'a'..=b'f' => Some(c - b'a' + 10),
+                b'A'..=b'F' => Some(c - b'A' + 10),
+                _ => None,
+            }
+        }
+
+        /// Offchain index key under which a proof hash is recorded.
+        pub fn offchain_proof_key(proof_hash: &[u8]) -> Vec<u8> {
+            let mut key = OFFCHAIN_PROOF_PREFIX.to_vec();
+            key.extend_from_slice(&sp_io::hashing::blake2_256(proof_hash));
+            key
+        }
+
+        /// Release the storage deposit held for a proof, if any.
+        fn release_proof_deposit(device_id: &DeviceId, index: u64) {
+            if let Some((depositor, amount)) = ProofDeposits::<T>::take(device_id, index) {
+                let _ = T::NativeBalance::release(
+                    &HoldReason::ProofDeposit.into(),
+                    &depositor,
+                    amount,
+                    Precision::BestEffort,
+                );
+            }
+        }
+
+        /// Release a challenger's held deposit.
+        fn release_challenge_deposit(challenge: &ProofChallenge<T>) {
+            let _ = T::NativeBalance::release(
+                &HoldReason::ChallengeDeposit.into(),
+                &challenge.challenger,
+                challenge.deposit,
+                Precision::BestEffort,
+            );
+        }
+
+        /// Deposit `event` indexed under the device's topic
+        /// ([`DeviceId::topic`]) so clients can filter by device.
+        fn deposit_device_event(device_id: &DeviceId, event: Event<T>) {
+            let mut topic = T::Hash::default();
+            let bytes = device_id.topic();
+            let len = topic.as_ref().len().min(bytes.len());
+            topic.as_mut()[..len].copy_from_slice(&bytes[..len]);
+
+            let event: <T as frame_system::Config>::RuntimeEvent =
+                <T as Config>::RuntimeEvent::from(event).into();
+            frame_system::Pallet::<T>::deposit_event_indexed(&[topic], event);
+        }
+
+        /// Ensure `prev_proof_hash` links to the device's latest stored proof.
+        fn ensure_chain_link(device_id: &[u8], prev_proof_hash: Option<H256>) -> DispatchResult {
+            let bounded_device_id: DeviceId = device_id
+                .to_vec()
+                .try_into()
+                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+            let latest = Self::latest_proof_hash(&bounded_device_id);
+            ensure!(latest == prev_proof_hash, Error::<T>::BrokenProofChain);
+            Ok(())
+        }
+
+        /// Ensure a proof window is plausible against on-chain time.
+        ///
+        /// Skipped while `BackfillMode` is set.
+        fn ensure_window_in_time(window_start: u64, window_end: u64) -> DispatchResult {
+            if BackfillMode::<T>::get() {
+                return Ok(());
+            }
+            let now = T::TimeProvider::now().as_secs();
+            ensure!(
+                window_end <= now.saturating_add(T::MaxClockDrift::get()),
+                Error::<T>::WindowInFuture
+            );
+            if let Some(max_age) = T::MaxWindowAge::get() {
+                ensure!(
+                    window_start >= now.saturating_sub(max_age),
+                    Error::<T>::WindowTooOld
+                );
+            }
+            Ok(())
+        }
+
+        /// Verify a proof signature against the device's current key.
+        ///
+        /// Returns `Ok(false)` when the device has no registered key, `Ok(true)`
+        /// when the signature is valid, and `InvalidDeviceSignature` when a key
+        /// is registered but the signature is missing or does not verify.
+        fn check_device_signature(
+            device_id: &[u8],
+            proof_hash: &H256,
+            window_start: u64,
+            window_end: u64,
+            signature: Option<&[u8]>,
+        ) -> Result<bool, DispatchError> {
+            let current_block = <frame_system::Pallet<T>>::block_number();
+            let Some(public_key) = DeviceId::try_from(device_id.to_vec())
+                .ok()
+                .and_then(|id| Self::device_key_at(&id, current_block))
+            else {
+                return Ok(false);
+            };
+
+            let payload = Self::signing_payload(device_id, proof_hash, window_start, window_end);
+            ensure!(
+                signature.is_some_and(|sig| Self::verify_with_key(&public_key, &payload, sig)),
+                Error::<T>::InvalidDeviceSignature
+            );
+
+            Ok(true)
+        }
+
+        /// Check an sr25519 signature over `payload`.
+        fn verify_with_key(public_key: &sr25519::Public, payload: &[u8], signature: &[u8]) -> bool {
+            sr25519::Signature::try_from(signature)
+                .is_ok_and(|sig| sp_io::crypto::sr25519_verify(&sig, payload, public_key))
+        }
+
+        /// Get the device's current signing key.
+        pub fn current_device_key(device_id: &DeviceId) -> Option<sr25519::Public> {
+            DeviceKeys::<T>::get(device_id).last().map(|(key, _)| *key)
+        }
+
+        /// Get the signing key that was valid for a device at `block`.
+        pub fn device_key_at(
+            device_id: &DeviceId,
+            block: BlockNumberFor<T>,
+        ) -> Option<sr25519::Public> {
+            DeviceKeys::<T>::get(device_id)
+                .iter()
+                .rev()
+                .find(|(_, valid_from)| *valid_from <= block)
+                .map(|(key, _)| *key)
+        }
+
+        /// Check a device signature over `payload` against the key that was
+        /// valid at `block`, e.g. the `submitted_block` of a stored proof.
+        pub fn verify_device_signature_at(
+            device_id: &DeviceId,
+            block: BlockNumberFor<T>,
+            payload: &[u8],
+            signature: &[u8],
+        ) -> bool {
+            Self::device_key_at(device_id, block)
+                .is_some_and(|key| Self::verify_with_key(&key, payload, signature))
+        }
+
+        /// Message a device signs to authenticate a proof.
+        ///
+        /// The hash is signed in its normalized 32-byte form, whichever
+        /// encoding was submitted.
+        pub fn signing_payload(
+            device_id: &[u8],
+            proof_hash: &H256,
+            window_start: u64,
+            window_end: u64,
+        ) -> Vec<u8> {
+            (device_id, proof_hash, window_start, window_end).encode()
+        }
+
+        /// Remove a retained proof and its secondary index entries.
+        ///
+        /// Only the oldest retained proof may be removed so that retained
+        /// proofs stay contiguous. Returns `false` if there was nothing to
+        /// remove at `index`.
+        fn remove_proof(device_id: &DeviceId, index: u64) -> bool {
+            if index != OldestProofIndex::<T>::get(device_id) {
+                return false;
+            }
+            let Some(metadata) = Proofs::<T>::take(device_id, index) else {
+                return false;
+            };
+
+            ProofIndexesByBlock::<T>::mutate_exists(
+                metadata.submitted_block,
+                device_id,
+                |indexes| {
+                    if let Some(list) = indexes {
+                        list.retain(|i| *i != index);
+                        if list.is_empty() {
+                            *indexes = None;
+                        }
+                    }
+                },
+            );
+            Self::unindex_window(device_id, index, metadata.window_end);
+            Self::release_proof_hash(device_id, index, &metadata.proof_hash);
+            Self::release_proof_deposit(device_id, index);
+            ProofIdLookup::<T>::remove(Self::proof_id(
+                device_id,
+                metadata.proof_hash.as_bytes(),
+                metadata.window_start,
+                metadata.window_end,
+            ));
+            let hash_key = sp_io::hashing::blake2_256(metadata.proof_hash.as_bytes());
+            if ProofLookup::<T>::get(device_id, hash_key) == Some(index) {
+                ProofLookup::<T>::remove(device_id, hash_key);
+            }
+            // A challenge cannot outlive its proof; return the deposit
+            if let Some(challenge) = Challenges::<T>::take(device_id, index) {
+                Self::release_challenge_deposit(&challenge);
+            }
+            ProofAnchors::<T>::remove(device_id, index);
+            OldestProofIndex::<T>::insert(device_id, index.saturating_add(1));
+            let remaining = StoredProofCount::<T>::mutate(device_id, |count| {
+                *count = count.saturating_sub(1);
+                *count
+            });
+            // Revoked proofs were already taken off the total
+            if metadata.revoked.is_none() {
+                TotalProofs::<T>::mutate(|total| {
+                    *total = total.saturating_sub(1);
+                });
+            }
+            if remaining == 0 {
+                LatestProofBlock::<T>::remove(device_id);
+                WindowIndexBounds::<T>::remove(device_id);
+            }
+
+            true
+        }
 
-            // Search for the proof in device's proof history
-            let proof_count = ProofCount::<T>::get(&bounded_device_id);
-            let mut exists = false;
+        /// Retained proofs at which a device is warned of nearly full storage.
+        pub fn storage_warning_threshold() -> u64 {
+            T::ProofStorageWarningPercent::get().mul_ceil(T::MaxProofsPerDevice::get()) as u64
+        }
 
-            for i in 0..proof_count {
-                if let Some(metadata) = Proofs::<T>::get(&bounded_device_id, i) {
-                    if metadata.proof_hash == bounded_proof {
-                        exists = true;
-                        break;
-                    }
-                }
+        /// Re-arm `ProofStorageNearlyFull` once pruning drops a device below
+        /// the warning threshold.
+        fn release_storage_warning(device_id: &DeviceId) {
+            if StoredProofCount::<T>::get(device_id) < Self::storage_warning_threshold() {
+                StorageWarned::<T>::remove(device_id);
             }
+        }
 
-            Self::deposit_event(Event::ProofVerified {
-                device_id: bounded_device_id,
-                proof_hash: bounded_proof,
-                exists,
+        /// Drop the per-device counters of a device whose last proof is gone.
+        fn clear_device_state(device_id: &DeviceId) {
+            ProofCount::<T>::remove(device_id);
+            StoredProofCount::<T>::remove(device_id);
+            OldestProofIndex::<T>::remove(device_id);
+            LatestProofBlock::<T>::remove(device_id);
+            WindowIndexBounds::<T>::remove(device_id);
+            TotalRecords::<T>::remove(device_id);
+            DeviceQuality::<T>::remove(device_id);
+            LatestRecordDay::<T>::remove(device_id);
+            StorageWarned::<T>::remove(device_id);
+            // At most `MaxDaysRetained` buckets and one entry per category
+            let _ = DailyRecordCounts::<T>::clear_prefix(device_id, u32::MAX, None);
+            let _ = CategoryProofCount::<T>::clear_prefix(device_id, u32::MAX, None);
+            KnownDeviceCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+        }
+
+        /// `ProofsByWindowEnd` bucket of a proof window ending at `window_end`.
+        pub fn window_bucket(window_end: u64) -> u64 {
+            window_end / T::WindowBucketSeconds::get().max(1)
+        }
+
+        /// Widen a device's `WindowIndexBounds` to cover a newly indexed proof.
+        pub(crate) fn extend_window_bounds(device_id: &DeviceId, bucket: u64, window_length: u64) {
+            WindowIndexBounds::<T>::mutate(device_id, |bounds| {
+                let bounds = bounds.get_or_insert(WindowBounds {
+                    first_bucket: bucket,
+                    last_bucket: bucket,
+                    longest_window: window_length,
+                });
+                bounds.first_bucket = bounds.first_bucket.min(bucket);
+                bounds.last_bucket = bounds.last_bucket.max(bucket);
+                bounds.longest_window = bounds.longest_window.max(window_length);
             });
+        }
 
-            Ok(())
+        /// Revoke a stored proof: drop it from the window and hash indexes,
+        /// its records from `TotalRecords` and itself from `TotalProofs`, and
+        /// release its deposit. The caller writes `metadata` back.
+        fn mark_revoked(
+            device_id: &DeviceId,
+            index: u64,
+            metadata: &mut ProofMetadata<T>,
+            reason: RevocationReason,
+        ) {
+            metadata.revoked = Some(reason);
+            Self::unindex_window(device_id, index, metadata.window_end);
+            Self::discount_records(device_id, metadata.record_count);
+            Self::release_proof_hash(device_id, index, &metadata.proof_hash);
+            Self::release_proof_deposit(device_id, index);
+            TotalProofs::<T>::mutate(|total| {
+                *total = total.saturating_sub(1);
+            });
         }
 
-        /// Submit a telemetry proof without requiring a signed transaction.
-        ///
-        /// This extrinsic is validated via ValidateUnsigned and is intended for
-        /// use by the blockchain bridge service where authentication happens at
-        /// the MQTT/API layer rather than the blockchain layer.
-        ///
-        /// # Arguments
-        ///
-        /// - `origin` - Must be none (unsigned)
-        /// - `device_id` - The device identifier (UUID format)
-        /// - `proof_hash` - The cryptographic hash of the telemetry batch
-        /// - `record_count` - Number of telemetry records in this batch
-        /// - `window_start` - Start timestamp of the telemetry window
-        /// - `window_end` - End timestamp of the telemetry window
-        #[pallet::call_index(3)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_proof())]
-        pub fn submit_proof_unsigned(
-            origin: OriginFor<T>,
-            device_id: Vec<u8>,
-            proof_hash: Vec<u8>,
-            record_count: u32,
-            window_start: u64,
-            window_end: u64,
-        ) -> DispatchResult {
-            ensure_none(origin)?;
+        /// Subtract a removed proof's records from the device's `TotalRecords`.
+        fn discount_records(device_id: &DeviceId, records: u32) {
+            TotalRecords::<T>::mutate(device_id, |total| {
+                *total = total.saturating_sub(records as u64);
+            });
+        }
 
-            // Validate time window
-            ensure!(window_start < window_end, Error::<T>::InvalidTimeWindow);
+        /// Drop a proof from `ProofsByWindowEnd`.
+        fn unindex_window(device_id: &DeviceId, index: u64, window_end: u64) {
+            ProofsByWindowEnd::<T>::mutate_exists(
+                device_id,
+                Self::window_bucket(window_end),
+                |indexes| {
+                    if let Some(list) = indexes {
+                        list.retain(|i| *i != index);
+                        if list.is_empty() {
+                            *indexes = None;
+                        }
+                    }
+                },
+            );
+        }
 
-            let bounded_device_id: BoundedVec<u8, T::MaxDeviceIdLength> = device_id
-                .try_into()
-                .map_err(|_| Error::<T>::DeviceIdTooLong)?;
+        /// Range of proof indexes currently retained for a device.
+        fn retained_range(device_id: &DeviceId) -> core::ops::Range<u64> {
+            OldestProofIndex::<T>::get(device_id)..ProofCount::<T>::get(device_id)
+        }
 
-            let bounded_proof: BoundedVec<u8, T::MaxProofLength> = proof_hash
-                .try_into()
-                .map_err(|_| Error::<T>::ProofTooLong)?;
+        /// Read the current on-chain timestamp from pallet_timestamp.
+        fn current_timestamp() -> u64 {
+            let moment = <pallet_timestamp::Pallet<T>>::get();
+            moment.try_into().unwrap_or(0)
+        }
 
-            let current_block = <frame_system::Pallet<T>>::block_number();
+        /// Check that proof counters and lookup indexes agree with `Proofs`.
+        ///
+        /// The offending device is logged when an invariant is violated.
+        #[cfg(any(feature = "try-runtime", test))]
+        pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+            let mut total = 0u64;
+            let mut devices = 0u32;
+            for (device_id, lifetime) in ProofCount::<T>::iter() {
+                devices = devices.saturating_add(1);
+                let stored = StoredProofCount::<T>::get(&device_id);
+                let entries = Proofs::<T>::iter_key_prefix(&device_id).collect::<Vec<_>>();
+                if entries.len() as u64 != stored {
+                    log::error!(
+                        target: LOG_TARGET,
+                        "device 0x{}: {} stored proofs but retained count is {}",
+                        sp_core::hexdisplay::HexDisplay::from(&device_id.as_slice()),
+                        entries.len(),
+                        stored,
+                    );
+                    return Err("retained count does not match stored proofs".into());
+                }
+                let retained = OldestProofIndex::<T>::get(&device_id)..lifetime;
+                if let Some(index) = entries.iter().find(|i| !retained.contains(*i)) {
+                    log::error!(
+                        target: LOG_TARGET,
+                        "device 0x{}: proof {} outside retained range {:?}",
+                        sp_core::hexdisplay::HexDisplay::from(&device_id.as_slice()),
+                        index,
+                        retained,
+                    );
+                    return Err("stored proof outside the retained range".into());
+                }
+                let unrevoked = Proofs::<T>::iter_prefix_values(&device_id)
+                    .filter(|metadata| metadata.revoked.is_none())
+                    .count() as u64;
+                total = total.saturating_add(unrevoked);
+            }
+
+            if let Some(device_id) = StoredProofCount::<T>::iter_keys()
+                .find(|device_id| !ProofCount::<T>::contains_key(device_id))
+            {
+                log::error!(
+                    target: LOG_TARGET,
+                    "device 0x{}: retained count without a lifetime count",
+                    sp_core::hexdisplay::HexDisplay::from(&device_id.as_slice()),
+                );
+                return Err("retained count without a lifetime count".into());
+            }
 
-            // Check max proofs per device
-            let current_count = ProofCount::<T>::get(&bounded_device_id);
             ensure!(
-                current_count < T::MaxProofsPerDevice::get() as u64,
-                Error::<T>::MaxProofsExceeded
+                TotalProofs::<T>::get() == total,
+                "TotalProofs does not equal the number of unrevoked proofs"
             );
-
-            // Check for duplicate at same block
             ensure!(
-                !ProofsByBlock::<T>::contains_key(current_block, &bounded_device_id),
-                Error::<T>::ProofAlreadyExists
+                KnownDeviceCount::<T>::get() == devices,
+                "KnownDeviceCount does not equal the number of devices with proofs"
             );
 
-            // Create proof metadata
-            let metadata = ProofMetadata::<T> {
-                proof_hash: bounded_proof.clone(),
-                block_number: current_block,
-                timestamp: Self::current_timestamp(),
-                record_count,
-                window_start,
-                window_end,
-            };
-
-            // Store proof with index
-            let proof_index = current_count;
-            Proofs::<T>::insert(&bounded_device_id, proof_index, metadata);
-            ProofsByBlock::<T>::insert(current_block, &bounded_device_id, bounded_proof.clone());
-            ProofCount::<T>::mutate(&bounded_device_id, |count| *count += 1);
-            TotalProofs::<T>::mutate(|total| *total += 1);
-            LatestProofBlock::<T>::insert(&bounded_device_id, current_block);
-
-            Self::deposit_event(Event::ProofSubmitted {
-                device_id: bounded_device_id,
-                proof_hash: bounded_proof,
-                block_number: current_block,
-                proof_index,
-            });
+            for (device_id, hash_key, index) in ProofLookup::<T>::iter() {
+                let points_at_proof = Proofs::<T>::get(&device_id, index).is_some_and(|metadata| {
+                    sp_io::hashing::blake2_256(metadata.proof_hash.as_bytes()) == hash_key
+                });
+                if !points_at_proof {
+                    log::error!(
+                        target: LOG_TARGET,
+                        "device 0x{}: lookup entry points at missing proof {}",
+                        sp_core::hexdisplay::HexDisplay::from(&device_id.as_slice()),
+                        index,
+                    );
+                    return Err("lookup entry points at a missing proof".into());
+                }
+            }
 
             Ok(())
         }
 
-        /// Submit multiple proofs in a single unsigned transaction.
+        /// Opaque identifier of a proof for off-chain systems.
         ///
-        /// This is more efficient than multiple individual submissions.
-        /// Validated via ValidateUnsigned for bridge service use.
+        /// `blake2_256(device_id ++ proof_hash ++ window_start ++ window_end)`
+        /// with the window bounds as little-endian `u64`s.
+        pub fn proof_id(
+            device_id: &[u8],
+            proof_hash: &[u8],
+            window_start: u64,
+            window_end: u64,
+        ) -> H256 {
+            let mut preimage = Vec::with_capacity(device_id.len() + proof_hash.len() + 16);
+            preimage.extend_from_slice(device_id);
+            preimage.extend_from_slice(proof_hash);
+            preimage.extend_from_slice(&window_start.to_le_bytes());
+            preimage.extend_from_slice(&window_end.to_le_bytes());
+            H256(sp_io::hashing::blake2_256(&preimage))
+        }
+
+        /// Resolve a proof id to its device, index and metadata.
+        pub fn lookup_proof(proof_id: H256) -> Option<(DeviceId, u64, ProofMetadata<T>)> {
+            let (device_id, index) = ProofIdLookup::<T>::get(proof_id)?;
+            let metadata = Proofs::<T>::get(&device_id, index)?;
+            Some((device_id, index, metadata))
+        }
+
+        /// Get proof metadata by device and index.
+        pub fn get_proof(device_id: &DeviceId, index: u64) -> Option<ProofMetadata<T>> {
+            Proofs::<T>::get(device_id, index)
+        }
+
+        /// Index the next proof submitted for a device will receive.
         ///
-        /// # Arguments
+        /// Proofs are numbered per device from zero, so this is also the
+        /// lifetime proof count.
+        pub fn next_proof_index(device_id: &DeviceId) -> u64 {
+            ProofCount::<T>::get(device_id)
+        }
+
+        /// Get all proofs for a device.
+        pub fn get_device_proofs(device_id: &DeviceId) -> Vec<ProofMetadata<T>> {
+            Self::retained_range(device_id)
+                .filter_map(|i| Proofs::<T>::get(device_id, i))
+                .collect()
+        }
+
+        /// Get up to `count` retained proofs starting at `start_index`.
         ///
-        /// - `origin` - Must be none (unsigned)
-        /// - `proofs` - Vector of (device_id, proof_hash, record_count, window_start, window_end)
-        #[pallet::call_index(4)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_batch_proofs(proofs.len() as u32))]
-        pub fn submit_batch_proofs_unsigned(
-            origin: OriginFor<T>,
-            proofs: Vec<BatchProofEntry>,
-        ) -> DispatchResult {
-            ensure_none(origin)?;
+        /// `count` is capped by `MaxPageSize`. Indexes below the oldest retained
+        /// proof are skipped. Returns the page and the index to resume from, or
+        /// `None` once the device's latest proof has been returned.
+        pub fn get_proofs_paged(
+            device_id: &DeviceId,
+            start_index: u64,
+            count: u32,
+        ) -> (Vec<ProofMetadata<T>>, Option<u64>) {
+            let retained = Self::retained_range(device_id);
+            let start = start_index.max(retained.start);
+            let end = start
+                .saturating_add(count.min(T::MaxPageSize::get()) as u64)
+                .min(retained.end);
+            let page = (start..end)
+                .filter_map(|i| Proofs::<T>::get(device_id, i))
+                .collect();
+            let next = (end < retained.end).then_some(end);
+            (page, next)
+        }
 
-            let batch_len = proofs.len() as u32;
-            ensure!(batch_len > 0, Error::<T>::EmptyBatch);
-            ensure!(
-                batch_len <= T::MaxBatchSize::get(),
-                Error::<T>::BatchTooLarge
-            );
+        /// Page through a device's retained proofs like `get_proofs_paged`,
+        /// flagging each proof with whether it has expired.
+        pub fn get_proof_history(
+            device_id: &DeviceId,
+            start_index: u64,
+            count: u32,
+        ) -> (Vec<(ProofMetadata<T>, bool)>, Option<u64>) {
+            let (page, next) = Self::get_proofs_paged(device_id, start_index, count);
+            let page = page
+                .into_iter()
+                .map(|metadata| {
+                    let expired = Self::is_expired(&metadata);
+                    (metadata, expired)
+                })
+                .collect();
+            (page, next)
+        }
 
-            let current_block = <frame_system::Pallet<T>>::block_number();
-            let mut successful_count = 0u32;
+        /// Get a device's latest `n` retained, unrevoked proofs, newest first.
+        ///
+        /// `n` is capped by `MaxPageSize`. Walks back from the latest proof
+        /// index, so the order follows proof indexes rather than timestamps.
+        pub fn get_recent_proofs(device_id: &DeviceId, n: u32) -> Vec<ProofMetadata<T>> {
+            Self::retained_range(device_id)
+                .rev()
+                .filter_map(|i| Proofs::<T>::get(device_id, i))
+                .filter(|m| m.revoked.is_none())
+                .take(n.min(T::MaxPageSize::get()) as usize)
+                .collect()
+        }
 
-            for (device_id, proof_hash, record_count, window_start, window_end) in proofs {
-                if window_start >= window_end {
-                    continue;
-                }
+        /// Get the retained proofs a device submitted in `block`, in index order.
+        pub fn get_proofs_in_block(
+            device_id: &DeviceId,
+            block: BlockNumberFor<T>,
+        ) -> Vec<ProofMetadata<T>> {
+            ProofIndexesByBlock::<T>::get(block, device_id)
+                .into_iter()
+                .filter_map(|i| Proofs::<T>::get(device_id, i))
+                .collect()
+        }
 
-                let bounded_device_id: BoundedVec<u8, T::MaxDeviceIdLength> =
-                    match device_id.try_into() {
-                        Ok(id) => id,
-                        Err(_) => continue,
-                    };
+        /// Check if a valid (not revoked, challenged, expired or signed with a
+        /// compromised key) proof hash exists for a device.
+        pub fn proof_exists(device_id: &DeviceId, proof_hash: &H256) -> bool {
+            Self::find_proof_index(device_id, proof_hash)
+                .and_then(|index| Proofs::<T>::get(device_id, index))
+                .is_some_and(|metadata| {
+                    metadata.is_valid()
+                        && !Self::is_expired(&metadata)
+                        && !Self::is_compromised(device_id, &metadata)
+                })
+        }
 
-                let bounded_proof: BoundedVec<u8, T::MaxProofLength> = match proof_hash.try_into() {
-                    Ok(hash) => hash,
-                    Err(_) => continue,
-                };
+        /// Whether a device-signed proof was signed with a key marked
+        /// compromised at or before the proof's `submitted_block`.
+        pub fn is_compromised(device_id: &DeviceId, metadata: &ProofMetadata<T>) -> bool {
+            if !metadata.device_signed {
+                return false;
+            }
+            let compromised = CompromisedKeys::<T>::get(device_id);
+            !compromised.is_empty()
+                && Self::signed_with_compromised_key(
+                    &DeviceKeys::<T>::get(device_id),
+                    &compromised,
+                    metadata,
+                )
+        }
 
-                let current_count = ProofCount::<T>::get(&bounded_device_id);
-                if current_count >= T::MaxProofsPerDevice::get() as u64 {
-                    continue;
-                }
-                if ProofsByBlock::<T>::contains_key(current_block, &bounded_device_id) {
-                    continue;
-                }
+        /// Check a proof against already loaded key history and compromised
+        /// key lists, so sweeps read them once per call.
+        fn signed_with_compromised_key(
+            history: &[(sr25519::Public, BlockNumberFor<T>)],
+            compromised: &[(sr25519::Public, BlockNumberFor<T>)],
+            metadata: &ProofMetadata<T>,
+        ) -> bool {
+            let block = metadata.submitted_block;
+            metadata.device_signed
+                && history
+                    .iter()
+                    .rev()
+                    .find(|(_, valid_from)| *valid_from <= block)
+                    .is_some_and(|(key, _)| {
+                        compromised
+                            .iter()
+                            .any(|(bad, from)| bad == key && *from <= block)
+                    })
+        }
 
-                let metadata = ProofMetadata::<T> {
-                    proof_hash: bounded_proof.clone(),
-                    block_number: current_block,
-                    timestamp: Self::current_timestamp(),
-                    record_count,
-                    window_start,
-                    window_end,
-                };
+        /// Whether a proof was submitted more than `ProofValidityBlocks` ago.
+        pub fn is_expired(metadata: &ProofMetadata<T>) -> bool {
+            ProofValidityBlocks::<T>::get().is_some_and(|validity| {
+                metadata.submitted_block
+                    < frame_system::Pallet::<T>::block_number().saturating_sub(validity)
+            })
+        }
 
-                let proof_index = current_count;
-                Proofs::<T>::insert(&bounded_device_id, proof_index, metadata);
-                ProofsByBlock::<T>::insert(
-                    current_block,
-                    &bounded_device_id,
-                    bounded_proof.clone(),
-                );
-                ProofCount::<T>::mutate(&bounded_device_id, |count| *count += 1);
-                TotalProofs::<T>::mutate(|total| *total += 1);
-                LatestProofBlock::<T>::insert(&bounded_device_id, current_block);
+        /// Minimum number of blocks between two proofs of the same device.
+        pub fn min_proof_interval() -> BlockNumberFor<T> {
+            MinProofIntervalOverride::<T>::get().unwrap_or_else(T::MinProofInterval::get)
+        }
 
-                Self::deposit_event(Event::ProofSubmitted {
-                    device_id: bounded_device_id,
-                    proof_hash: bounded_proof,
-                    block_number: current_block,
-                    proof_index,
-                });
+        /// Get the hash of a device's latest stored proof.
+        pub fn latest_proof_hash(device_id: &DeviceId) -> Option<H256> {
+            let latest_index = ProofCount::<T>::get(device_id).checked_sub(1)?;
+            Proofs::<T>::get(device_id, latest_index).map(|metadata| metadata.proof_hash)
+        }
 
-                successful_count += 1;
+        /// Check that proofs `from_index..=to_index` form an unbroken chain.
+        ///
+        /// Every proof in the range must still be stored and commit to the
+        /// hash of its predecessor. Pruned or missing proofs break the chain.
+        pub fn verify_chain(device_id: &DeviceId, from_index: u64, to_index: u64) -> bool {
+            if from_index > to_index {
+                return false;
+            }
+            let Some(mut previous) = Proofs::<T>::get(device_id, from_index) else {
+                return false;
+            };
+            for index in from_index.saturating_add(1)..=to_index {
+                let Some(current) = Proofs::<T>::get(device_id, index) else {
+                    return false;
+                };
+                if current.prev_proof_hash != Some(previous.proof_hash) {
+                    return false;
+                }
+                previous = current;
             }
+            true
+        }
 
-            Self::deposit_event(Event::UnsignedBatchProofsSubmitted {
-                proof_count: successful_count,
-                block_number: current_block,
-            });
+        /// Recompute a Merkle root from a leaf and its sibling path.
+        ///
+        /// Each path entry is `(sibling, sibling_is_left)`.
+        pub fn compute_merkle_root(leaf_hash: H256, path: &[(H256, bool)]) -> H256 {
+            path.iter()
+                .fold(leaf_hash, |node, (sibling, sibling_is_left)| {
+                    let (left, right) = if *sibling_is_left {
+                        (sibling, &node)
+                    } else {
+                        (&node, sibling)
+                    };
+                    let mut pair = [0u8; 64];
+                    pair[..32].copy_from_slice(left.as_bytes());
+                    pair[32..].copy_from_slice(right.as_bytes());
+                    H256(sp_io::hashing::blake2_256(&pair))
+                })
+        }
 
-            Ok(())
+        /// Leaf contributed by a proof to its block's proof root.
+        pub fn block_proof_leaf(device_id: &DeviceId, proof_index: u64, proof_hash: &H256) -> H256 {
+            H256(sp_io::hashing::blake2_256(
+                &(device_id, proof_index, proof_hash).encode(),
+            ))
         }
-    }
 
-    // Public query functions for runtime APIs
-    impl<T: Config> Pallet<T> {
-        /// Read the current on-chain timestamp from pallet_timestamp.
-        fn current_timestamp() -> u64 {
-            let moment = <pallet_timestamp::Pallet<T>>::get();
-            moment.try_into().unwrap_or(0)
+        /// Merkle root over `leaves`; an unpaired node is carried up unchanged.
+        pub fn merkle_root_of(leaves: &[H256]) -> H256 {
+            let mut level = leaves.to_vec();
+            while level.len() > 1 {
+                level = Self::next_merkle_level(&level);
+            }
+            level.first().copied().unwrap_or_default()
         }
 
-        /// Get proof metadata by device and index.
-        pub fn get_proof(
-            device_id: &BoundedVec<u8, T::MaxDeviceIdLength>,
-            index: u64,
-        ) -> Option<ProofMetadata<T>> {
-            Proofs::<T>::get(device_id, index)
+        /// Inclusion path of `leaves[index]` in the tree of [`Self::merkle_root_of`].
+        pub fn merkle_inclusion_path(leaves: &[H256], index: usize) -> Option<Vec<(H256, bool)>> {
+            if index >= leaves.len() {
+                return None;
+            }
+            let mut level = leaves.to_vec();
+            let mut index = index;
+            let mut path = Vec::new();
+            while level.len() > 1 {
+                let sibling = index ^ 1;
+                if let Some(node) = level.get(sibling) {
+                    path.push((*node, sibling < index));
+                }
+                level = Self::next_merkle_level(&level);
+                index /= 2;
+            }
+            Some(path)
         }
 
-        /// Get all proofs for a device.
-        pub fn get_device_proofs(
-            device_id: &BoundedVec<u8, T::MaxDeviceIdLength>,
-        ) -> Vec<ProofMetadata<T>> {
-            let count = ProofCount::<T>::get(device_id);
-            (0..count)
-                .filter_map(|i| Proofs::<T>::get(device_id, i))
+        /// Inclusion path of a proof accepted in the current block, by the
+        /// order in which proofs were accepted.
+        pub fn block_proof_inclusion_path(leaf_index: u32) -> Option<Vec<(H256, bool)>> {
+            Self::merkle_inclusion_path(&BlockProofLeaves::<T>::get(), leaf_index as usize)
+        }
+
+        fn next_merkle_level(level: &[H256]) -> Vec<H256> {
+            level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => Self::compute_merkle_root(*left, &[(*right, false)]),
+                    _ => pair[0],
+                })
                 .collect()
         }
 
-        /// Check if a specific proof hash exists for a device.
-        pub fn proof_exists(
-            device_id: &BoundedVec<u8, T::MaxDeviceIdLength>,
-            proof_hash: &BoundedVec<u8, T::MaxProofLength>,
+        /// Check that a leaf is included in a valid stored proof.
+        ///
+        /// Returns `false` when the proof is missing, revoked or challenged,
+        /// when the path exceeds `MaxMerkleDepth`, or when the recomputed root
+        /// differs from the stored proof hash.
+        pub fn check_merkle_inclusion(
+            device_id: &DeviceId,
+            proof_index: u64,
+            leaf_hash: H256,
+            path: &[(H256, bool)],
         ) -> bool {
-            let count = ProofCount::<T>::get(device_id);
-            for i in 0..count {
-                if let Some(metadata) = Proofs::<T>::get(device_id, i) {
-                    if &metadata.proof_hash == proof_hash {
-                        return true;
+            if path.len() > T::MaxMerkleDepth::get() as usize {
+                return false;
+            }
+            Proofs::<T>::get(device_id, proof_index).is_some_and(|metadata| {
+                metadata.is_valid()
+                    && !Self::is_compromised(device_id, &metadata)
+                    && metadata.proof_hash == Self::compute_merkle_root(leaf_hash, path)
+            })
+        }
+
+        /// Find the index of a proof hash in a device's proof history.
+        ///
+        /// Revoked proofs are still found so they remain auditable.
+        pub fn find_proof_index(device_id: &DeviceId, proof_hash: &H256) -> Option<u64> {
+            let hash_key = sp_io::hashing::blake2_256(proof_hash.as_bytes());
+            ProofLookup::<T>::get(device_id, hash_key)
+        }
+
+        /// Remove proofs older than `RetentionBlocks` within `remaining_weight`.
+        ///
+        /// Resumes from `RetentionCursor`, removing each device's expired
+        /// proofs oldest first. The cursor is saved when the weight runs out
+        /// and cleared after the last device. Returns the weight consumed.
+        pub fn prune_expired_proofs(remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            // Reading the retention period and cursor, saving or clearing the cursor
+            let mut used = db_weight.reads_writes(2, 1);
+            if used.any_gt(remaining_weight) {
+                return Weight::zero();
+            }
+            let Some(retention) = RetentionBlocks::<T>::get() else {
+                return db_weight.reads(1);
+            };
+            let cutoff = <frame_system::Pallet<T>>::block_number().saturating_sub(retention);
+
+            // Next device key, its oldest index and oldest proof
+            let per_device = db_weight.reads(3);
+            let per_proof = T::WeightInfo::prune_proofs_before(1);
+
+            let mut resume_from = RetentionCursor::<T>::get();
+            let mut devices = match &resume_from {
+                Some(cursor) => StoredProofCount::<T>::iter_keys_from(cursor.to_vec()),
+                None => StoredProofCount::<T>::iter_keys(),
+            };
+            loop {
+                if used.saturating_add(per_device).any_gt(remaining_weight) {
+                    break;
+                }
+                let Some(device_id) = devices.next() else {
+                    resume_from = None;
+                    break;
+                };
+                used.saturating_accrue(per_device);
+
+                let mut removed = 0u32;
+                let mut finished = true;
+                loop {
+                    let oldest = OldestProofIndex::<T>::get(&device_id);
+                    let expired = Proofs::<T>::get(&device_id, oldest)
+                        .is_some_and(|metadata| metadata.submitted_block < cutoff);
+                    if !expired {
+                        break;
+                    }
+                    if used.saturating_add(per_proof).any_gt(remaining_weight) {
+                        finished = false;
+                        break;
                     }
+                    Self::remove_proof(&device_id, oldest);
+                    used.saturating_accrue(per_proof);
+                    removed = removed.saturating_add(1);
+                }
+
+                if removed > 0 {
+                    Self::release_storage_warning(&device_id);
+                    Self::deposit_event(Event::ProofsPruned {
+                        device_id: device_id.clone(),
+                        removed,
+                    });
+                }
+                if !finished {
+                    break;
+                }
+                // Unreachable for bounded keys; restarting the sweep is safe
+                resume_from = BackfillCursor::try_from(devices.last_raw_key().to_vec()).ok();
+            }
+
+            match resume_from {
+                Some(cursor) => RetentionCursor::<T>::put(cursor),
+                None => RetentionCursor::<T>::kill(),
+            }
+
+            used
+        }
+
+        /// Remove proofs of decommissioned devices within `remaining_weight`.
+        ///
+        /// Works on one device from `PendingProofRemovals` per call, removing
+        /// its proofs oldest first. Once none are left the device's proof
+        /// state is cleared and it leaves the queue. Returns the weight
+        /// consumed.
+        pub fn prune_decommissioned(remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            // Reading the queue head, clearing the device and its queue entry
+            let mut used = db_weight
+                .reads(1)
+                .saturating_add(T::WeightInfo::remove_device_proofs(
+                    0,
+                    T::MaxDaysRetained::get(),
+                ));
+            if used.any_gt(remaining_weight) {
+                return Weight::zero();
+            }
+            let Some(device_id) = PendingProofRemovals::<T>::iter_keys().next() else {
+                return db_weight.reads(1);
+            };
+
+            let per_proof = T::WeightInfo::prune_proofs_before(1);
+            let mut removed = 0u32;
+            while !used.saturating_add(per_proof).any_gt(remaining_weight) {
+                let oldest = OldestProofIndex::<T>::get(&device_id);
+                if !Self::remove_proof(&device_id, oldest) {
+                    break;
                 }
+                used.saturating_accrue(per_proof);
+                removed = removed.saturating_add(1);
+            }
+            Self::release_storage_warning(&device_id);
+
+            let complete = StoredProofCount::<T>::get(&device_id) == 0;
+            if complete {
+                Self::clear_device_state(&device_id);
+                PendingProofRemovals::<T>::remove(&device_id);
+            }
+            if removed > 0 || complete {
+                Self::deposit_event(Event::DeviceProofsRemoved {
+                    device_id,
+                    removed,
+                    complete,
+                });
             }
-            false
+
+            used
         }
 
-        /// Get proofs within a time window for a device.
+        /// Get unrevoked proofs whose telemetry window overlaps
+        /// `[start_time, end_time]`, optionally only those of `category`.
+        ///
+        /// Boundaries are inclusive: a proof ending exactly at `start_time` or
+        /// starting exactly at `end_time` is returned. Results are in proof
+        /// index order and capped at `MaxQueryResults` entries.
+        ///
+        /// Only the `ProofsByWindowEnd` buckets in which an overlapping window
+        /// can end are read: from `start_time` up to `end_time` plus the
+        /// device's longest window.
         pub fn get_proofs_in_window(
-            device_id: &BoundedVec<u8, T::MaxDeviceIdLength>,
+            device_id: &DeviceId,
             start_time: u64,
             end_time: u64,
+            category: Option<ProofCategory>,
         ) -> Vec<ProofMetadata<T>> {
-            let count = ProofCount::<T>::get(device_id);
-            (0..count)
+            let Some(bounds) = WindowIndexBounds::<T>::get(device_id) else {
+                return Vec::new();
+            };
+            let first = Self::window_bucket(start_time).max(bounds.first_bucket);
+            let last = Self::window_bucket(end_time.saturating_add(bounds.longest_window))
+                .min(bounds.last_bucket);
+            let mut indexes: Vec<u64> = (first..=last)
+                .flat_map(|bucket| ProofsByWindowEnd::<T>::get(device_id, bucket))
+                .collect();
+            indexes.sort_unstable();
+
+            indexes
+                .into_iter()
                 .filter_map(|i| Proofs::<T>::get(device_id, i))
-                .filter(|m| m.window_start >= start_time && m.window_end <= end_time)
+                .filter(|m| m.window_start <= end_time && m.window_end >= start_time)
+                .filter(|m| category.map_or(true, |category| m.category == category))
+                .take(T::MaxQueryResults::get() as usize)
                 .collect()
         }
     }
@@ -651,62 +4309,50 @@ pub mod pallet {
         type Call = Call<T>;
 
         fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-            // Accept from all sources: External (RPC submission by authenticated bridge),
+            // Accept from all sources: External (RPC submission by the bridge),
             // Local (same-node submission), and InBlock (re-validation during import).
-            // Bridge authentication is enforced at the API layer (subxt-bridge API key).
+            // Proofs are authenticated by their payload signature.
             let _ = source;
 
             match call {
-                Call::submit_proof_unsigned {
-                    device_id,
-                    proof_hash,
-                    window_start,
-                    window_end,
-                    ..
-                } => {
-                    if device_id.len() > T::MaxDeviceIdLength::get() as usize {
-                        return InvalidTransaction::Custom(1).into();
-                    }
-                    if proof_hash.len() > T::MaxProofLength::get() as usize {
-                        return InvalidTransaction::Custom(2).into();
-                    }
-                    if window_start >= window_end {
-                        return InvalidTransaction::Custom(3).into();
+                Call::submit_proof_unsigned { payload, signature } => {
+                    let (device_id, expected_nonce) =
+                        Self::validate_proof_payload(payload, signature)?;
+                    if payload.nonce
+                        > expected_nonce.saturating_add(T::UnsignedNonceLookahead::get())
+                    {
+                        return InvalidTransaction::Future.into();
                     }
 
-                    // Provides tag ties to (device_id, proof_hash) so the same
-                    // proof cannot sit in the pool twice.
-                    ValidTransaction::with_tag_prefix("TelemetryProof")
+                    // One payload per device and nonce can sit in the pool,
+                    // behind the payload of the nonce before it; once included
+                    // the nonce advances and replays are stale
+                    let mut valid = ValidTransaction::with_tag_prefix("TelemetryProof")
                         .priority(100)
                         .longevity(5)
-                        .and_provides((device_id.clone(), proof_hash.clone()))
-                        .propagate(false)
-                        .build()
-                }
-                Call::submit_batch_proofs_unsigned { proofs } => {
-                    if proofs.is_empty() {
-                        return InvalidTransaction::Custom(4).into();
+                        .and_provides((device_id.clone(), payload.nonce))
+                        .propagate(true);
+                    if payload.nonce > expected_nonce {
+                        valid = valid.and_requires((device_id, payload.nonce - 1));
                     }
-                    if proofs.len() > T::MaxBatchSize::get() as usize {
-                        return InvalidTransaction::Custom(5).into();
-                    }
-
-                    // Build a deterministic provides tag from the first and last
-                    // entries so identical batches are deduplicated in the pool.
-                    let tag: (Vec<u8>, Vec<u8>) = (
-                        proofs.first().map(|p| p.0.clone()).unwrap_or_default(),
-                        proofs.last().map(|p| p.1.clone()).unwrap_or_default(),
-                    );
-
-                    ValidTransaction::with_tag_prefix("TelemetryProofBatch")
-                        .priority(100)
-                        .longevity(5)
-                        .and_provides(tag)
-                        .propagate(false)
-                        .build()
+                    valid.build()
                 }
                 _ => InvalidTransaction::Call.into(),
             }
         }
+
+        fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+            match call {
+                // Only the device's next nonce can be included
+                Call::submit_proof_unsigned { payload, signature } => {
+                    let (_, expected_nonce) = Self::validate_proof_payload(payload, signature)?;
+                    if payload.nonce > expected_nonce {
+                        return Err(InvalidTransaction::Future.into());
+                    }
+                    Ok(())
+                }
+                _ => Err(InvalidTransaction::Call.into()),
+            }
+        }
     }
 }
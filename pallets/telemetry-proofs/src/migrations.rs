@@ -0,0 +1,411 @@
+//! Storage migrations for the Telemetry Proofs pallet.
+
+use super::*;
+use alloc::vec::Vec;
+use frame_support::migrations::{MigrationId, SteppedMigration, SteppedMigrationError};
+use frame_support::pallet_prelude::*;
+use frame_support::traits::GetStorageVersion;
+use frame_support::weights::WeightMeter;
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_core::H256;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// Identifies this pallet's migrations to `pallet-migrations`
+const PALLET_MIGRATIONS_ID: &[u8; 16] = b"telemetry-proofs";
+
+/// The layout before the pallet was versioned.
+pub mod v0 {
+    use super::*;
+    use frame_support::storage_alias;
+
+    /// Proof metadata as first stored, with the proof hash kept as the
+    /// submitted bytes.
+    #[derive(Encode, Decode)]
+    pub struct ProofMetadataV0<T: Config> {
+        pub proof_hash: Vec<u8>,
+        pub block_number: BlockNumberFor<T>,
+        pub timestamp: u64,
+        pub record_count: u32,
+        pub window_start: u64,
+        pub window_end: u64,
+    }
+
+    /// `Proofs` in its version 0 layout.
+    #[storage_alias]
+    pub type Proofs<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        DeviceId,
+        Blake2_128Concat,
+        u64,
+        ProofMetadataV0<T>,
+        OptionQuery,
+    >;
+
+    /// The version 0 block index, holding a single proof hash per block and
+    /// device.
+    #[storage_alias]
+    pub type ProofsByBlock<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        DeviceId,
+        Vec<u8>,
+        OptionQuery,
+    >;
+}
+
+/// Version 9: the proof layout, reached from version 0 in one migration.
+pub mod v9 {
+    use super::*;
+
+    /// Convert a version 0 proof hash. 32 raw bytes and 64 hex characters
+    /// are decoded as on submission; anything else is hashed so the entry
+    /// stays addressable.
+    fn convert_hash<T: Config>(raw: &[u8]) -> H256 {
+        Pallet::<T>::normalize_proof_hash(raw)
+            .unwrap_or_else(|| H256(sp_io::hashing::blake2_256(raw)))
+    }
+
+    /// Migrates proofs stored before the pallet was versioned, a bounded
+    /// number per block.
+    ///
+    /// Each proof is rewritten in the current layout and added to the
+    /// indexes and per-device counters introduced since: `ProofIndexesByBlock`,
+    /// `ProofsByWindowEnd`, `ProofHashIndex`, `ProofLookup`, `ProofIdLookup`,
+    /// `CategoryProofCount`, `TotalRecords`, `StoredProofCount` and
+    /// `KnownDeviceCount`. Its `ProofsByBlock` entry is removed. Fields the
+    /// old layout lacked take the values of an unsigned, unscored
+    /// `EnergyUsage` proof on schema 1, and schema 1 is registered once the
+    /// last proof is done.
+    ///
+    /// Runs under `pallet-migrations`, which keeps transactions out of blocks
+    /// until the migration completes. The cursor is the key of the last
+    /// migrated proof. Chains already past version 0, such as those built
+    /// from genesis, are left unchanged.
+    pub struct MigrateV0ToV9<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> MigrateV0ToV9<T> {
+        /// Weight of migrating one proof.
+        pub fn proof_weight() -> Weight {
+            T::DbWeight::get().reads_writes(9, 12)
+        }
+
+        /// Rewrite one proof in the current layout and index it.
+        fn migrate_proof(device_id: &DeviceId, index: u64, old: v0::ProofMetadataV0<T>) {
+            let proof_hash = convert_hash::<T>(&old.proof_hash);
+            let hash_key = sp_io::hashing::blake2_256(proof_hash.as_bytes());
+
+            v0::ProofsByBlock::<T>::remove(old.block_number, device_id);
+            if ProofIndexesByBlock::<T>::try_mutate(old.block_number, device_id, |indexes| {
+                indexes.try_push(index)
+            })
+            .is_err()
+            {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "proof {} of device 0x{} left out of ProofIndexesByBlock: block full",
+                    index,
+                    sp_core::hexdisplay::HexDisplay::from(&device_id.as_slice()),
+                );
+            }
+            let bucket = Pallet::<T>::window_bucket(old.window_end);
+            if ProofsByWindowEnd::<T>::try_append(device_id, bucket, index).is_ok() {
+                Pallet::<T>::extend_window_bounds(
+                    device_id,
+                    bucket,
+                    old.window_end.saturating_sub(old.window_start),
+                );
+            } else {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "proof {} of device 0x{} left out of ProofsByWindowEnd: bucket full",
+                    index,
+                    sp_core::hexdisplay::HexDisplay::from(&device_id.as_slice()),
+                );
+            }
+
+            ProofHashIndex::<T>::insert(hash_key, (device_id.clone(), index));
+            // Keep the newest index when a hash was resubmitted
+            if ProofLookup::<T>::get(device_id, hash_key).map_or(true, |existing| existing < index)
+            {
+                ProofLookup::<T>::insert(device_id, hash_key, index);
+            }
+            let proof_id = Pallet::<T>::proof_id(
+                device_id,
+                proof_hash.as_bytes(),
+                old.window_start,
+                old.window_end,
+            );
+            ProofIdLookup::<T>::insert(proof_id, (device_id.clone(), index));
+
+            CategoryProofCount::<T>::mutate(device_id, ProofCategory::EnergyUsage, |count| {
+                *count = count.saturating_add(1);
+            });
+            TotalRecords::<T>::mutate(device_id, |total| {
+                *total = total.saturating_add(old.record_count as u64);
+            });
+            let retained = StoredProofCount::<T>::mutate(device_id, |count| {
+                *count = count.saturating_add(1);
+                *count
+            });
+            if retained == 1 {
+                KnownDeviceCount::<T>::mutate(|count| *count = count.saturating_add(1));
+            }
+
+            Proofs::<T>::insert(
+                device_id,
+                index,
+                ProofMetadata::<T> {
+                    proof_hash,
+                    submitted_block: old.block_number,
+                    submitter: None,
+                    timestamp: old.timestamp,
+                    record_count: old.record_count,
+                    window_start: old.window_start,
+                    window_end: old.window_end,
+                    data_size_bytes: 0,
+                    schema_version: 1,
+                    category: ProofCategory::EnergyUsage,
+                    device_signed: false,
+                    prev_proof_hash: None,
+                    revoked: None,
+                    challenged: false,
+                    quality_score: None,
+                    content_cid: None,
+                },
+            );
+        }
+    }
+
+    impl<T: Config> SteppedMigration for MigrateV0ToV9<T> {
+        type Cursor = (DeviceId, u64);
+        type Identifier = MigrationId<16>;
+
+        fn id() -> Self::Identifier {
+            MigrationId {
+                pallet_id: *PALLET_MIGRATIONS_ID,
+                version_from: 0,
+                version_to: 9,
+            }
+        }
+
+        fn step(
+            mut cursor: Option<Self::Cursor>,
+            meter: &mut WeightMeter,
+        ) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+            // Reading the storage version, registering schema 1 and bumping
+            // the version after the last proof
+            let overhead = T::DbWeight::get().reads_writes(2, 2);
+            let per_proof = Self::proof_weight();
+            let required = overhead.saturating_add(per_proof);
+            if meter.remaining().any_lt(required) {
+                return Err(SteppedMigrationError::InsufficientWeight { required });
+            }
+            meter.consume(overhead);
+
+            if cursor.is_none() && Pallet::<T>::on_chain_storage_version() != 0 {
+                return Ok(None);
+            }
+
+            let mut proofs = match &cursor {
+                Some((device_id, index)) => {
+                    v0::Proofs::<T>::iter_from(v0::Proofs::<T>::hashed_key_for(device_id, index))
+                }
+                None => v0::Proofs::<T>::iter(),
+            };
+            while meter.try_consume(per_proof).is_ok() {
+                let Some((device_id, index, old)) = proofs.next() else {
+                    if !SupportedSchemas::<T>::contains_key(1) {
+                        SupportedSchemas::<T>::insert(1, ());
+                    }
+                    StorageVersion::new(9).put::<Pallet<T>>();
+                    return Ok(None);
+                };
+                Self::migrate_proof(&device_id, index, old);
+                cursor = Some((device_id, index));
+            }
+
+            Ok(cursor)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            Ok((
+                v0::Proofs::<T>::iter_keys().count() as u64,
+                TotalProofs::<T>::get(),
+            )
+                .encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            let (proofs, total) = <(u64, u64)>::decode(&mut &state[..])
+                .map_err(|_| TryRuntimeError::Other("invalid pre-upgrade state"))?;
+            ensure!(
+                Proofs::<T>::iter_values().count() as u64 == proofs,
+                TryRuntimeError::Other("stored proofs changed")
+            );
+            ensure!(
+                ProofIdLookup::<T>::iter_keys().count() as u64 == proofs,
+                TryRuntimeError::Other("proof id lookup does not cover every stored proof")
+            );
+            ensure!(
+                TotalProofs::<T>::get() == total,
+                TryRuntimeError::Other("total proofs changed")
+            );
+            ensure!(
+                v0::ProofsByBlock::<T>::iter_keys().next().is_none(),
+                TryRuntimeError::Other("old block index not cleared")
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() >= 9,
+                TryRuntimeError::Other("storage version not bumped")
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Version 10: challenge deposits are holds instead of reserves.
+pub mod v10 {
+    use super::*;
+    use frame_support::traits::{fungible::MutateHold, ReservableCurrency};
+    use sp_runtime::{traits::Zero, Saturating};
+
+    /// Moves the deposits of open challenges from reserves, taken through
+    /// `OldCurrency`, to `HoldReason::ChallengeDeposit` holds on
+    /// `NativeBalance`, a bounded number of challenges per block.
+    ///
+    /// A deposit is unreserved and the amount actually released is held
+    /// again; a challenge whose deposit cannot be held keeps only what was
+    /// held, so closing it never releases more. The cursor is the key of the
+    /// last migrated challenge.
+    pub struct MigrateChallengeDepositsToHolds<T, OldCurrency>(
+        core::marker::PhantomData<(T, OldCurrency)>,
+    );
+
+    impl<T, OldCurrency> MigrateChallengeDepositsToHolds<T, OldCurrency>
+    where
+        T: Config,
+        OldCurrency: ReservableCurrency<T::AccountId, Balance = DepositBalanceOf<T>>,
+    {
+        /// Weight of migrating one challenge.
+        pub fn challenge_weight() -> Weight {
+            T::DbWeight::get().reads_writes(3, 3)
+        }
+
+        /// Turn one challenge's reserve into a hold.
+        fn migrate_challenge(device_id: &DeviceId, index: u64, mut challenge: ProofChallenge<T>) {
+            let unreserved = challenge.deposit.saturating_sub(OldCurrency::unreserve(
+                &challenge.challenger,
+                challenge.deposit,
+            ));
+            let held = match T::NativeBalance::hold(
+                &HoldReason::ChallengeDeposit.into(),
+                &challenge.challenger,
+                unreserved,
+            ) {
+                Ok(()) => unreserved,
+                Err(_) => {
+                    log::warn!(
+                        target: LOG_TARGET,
+                        "challenge on proof {} of device 0x{}: deposit could not be held",
+                        index,
+                        sp_core::hexdisplay::HexDisplay::from(&device_id.as_slice()),
+                    );
+                    Zero::zero()
+                }
+            };
+            if held != challenge.deposit {
+                challenge.deposit = held;
+                Challenges::<T>::insert(device_id, index, challenge);
+            }
+        }
+    }
+
+    impl<T, OldCurrency> SteppedMigration for MigrateChallengeDepositsToHolds<T, OldCurrency>
+    where
+        T: Config,
+        OldCurrency: ReservableCurrency<T::AccountId, Balance = DepositBalanceOf<T>>,
+    {
+        type Cursor = (DeviceId, u64);
+        type Identifier = MigrationId<16>;
+
+        fn id() -> Self::Identifier {
+            MigrationId {
+                pallet_id: *PALLET_MIGRATIONS_ID,
+                version_from: 9,
+                version_to: 10,
+            }
+        }
+
+        fn step(
+            mut cursor: Option<Self::Cursor>,
+            meter: &mut WeightMeter,
+        ) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+            // Reading and bumping the storage version
+            let overhead = T::DbWeight::get().reads_writes(1, 1);
+            let per_challenge = Self::challenge_weight();
+            let required = overhead.saturating_add(per_challenge);
+            if meter.remaining().any_lt(required) {
+                return Err(SteppedMigrationError::InsufficientWeight { required });
+            }
+            meter.consume(overhead);
+
+            if cursor.is_none() && Pallet::<T>::on_chain_storage_version() >= 10 {
+                return Ok(None);
+            }
+
+            let mut challenges = match &cursor {
+                Some((device_id, index)) => {
+                    Challenges::<T>::iter_from(Challenges::<T>::hashed_key_for(device_id, index))
+                }
+                None => Challenges::<T>::iter(),
+            };
+            while meter.try_consume(per_challenge).is_ok() {
+                let Some((device_id, index, challenge)) = challenges.next() else {
+                    StorageVersion::new(10).put::<Pallet<T>>();
+                    return Ok(None);
+                };
+                Self::migrate_challenge(&device_id, index, challenge);
+                cursor = Some((device_id, index));
+            }
+
+            Ok(cursor)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            Ok((Challenges::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            use frame_support::traits::fungible::InspectHold;
+
+            let challenges = u64::decode(&mut &state[..])
+                .map_err(|_| TryRuntimeError::Other("invalid pre-upgrade state"))?;
+            ensure!(
+                Challenges::<T>::iter_keys().count() as u64 == challenges,
+                TryRuntimeError::Other("open challenges changed")
+            );
+            ensure!(
+                Challenges::<T>::iter_values().all(|challenge| {
+                    T::NativeBalance::balance_on_hold(
+                        &HoldReason::ChallengeDeposit.into(),
+                        &challenge.challenger,
+                    ) >= challenge.deposit
+                }),
+                TryRuntimeError::Other("challenge deposit not held")
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() >= 10,
+                TryRuntimeError::Other("storage version not bumped")
+            );
+            Ok(())
+        }
+    }
+}
@@ -1,15 +1,17 @@
 //! Unit tests for the Telemetry Proofs pallet.
 
 use crate::{self as pallet_telemetry_proofs, *};
+use codec::Encode;
 use frame_support::{
     assert_noop, assert_ok,
-    traits::{ConstU32, ConstU64},
-    BoundedVec,
+    traits::{fungible, ConstU32, ConstU64, Get, Hooks},
+    weights::Weight,
 };
-use sp_core::H256;
+use sp_core::{sr25519, Pair, H256};
 use sp_runtime::{
+    testing::{TestSignature, TestXt, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
-    BuildStorage,
+    BuildStorage, DispatchResult, Percent,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -18,6 +20,7 @@ frame_support::construct_runtime!(
     pub enum Test {
         System: frame_system,
         Timestamp: pallet_timestamp,
+        Balances: pallet_balances,
         TelemetryProofs: pallet_telemetry_proofs,
     }
 );
@@ -26,7 +29,7 @@ impl frame_system::Config for Test {
     type BaseCallFilter = frame_support::traits::Everything;
     type BlockWeights = ();
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = frame_support::weights::constants::RocksDbWeight;
     type RuntimeOrigin = RuntimeOrigin;
     type RuntimeCall = RuntimeCall;
     type Nonce = u64;
@@ -39,7 +42,7 @@ impl frame_system::Config for Test {
     type BlockHashCount = ConstU64<250>;
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<u64>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
@@ -61,28 +64,281 @@ impl pallet_timestamp::Config for Test {
     type WeightInfo = ();
 }
 
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU64<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type RuntimeFreezeReason = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+}
+
+const TREASURY: u64 = 99;
+
+/// On-chain time (seconds) at the start of every test
+const NOW: u64 = 100_000;
+
+frame_support::parameter_types! {
+    pub static MaxProofsPerDevice: u32 = 1000;
+    pub static PruneOldestProofs: bool = false;
+    pub const ProofStorageWarningPercent: Percent = Percent::from_percent(75);
+    pub static AllowSameDeviceResubmission: bool = false;
+    pub static ProofDeposit: u64 = 0;
+    pub static OffchainIndexing: bool = false;
+    pub static MaxWindowAge: Option<u64> = None;
+    pub static MinProofInterval: u64 = 0;
+    pub static ExemptTrustedFromProofInterval: bool = false;
+    pub static MaxProofsPerWindowBucket: u32 = 100;
+    pub static RetiredDevices: Vec<Vec<u8>> = vec![];
+    pub static DeviceOwner: Option<u64> = None;
+    pub static FeelessAccounts: Vec<u64> = vec![];
+    pub static SubmittedProofs: Vec<(DeviceId, u64, u32, u64, u64, u64)> = vec![];
+}
+
+/// Proof hook recording every notification in `SubmittedProofs`
+pub struct MockProofHook;
+
+impl OnProofSubmitted<DeviceId, u64> for MockProofHook {
+    fn on_proof_submitted(
+        device: &DeviceId,
+        proof_index: u64,
+        record_count: u32,
+        window_start: u64,
+        window_end: u64,
+        block: u64,
+    ) {
+        SubmittedProofs::mutate(|calls| {
+            calls.push((
+                device.clone(),
+                proof_index,
+                record_count,
+                window_start,
+                window_end,
+                block,
+            ))
+        });
+    }
+
+    fn weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Device registry reporting the devices in `RetiredDevices` as retired and
+/// `DeviceOwner` as the owner of every device
+pub struct MockDevices;
+
+impl pallet_device_registry::DeviceInterface<DeviceId, u64> for MockDevices {
+    fn status(device: &DeviceId) -> Option<pallet_device_registry::DeviceStatus> {
+        if RetiredDevices::get().contains(&device.to_vec()) {
+            Some(pallet_device_registry::DeviceStatus::Retired)
+        } else {
+            Some(pallet_device_registry::DeviceStatus::Active)
+        }
+    }
+
+    fn owner(_device: &DeviceId) -> Option<u64> {
+        DeviceOwner::get()
+    }
+
+    fn max_power(_device: &DeviceId) -> Option<u32> {
+        None
+    }
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+    type Public = UintAuthorityId;
+    type Signature = TestSignature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+    RuntimeCall: From<C>,
+{
+    type OverarchingCall = RuntimeCall;
+    type Extrinsic = TestXt<RuntimeCall, ()>;
+}
+
+impl<C> frame_system::offchain::CreateSignedTransaction<C> for Test
+where
+    RuntimeCall: From<C>,
+{
+    fn create_transaction<S: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+        call: RuntimeCall,
+        _public: UintAuthorityId,
+        account: u64,
+        _nonce: u64,
+    ) -> Option<(RuntimeCall, (u64, ()))> {
+        Some((call, (account, ())))
+    }
+}
+
+/// Offchain worker key of the mock; `UintAuthorityId` signs without a keystore
+pub struct TestAuthorityId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthorityId {
+    type RuntimeAppPublic = UintAuthorityId;
+    type GenericPublic = UintAuthorityId;
+    type GenericSignature = TestSignature;
+}
+
 impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
-    type MaxDeviceIdLength = ConstU32<64>;
-    type MaxProofLength = ConstU32<128>;
+    type AdminOrigin = frame_system::EnsureRoot<u64>;
+    type SubmitterOrigin = EnsureAuthorizedSubmitter<Test>;
+    type FeelessSubmitters = frame_support::traits::IsInVec<FeelessAccounts>;
+    type ExemptSubmitters = TrustedSubmitterList<Test>;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type NativeBalance = Balances;
+    type ProofDeposit = ProofDeposit;
+    type DisputeOrigin = frame_system::EnsureRoot<u64>;
+    type AnchorOrigin = frame_system::EnsureRoot<u64>;
+    type OracleOrigin = frame_system::EnsureRoot<u64>;
+    type ChallengeDeposit = ConstU64<10>;
+    type ChallengePeriod = ConstU64<20>;
+    type TreasuryAccount = ConstU64<TREASURY>;
+    type AllowSameDeviceResubmission = AllowSameDeviceResubmission;
+    type OffchainIndexing = OffchainIndexing;
     type MaxBatchSize = ConstU32<100>;
-    type MaxProofsPerDevice = ConstU32<1000>;
+    type UnsignedNonceLookahead = ConstU64<2>;
+    type MaxRecordsPerProof = ConstU32<1_000>;
+    type MaxCidLength = ConstU32<64>;
+    type MaxProofsPerDevice = MaxProofsPerDevice;
+    type PruneOldestProofs = PruneOldestProofs;
+    type ProofStorageWarningPercent = ProofStorageWarningPercent;
+    type MaxKeyHistory = ConstU32<3>;
+    type MaxQueryResults = ConstU32<5>;
+    type MaxPageSize = ConstU32<10>;
+    type MaxMerkleDepth = ConstU32<4>;
+    type MaxProofsPerBlock = ConstU32<200>;
+    type ProofRootRetention = ConstU64<2>;
+    type TimeProvider = Timestamp;
+    type Devices = MockDevices;
+    type OnProofAccepted = ();
+    type OnProofSubmitted = MockProofHook;
+    type MaxClockDrift = ConstU64<600>;
+    type MaxWindowAge = MaxWindowAge;
+    type MinProofInterval = MinProofInterval;
+    type ExemptTrustedFromProofInterval = ExemptTrustedFromProofInterval;
+    type MaxDaysRetained = ConstU32<3>;
+    type WindowBucketSeconds = ConstU64<5_000>;
+    type MaxProofsPerWindowBucket = MaxProofsPerWindowBucket;
+    type AuthorityId = TestAuthorityId;
+    type HeartbeatInterval = ConstU64<5>;
 }
 
-fn new_test_ext() -> sp_io::TestExternalities {
-    let t = frame_system::GenesisConfig::<Test>::default()
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+    new_test_ext_with_submitters(vec![], true)
+}
+
+fn new_test_ext_with_submitters(trusted: Vec<u64>, open: bool) -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
         .build_storage()
         .unwrap();
-    t.into()
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 100), (2, 100), (3, 100)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    pallet_telemetry_proofs::GenesisConfig::<Test> {
+        trusted_submitters: trusted,
+        open_submission: open,
+        supported_schemas: vec![1],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| Timestamp::set_timestamp(NOW * 1000));
+    ext
 }
 
 fn device_id(id: &str) -> Vec<u8> {
     id.as_bytes().to_vec()
 }
 
+/// 32 raw hash bytes derived from a readable label.
 fn proof_hash(hash: &str) -> Vec<u8> {
-    hash.as_bytes().to_vec()
+    sp_io::hashing::blake2_256(hash.as_bytes()).to_vec()
+}
+
+fn h256(hash: &str) -> H256 {
+    H256::from_slice(&proof_hash(hash))
+}
+
+fn hex_encode(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .flat_map(|byte| {
+            [
+                b"0123456789abcdef"[(byte >> 4) as usize],
+                b"0123456789abcdef"[(byte & 0x0f) as usize],
+            ]
+        })
+        .collect()
+}
+
+/// The submission nonce the device's next proof must carry.
+fn nonce(device: &[u8]) -> u64 {
+    DeviceId::try_from(device.to_vec())
+        .map(|id| TelemetryProofs::submission_nonce(&id))
+        .unwrap_or_default()
+}
+
+/// Unsigned proof of `device` at its current nonce, signed by bridge key `key`.
+fn unsigned_proof(
+    key: u64,
+    device: &[u8],
+    hash: &str,
+    window_start: u64,
+    window_end: u64,
+    category: ProofCategory,
+) -> (ProofPayload<UintAuthorityId, H256>, TestSignature) {
+    let payload = ProofPayload {
+        device_id: device.to_vec(),
+        proof_hash: proof_hash(hash),
+        record_count: 10,
+        window_start,
+        window_end,
+        data_size_bytes: 1024,
+        schema_version: 1,
+        category,
+        nonce: nonce(device),
+        genesis_hash: System::block_hash(0),
+        public: UintAuthorityId(key),
+    };
+    let signature = TestSignature(key, payload.encode());
+    (payload, signature)
+}
+
+/// Submit an unsigned `EnergyUsage` proof signed by bridge key 1.
+fn submit_unsigned(
+    device: &[u8],
+    hash: &str,
+    window_start: u64,
+    window_end: u64,
+) -> DispatchResult {
+    let (payload, signature) = unsigned_proof(
+        1,
+        device,
+        hash,
+        window_start,
+        window_end,
+        ProofCategory::EnergyUsage,
+    );
+    TelemetryProofs::submit_proof_unsigned(RuntimeOrigin::none(), payload, signature)
+}
+
+fn latest_hash(id: &str) -> Option<Vec<u8>> {
+    let bounded_dev_id: DeviceId = device_id(id).try_into().unwrap();
+    TelemetryProofs::latest_proof_hash(&bounded_dev_id).map(|hash| hash.as_bytes().to_vec())
 }
 
 #[test]
@@ -100,10 +356,17 @@ fn submit_proof_works() {
             10,   // record_count
             1000, // window_start
             2000, // window_end
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(&dev_id),
+            None,
+            None,
+            None,
         ));
 
         // Check proof count
-        let bounded_dev_id: BoundedVec<u8, ConstU32<64>> = dev_id.try_into().unwrap();
+        let bounded_dev_id: DeviceId = dev_id.try_into().unwrap();
         assert_eq!(TelemetryProofs::proof_count(&bounded_dev_id), 1);
 
         // Check total proofs
@@ -116,31 +379,442 @@ fn submit_proof_works() {
         assert_eq!(metadata.record_count, 10);
         assert_eq!(metadata.window_start, 1000);
         assert_eq!(metadata.window_end, 2000);
+        assert_eq!(metadata.submitted_block, 1);
+        assert_eq!(metadata.submitter, Some(1));
+
+        // Event carries the telemetry window
+        System::assert_last_event(
+            Event::ProofSubmitted {
+                device_id: bounded_dev_id,
+                proof_hash: H256::from_slice(&hash),
+                block_number: 1,
+                proof_index: 0,
+                proof_id: TelemetryProofs::proof_id(
+                    b"device-001",
+                    &proof_hash("abc123hash"),
+                    1000,
+                    2000,
+                ),
+                window_start: 1000,
+                window_end: 2000,
+                data_size_bytes: 1024,
+                schema_version: 1,
+                category: ProofCategory::EnergyUsage,
+                content_cid: None,
+            }
+            .into(),
+        );
     });
 }
 
 #[test]
-fn submit_proof_rejects_duplicate_in_same_block() {
+fn proof_submitted_is_indexed_by_device_topic() {
     new_test_ext().execute_with(|| {
         System::set_block_number(1);
-
         let dev_id = device_id("device-001");
-        let hash1 = proof_hash("hash1");
-        let hash2 = proof_hash("hash2");
 
         assert_ok!(TelemetryProofs::submit_proof(
             RuntimeOrigin::signed(1),
             dev_id.clone(),
-            hash1,
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(&dev_id),
+            None,
+            None,
+            None,
+        ));
+
+        let topic = H256(sp_io::hashing::blake2_256(b"device-001"));
+        let bounded_dev_id: DeviceId = dev_id.try_into().unwrap();
+        assert_eq!(bounded_dev_id.topic(), topic.0);
+
+        let events = System::events();
+        let index = events
+            .iter()
+            .position(|record| {
+                matches!(
+                    record.event,
+                    RuntimeEvent::TelemetryProofs(Event::ProofSubmitted { .. })
+                )
+            })
+            .unwrap();
+        assert_eq!(events[index].topics, vec![topic]);
+        assert_eq!(System::event_topics(topic), vec![(1, index as u32)]);
+        assert!(System::event_topics(H256(sp_io::hashing::blake2_256(b"device-002"))).is_empty());
+    });
+}
+
+#[test]
+fn submit_proof_accepts_raw_and_hex_hashes() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let raw = proof_hash("hash1");
+        let upper_hex = hex_encode(&proof_hash("hash2")).to_ascii_uppercase();
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            raw.clone(),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            None,
+            None,
+        ));
+        // The chain link may use the other encoding
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            upper_hex,
             10,
+            2000,
+            3000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            Some(hex_encode(&raw)),
+            None,
+            None,
+        ));
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(
+            TelemetryProofs::proofs(&bounded_dev_id, 0)
+                .unwrap()
+                .proof_hash,
+            h256("hash1")
+        );
+        let second = TelemetryProofs::proofs(&bounded_dev_id, 1).unwrap();
+        assert_eq!(second.proof_hash, h256("hash2"));
+        assert_eq!(second.prev_proof_hash, Some(h256("hash1")));
+
+        // Both encodings of the same hash collide
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-002"),
+                hex_encode(&raw),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-002"),
+                None,
+                None,
+                None,
+            ),
+            Error::<Test>::DuplicateProofHash
+        );
+    });
+}
+
+#[test]
+fn submit_proof_rejects_malformed_hashes() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let mut bad_hex = hex_encode(&proof_hash("hash1"));
+        bad_hex[10] = b'g';
+
+        for hash in [bad_hex, b"hash1".to_vec(), vec![0u8; 33], Vec::new()] {
+            assert_noop!(
+                TelemetryProofs::submit_proof(
+                    RuntimeOrigin::signed(1),
+                    device_id("device-001"),
+                    hash,
+                    10,
+                    1000,
+                    2000,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-001"),
+                    None,
+                    None,
+                    None,
+                ),
+                Error::<Test>::InvalidProofHash
+            );
+        }
+        assert_noop!(
+            TelemetryProofs::verify_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                b"not-a-hash".to_vec(),
+            ),
+            Error::<Test>::InvalidProofHash
+        );
+    });
+}
+
+#[test]
+fn submission_nonce_must_advance_by_one() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let submit = |hash: &str, start: u64, nonce: u64| {
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash(hash),
+                10,
+                start,
+                start + 1000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce,
+                latest_hash("device-001"),
+                None,
+                None,
+            )
+        };
+
+        // A gap is rejected until the expected nonce is used
+        assert_noop!(submit("hash1", 1000, 1), Error::<Test>::BadSubmissionNonce);
+        assert_ok!(submit("hash1", 1000, 0));
+        assert_ok!(submit("hash2", 2000, 1));
+        assert_eq!(nonce(b"device-001"), 2);
+
+        // A replayed nonce is rejected even with a fresh payload
+        assert_noop!(submit("hash3", 3000, 1), Error::<Test>::BadSubmissionNonce);
+        // Nonces are tracked per device
+        assert_eq!(nonce(b"device-002"), 0);
+
+        // Batch entries carry their own nonces, and a rejected entry does not
+        // consume one
+        let entry = |device: &str, hash: &str, nonce: u64| {
+            (
+                device_id(device),
+                proof_hash(hash),
+                10,
+                3000,
+                4000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce,
+            )
+        };
+        System::reset_events();
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            vec![
+                entry("device-002", "hash4", 0),
+                entry("device-002", "hash5", 0),
+                entry("device-002", "hash6", 1),
+                entry("device-003", "hash7", 5),
+            ],
+        ));
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 1,
+                reason: BatchRejectReason::BadSubmissionNonce,
+            }
+            .into(),
+        );
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 3,
+                reason: BatchRejectReason::BadSubmissionNonce,
+            }
+            .into(),
+        );
+        assert_eq!(nonce(b"device-002"), 2);
+        assert_eq!(nonce(b"device-003"), 0);
+    });
+}
+
+#[test]
+fn submit_proof_unsigned_records_no_submitter() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let dev_id = device_id("device-001");
+
+        assert_ok!(submit_unsigned(&dev_id, "abc123hash", 1000, 2000));
+
+        let bounded_dev_id: DeviceId = dev_id.try_into().unwrap();
+        let metadata = TelemetryProofs::proofs(&bounded_dev_id, 0).unwrap();
+        assert_eq!(metadata.submitter, None);
+    });
+}
+
+#[test]
+fn unsigned_proofs_need_a_submitter_signature_and_the_current_nonce() {
+    use sp_runtime::{
+        traits::ValidateUnsigned,
+        transaction_validity::{InvalidTransaction, TransactionSource},
+    };
+
+    new_test_ext_with_submitters(vec![1], false).execute_with(|| {
+        System::set_block_number(1);
+        let validate = |payload: &ProofPayload<UintAuthorityId, H256>,
+                        signature: &TestSignature| {
+            TelemetryProofs::validate_unsigned(
+                TransactionSource::External,
+                &Call::<Test>::submit_proof_unsigned {
+                    payload: payload.clone(),
+                    signature: signature.clone(),
+                },
+            )
+        };
+        let (payload, signature) = unsigned_proof(
+            1,
+            b"device-001",
+            "hash1",
+            1000,
+            2000,
+            ProofCategory::EnergyUsage,
+        );
+
+        // Signed by a trusted submitter's key: accepted, tagged by device and nonce
+        let valid = validate(&payload, &signature).unwrap();
+        let device: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(
+            valid.provides,
+            vec![("TelemetryProof", device.clone(), 0u64).encode()]
+        );
+
+        // Forged: signed by another key, or over a different payload
+        assert_eq!(
+            validate(&payload, &TestSignature(2, payload.encode())),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        let mut tampered = payload.clone();
+        tampered.record_count = 1_000;
+        assert_eq!(
+            validate(&tampered, &signature),
+            Err(InvalidTransaction::BadProof.into())
+        );
+
+        // Correctly signed by a key outside the submitters
+        let (outsider, outsider_signature) = unsigned_proof(
+            2,
+            b"device-001",
+            "hash1",
             1000,
             2000,
+            ProofCategory::EnergyUsage,
+        );
+        assert_eq!(
+            validate(&outsider, &outsider_signature),
+            Err(InvalidTransaction::BadSigner.into())
+        );
+
+        // Signed for another chain
+        let mut other_chain = payload.clone();
+        other_chain.genesis_hash = H256::repeat_byte(1);
+        assert_eq!(
+            validate(&other_chain, &TestSignature(1, other_chain.encode())),
+            Err(InvalidTransaction::BadProof.into())
+        );
+
+        // A nonce within the lookahead waits for its predecessor
+        let mut ahead = payload.clone();
+        ahead.nonce = 2;
+        let ahead_signature = TestSignature(1, ahead.encode());
+        let queued = validate(&ahead, &ahead_signature).unwrap();
+        assert_eq!(
+            queued.provides,
+            vec![("TelemetryProof", device.clone(), 2u64).encode()]
+        );
+        assert_eq!(
+            queued.requires,
+            vec![("TelemetryProof", device, 1u64).encode()]
+        );
+        assert_eq!(
+            TelemetryProofs::pre_dispatch(&Call::<Test>::submit_proof_unsigned {
+                payload: ahead.clone(),
+                signature: ahead_signature,
+            }),
+            Err(InvalidTransaction::Future.into())
+        );
+
+        // Beyond the lookahead (2) it is not valid yet
+        let mut too_far = payload.clone();
+        too_far.nonce = 3;
+        assert_eq!(
+            validate(&too_far, &TestSignature(1, too_far.encode())),
+            Err(InvalidTransaction::Future.into())
+        );
+
+        // Once included, the same payload is stale and cannot be replayed
+        assert_ok!(TelemetryProofs::submit_proof_unsigned(
+            RuntimeOrigin::none(),
+            payload.clone(),
+            signature.clone(),
         ));
+        assert_eq!(
+            validate(&payload, &signature),
+            Err(InvalidTransaction::Stale.into())
+        );
+    });
+}
+
+#[test]
+fn submit_proof_allows_multiple_proofs_per_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let dev_id = device_id("device-001");
+        for (hash, start) in [("hash1", 1000), ("hash2", 2000)] {
+            assert_ok!(TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                dev_id.clone(),
+                proof_hash(hash),
+                10,
+                start,
+                start + 1000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(&dev_id),
+                latest_hash("device-001"),
+                None,
+                None,
+            ));
+        }
+
+        let bounded_dev_id: DeviceId = dev_id.clone().try_into().unwrap();
+        assert_eq!(TelemetryProofs::proof_count(&bounded_dev_id), 2);
+        assert_eq!(
+            TelemetryProofs::proof_indexes_by_block(1, &bounded_dev_id).into_inner(),
+            vec![0, 1]
+        );
+        let in_block = TelemetryProofs::get_proofs_in_block(&bounded_dev_id, 1);
+        assert_eq!(in_block.len(), 2);
+        assert_eq!(in_block[1].proof_hash, h256("hash2"));
 
-        // Second proof in same block should fail
+        // Resubmitting a hash in the same block is still a duplicate
         assert_noop!(
-            TelemetryProofs::submit_proof(RuntimeOrigin::signed(1), dev_id, hash2, 10, 2000, 3000,),
-            Error::<Test>::ProofAlreadyExists
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                dev_id,
+                proof_hash("hash1"),
+                10,
+                3000,
+                4000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                None,
+                None
+            ),
+            Error::<Test>::DuplicateProofHash
         );
     });
 }
@@ -158,6 +832,13 @@ fn submit_proof_allows_same_device_different_blocks() {
             10,
             1000,
             2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(&dev_id),
+            latest_hash("device-001"),
+            None,
+            None,
         ));
 
         System::set_block_number(2);
@@ -168,13 +849,87 @@ fn submit_proof_allows_same_device_different_blocks() {
             10,
             2000,
             3000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(&dev_id),
+            latest_hash("device-001"),
+            None,
+            None,
         ));
 
-        let bounded_dev_id: BoundedVec<u8, ConstU32<64>> = dev_id.try_into().unwrap();
+        let bounded_dev_id: DeviceId = dev_id.try_into().unwrap();
         assert_eq!(TelemetryProofs::proof_count(&bounded_dev_id), 2);
     });
 }
 
+#[test]
+fn submit_proof_bounds_record_count() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let max = <Test as Config>::MaxRecordsPerProof::get();
+        let submit = |hash: &str, start: u64, record_count: u32| {
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash(hash),
+                record_count,
+                start,
+                start + 1000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                None,
+                None,
+            )
+        };
+
+        assert_noop!(submit("hash0", 1000, 0), Error::<Test>::EmptyProof);
+        assert_ok!(submit("hash1", 1000, 1));
+        assert_ok!(submit("hash2", 2000, max));
+        assert_noop!(
+            submit("hash3", 3000, max + 1),
+            Error::<Test>::TooManyRecords
+        );
+
+        let entry = |hash: &str, record_count: u32, nonce: u64| {
+            (
+                device_id("device-002"),
+                proof_hash(hash),
+                record_count,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce,
+            )
+        };
+        System::reset_events();
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            vec![entry("hash4", 0, 0), entry("hash5", max + 1, 0)],
+        ));
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 0,
+                reason: BatchRejectReason::EmptyProof,
+            }
+            .into(),
+        );
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 1,
+                reason: BatchRejectReason::TooManyRecords,
+            }
+            .into(),
+        );
+        assert_eq!(TelemetryProofs::total_proofs(), 2);
+    });
+}
+
 #[test]
 fn submit_proof_rejects_invalid_time_window() {
     new_test_ext().execute_with(|| {
@@ -189,6 +944,13 @@ fn submit_proof_rejects_invalid_time_window() {
                 10,
                 2000, // start
                 1000, // end (before start)
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                None,
+                None,
+                None,
             ),
             Error::<Test>::InvalidTimeWindow
         );
@@ -202,6 +964,13 @@ fn submit_proof_rejects_invalid_time_window() {
                 10,
                 1000,
                 1000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                None,
+                None,
+                None,
             ),
             Error::<Test>::InvalidTimeWindow
         );
@@ -223,6 +992,13 @@ fn submit_proof_rejects_too_long_device_id() {
                 10,
                 1000,
                 2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                0,
+                None,
+                None,
+                None,
             ),
             Error::<Test>::DeviceIdTooLong
         );
@@ -241,6 +1017,10 @@ fn submit_batch_proofs_works() {
                 10,
                 1000u64,
                 2000u64,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
             ),
             (
                 device_id("device-002"),
@@ -248,6 +1028,10 @@ fn submit_batch_proofs_works() {
                 20,
                 2000u64,
                 3000u64,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-002"),
             ),
             (
                 device_id("device-003"),
@@ -255,6 +1039,10 @@ fn submit_batch_proofs_works() {
                 30,
                 3000u64,
                 4000u64,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-003"),
             ),
         ];
 
@@ -267,9 +1055,9 @@ fn submit_batch_proofs_works() {
         assert_eq!(TelemetryProofs::total_proofs(), 3);
 
         // Check each device
-        let dev1: BoundedVec<u8, ConstU32<64>> = device_id("device-001").try_into().unwrap();
-        let dev2: BoundedVec<u8, ConstU32<64>> = device_id("device-002").try_into().unwrap();
-        let dev3: BoundedVec<u8, ConstU32<64>> = device_id("device-003").try_into().unwrap();
+        let dev1: DeviceId = device_id("device-001").try_into().unwrap();
+        let dev2: DeviceId = device_id("device-002").try_into().unwrap();
+        let dev3: DeviceId = device_id("device-003").try_into().unwrap();
 
         assert_eq!(TelemetryProofs::proof_count(&dev1), 1);
         assert_eq!(TelemetryProofs::proof_count(&dev2), 1);
@@ -289,6 +1077,10 @@ fn submit_batch_proofs_skips_invalid_entries() {
                 10,
                 1000u64,
                 2000u64,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
             ), // Valid
             (
                 device_id("device-002"),
@@ -296,6 +1088,10 @@ fn submit_batch_proofs_skips_invalid_entries() {
                 20,
                 3000u64,
                 2000u64,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-002"),
             ), // Invalid window
             (
                 device_id("device-003"),
@@ -303,6 +1099,10 @@ fn submit_batch_proofs_skips_invalid_entries() {
                 30,
                 3000u64,
                 4000u64,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-003"),
             ), // Valid
         ];
 
@@ -329,36 +1129,223 @@ fn submit_batch_proofs_rejects_empty_batch() {
 }
 
 #[test]
-fn verify_proof_works() {
+fn total_proofs_tracks_single_and_batch_submissions() {
     new_test_ext().execute_with(|| {
         System::set_block_number(1);
+        assert_eq!(TelemetryProofs::total_proofs(), 0);
 
-        let dev_id = device_id("device-001");
-        let hash = proof_hash("abc123hash");
-
-        // Submit a proof first
         assert_ok!(TelemetryProofs::submit_proof(
             RuntimeOrigin::signed(1),
-            dev_id.clone(),
-            hash.clone(),
+            device_id("device-001"),
+            proof_hash("hash1"),
             10,
             1000,
             2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            None,
+            None,
         ));
+        assert_eq!(TelemetryProofs::total_proofs(), 1);
 
-        // Verify existing proof
-        assert_ok!(TelemetryProofs::verify_proof(
+        // Batch with one invalid window and one over-long device id
+        let proofs = vec![
+            (
+                device_id("device-002"),
+                proof_hash("hash2"),
+                10,
+                1000u64,
+                2000u64,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-002"),
+            ),
+            (
+                device_id("device-003"),
+                proof_hash("hash3"),
+                10,
+                2000u64,
+                1000u64,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-003"),
+            ),
+            (
+                vec![b'a'; 100],
+                proof_hash("hash4"),
+                10,
+                1000u64,
+                2000u64,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                0,
+            ),
+            (
+                device_id("device-004"),
+                proof_hash("hash5"),
+                10,
+                1000u64,
+                2000u64,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-004"),
+            ),
+        ];
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
             RuntimeOrigin::signed(1),
-            dev_id.clone(),
-            hash,
+            proofs,
         ));
 
-        // Verify non-existent proof
-        assert_ok!(TelemetryProofs::verify_proof(
-            RuntimeOrigin::signed(1),
-            dev_id,
-            proof_hash("nonexistent"),
-        ));
+        // Only accepted entries are counted
+        assert_eq!(TelemetryProofs::total_proofs(), 3);
+    });
+}
+
+#[test]
+fn verify_proof_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let dev_id = device_id("device-001");
+        let hash = proof_hash("abc123hash");
+
+        // Submit a proof first
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            hash.clone(),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(&dev_id),
+            None,
+            None,
+            None,
+        ));
+
+        let bounded_dev_id: DeviceId = dev_id.clone().try_into().unwrap();
+
+        // Verify existing proof
+        assert_ok!(TelemetryProofs::verify_proof(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            hash.clone(),
+        ));
+        System::assert_last_event(
+            Event::ProofVerificationResult {
+                device_id: bounded_dev_id.clone(),
+                proof_hash: H256::from_slice(&hash),
+                found: true,
+                proof_index: Some(0),
+                valid: true,
+                expired: false,
+            }
+            .into(),
+        );
+
+        // Verify non-existent proof: succeeds, result carried in the event
+        assert_ok!(TelemetryProofs::verify_proof(
+            RuntimeOrigin::signed(1),
+            dev_id,
+            proof_hash("nonexistent"),
+        ));
+        System::assert_last_event(
+            Event::ProofVerificationResult {
+                device_id: bounded_dev_id,
+                proof_hash: h256("nonexistent"),
+                found: false,
+                proof_index: None,
+                valid: false,
+                expired: false,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn verify_proofs_reports_bitmap_in_input_order() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for hash in ["hash-a", "hash-b", "hash-c"] {
+            assert_ok!(submit_with_cid("device-001", hash, None));
+        }
+        assert_ok!(submit_with_cid("device-002", "hash-d", None));
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            1,
+            RevocationReason::CorruptedData,
+        ));
+
+        let queries: Vec<(Vec<u8>, Vec<u8>)> = vec![
+            ("device-001", "hash-a"),
+            ("device-001", "hash-b"),
+            ("device-001", "missing"),
+            ("device-002", "hash-d"),
+            ("device-002", "hash-a"),
+            ("device-001", "hash-c"),
+            ("unknown", "hash-a"),
+            ("device-001", "hash-a"),
+            ("device-002", "hash-d"),
+        ]
+        .into_iter()
+        .map(|(device, hash)| (device_id(device), proof_hash(hash)))
+        .chain(core::iter::once((
+            device_id("device-001"),
+            b"not-a-hash".to_vec(),
+        )))
+        .collect();
+        assert_ok!(TelemetryProofs::verify_proofs(
+            RuntimeOrigin::signed(2),
+            queries
+        ));
+
+        let (count, results) = System::events()
+            .into_iter()
+            .rev()
+            .find_map(|record| match record.event {
+                RuntimeEvent::TelemetryProofs(Event::BulkVerificationResult { count, results }) => {
+                    Some((count, results))
+                }
+                _ => None,
+            })
+            .expect("bulk verification event");
+        assert_eq!(count, 10);
+        assert_eq!(results.len(), 2);
+        let decoded: Vec<bool> = (0..count as usize)
+            .map(|i| results[i / 8] & (1 << (i % 8)) != 0)
+            .collect();
+        assert_eq!(
+            decoded,
+            vec![true, false, false, true, false, true, false, true, true, false]
+        );
+    });
+}
+
+#[test]
+fn verify_proofs_bounds_query_count() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            TelemetryProofs::verify_proofs(RuntimeOrigin::signed(1), vec![]),
+            Error::<Test>::EmptyBatch
+        );
+        let max = <Test as Config>::MaxBatchSize::get() as usize;
+        let queries = vec![(device_id("device-001"), proof_hash("hash")); max + 1];
+        assert_noop!(
+            TelemetryProofs::verify_proofs(RuntimeOrigin::signed(1), queries),
+            Error::<Test>::BatchTooLarge
+        );
     });
 }
 
@@ -377,18 +1364,24 @@ fn proof_exists_helper_works() {
             10,
             1000,
             2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(&dev_id),
+            None,
+            None,
+            None,
         ));
 
-        let bounded_dev_id: BoundedVec<u8, ConstU32<64>> = dev_id.try_into().unwrap();
-        let bounded_hash: BoundedVec<u8, ConstU32<128>> = hash.try_into().unwrap();
+        let bounded_dev_id: DeviceId = dev_id.try_into().unwrap();
+        let bounded_hash = H256::from_slice(&hash);
 
         assert!(TelemetryProofs::proof_exists(
             &bounded_dev_id,
             &bounded_hash
         ));
 
-        let nonexistent: BoundedVec<u8, ConstU32<128>> =
-            proof_hash("nonexistent").try_into().unwrap();
+        let nonexistent = h256("nonexistent");
         assert!(!TelemetryProofs::proof_exists(
             &bounded_dev_id,
             &nonexistent
@@ -410,6 +1403,13 @@ fn get_proofs_in_window_works() {
             10,
             1000,
             2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(&dev_id),
+            latest_hash("device-001"),
+            None,
+            None,
         ));
 
         System::set_block_number(2);
@@ -420,6 +1420,13 @@ fn get_proofs_in_window_works() {
             10,
             2000,
             3000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(&dev_id),
+            latest_hash("device-001"),
+            None,
+            None,
         ));
 
         System::set_block_number(3);
@@ -430,21 +1437,288 @@ fn get_proofs_in_window_works() {
             10,
             5000,
             6000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(&dev_id),
+            latest_hash("device-001"),
+            None,
+            None,
         ));
 
-        let bounded_dev_id: BoundedVec<u8, ConstU32<64>> = dev_id.try_into().unwrap();
+        let bounded_dev_id: DeviceId = dev_id.try_into().unwrap();
 
         // Query window 1000-3000 should return first two proofs
-        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 1000, 3000);
+        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 1000, 3000, None);
         assert_eq!(proofs.len(), 2);
 
         // Query window 4000-7000 should return last proof
-        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 4000, 7000);
+        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 4000, 7000, None);
         assert_eq!(proofs.len(), 1);
 
         // Query window 0-10000 should return all proofs
-        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 0, 10000);
+        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 0, 10000, None);
         assert_eq!(proofs.len(), 3);
+
+        // Partially overlapping query returns both touched windows
+        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 1500, 2500, None);
+        assert_eq!(proofs.len(), 2);
+
+        // Boundaries are inclusive: 3000 ends proof 2, 5000 starts proof 3
+        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 3000, 5000, None);
+        assert_eq!(proofs.len(), 2);
+        assert_eq!(proofs[0].window_end, 3000);
+        assert_eq!(proofs[1].window_start, 5000);
+
+        // Disjoint query falls in the gap between windows
+        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 3001, 4999, None);
+        assert!(proofs.is_empty());
+    });
+}
+
+#[test]
+fn proofs_are_filtered_and_counted_by_category() {
+    new_test_ext().execute_with(|| {
+        let categories = [
+            ProofCategory::EnergyUsage,
+            ProofCategory::Temperature,
+            ProofCategory::Uptime,
+            ProofCategory::Other(7),
+            ProofCategory::EnergyUsage,
+        ];
+        for (i, category) in categories.into_iter().enumerate() {
+            let start = 1000 * (i as u64 + 1);
+            System::set_block_number(i as u64 + 1);
+            assert_ok!(TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash(&format!("hash{}", i)),
+                10,
+                start,
+                start + 1000,
+                1024,
+                1,
+                category,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                None,
+                None,
+            ));
+        }
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        let in_window = |category| {
+            TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 0, 10_000, category)
+                .into_iter()
+                .map(|metadata| metadata.window_start)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(in_window(None).len(), 5);
+        assert_eq!(
+            in_window(Some(ProofCategory::EnergyUsage)),
+            vec![1000, 5000]
+        );
+        assert_eq!(in_window(Some(ProofCategory::Temperature)), vec![2000]);
+        assert_eq!(in_window(Some(ProofCategory::Other(7))), vec![4000]);
+        assert!(in_window(Some(ProofCategory::Other(8))).is_empty());
+
+        let count = |category| TelemetryProofs::category_proof_count(&bounded_dev_id, category);
+        assert_eq!(count(ProofCategory::EnergyUsage), 2);
+        assert_eq!(count(ProofCategory::Temperature), 1);
+        assert_eq!(count(ProofCategory::Uptime), 1);
+        assert_eq!(count(ProofCategory::Other(7)), 1);
+        assert_eq!(count(ProofCategory::Other(8)), 0);
+
+        // Only energy usage proofs back carbon-credit energy records
+        assert!(<TelemetryProofs as ProofProvider<_>>::proof_exists(
+            &bounded_dev_id,
+            0
+        ));
+        assert!(!<TelemetryProofs as ProofProvider<_>>::proof_exists(
+            &bounded_dev_id,
+            1
+        ));
+    });
+}
+
+#[test]
+fn get_proofs_in_window_is_bounded() {
+    new_test_ext().execute_with(|| {
+        let dev_id = device_id("device-001");
+
+        for i in 0..7u64 {
+            System::set_block_number(i + 1);
+            assert_ok!(TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                dev_id.clone(),
+                proof_hash(&format!("hash{}", i)),
+                10,
+                i * 1000,
+                (i + 1) * 1000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(&dev_id),
+                latest_hash("device-001"),
+                None,
+                None,
+            ));
+        }
+
+        let bounded_dev_id: DeviceId = dev_id.try_into().unwrap();
+
+        // Seven matching proofs, capped at MaxQueryResults (5), oldest first
+        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 0, 10_000, None);
+        assert_eq!(proofs.len(), 5);
+        assert_eq!(proofs[0].window_start, 0);
+        assert_eq!(proofs[4].window_start, 4000);
+    });
+}
+
+/// Window query by scanning every retained proof, as before the window index.
+fn proofs_in_window_by_scan(
+    device: &DeviceId,
+    start: u64,
+    end: u64,
+    category: Option<ProofCategory>,
+) -> Vec<ProofMetadata<Test>> {
+    TelemetryProofs::get_device_proofs(device)
+        .into_iter()
+        .filter(|m| m.revoked.is_none())
+        .filter(|m| m.window_start <= end && m.window_end >= start)
+        .filter(|m| category.map_or(true, |category| m.category == category))
+        .take(<Test as Config>::MaxQueryResults::get() as usize)
+        .collect()
+}
+
+#[test]
+fn window_index_matches_full_scan_on_random_data() {
+    new_test_ext().execute_with(|| {
+        // Fixed-seed LCG so failures are reproducible
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move |bound: u64| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) % bound
+        };
+        let categories = [
+            ProofCategory::EnergyUsage,
+            ProofCategory::Temperature,
+            ProofCategory::Uptime,
+        ];
+        let devices = ["device-001", "device-002"];
+
+        for i in 0..80u64 {
+            System::set_block_number(i + 1);
+            let device = devices[next(2) as usize];
+            let window_start = next(88_000);
+            let (payload, signature) = unsigned_proof(
+                1,
+                device.as_bytes(),
+                &format!("random{}", i),
+                window_start,
+                window_start + 1 + next(12_000),
+                categories[next(3) as usize],
+            );
+            assert_ok!(TelemetryProofs::submit_proof_unsigned(
+                RuntimeOrigin::none(),
+                payload,
+                signature,
+            ));
+        }
+        for device in devices {
+            let bounded_dev_id: DeviceId = device_id(device).try_into().unwrap();
+            let count = TelemetryProofs::proof_count(&bounded_dev_id);
+            for _ in 0..4 {
+                let _ = TelemetryProofs::revoke_proof(
+                    RuntimeOrigin::root(),
+                    device_id(device),
+                    next(count),
+                    RevocationReason::CorruptedData,
+                );
+            }
+            assert_ok!(TelemetryProofs::prune_proofs_before(
+                RuntimeOrigin::root(),
+                device_id(device),
+                5,
+                5,
+            ));
+        }
+
+        for _ in 0..300 {
+            let device: DeviceId = device_id(devices[next(2) as usize]).try_into().unwrap();
+            let start = next(105_000);
+            let end = start + next(20_000);
+            let category = match next(4) {
+                3 => None,
+                i => Some(categories[i as usize]),
+            };
+            assert_eq!(
+                TelemetryProofs::get_proofs_in_window(&device, start, end, category),
+                proofs_in_window_by_scan(&device, start, end, category)
+            );
+        }
+    });
+}
+
+#[test]
+fn window_index_tracks_revocation_and_bucket_capacity() {
+    new_test_ext().execute_with(|| {
+        MaxProofsPerWindowBucket::set(2);
+        submit_proofs_in_blocks("device-001", 1, 2);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(
+            TelemetryProofs::window_index_bounds(&bounded_dev_id),
+            Some(WindowBounds {
+                first_bucket: 0,
+                last_bucket: 0,
+                longest_window: 1000
+            })
+        );
+
+        // Windows ending at 2000, 3000 and 4000 share the first bucket
+        System::set_block_number(3);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash3"),
+                10,
+                3000,
+                4000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                None,
+                None,
+            ),
+            Error::<Test>::WindowBucketFull
+        );
+
+        // Revocation frees the slot and hides the proof from window queries
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            RevocationReason::CorruptedData,
+        ));
+        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 0, 10_000, None);
+        assert_eq!(proofs.len(), 1);
+        assert_eq!(proofs[0].window_start, 2000);
+        submit_proofs_in_blocks("device-001", 3, 1);
+
+        // Pruning every proof drops the device from the index
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            3,
+            3,
+        ));
+        assert_eq!(TelemetryProofs::window_index_bounds(&bounded_dev_id), None);
+        assert_eq!(ProofsByWindowEnd::<Test>::iter().count(), 0);
     });
 }
 
@@ -452,7 +1726,7 @@ fn get_proofs_in_window_works() {
 fn latest_proof_block_updated() {
     new_test_ext().execute_with(|| {
         let dev_id = device_id("device-001");
-        let bounded_dev_id: BoundedVec<u8, ConstU32<64>> = dev_id.clone().try_into().unwrap();
+        let bounded_dev_id: DeviceId = dev_id.clone().try_into().unwrap();
 
         // Initially no latest block
         assert!(TelemetryProofs::latest_proof_block(&bounded_dev_id).is_none());
@@ -465,6 +1739,13 @@ fn latest_proof_block_updated() {
             10,
             1000,
             2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(&dev_id),
+            latest_hash("device-001"),
+            None,
+            None,
         ));
 
         assert_eq!(
@@ -480,6 +1761,13 @@ fn latest_proof_block_updated() {
             10,
             2000,
             3000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            latest_hash("device-001"),
+            None,
+            None,
         ));
 
         assert_eq!(
@@ -488,3 +1776,4675 @@ fn latest_proof_block_updated() {
         );
     });
 }
+
+#[test]
+fn latest_proof_block_updated_by_batch() {
+    new_test_ext().execute_with(|| {
+        let dev1: DeviceId = device_id("device-001").try_into().unwrap();
+        let dev2: DeviceId = device_id("device-002").try_into().unwrap();
+
+        System::set_block_number(3);
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            vec![
+                (
+                    device_id("device-001"),
+                    proof_hash("hash1"),
+                    10,
+                    1000u64,
+                    2000u64,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-001")
+                ),
+                (
+                    device_id("device-002"),
+                    proof_hash("hash2"),
+                    10,
+                    2000u64,
+                    1000u64,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-002")
+                ), // Invalid window, skipped
+            ],
+        ));
+
+        assert_eq!(TelemetryProofs::latest_proof_block(&dev1), Some(3));
+        // Skipped entries do not touch the latest block
+        assert!(TelemetryProofs::latest_proof_block(&dev2).is_none());
+    });
+}
+
+/// Submit one proof per block for `device` starting at `first_block`.
+fn submit_proofs_in_blocks(device: &str, first_block: u64, n: u64) {
+    for i in 0..n {
+        let block = first_block + i;
+        System::set_block_number(block);
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id(device),
+            proof_hash(&format!("hash{}", block)),
+            10,
+            block * 1000,
+            (block + 1) * 1000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(device.as_bytes()),
+            latest_hash(device),
+            None,
+            None,
+        ));
+    }
+}
+
+#[test]
+fn max_proofs_per_device_rejects_when_full() {
+    new_test_ext().execute_with(|| {
+        MaxProofsPerDevice::set(3);
+
+        submit_proofs_in_blocks("device-001", 1, 3);
+
+        System::set_block_number(4);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash4"),
+                10,
+                4000,
+                5000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                None,
+                None,
+            ),
+            Error::<Test>::MaxProofsExceeded
+        );
+    });
+}
+
+#[test]
+fn max_proofs_per_device_evicts_oldest_in_ring_buffer_mode() {
+    new_test_ext().execute_with(|| {
+        MaxProofsPerDevice::set(3);
+        PruneOldestProofs::set(true);
+
+        submit_proofs_in_blocks("device-001", 1, 5);
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        // Lifetime counter keeps growing, retained count stays at the bound
+        assert_eq!(TelemetryProofs::proof_count(&bounded_dev_id), 5);
+        assert_eq!(TelemetryProofs::stored_proof_count(&bounded_dev_id), 3);
+        assert_eq!(TelemetryProofs::oldest_proof_index(&bounded_dev_id), 2);
+        assert_eq!(TelemetryProofs::total_proofs(), 3);
+
+        // Oldest entries and their block index are gone
+        assert!(TelemetryProofs::proofs(&bounded_dev_id, 0).is_none());
+        assert!(TelemetryProofs::proofs(&bounded_dev_id, 1).is_none());
+        assert!(!ProofIndexesByBlock::<Test>::contains_key(
+            1,
+            &bounded_dev_id
+        ));
+        assert!(!ProofIndexesByBlock::<Test>::contains_key(
+            2,
+            &bounded_dev_id
+        ));
+        assert!(TelemetryProofs::proofs(&bounded_dev_id, 2).is_some());
+        assert_eq!(
+            TelemetryProofs::proof_indexes_by_block(5, &bounded_dev_id).into_inner(),
+            vec![4]
+        );
+
+        // Queries only see retained proofs
+        let proofs = TelemetryProofs::get_device_proofs(&bounded_dev_id);
+        assert_eq!(proofs.len(), 3);
+        assert_eq!(proofs[0].submitted_block, 3);
+
+        let evicted = h256("hash1");
+        assert!(!TelemetryProofs::proof_exists(&bounded_dev_id, &evicted));
+    });
+}
+
+#[test]
+fn prune_proofs_before_works_in_multiple_passes() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 5);
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        // First pass removes two of the four eligible proofs
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            4,
+            2,
+        ));
+        System::assert_last_event(
+            Event::ProofsPruned {
+                device_id: bounded_dev_id.clone(),
+                removed: 2,
+            }
+            .into(),
+        );
+        assert_eq!(TelemetryProofs::stored_proof_count(&bounded_dev_id), 3);
+
+        // Second pass stops at before_index
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            4,
+            10,
+        ));
+        assert_eq!(TelemetryProofs::stored_proof_count(&bounded_dev_id), 1);
+        assert_eq!(TelemetryProofs::oldest_proof_index(&bounded_dev_id), 4);
+        assert_eq!(TelemetryProofs::total_proofs(), 1);
+        assert_eq!(TelemetryProofs::proof_count(&bounded_dev_id), 5);
+
+        // Window queries no longer return pruned proofs
+        let proofs = TelemetryProofs::get_proofs_in_window(&bounded_dev_id, 0, 10_000, None);
+        assert_eq!(proofs.len(), 1);
+        assert_eq!(proofs[0].submitted_block, 5);
+        assert!(!ProofIndexesByBlock::<Test>::contains_key(
+            1,
+            &bounded_dev_id
+        ));
+        assert_eq!(
+            TelemetryProofs::latest_proof_block(&bounded_dev_id),
+            Some(5)
+        );
+    });
+}
+
+#[test]
+fn pruning_all_proofs_clears_latest_proof_block() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 2);
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            u64::MAX,
+            10,
+        ));
+
+        assert_eq!(TelemetryProofs::stored_proof_count(&bounded_dev_id), 0);
+        assert!(TelemetryProofs::latest_proof_block(&bounded_dev_id).is_none());
+
+        // New proofs continue the sequence
+        submit_proofs_in_blocks("device-001", 3, 1);
+        assert!(TelemetryProofs::proofs(&bounded_dev_id, 2).is_some());
+        assert_eq!(
+            TelemetryProofs::latest_proof_block(&bounded_dev_id),
+            Some(3)
+        );
+    });
+}
+
+#[test]
+fn prune_proofs_before_requires_admin_or_owner() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 2);
+        DeviceOwner::set(Some(2));
+
+        assert_noop!(
+            TelemetryProofs::prune_proofs_before(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                10,
+                10,
+            ),
+            Error::<Test>::NotDeviceOwner
+        );
+        assert_noop!(
+            TelemetryProofs::prune_proofs_before(
+                RuntimeOrigin::none(),
+                device_id("device-001"),
+                10,
+                10,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn device_owner_prunes_its_own_proofs() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 3);
+        DeviceOwner::set(Some(2));
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::signed(2),
+            device_id("device-001"),
+            2,
+            10,
+        ));
+        System::assert_last_event(
+            Event::ProofsPruned {
+                device_id: bounded_dev_id.clone(),
+                removed: 2,
+            }
+            .into(),
+        );
+        assert_eq!(TelemetryProofs::stored_proof_count(&bounded_dev_id), 1);
+    });
+}
+
+fn device_pair(seed: u8) -> sr25519::Pair {
+    sr25519::Pair::from_seed(&[seed; 32])
+}
+
+fn sign_proof(pair: &sr25519::Pair, device: &str, hash: &str, start: u64, end: u64) -> Vec<u8> {
+    let payload = TelemetryProofs::signing_payload(device.as_bytes(), &h256(hash), start, end);
+    pair.sign(&payload).0.to_vec()
+}
+
+#[test]
+fn register_device_key_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let pair = device_pair(1);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        assert_noop!(
+            TelemetryProofs::register_device_key(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                pair.public(),
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(TelemetryProofs::register_device_key(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            pair.public(),
+        ));
+        assert_eq!(
+            TelemetryProofs::current_device_key(&bounded_dev_id),
+            Some(pair.public())
+        );
+
+        assert_noop!(
+            TelemetryProofs::register_device_key(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                device_pair(2).public(),
+            ),
+            Error::<Test>::DeviceKeyAlreadyRegistered
+        );
+    });
+}
+
+#[test]
+fn signed_proof_verifies_against_device_key() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let pair = device_pair(1);
+        assert_ok!(TelemetryProofs::register_device_key(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            pair.public(),
+        ));
+
+        let signature = sign_proof(&pair, "device-001", "hash1", 1000, 2000);
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            Some(signature),
+            None,
+        ));
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert!(
+            TelemetryProofs::proofs(&bounded_dev_id, 0)
+                .unwrap()
+                .device_signed
+        );
+    });
+}
+
+#[test]
+fn signed_proof_rejects_invalid_or_missing_signature() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let pair = device_pair(1);
+        assert_ok!(TelemetryProofs::register_device_key(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            pair.public(),
+        ));
+
+        // Missing signature
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash1"),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                None,
+                None,
+                None,
+            ),
+            Error::<Test>::InvalidDeviceSignature
+        );
+
+        // Signed by a different key
+        let wrong_key = sign_proof(&device_pair(2), "device-001", "hash1", 1000, 2000);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash1"),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                None,
+                Some(wrong_key),
+                None,
+            ),
+            Error::<Test>::InvalidDeviceSignature
+        );
+
+        // Signature over a different window
+        let wrong_payload = sign_proof(&pair, "device-001", "hash1", 1000, 3000);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash1"),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                None,
+                Some(wrong_payload),
+                None,
+            ),
+            Error::<Test>::InvalidDeviceSignature
+        );
+
+        // Malformed signature bytes
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash1"),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                None,
+                Some(vec![0u8; 10]),
+                None,
+            ),
+            Error::<Test>::InvalidDeviceSignature
+        );
+    });
+}
+
+#[test]
+fn unsigned_proof_accepted_without_device_key() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            None,
+            None,
+        ));
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert!(
+            !TelemetryProofs::proofs(&bounded_dev_id, 0)
+                .unwrap()
+                .device_signed
+        );
+    });
+}
+
+#[test]
+fn rotate_device_key_keeps_history_for_old_proofs() {
+    new_test_ext().execute_with(|| {
+        let old_pair = device_pair(1);
+        let new_pair = device_pair(2);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        System::set_block_number(1);
+        assert_ok!(TelemetryProofs::register_device_key(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            old_pair.public(),
+        ));
+        let old_signature = sign_proof(&old_pair, "device-001", "hash1", 1000, 2000);
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            latest_hash("device-001"),
+            Some(old_signature.clone()),
+            None,
+        ));
+
+        System::set_block_number(5);
+        assert_ok!(TelemetryProofs::rotate_device_key(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            new_pair.public(),
+        ));
+        System::assert_last_event(
+            Event::DeviceKeyRotated {
+                device_id: bounded_dev_id.clone(),
+                old_key: old_pair.public(),
+                new_key: new_pair.public(),
+                valid_from: 5,
+            }
+            .into(),
+        );
+
+        // Old key is rejected for new proofs
+        System::set_block_number(6);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash2"),
+                10,
+                2000,
+                3000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                Some(sign_proof(&old_pair, "device-001", "hash2", 2000, 3000)),
+                None,
+            ),
+            Error::<Test>::InvalidDeviceSignature
+        );
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash2"),
+            10,
+            2000,
+            3000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            latest_hash("device-001"),
+            Some(sign_proof(&new_pair, "device-001", "hash2", 2000, 3000)),
+            None,
+        ));
+
+        // Historical verification still uses the key valid at submission
+        let first = TelemetryProofs::proofs(&bounded_dev_id, 0).unwrap();
+        let payload = TelemetryProofs::signing_payload(b"device-001", &h256("hash1"), 1000, 2000);
+        assert!(TelemetryProofs::verify_device_signature_at(
+            &bounded_dev_id,
+            first.submitted_block,
+            &payload,
+            &old_signature,
+        ));
+        assert!(!TelemetryProofs::verify_device_signature_at(
+            &bounded_dev_id,
+            6,
+            &payload,
+            &old_signature,
+        ));
+    });
+}
+
+#[test]
+fn compromised_key_invalidates_proofs_from_compromise_block() {
+    new_test_ext().execute_with(|| {
+        let pair = device_pair(1);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        System::set_block_number(1);
+        assert_ok!(TelemetryProofs::register_device_key(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            pair.public(),
+        ));
+        for (block, hash, start) in [(1, "hash1", 1000), (3, "hash2", 2000)] {
+            System::set_block_number(block);
+            assert_ok!(TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash(hash),
+                10,
+                start,
+                start + 1000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                Some(sign_proof(&pair, "device-001", hash, start, start + 1000)),
+                None,
+            ));
+        }
+
+        assert_noop!(
+            TelemetryProofs::mark_key_compromised(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                pair.public(),
+                3,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            TelemetryProofs::mark_key_compromised(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                device_pair(2).public(),
+                3,
+            ),
+            Error::<Test>::UnknownDeviceKey
+        );
+        assert_noop!(
+            TelemetryProofs::revoke_compromised_proofs(
+                RuntimeOrigin::signed(2),
+                device_id("device-001"),
+                10,
+            ),
+            Error::<Test>::NoCompromiseSweep
+        );
+
+        assert_ok!(TelemetryProofs::mark_key_compromised(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            pair.public(),
+            3,
+        ));
+        System::assert_last_event(
+            Event::DeviceKeyCompromised {
+                device_id: bounded_dev_id.clone(),
+                key: pair.public(),
+                compromised_from_block: 3,
+            }
+            .into(),
+        );
+        assert_noop!(
+            TelemetryProofs::mark_key_compromised(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                pair.public(),
+                1,
+            ),
+            Error::<Test>::KeyAlreadyCompromised
+        );
+
+        // Only the proof signed inside the compromise window is invalid
+        assert!(TelemetryProofs::proof_exists(
+            &bounded_dev_id,
+            &h256("hash1")
+        ));
+        assert!(!TelemetryProofs::proof_exists(
+            &bounded_dev_id,
+            &h256("hash2")
+        ));
+        assert_ok!(TelemetryProofs::verify_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash2"),
+        ));
+        System::assert_last_event(
+            Event::ProofVerificationResult {
+                device_id: bounded_dev_id.clone(),
+                proof_hash: h256("hash2"),
+                found: true,
+                proof_index: Some(1),
+                valid: false,
+                expired: false,
+            }
+            .into(),
+        );
+
+        // The sweep revokes the compromised proof and leaves the earlier one
+        assert_ok!(TelemetryProofs::revoke_compromised_proofs(
+            RuntimeOrigin::signed(2),
+            device_id("device-001"),
+            10,
+        ));
+        System::assert_last_event(
+            Event::ProofRevoked {
+                device_id: bounded_dev_id.clone(),
+                proof_index: 1,
+                reason: RevocationReason::DeviceCompromised,
+            }
+            .into(),
+        );
+        assert_eq!(
+            TelemetryProofs::proofs(&bounded_dev_id, 1).unwrap().revoked,
+            Some(RevocationReason::DeviceCompromised)
+        );
+        assert_eq!(
+            TelemetryProofs::proofs(&bounded_dev_id, 0).unwrap().revoked,
+            None
+        );
+        assert_eq!(
+            TelemetryProofs::compromise_sweep_cursor(&bounded_dev_id),
+            None
+        );
+        assert!(TelemetryProofs::proof_exists(
+            &bounded_dev_id,
+            &h256("hash1")
+        ));
+    });
+}
+
+#[test]
+fn rotate_device_key_rejects_invalid_rotations() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let pair = device_pair(1);
+
+        assert_noop!(
+            TelemetryProofs::rotate_device_key(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                pair.public(),
+            ),
+            Error::<Test>::DeviceKeyNotRegistered
+        );
+
+        assert_ok!(TelemetryProofs::register_device_key(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            pair.public(),
+        ));
+        assert_noop!(
+            TelemetryProofs::rotate_device_key(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                pair.public(),
+            ),
+            Error::<Test>::SameDeviceKey
+        );
+    });
+}
+
+#[test]
+fn rotate_device_key_requires_admin_or_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TelemetryProofs::register_device_key(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            device_pair(1).public(),
+        ));
+        DeviceOwner::set(Some(2));
+
+        assert_noop!(
+            TelemetryProofs::rotate_device_key(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                device_pair(2).public(),
+            ),
+            Error::<Test>::NotDeviceOwner
+        );
+        assert_noop!(
+            TelemetryProofs::rotate_device_key(
+                RuntimeOrigin::none(),
+                device_id("device-001"),
+                device_pair(2).public(),
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn device_owner_rotates_its_own_key() {
+    new_test_ext().execute_with(|| {
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        System::set_block_number(1);
+        assert_ok!(TelemetryProofs::register_device_key(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            device_pair(1).public(),
+        ));
+        DeviceOwner::set(Some(2));
+
+        System::set_block_number(4);
+        assert_ok!(TelemetryProofs::rotate_device_key(
+            RuntimeOrigin::signed(2),
+            device_id("device-001"),
+            device_pair(2).public(),
+        ));
+        System::assert_last_event(
+            Event::DeviceKeyRotated {
+                device_id: bounded_dev_id.clone(),
+                old_key: device_pair(1).public(),
+                new_key: device_pair(2).public(),
+                valid_from: 4,
+            }
+            .into(),
+        );
+        assert_eq!(
+            TelemetryProofs::device_key_at(&bounded_dev_id, 4),
+            Some(device_pair(2).public())
+        );
+    });
+}
+
+#[test]
+fn device_key_history_is_bounded() {
+    new_test_ext().execute_with(|| {
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        System::set_block_number(1);
+        assert_ok!(TelemetryProofs::register_device_key(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            device_pair(1).public(),
+        ));
+        for seed in 2..=4u8 {
+            System::set_block_number(seed as u64);
+            assert_ok!(TelemetryProofs::rotate_device_key(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                device_pair(seed).public(),
+            ));
+        }
+
+        // MaxKeyHistory (3) keeps only the latest keys
+        let history = TelemetryProofs::device_key_history(&bounded_dev_id);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0], (device_pair(2).public(), 2));
+        assert!(TelemetryProofs::device_key_at(&bounded_dev_id, 1).is_none());
+    });
+}
+
+#[test]
+fn open_submission_accepts_any_signed_account() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert!(TelemetryProofs::open_submission());
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(42),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            None,
+            None,
+        ));
+    });
+}
+
+#[test]
+fn closed_submission_rejects_untrusted_accounts() {
+    new_test_ext_with_submitters(vec![1], false).execute_with(|| {
+        System::set_block_number(1);
+
+        assert!(!TelemetryProofs::open_submission());
+        assert!(TelemetryProofs::trusted_submitters(1).is_some());
+
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(2),
+                device_id("device-001"),
+                proof_hash("hash1"),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                None,
+                None,
+                None,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            TelemetryProofs::submit_batch_proofs(
+                RuntimeOrigin::signed(2),
+                vec![(
+                    device_id("device-001"),
+                    proof_hash("hash1"),
+                    10,
+                    1000,
+                    2000,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-001")
+                )],
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            None,
+            None,
+        ));
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            vec![(
+                device_id("device-002"),
+                proof_hash("hash2"),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-002")
+            )],
+        ));
+        assert_eq!(TelemetryProofs::total_proofs(), 2);
+    });
+}
+
+#[test]
+fn submissions_are_feeless_only_for_authorized_feeless_submitters() {
+    use frame_support::traits::CheckIfFeeless;
+
+    new_test_ext_with_submitters(vec![1, 2], false).execute_with(|| {
+        FeelessAccounts::set(vec![1, 3]);
+        let batch = RuntimeCall::TelemetryProofs(Call::submit_batch_proofs { proofs: vec![] });
+
+        assert!(batch.is_feeless(&RuntimeOrigin::signed(1)));
+        // Authorized, but not listed as feeless
+        assert!(!batch.is_feeless(&RuntimeOrigin::signed(2)));
+        // Listed, but not admitted by `SubmitterOrigin`
+        assert!(!batch.is_feeless(&RuntimeOrigin::signed(3)));
+        assert!(!batch.is_feeless(&RuntimeOrigin::root()));
+
+        // Other calls always pay
+        let admin = RuntimeCall::TelemetryProofs(Call::set_open_submission { open: true });
+        assert!(!admin.is_feeless(&RuntimeOrigin::signed(1)));
+    });
+}
+
+#[test]
+fn trusted_submitter_lifecycle() {
+    new_test_ext_with_submitters(vec![], false).execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            TelemetryProofs::add_trusted_submitter(RuntimeOrigin::signed(1), 2),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(TelemetryProofs::add_trusted_submitter(
+            RuntimeOrigin::root(),
+            2
+        ));
+        System::assert_last_event(Event::TrustedSubmitterAdded { who: 2 }.into());
+        assert_noop!(
+            TelemetryProofs::add_trusted_submitter(RuntimeOrigin::root(), 2),
+            Error::<Test>::AlreadyTrustedSubmitter
+        );
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(2),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            latest_hash("device-001"),
+            None,
+            None,
+        ));
+
+        assert_ok!(TelemetryProofs::remove_trusted_submitter(
+            RuntimeOrigin::root(),
+            2
+        ));
+        System::assert_last_event(Event::TrustedSubmitterRemoved { who: 2 }.into());
+        assert_noop!(
+            TelemetryProofs::remove_trusted_submitter(RuntimeOrigin::root(), 2),
+            Error::<Test>::NotTrustedSubmitter
+        );
+
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(2),
+                device_id("device-001"),
+                proof_hash("hash2"),
+                10,
+                2000,
+                3000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                None,
+                None,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        // Reopening submission admits any signed account again
+        assert_ok!(TelemetryProofs::set_open_submission(
+            RuntimeOrigin::root(),
+            true
+        ));
+        System::assert_last_event(Event::OpenSubmissionSet { open: true }.into());
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(2),
+            device_id("device-001"),
+            proof_hash("hash2"),
+            10,
+            2000,
+            3000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            latest_hash("device-001"),
+            None,
+            None,
+        ));
+    });
+}
+
+#[test]
+fn revoke_proof_invalidates_verification() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let dev_id = device_id("device-001");
+        let hash = proof_hash("hash1");
+        let bounded_dev_id: DeviceId = dev_id.clone().try_into().unwrap();
+        let bounded_hash = H256::from_slice(&hash);
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            hash.clone(),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(&dev_id),
+            None,
+            None,
+            None,
+        ));
+        assert!(TelemetryProofs::proof_exists(
+            &bounded_dev_id,
+            &bounded_hash
+        ));
+
+        // Only the submitter or admin may revoke
+        assert_noop!(
+            TelemetryProofs::revoke_proof(
+                RuntimeOrigin::signed(2),
+                dev_id.clone(),
+                0,
+                RevocationReason::CorruptedData,
+            ),
+            Error::<Test>::NotProofSubmitter
+        );
+
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::signed(1),
+            dev_id.clone(),
+            0,
+            RevocationReason::CorruptedData,
+        ));
+        System::assert_last_event(
+            Event::ProofRevoked {
+                device_id: bounded_dev_id.clone(),
+                proof_index: 0,
+                reason: RevocationReason::CorruptedData,
+            }
+            .into(),
+        );
+
+        // Revoked proof is no longer valid but stays readable
+        assert!(!TelemetryProofs::proof_exists(
+            &bounded_dev_id,
+            &bounded_hash
+        ));
+        let metadata = TelemetryProofs::proofs(&bounded_dev_id, 0).unwrap();
+        assert_eq!(metadata.revoked, Some(RevocationReason::CorruptedData));
+        assert_eq!(metadata.proof_hash, bounded_hash);
+
+        assert_ok!(TelemetryProofs::verify_proof(
+            RuntimeOrigin::signed(3),
+            dev_id.clone(),
+            hash,
+        ));
+        System::assert_last_event(
+            Event::ProofVerificationResult {
+                device_id: bounded_dev_id,
+                proof_hash: bounded_hash,
+                found: true,
+                proof_index: Some(0),
+                valid: false,
+                expired: false,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            TelemetryProofs::revoke_proof(
+                RuntimeOrigin::root(),
+                dev_id,
+                0,
+                RevocationReason::Other
+            ),
+            Error::<Test>::ProofAlreadyRevoked
+        );
+    });
+}
+
+#[test]
+fn admin_can_revoke_any_proof() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(submit_unsigned(b"device-001", "hash1", 1000, 2000));
+
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            RevocationReason::DeviceCompromised,
+        ));
+        assert_noop!(
+            TelemetryProofs::revoke_proof(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                1,
+                RevocationReason::Other,
+            ),
+            Error::<Test>::ProofNotFound
+        );
+    });
+}
+
+#[test]
+fn total_proofs_counts_unrevoked_proofs() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 3);
+        assert_eq!(TelemetryProofs::total_proofs(), 3);
+
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            RevocationReason::CorruptedData,
+        ));
+        assert_eq!(TelemetryProofs::total_proofs(), 2);
+        assert_ok!(TelemetryProofs::do_try_state());
+
+        // Pruning the revoked proof leaves the total alone; pruning a live one
+        // lowers it
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            2,
+            10,
+        ));
+        assert_eq!(TelemetryProofs::total_proofs(), 1);
+        assert_ok!(TelemetryProofs::do_try_state());
+    });
+}
+
+fn challenge_deposit_held(who: u64) -> u64 {
+    <Balances as fungible::InspectHold<u64>>::balance_on_hold(
+        &HoldReason::ChallengeDeposit.into(),
+        &who,
+    )
+}
+
+fn submit_and_challenge(submitter: Option<u64>, challenger: u64) {
+    match submitter {
+        Some(who) => assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(who),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            None,
+            None,
+        )),
+        None => assert_ok!(submit_unsigned(b"device-001", "hash1", 1000, 2000)),
+    }
+    assert_ok!(TelemetryProofs::challenge_proof(
+        RuntimeOrigin::signed(challenger),
+        device_id("device-001"),
+        0,
+        H256::repeat_byte(7),
+    ));
+}
+
+#[test]
+fn challenge_holds_deposit_and_invalidates_proof() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        let bounded_hash = h256("hash1");
+
+        submit_and_challenge(Some(1), 2);
+        System::assert_last_event(
+            Event::ProofChallenged {
+                device_id: bounded_dev_id.clone(),
+                proof_index: 0,
+                challenger: 2,
+                evidence_hash: H256::repeat_byte(7),
+            }
+            .into(),
+        );
+
+        assert_eq!(challenge_deposit_held(2), 10);
+        assert!(
+            TelemetryProofs::proofs(&bounded_dev_id, 0)
+                .unwrap()
+                .challenged
+        );
+        assert!(!TelemetryProofs::proof_exists(
+            &bounded_dev_id,
+            &bounded_hash
+        ));
+
+        assert_noop!(
+            TelemetryProofs::challenge_proof(
+                RuntimeOrigin::signed(3),
+                device_id("device-001"),
+                0,
+                H256::repeat_byte(8),
+            ),
+            Error::<Test>::ProofAlreadyChallenged
+        );
+        assert_noop!(
+            TelemetryProofs::challenge_proof(
+                RuntimeOrigin::signed(3),
+                device_id("device-001"),
+                1,
+                H256::repeat_byte(8),
+            ),
+            Error::<Test>::ProofNotFound
+        );
+    });
+}
+
+#[test]
+fn upheld_challenge_revokes_proof_and_returns_deposit() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        submit_and_challenge(Some(1), 2);
+
+        assert_noop!(
+            TelemetryProofs::resolve_challenge(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                0,
+                true,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(TelemetryProofs::resolve_challenge(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            true,
+        ));
+        System::assert_last_event(
+            Event::ChallengeUpheld {
+                device_id: bounded_dev_id.clone(),
+                proof_index: 0,
+                challenger: 2,
+            }
+            .into(),
+        );
+
+        let metadata = TelemetryProofs::proofs(&bounded_dev_id, 0).unwrap();
+        assert_eq!(metadata.revoked, Some(RevocationReason::ChallengeUpheld));
+        assert!(!metadata.challenged);
+        assert!(TelemetryProofs::challenges(&bounded_dev_id, 0).is_none());
+        assert_eq!(challenge_deposit_held(2), 0);
+        assert_eq!(Balances::free_balance(2), 100);
+
+        assert_noop!(
+            TelemetryProofs::resolve_challenge(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                0,
+                true,
+            ),
+            Error::<Test>::ChallengeNotFound
+        );
+    });
+}
+
+#[test]
+fn upholding_a_challenge_on_a_revoked_proof_does_not_revoke_it_twice() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        submit_and_challenge(Some(1), 2);
+        assert_ok!(submit_window(2000, 3000));
+        assert_eq!(TelemetryProofs::total_records(&bounded_dev_id), 20);
+
+        // The submitter revokes the proof while the challenge is open
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            0,
+            RevocationReason::CorruptedData,
+        ));
+        assert_eq!(TelemetryProofs::total_records(&bounded_dev_id), 10);
+
+        assert_ok!(TelemetryProofs::resolve_challenge(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            true,
+        ));
+
+        // Only the other proof's records remain, and the reason is kept
+        assert_eq!(TelemetryProofs::total_records(&bounded_dev_id), 10);
+        let metadata = TelemetryProofs::proofs(&bounded_dev_id, 0).unwrap();
+        assert_eq!(metadata.revoked, Some(RevocationReason::CorruptedData));
+        assert!(!metadata.challenged);
+        assert_eq!(challenge_deposit_held(2), 0);
+        assert_eq!(Balances::free_balance(2), 100);
+    });
+}
+
+#[test]
+fn dismissed_challenge_pays_deposit_to_submitter() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        let bounded_hash = h256("hash1");
+
+        submit_and_challenge(Some(1), 2);
+
+        assert_ok!(TelemetryProofs::resolve_challenge(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            false,
+        ));
+        System::assert_last_event(
+            Event::ChallengeDismissed {
+                device_id: bounded_dev_id.clone(),
+                proof_index: 0,
+                challenger: 2,
+                beneficiary: 1,
+            }
+            .into(),
+        );
+
+        assert!(TelemetryProofs::proof_exists(
+            &bounded_dev_id,
+            &bounded_hash
+        ));
+        assert_eq!(Balances::free_balance(1), 110);
+        assert_eq!(Balances::free_balance(2), 90);
+        assert_eq!(challenge_deposit_held(2), 0);
+    });
+}
+
+#[test]
+fn dismissed_challenge_on_unsigned_proof_pays_treasury() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        submit_and_challenge(None, 2);
+
+        assert_ok!(TelemetryProofs::resolve_challenge(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            false,
+        ));
+
+        assert_eq!(Balances::free_balance(TREASURY), 10);
+        assert_eq!(Balances::free_balance(2), 90);
+    });
+}
+
+#[test]
+fn unresolved_challenge_expires_after_period() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        let bounded_hash = h256("hash1");
+
+        submit_and_challenge(Some(1), 2);
+
+        System::set_block_number(20);
+        assert_noop!(
+            TelemetryProofs::expire_challenge(RuntimeOrigin::signed(3), device_id("device-001"), 0),
+            Error::<Test>::ChallengeNotExpired
+        );
+
+        System::set_block_number(21);
+        assert_ok!(TelemetryProofs::expire_challenge(
+            RuntimeOrigin::signed(3),
+            device_id("device-001"),
+            0,
+        ));
+        System::assert_last_event(
+            Event::ChallengeExpired {
+                device_id: bounded_dev_id.clone(),
+                proof_index: 0,
+                challenger: 2,
+            }
+            .into(),
+        );
+
+        assert!(TelemetryProofs::proof_exists(
+            &bounded_dev_id,
+            &bounded_hash
+        ));
+        assert_eq!(challenge_deposit_held(2), 0);
+        assert_eq!(Balances::free_balance(2), 100);
+        assert_noop!(
+            TelemetryProofs::expire_challenge(RuntimeOrigin::signed(3), device_id("device-001"), 0),
+            Error::<Test>::ChallengeNotFound
+        );
+    });
+}
+
+#[test]
+fn duplicate_proof_hash_rejected_across_devices() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            None,
+            None,
+        ));
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(
+            TelemetryProofs::proof_hash_index(sp_io::hashing::blake2_256(&proof_hash("hash1"))),
+            Some((bounded_dev_id, 0))
+        );
+
+        // Other device, same hash: rejected even with resubmission allowed
+        AllowSameDeviceResubmission::set(true);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-002"),
+                proof_hash("hash1"),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-002"),
+                None,
+                None,
+                None,
+            ),
+            Error::<Test>::DuplicateProofHash
+        );
+    });
+}
+
+#[test]
+fn duplicate_proof_hash_same_device_is_configurable() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            latest_hash("device-001"),
+            None,
+            None,
+        ));
+
+        System::set_block_number(2);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash1"),
+                10,
+                2000,
+                3000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                None,
+                None,
+            ),
+            Error::<Test>::DuplicateProofHash
+        );
+
+        AllowSameDeviceResubmission::set(true);
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            2000,
+            3000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            latest_hash("device-001"),
+            None,
+            None,
+        ));
+
+        // Index follows the newest proof
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(
+            TelemetryProofs::proof_hash_index(sp_io::hashing::blake2_256(&proof_hash("hash1"))),
+            Some((bounded_dev_id, 1))
+        );
+    });
+}
+
+#[test]
+fn proof_hash_reusable_after_prune_or_revocation() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 2);
+
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            1,
+            10,
+        ));
+        assert!(
+            TelemetryProofs::proof_hash_index(sp_io::hashing::blake2_256(&proof_hash("hash1")))
+                .is_none()
+        );
+
+        System::set_block_number(3);
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-002"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-002"),
+            latest_hash("device-002"),
+            None,
+            None,
+        ));
+
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            1,
+            RevocationReason::CorruptedData,
+        ));
+        assert!(
+            TelemetryProofs::proof_hash_index(sp_io::hashing::blake2_256(&proof_hash("hash2")))
+                .is_none()
+        );
+
+        System::set_block_number(4);
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-002"),
+            proof_hash("hash2"),
+            10,
+            2000,
+            3000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-002"),
+            latest_hash("device-002"),
+            None,
+            None,
+        ));
+    });
+}
+
+#[test]
+fn proof_chain_links_consecutive_proofs() {
+    new_test_ext().execute_with(|| {
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        // Three-link chain: first proof has no predecessor
+        for (block, hash, prev) in [
+            (1, "hash1", None),
+            (2, "hash2", Some("hash1")),
+            (3, "hash3", Some("hash2")),
+        ] {
+            System::set_block_number(block);
+            assert_ok!(TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash(hash),
+                10,
+                block * 1000,
+                (block + 1) * 1000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                prev.map(proof_hash),
+                None,
+                None,
+            ));
+        }
+
+        let second = TelemetryProofs::proofs(&bounded_dev_id, 1).unwrap();
+        assert_eq!(second.prev_proof_hash, Some(h256("hash1")));
+        assert!(TelemetryProofs::verify_chain(&bounded_dev_id, 0, 2));
+
+        // Out-of-order insert: linking to an older proof breaks the chain
+        System::set_block_number(4);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash4"),
+                10,
+                4000,
+                5000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                Some(proof_hash("hash2")),
+                None,
+                None,
+            ),
+            Error::<Test>::BrokenProofChain
+        );
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash4"),
+                10,
+                4000,
+                5000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                None,
+                None,
+                None,
+            ),
+            Error::<Test>::BrokenProofChain
+        );
+    });
+}
+
+#[test]
+fn verify_chain_detects_corrupted_entry() {
+    new_test_ext().execute_with(|| {
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        submit_proofs_in_blocks("device-001", 1, 3);
+        assert!(TelemetryProofs::verify_chain(&bounded_dev_id, 0, 2));
+
+        // Tamper with the middle proof's hash
+        Proofs::<Test>::mutate(&bounded_dev_id, 1, |maybe_metadata| {
+            maybe_metadata.as_mut().unwrap().proof_hash = h256("forged");
+        });
+
+        assert!(!TelemetryProofs::verify_chain(&bounded_dev_id, 0, 2));
+        assert!(TelemetryProofs::verify_chain(&bounded_dev_id, 0, 0));
+        assert!(!TelemetryProofs::verify_chain(&bounded_dev_id, 2, 1));
+        assert!(!TelemetryProofs::verify_chain(&bounded_dev_id, 0, 3));
+    });
+}
+
+fn hash_pair(left: H256, right: H256) -> H256 {
+    H256(sp_io::hashing::blake2_256(
+        &[left.as_bytes(), right.as_bytes()].concat(),
+    ))
+}
+
+/// Four-leaf tree: returns the leaves and the root.
+fn merkle_tree() -> (Vec<H256>, H256) {
+    let leaves: Vec<H256> = (0u8..4)
+        .map(|i| H256(sp_io::hashing::blake2_256(&[i])))
+        .collect();
+    let left = hash_pair(leaves[0], leaves[1]);
+    let right = hash_pair(leaves[2], leaves[3]);
+    (leaves, hash_pair(left, right))
+}
+
+fn submit_root(root: H256) {
+    System::set_block_number(1);
+    assert_ok!(TelemetryProofs::submit_proof(
+        RuntimeOrigin::signed(1),
+        device_id("device-001"),
+        root.as_bytes().to_vec(),
+        4,
+        1000,
+        2000,
+        1024,
+        1,
+        ProofCategory::EnergyUsage,
+        nonce(b"device-001"),
+        None,
+        None,
+        None,
+    ));
+}
+
+#[test]
+fn merkle_inclusion_accepts_valid_paths() {
+    new_test_ext().execute_with(|| {
+        let (leaves, root) = merkle_tree();
+        submit_root(root);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        // Leaf 2: sibling leaf 3 on the right, then the left subtree on the left
+        let path = vec![(leaves[3], false), (hash_pair(leaves[0], leaves[1]), true)];
+        assert_eq!(TelemetryProofs::compute_merkle_root(leaves[2], &path), root);
+        assert!(TelemetryProofs::check_merkle_inclusion(
+            &bounded_dev_id,
+            0,
+            leaves[2],
+            &path
+        ));
+
+        assert_ok!(TelemetryProofs::verify_merkle_inclusion(
+            RuntimeOrigin::signed(2),
+            device_id("device-001"),
+            0,
+            leaves[2],
+            path,
+        ));
+        System::assert_last_event(
+            Event::InclusionVerified {
+                device_id: bounded_dev_id,
+                proof_index: 0,
+                leaf_hash: leaves[2],
+                valid: true,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn merkle_inclusion_rejects_tampered_paths() {
+    new_test_ext().execute_with(|| {
+        let (leaves, root) = merkle_tree();
+        submit_root(root);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        let path = vec![(leaves[1], false), (hash_pair(leaves[2], leaves[3]), false)];
+        assert!(TelemetryProofs::check_merkle_inclusion(
+            &bounded_dev_id,
+            0,
+            leaves[0],
+            &path
+        ));
+
+        // Wrong leaf, tampered sibling and flipped direction all fail
+        assert!(!TelemetryProofs::check_merkle_inclusion(
+            &bounded_dev_id,
+            0,
+            leaves[1],
+            &path
+        ));
+        let mut tampered = path.clone();
+        tampered[1].0 = H256::repeat_byte(1);
+        assert!(!TelemetryProofs::check_merkle_inclusion(
+            &bounded_dev_id,
+            0,
+            leaves[0],
+            &tampered
+        ));
+        let mut flipped = path.clone();
+        flipped[0].1 = true;
+        assert!(!TelemetryProofs::check_merkle_inclusion(
+            &bounded_dev_id,
+            0,
+            leaves[0],
+            &flipped
+        ));
+
+        assert_ok!(TelemetryProofs::verify_merkle_inclusion(
+            RuntimeOrigin::signed(2),
+            device_id("device-001"),
+            0,
+            leaves[0],
+            tampered,
+        ));
+        System::assert_last_event(
+            Event::InclusionVerified {
+                device_id: bounded_dev_id,
+                proof_index: 0,
+                leaf_hash: leaves[0],
+                valid: false,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            TelemetryProofs::verify_merkle_inclusion(
+                RuntimeOrigin::signed(2),
+                device_id("device-001"),
+                0,
+                leaves[0],
+                vec![(leaves[1], false); 5],
+            ),
+            Error::<Test>::MerklePathTooLong
+        );
+        assert_noop!(
+            TelemetryProofs::verify_merkle_inclusion(
+                RuntimeOrigin::signed(2),
+                device_id("device-001"),
+                1,
+                leaves[0],
+                path,
+            ),
+            Error::<Test>::ProofNotFound
+        );
+    });
+}
+
+#[test]
+fn proof_lookup_tracks_pruning_and_revocation() {
+    new_test_ext().execute_with(|| {
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        let hash = h256;
+
+        submit_proofs_in_blocks("device-001", 1, 3);
+        assert_eq!(
+            TelemetryProofs::proof_lookup(
+                &bounded_dev_id,
+                sp_io::hashing::blake2_256(&proof_hash("hash2"))
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            TelemetryProofs::find_proof_index(&bounded_dev_id, &hash("hash3")),
+            Some(2)
+        );
+
+        // Pruned proofs drop out of the lookup
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            1,
+            10,
+        ));
+        assert!(TelemetryProofs::proof_lookup(
+            &bounded_dev_id,
+            sp_io::hashing::blake2_256(&proof_hash("hash1"))
+        )
+        .is_none());
+        assert_eq!(
+            TelemetryProofs::find_proof_index(&bounded_dev_id, &hash("hash1")),
+            None
+        );
+
+        // Revoked proofs stay resolvable for audit but are not valid
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            1,
+            RevocationReason::IngestError,
+        ));
+        assert_eq!(
+            TelemetryProofs::find_proof_index(&bounded_dev_id, &hash("hash2")),
+            Some(1)
+        );
+        assert!(!TelemetryProofs::proof_exists(
+            &bounded_dev_id,
+            &hash("hash2")
+        ));
+        assert!(TelemetryProofs::proof_exists(
+            &bounded_dev_id,
+            &hash("hash3")
+        ));
+    });
+}
+
+/// Rewrite every stored proof in the version 0 layout, encoding hashes with
+/// `encode`, and drop the indexes and counters introduced since.
+fn store_proofs_in_v0_layout(encode: impl Fn(&H256) -> Vec<u8>) {
+    use crate::migrations::v0;
+    use frame_support::traits::StorageVersion;
+
+    for (device, index, metadata) in Proofs::<Test>::iter().collect::<Vec<_>>() {
+        v0::ProofsByBlock::<Test>::insert(
+            metadata.submitted_block,
+            &device,
+            encode(&metadata.proof_hash),
+        );
+        v0::Proofs::<Test>::insert(
+            &device,
+            index,
+            v0::ProofMetadataV0::<Test> {
+                proof_hash: encode(&metadata.proof_hash),
+                block_number: metadata.submitted_block,
+                timestamp: metadata.timestamp,
+                record_count: metadata.record_count,
+                window_start: metadata.window_start,
+                window_end: metadata.window_end,
+            },
+        );
+    }
+    let _ = ProofIndexesByBlock::<Test>::clear(u32::MAX, None);
+    let _ = ProofsByWindowEnd::<Test>::clear(u32::MAX, None);
+    let _ = WindowIndexBounds::<Test>::clear(u32::MAX, None);
+    let _ = ProofHashIndex::<Test>::clear(u32::MAX, None);
+    let _ = ProofLookup::<Test>::clear(u32::MAX, None);
+    let _ = ProofIdLookup::<Test>::clear(u32::MAX, None);
+    let _ = CategoryProofCount::<Test>::clear(u32::MAX, None);
+    let _ = TotalRecords::<Test>::clear(u32::MAX, None);
+    let _ = StoredProofCount::<Test>::clear(u32::MAX, None);
+    let _ = SupportedSchemas::<Test>::clear(u32::MAX, None);
+    KnownDeviceCount::<Test>::kill();
+    StorageVersion::new(0).put::<TelemetryProofs>();
+}
+
+/// Step `MigrateV0ToV9` with `per_block` weight until it completes, returning
+/// the number of steps taken.
+fn migrate_from_v0(per_block: Weight) -> u32 {
+    use crate::migrations::v9::MigrateV0ToV9;
+    use frame_support::{migrations::SteppedMigration, weights::WeightMeter};
+
+    let mut cursor = None;
+    let mut steps = 0;
+    loop {
+        cursor =
+            MigrateV0ToV9::<Test>::step(cursor, &mut WeightMeter::with_limit(per_block)).unwrap();
+        steps += 1;
+        if cursor.is_none() {
+            return steps;
+        }
+    }
+}
+
+#[test]
+fn migration_from_v0_runs_across_blocks() {
+    use crate::migrations::{v0, v9::MigrateV0ToV9};
+    use frame_support::traits::GetStorageVersion;
+    use frame_support::{migrations::SteppedMigration, weights::WeightMeter};
+
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 3);
+        submit_proofs_in_blocks("device-002", 4, 2);
+        let dev1: DeviceId = device_id("device-001").try_into().unwrap();
+        let dev2: DeviceId = device_id("device-002").try_into().unwrap();
+        store_proofs_in_v0_layout(|hash| hash.as_bytes().to_vec());
+        assert_eq!(
+            TelemetryProofs::find_proof_index(&dev1, &h256("hash2")),
+            None
+        );
+
+        // Room for the step overhead and two proofs per block
+        let db_weight = <Test as frame_system::Config>::DbWeight::get();
+        let budget = db_weight
+            .reads_writes(2, 2)
+            .saturating_add(MigrateV0ToV9::<Test>::proof_weight().saturating_mul(2));
+        let cursor =
+            MigrateV0ToV9::<Test>::step(None, &mut WeightMeter::with_limit(budget)).unwrap();
+        assert!(cursor.is_some());
+        assert_eq!(ProofIdLookup::<Test>::iter().count(), 2);
+        assert_eq!(TelemetryProofs::on_chain_storage_version(), 0);
+
+        // The remaining three proofs take two more blocks
+        let mut cursor = cursor;
+        for migrated in [4, 5] {
+            cursor =
+                MigrateV0ToV9::<Test>::step(cursor, &mut WeightMeter::with_limit(budget)).unwrap();
+            assert_eq!(ProofIdLookup::<Test>::iter().count(), migrated);
+        }
+        assert!(cursor.is_none());
+        assert_eq!(TelemetryProofs::on_chain_storage_version(), 9);
+        assert_eq!(v0::ProofsByBlock::<Test>::iter().count(), 0);
+
+        let migrated = TelemetryProofs::proofs(&dev1, 1).unwrap();
+        assert_eq!(migrated.proof_hash, h256("hash2"));
+        assert_eq!(migrated.submitted_block, 2);
+        assert_eq!(migrated.submitter, None);
+        assert_eq!(migrated.schema_version, 1);
+        assert_eq!(migrated.category, ProofCategory::EnergyUsage);
+        assert!(migrated.is_valid());
+
+        assert_eq!(
+            TelemetryProofs::proof_indexes_by_block(2, &dev1).into_inner(),
+            vec![1]
+        );
+        assert_eq!(
+            TelemetryProofs::find_proof_index(&dev1, &h256("hash2")),
+            Some(1)
+        );
+        assert_eq!(
+            TelemetryProofs::get_proofs_in_window(&dev1, 0, 10_000, None).len(),
+            3
+        );
+        assert_eq!(TelemetryProofs::stored_proof_count(&dev1), 3);
+        assert_eq!(TelemetryProofs::stored_proof_count(&dev2), 2);
+        assert_eq!(TelemetryProofs::total_records(&dev1), 30);
+        assert_eq!(
+            TelemetryProofs::category_proof_count(&dev2, ProofCategory::EnergyUsage),
+            2
+        );
+        assert_eq!(TelemetryProofs::known_device_count(), 2);
+        assert!(TelemetryProofs::supported_schemas(1).is_some());
+        assert_ok!(TelemetryProofs::do_try_state());
+
+        // Migrated devices keep submitting
+        submit_proofs_in_blocks("device-001", 6, 1);
+        assert_eq!(TelemetryProofs::stored_proof_count(&dev1), 4);
+
+        // Running the migration again changes nothing
+        let root = sp_io::storage::root(sp_runtime::StateVersion::V1);
+        assert_eq!(migrate_from_v0(Weight::MAX), 1);
+        assert_eq!(sp_io::storage::root(sp_runtime::StateVersion::V1), root);
+    });
+}
+
+#[test]
+fn migration_from_v0_converts_raw_old_format_keys() {
+    use crate::migrations::v0;
+    #[cfg(feature = "try-runtime")]
+    use crate::migrations::v9::MigrateV0ToV9;
+    #[cfg(feature = "try-runtime")]
+    use frame_support::migrations::SteppedMigration;
+    use frame_support::{traits::GetStorageVersion, Blake2_128Concat, StorageHasher};
+
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 3);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        // Old submissions mixed hex strings with raw bytes
+        store_proofs_in_v0_layout(|hash| hex_encode(hash.as_bytes()));
+        let mut legacy = v0::Proofs::<Test>::get(&bounded_dev_id, 2).unwrap();
+        legacy.proof_hash = b"legacy".to_vec();
+        v0::Proofs::<Test>::insert(&bounded_dev_id, 2, legacy);
+
+        // Write the old `ProofsByBlock` entries directly, bypassing any typed alias
+        let _ = v0::ProofsByBlock::<Test>::clear(u32::MAX, None);
+        let mut raw_keys = Vec::new();
+        for block in 1..=3u64 {
+            let mut key = sp_io::hashing::twox_128(b"TelemetryProofs").to_vec();
+            key.extend(sp_io::hashing::twox_128(b"ProofsByBlock"));
+            key.extend(Blake2_128Concat::hash(&block.encode()));
+            key.extend(Blake2_128Concat::hash(&bounded_dev_id.encode()));
+            sp_io::storage::set(&key, &proof_hash("old").encode());
+            raw_keys.push(key);
+        }
+        assert_eq!(v0::ProofsByBlock::<Test>::iter().count(), 3);
+
+        #[cfg(feature = "try-runtime")]
+        let state = MigrateV0ToV9::<Test>::pre_upgrade().unwrap();
+        migrate_from_v0(Weight::MAX);
+        #[cfg(feature = "try-runtime")]
+        MigrateV0ToV9::<Test>::post_upgrade(state).unwrap();
+
+        assert_eq!(TelemetryProofs::on_chain_storage_version(), 9);
+        assert!(raw_keys
+            .iter()
+            .all(|key| sp_io::storage::get(key).is_none()));
+        assert_eq!(
+            TelemetryProofs::proofs(&bounded_dev_id, 0).map(|proof| proof.proof_hash),
+            Some(h256("hash1"))
+        );
+
+        // Hashes that were neither encoding are kept addressable by their blake2 hash
+        let legacy_hash = H256(sp_io::hashing::blake2_256(b"legacy"));
+        assert_eq!(
+            TelemetryProofs::proofs(&bounded_dev_id, 2).map(|proof| proof.proof_hash),
+            Some(legacy_hash)
+        );
+        assert_eq!(
+            TelemetryProofs::find_proof_index(&bounded_dev_id, &legacy_hash),
+            Some(2)
+        );
+        let proof_id =
+            TelemetryProofs::proof_id(&bounded_dev_id, h256("hash2").as_bytes(), 2000, 3000);
+        assert_eq!(
+            TelemetryProofs::lookup_proof(proof_id).map(|(_, index, _)| index),
+            Some(1)
+        );
+        assert_ok!(TelemetryProofs::do_try_state());
+    });
+}
+
+#[test]
+fn migration_from_v0_needs_room_for_one_proof() {
+    use crate::migrations::v9::MigrateV0ToV9;
+    use frame_support::{
+        migrations::{SteppedMigration, SteppedMigrationError},
+        weights::WeightMeter,
+    };
+
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 1);
+        store_proofs_in_v0_layout(|hash| hash.as_bytes().to_vec());
+
+        let required = <Test as frame_system::Config>::DbWeight::get()
+            .reads_writes(2, 2)
+            .saturating_add(MigrateV0ToV9::<Test>::proof_weight());
+        assert_eq!(
+            MigrateV0ToV9::<Test>::step(
+                None,
+                &mut WeightMeter::with_limit(required.saturating_sub(Weight::from_parts(1, 0)))
+            ),
+            Err(SteppedMigrationError::InsufficientWeight { required })
+        );
+        assert_eq!(ProofIdLookup::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn migrate_to_v10_moves_challenge_deposits_to_holds() {
+    use crate::migrations::v10::MigrateChallengeDepositsToHolds;
+    use frame_support::{
+        migrations::SteppedMigration,
+        traits::{GetStorageVersion, ReservableCurrency, StorageVersion},
+        weights::WeightMeter,
+    };
+    type Migration = MigrateChallengeDepositsToHolds<Test, Balances>;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        submit_proofs_in_blocks("device-001", 1, 3);
+        let dev: DeviceId = device_id("device-001").try_into().unwrap();
+
+        // Challenges as opened before version 10, with reserved deposits
+        for (index, challenger) in [(0u64, 2u64), (1, 3), (2, 2)] {
+            assert_ok!(Balances::reserve(&challenger, 10));
+            Challenges::<Test>::insert(
+                &dev,
+                index,
+                ProofChallenge::<Test> {
+                    challenger,
+                    deposit: 10,
+                    evidence_hash: H256::repeat_byte(7),
+                    challenged_at: 1,
+                },
+            );
+        }
+        StorageVersion::new(9).put::<TelemetryProofs>();
+
+        // Room for the step overhead and two challenges per block
+        let budget = <Test as frame_system::Config>::DbWeight::get()
+            .reads_writes(1, 1)
+            .saturating_add(Migration::challenge_weight().saturating_mul(2));
+        let mut cursor = Migration::step(None, &mut WeightMeter::with_limit(budget)).unwrap();
+        assert!(cursor.is_some());
+        assert_eq!(TelemetryProofs::on_chain_storage_version(), 9);
+        cursor = Migration::step(cursor, &mut WeightMeter::with_limit(budget)).unwrap();
+        assert!(cursor.is_none());
+        assert_eq!(TelemetryProofs::on_chain_storage_version(), 10);
+
+        assert_eq!(challenge_deposit_held(2), 20);
+        assert_eq!(challenge_deposit_held(3), 10);
+        assert_eq!(Balances::reserved_balance(2), 20);
+
+        // Closing a migrated challenge releases its hold
+        assert_ok!(TelemetryProofs::resolve_challenge(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            1,
+            true,
+        ));
+        assert_eq!(challenge_deposit_held(3), 0);
+        assert_eq!(Balances::free_balance(3), 100);
+
+        // A chain already at version 10 is left unchanged
+        let root = sp_io::storage::root(sp_runtime::StateVersion::V1);
+        assert_eq!(
+            Migration::step(None, &mut WeightMeter::with_limit(budget)),
+            Ok(None)
+        );
+        assert_eq!(sp_io::storage::root(sp_runtime::StateVersion::V1), root);
+    });
+}
+
+#[test]
+fn submitter_stats_track_single_and_batch_submissions() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            None,
+            None,
+        ));
+
+        System::set_block_number(2);
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(2),
+            vec![
+                (
+                    device_id("device-002"),
+                    proof_hash("hash2"),
+                    20,
+                    1000,
+                    2000,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-002")
+                ),
+                (
+                    device_id("device-003"),
+                    proof_hash("hash3"),
+                    30,
+                    1000,
+                    2000,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-003")
+                ),
+                // Invalid window: skipped and not counted
+                (
+                    device_id("device-004"),
+                    proof_hash("hash4"),
+                    40,
+                    2000,
+                    1000,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-004")
+                ),
+            ],
+        ));
+
+        System::set_block_number(3);
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash5"),
+            5,
+            2000,
+            3000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            latest_hash("device-001"),
+            None,
+            None,
+        ));
+
+        assert_eq!(
+            TelemetryProofs::submitter_stats(1),
+            SubmitterInfo {
+                proofs: 2,
+                batches: 0,
+                records: 15,
+                last_block: 3
+            }
+        );
+        assert_eq!(
+            TelemetryProofs::submitter_stats(2),
+            SubmitterInfo {
+                proofs: 2,
+                batches: 1,
+                records: 50,
+                last_block: 2
+            }
+        );
+
+        // The submitting account is kept in state for both paths
+        let submitter_of = |id: &str, index: u64| {
+            let bounded_dev_id: DeviceId = device_id(id).try_into().unwrap();
+            TelemetryProofs::proofs(&bounded_dev_id, index)
+                .unwrap()
+                .submitter
+        };
+        assert_eq!(submitter_of("device-001", 0), Some(1));
+        assert_eq!(submitter_of("device-001", 1), Some(1));
+        assert_eq!(submitter_of("device-002", 0), Some(2));
+        assert_eq!(submitter_of("device-003", 0), Some(2));
+    });
+}
+
+#[test]
+fn batch_reports_each_rejected_entry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MaxProofsPerDevice::set(1);
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-full"),
+            proof_hash("hash0"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-full"),
+            None,
+            None,
+            None,
+        ));
+        System::reset_events();
+
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            vec![
+                (
+                    device_id("device-001"),
+                    proof_hash("hash1"),
+                    10,
+                    1000,
+                    2000,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-001")
+                ),
+                (
+                    device_id("device-002"),
+                    proof_hash("hash2"),
+                    10,
+                    2000,
+                    1000,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-002")
+                ),
+                (
+                    vec![b'a'; 100],
+                    proof_hash("hash3"),
+                    10,
+                    1000,
+                    2000,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    0
+                ),
+                (
+                    device_id("device-003"),
+                    vec![b'b'; 200],
+                    10,
+                    1000,
+                    2000,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-003")
+                ),
+                (
+                    device_id("device-001"),
+                    proof_hash("hash1"),
+                    10,
+                    1000,
+                    2000,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-001") + 1
+                ),
+                (
+                    device_id("device-full"),
+                    proof_hash("hash4"),
+                    10,
+                    1000,
+                    2000,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-full")
+                ),
+            ],
+        ));
+
+        let batch_events: Vec<_> = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                RuntimeEvent::TelemetryProofs(
+                    event @ (Event::BatchEntryRejected { .. } | Event::BatchProcessed { .. }),
+                ) => Some(event),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            batch_events,
+            vec![
+                Event::BatchEntryRejected {
+                    index: 1,
+                    reason: BatchRejectReason::InvalidWindow
+                },
+                Event::BatchEntryRejected {
+                    index: 2,
+                    reason: BatchRejectReason::DeviceIdTooLong
+                },
+                Event::BatchEntryRejected {
+                    index: 3,
+                    reason: BatchRejectReason::InvalidProofHash
+                },
+                Event::BatchEntryRejected {
+                    index: 4,
+                    reason: BatchRejectReason::Duplicate
+                },
+                Event::BatchEntryRejected {
+                    index: 5,
+                    reason: BatchRejectReason::TooManyProofs
+                },
+                Event::BatchProcessed {
+                    submitted: 6,
+                    accepted: 1,
+                    rejected: 5
+                },
+            ]
+        );
+    });
+}
+
+fn proof_deposit_held(who: u64) -> u64 {
+    <Balances as fungible::InspectHold<u64>>::balance_on_hold(
+        &HoldReason::ProofDeposit.into(),
+        &who,
+    )
+}
+
+#[test]
+fn submit_proof_holds_deposit_from_submitter() {
+    new_test_ext().execute_with(|| {
+        ProofDeposit::set(5);
+
+        submit_proofs_in_blocks("device-001", 1, 2);
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(proof_deposit_held(1), 10);
+        assert_eq!(Balances::free_balance(1), 90);
+        assert_eq!(
+            TelemetryProofs::proof_deposits(&bounded_dev_id, 1),
+            Some((1, 5))
+        );
+    });
+}
+
+#[test]
+fn submit_proof_fails_without_funds_for_deposit() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        ProofDeposit::set(5);
+
+        assert!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(4),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            None,
+            None,
+        )
+        .is_err());
+        assert_eq!(TelemetryProofs::total_proofs(), 0);
+    });
+}
+
+#[test]
+fn trusted_submitters_are_exempt_from_proof_deposit() {
+    new_test_ext_with_submitters(vec![2], true).execute_with(|| {
+        System::set_block_number(1);
+        ProofDeposit::set(5);
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(2),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            None,
+            None,
+        ));
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(proof_deposit_held(2), 0);
+        assert_eq!(TelemetryProofs::proof_deposits(&bounded_dev_id, 0), None);
+    });
+}
+
+#[test]
+fn proof_deposit_released_on_prune_and_revoke() {
+    new_test_ext().execute_with(|| {
+        ProofDeposit::set(5);
+
+        submit_proofs_in_blocks("device-001", 1, 3);
+        assert_eq!(proof_deposit_held(1), 15);
+
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            2,
+            10,
+        ));
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(proof_deposit_held(1), 5);
+        assert_eq!(TelemetryProofs::proof_deposits(&bounded_dev_id, 0), None);
+
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            2,
+            RevocationReason::IngestError,
+        ));
+        assert_eq!(proof_deposit_held(1), 0);
+        assert_eq!(Balances::free_balance(1), 100);
+    });
+}
+
+#[test]
+fn proof_metadata_records_size_and_schema() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TelemetryProofs::add_supported_schema(
+            RuntimeOrigin::root(),
+            2
+        ));
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash1"),
+            10,
+            1000,
+            2000,
+            4096,
+            2,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            None,
+            None,
+            None,
+        ));
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        let metadata = TelemetryProofs::proofs(&bounded_dev_id, 0).unwrap();
+        assert_eq!(metadata.data_size_bytes, 4096);
+        assert_eq!(metadata.schema_version, 2);
+    });
+}
+
+#[test]
+fn unsupported_schema_is_rejected() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash1"),
+                10,
+                1000,
+                2000,
+                1024,
+                7,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                None,
+                None,
+                None,
+            ),
+            Error::<Test>::UnsupportedSchema
+        );
+
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            vec![(
+                device_id("device-001"),
+                proof_hash("hash1"),
+                10,
+                1000,
+                2000,
+                1024,
+                7,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001")
+            )],
+        ));
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 0,
+                reason: BatchRejectReason::UnsupportedSchema,
+            }
+            .into(),
+        );
+
+        // Removing a version stops new submissions using it
+        assert_ok!(TelemetryProofs::remove_supported_schema(
+            RuntimeOrigin::root(),
+            1
+        ));
+        System::assert_last_event(Event::SchemaRemoved { version: 1 }.into());
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash1"),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                None,
+                None,
+                None,
+            ),
+            Error::<Test>::UnsupportedSchema
+        );
+        assert_eq!(TelemetryProofs::total_proofs(), 0);
+    });
+}
+
+#[test]
+fn supported_schema_management_requires_admin() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert!(TelemetryProofs::supported_schemas(1).is_some());
+
+        assert_noop!(
+            TelemetryProofs::add_supported_schema(RuntimeOrigin::signed(1), 2),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            TelemetryProofs::add_supported_schema(RuntimeOrigin::root(), 1),
+            Error::<Test>::SchemaAlreadySupported
+        );
+        assert_noop!(
+            TelemetryProofs::remove_supported_schema(RuntimeOrigin::root(), 2),
+            Error::<Test>::SchemaNotSupported
+        );
+
+        assert_ok!(TelemetryProofs::add_supported_schema(
+            RuntimeOrigin::root(),
+            2
+        ));
+        System::assert_last_event(Event::SchemaAdded { version: 2 }.into());
+        assert!(TelemetryProofs::supported_schemas(2).is_some());
+    });
+}
+
+#[test]
+fn get_proofs_paged_walks_all_proofs() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 25);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        let (page, next) = TelemetryProofs::get_proofs_paged(&bounded_dev_id, 0, 10);
+        assert_eq!(page.len(), 10);
+        assert_eq!(page[0].submitted_block, 1);
+        assert_eq!(page[9].submitted_block, 10);
+        assert_eq!(next, Some(10));
+
+        let (page, next) = TelemetryProofs::get_proofs_paged(&bounded_dev_id, 10, 10);
+        assert_eq!(page.len(), 10);
+        assert_eq!(page[0].submitted_block, 11);
+        assert_eq!(next, Some(20));
+
+        let (page, next) = TelemetryProofs::get_proofs_paged(&bounded_dev_id, 20, 10);
+        assert_eq!(page.len(), 5);
+        assert_eq!(page[4].submitted_block, 25);
+        assert_eq!(next, None);
+
+        // Requests above MaxPageSize are capped
+        let (page, next) = TelemetryProofs::get_proofs_paged(&bounded_dev_id, 0, 50);
+        assert_eq!(page.len(), 10);
+        assert_eq!(next, Some(10));
+
+        let (page, next) = TelemetryProofs::get_proofs_paged(&bounded_dev_id, 25, 10);
+        assert!(page.is_empty());
+        assert_eq!(next, None);
+    });
+}
+
+#[test]
+fn get_recent_proofs_returns_newest_first() {
+    new_test_ext().execute_with(|| {
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert!(TelemetryProofs::get_recent_proofs(&bounded_dev_id, 5).is_empty());
+
+        // Fewer proofs stored than requested
+        submit_proofs_in_blocks("device-001", 1, 3);
+        let blocks = |proofs: Vec<ProofMetadata<Test>>| {
+            proofs.iter().map(|m| m.submitted_block).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            blocks(TelemetryProofs::get_recent_proofs(&bounded_dev_id, 5)),
+            vec![3, 2, 1]
+        );
+
+        // Revoked proofs in the middle are skipped, not counted
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            1,
+            RevocationReason::CorruptedData,
+        ));
+        assert_eq!(
+            blocks(TelemetryProofs::get_recent_proofs(&bounded_dev_id, 2)),
+            vec![3, 1]
+        );
+
+        // Requests above MaxPageSize are capped
+        submit_proofs_in_blocks("device-001", 4, 20);
+        let recent = blocks(TelemetryProofs::get_recent_proofs(&bounded_dev_id, 50));
+        assert_eq!(recent, (14..=23).rev().collect::<Vec<_>>());
+    });
+}
+
+fn offchain_index_entry(key: &[u8]) -> Option<Vec<u8>> {
+    sp_io::offchain::local_storage_get(sp_core::offchain::StorageKind::PERSISTENT, key)
+}
+
+#[test]
+fn accepted_proofs_are_written_to_offchain_index() {
+    use sp_core::offchain::{testing::TestOffchainExt, OffchainDbExt, OffchainWorkerExt};
+
+    let mut ext = new_test_ext();
+    let (offchain, _state) = TestOffchainExt::with_offchain_db(ext.offchain_db());
+    ext.register_extension(OffchainDbExt::new(offchain.clone()));
+    ext.register_extension(OffchainWorkerExt::new(offchain));
+
+    ext.execute_with(|| {
+        OffchainIndexing::set(true);
+        submit_proofs_in_blocks("device-001", 3, 2);
+
+        // Disabled indexing skips the host call
+        OffchainIndexing::set(false);
+        submit_proofs_in_blocks("device-002", 5, 1);
+    });
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        let key = TelemetryProofs::offchain_proof_key(&proof_hash("hash4"));
+        assert!(key.starts_with(OFFCHAIN_PROOF_PREFIX));
+        assert_eq!(
+            key[OFFCHAIN_PROOF_PREFIX.len()..],
+            sp_io::hashing::blake2_256(&proof_hash("hash4"))
+        );
+
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(
+            offchain_index_entry(&key),
+            Some((bounded_dev_id, 4u64, 1u64).encode())
+        );
+        assert_eq!(
+            offchain_index_entry(&TelemetryProofs::offchain_proof_key(&proof_hash("hash5"))),
+            None
+        );
+    });
+}
+
+fn submit_to_devices(devices: &[&str], first_block: u64, n: u64) {
+    for i in 0..n {
+        let block = first_block + i;
+        System::set_block_number(block);
+        for device in devices {
+            assert_ok!(TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id(device),
+                proof_hash(&format!("{}-hash{}", device, block)),
+                10,
+                block * 1000,
+                (block + 1) * 1000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(device.as_bytes()),
+                latest_hash(device),
+                None,
+                None,
+            ));
+        }
+    }
+}
+
+#[test]
+fn set_retention_blocks_requires_admin() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            TelemetryProofs::set_retention_blocks(RuntimeOrigin::signed(1), Some(5)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(TelemetryProofs::set_retention_blocks(
+            RuntimeOrigin::root(),
+            Some(5)
+        ));
+        assert_eq!(TelemetryProofs::retention_blocks(), Some(5));
+        System::assert_last_event(Event::RetentionBlocksSet { retention: Some(5) }.into());
+
+        assert_ok!(TelemetryProofs::set_retention_blocks(
+            RuntimeOrigin::root(),
+            None
+        ));
+        assert_eq!(TelemetryProofs::retention_blocks(), None);
+    });
+}
+
+#[test]
+fn on_idle_keeps_proofs_without_retention() {
+    new_test_ext().execute_with(|| {
+        submit_to_devices(&["device-001"], 1, 3);
+        System::set_block_number(100);
+
+        TelemetryProofs::on_idle(100, Weight::MAX);
+        assert_eq!(TelemetryProofs::total_proofs(), 3);
+    });
+}
+
+#[test]
+fn on_idle_prunes_expired_proofs_incrementally() {
+    new_test_ext().execute_with(|| {
+        let devices = ["device-001", "device-002", "device-003"];
+        submit_to_devices(&devices, 1, 4);
+        assert_eq!(TelemetryProofs::total_proofs(), 12);
+
+        // Proofs from blocks 1 and 2 are older than 8 - 5
+        assert_ok!(TelemetryProofs::set_retention_blocks(
+            RuntimeOrigin::root(),
+            Some(5)
+        ));
+        System::set_block_number(8);
+
+        // Budget for the sweep overhead and one device with two expired proofs
+        let db_weight = <Test as frame_system::Config>::DbWeight::get();
+        let budget = db_weight
+            .reads_writes(2, 1)
+            .saturating_add(db_weight.reads(3))
+            .saturating_add(<() as WeightInfo>::prune_proofs_before(1).saturating_mul(2));
+
+        for expected in [10, 8, 6] {
+            TelemetryProofs::on_idle(8, budget);
+            assert_eq!(TelemetryProofs::total_proofs(), expected);
+            assert!(RetentionCursor::<Test>::get().is_some());
+        }
+
+        // The next sweep finds nothing left and clears the cursor
+        TelemetryProofs::on_idle(8, budget);
+        assert_eq!(TelemetryProofs::total_proofs(), 6);
+        assert!(RetentionCursor::<Test>::get().is_none());
+
+        for device in devices {
+            let bounded_dev_id: DeviceId = device_id(device).try_into().unwrap();
+            assert_eq!(TelemetryProofs::stored_proof_count(&bounded_dev_id), 2);
+            assert_eq!(TelemetryProofs::oldest_proof_index(&bounded_dev_id), 2);
+            assert_eq!(TelemetryProofs::get_proof(&bounded_dev_id, 1), None);
+        }
+    });
+}
+
+#[test]
+fn on_idle_resumes_within_a_device() {
+    new_test_ext().execute_with(|| {
+        submit_to_devices(&["device-001"], 1, 4);
+        assert_ok!(TelemetryProofs::set_retention_blocks(
+            RuntimeOrigin::root(),
+            Some(1)
+        ));
+        System::set_block_number(10);
+
+        // Room for a single proof removal per block
+        let db_weight = <Test as frame_system::Config>::DbWeight::get();
+        let budget = db_weight
+            .reads_writes(2, 1)
+            .saturating_add(db_weight.reads(3))
+            .saturating_add(<() as WeightInfo>::prune_proofs_before(1));
+
+        for expected in [3, 2, 1, 0] {
+            TelemetryProofs::on_idle(10, budget);
+            assert_eq!(TelemetryProofs::total_proofs(), expected);
+        }
+        System::assert_last_event(
+            Event::ProofsPruned {
+                device_id: device_id("device-001").try_into().unwrap(),
+                removed: 1,
+            }
+            .into(),
+        );
+    });
+}
+
+fn anchor_ref(chain_id: u32) -> AnchorRef {
+    AnchorRef {
+        chain_id,
+        tx_hash: H256::repeat_byte(0xab),
+        anchored_at: 1_700_000_000,
+    }
+}
+
+#[test]
+fn set_anchor_records_anchor_once() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 1);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        assert_noop!(
+            TelemetryProofs::set_anchor(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                0,
+                anchor_ref(1)
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(TelemetryProofs::set_anchor(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            anchor_ref(1)
+        ));
+        System::assert_last_event(
+            Event::ProofAnchored {
+                device_id: bounded_dev_id.clone(),
+                proof_index: 0,
+                anchor: anchor_ref(1),
+            }
+            .into(),
+        );
+        assert_eq!(
+            TelemetryProofs::proof_anchor(&bounded_dev_id, 0),
+            Some(anchor_ref(1))
+        );
+
+        assert_noop!(
+            TelemetryProofs::set_anchor(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                0,
+                anchor_ref(2)
+            ),
+            Error::<Test>::ProofAlreadyAnchored
+        );
+    });
+}
+
+#[test]
+fn set_anchor_rejects_missing_and_revoked_proofs() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 1);
+
+        assert_noop!(
+            TelemetryProofs::set_anchor(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                5,
+                anchor_ref(1)
+            ),
+            Error::<Test>::ProofNotFound
+        );
+
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            RevocationReason::CorruptedData,
+        ));
+        assert_noop!(
+            TelemetryProofs::set_anchor(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                0,
+                anchor_ref(1)
+            ),
+            Error::<Test>::ProofAlreadyRevoked
+        );
+    });
+}
+
+#[test]
+fn set_proof_quality_keeps_a_running_average() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 2);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(
+            TelemetryProofs::device_quality(&bounded_dev_id).average(),
+            None
+        );
+
+        assert_ok!(TelemetryProofs::set_proof_quality(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            80,
+        ));
+        System::assert_last_event(
+            Event::ProofQualityScored {
+                device_id: bounded_dev_id.clone(),
+                proof_index: 0,
+                score: 80,
+            }
+            .into(),
+        );
+        assert_ok!(TelemetryProofs::set_proof_quality(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            1,
+            61,
+        ));
+        assert_eq!(
+            TelemetryProofs::device_quality(&bounded_dev_id),
+            QualitySummary {
+                total: 141,
+                scored: 2
+            }
+        );
+        assert_eq!(
+            TelemetryProofs::device_quality(&bounded_dev_id).average(),
+            Some(70)
+        );
+
+        // A new score replaces the old one rather than adding a sample
+        assert_ok!(TelemetryProofs::set_proof_quality(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            20,
+        ));
+        assert_eq!(
+            TelemetryProofs::proofs(&bounded_dev_id, 0)
+                .unwrap()
+                .quality_score,
+            Some(20)
+        );
+        assert_eq!(
+            TelemetryProofs::device_quality(&bounded_dev_id),
+            QualitySummary {
+                total: 81,
+                scored: 2
+            }
+        );
+        assert_eq!(
+            TelemetryProofs::device_quality(&bounded_dev_id).average(),
+            Some(40)
+        );
+    });
+}
+
+#[test]
+fn set_proof_quality_rejects_missing_and_revoked_proofs() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 1);
+
+        assert_noop!(
+            TelemetryProofs::set_proof_quality(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                0,
+                50
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            TelemetryProofs::set_proof_quality(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                5,
+                50
+            ),
+            Error::<Test>::ProofNotFound
+        );
+
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            RevocationReason::CorruptedData,
+        ));
+        assert_noop!(
+            TelemetryProofs::set_proof_quality(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                0,
+                50
+            ),
+            Error::<Test>::ProofAlreadyRevoked
+        );
+    });
+}
+
+fn submit_with_cid(device: &str, hash: &str, content_cid: Option<Vec<u8>>) -> DispatchResult {
+    TelemetryProofs::submit_proof(
+        RuntimeOrigin::signed(1),
+        device_id(device),
+        proof_hash(hash),
+        10,
+        1000,
+        2000,
+        1024,
+        1,
+        ProofCategory::EnergyUsage,
+        nonce(device.as_bytes()),
+        latest_hash(device),
+        None,
+        content_cid,
+    )
+}
+
+#[test]
+fn submit_proof_stores_optional_content_cid() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let cid = b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec();
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        assert_ok!(submit_with_cid("device-001", "hash1", Some(cid.clone())));
+        let stored = TelemetryProofs::proofs(&bounded_dev_id, 0).unwrap();
+        assert_eq!(
+            stored.content_cid.map(|c| c.into_inner()),
+            Some(cid.clone())
+        );
+        assert!(System::events().iter().any(|record| matches!(
+            &record.event,
+            RuntimeEvent::TelemetryProofs(Event::ProofSubmitted { content_cid: Some(c), .. })
+                if c[..] == cid[..]
+        )));
+
+        assert_ok!(submit_with_cid("device-001", "hash2", None));
+        assert_eq!(
+            TelemetryProofs::proofs(&bounded_dev_id, 1)
+                .unwrap()
+                .content_cid,
+            None
+        );
+
+        // Only the length is validated
+        assert_noop!(
+            submit_with_cid("device-001", "hash3", Some(vec![b'x'; 65])),
+            Error::<Test>::CidTooLong
+        );
+    });
+}
+
+#[test]
+fn set_content_cid_attaches_once_by_submitter() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let cid = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec();
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_ok!(submit_with_cid("device-001", "hash1", None));
+        assert_ok!(submit_with_cid("device-001", "hash2", Some(cid.clone())));
+
+        assert_noop!(
+            TelemetryProofs::set_content_cid(
+                RuntimeOrigin::signed(2),
+                device_id("device-001"),
+                0,
+                cid.clone()
+            ),
+            Error::<Test>::NotProofSubmitter
+        );
+        assert_noop!(
+            TelemetryProofs::set_content_cid(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                5,
+                cid.clone()
+            ),
+            Error::<Test>::ProofNotFound
+        );
+        assert_noop!(
+            TelemetryProofs::set_content_cid(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                0,
+                vec![b'x'; 65]
+            ),
+            Error::<Test>::CidTooLong
+        );
+
+        // Late attachment
+        assert_ok!(TelemetryProofs::set_content_cid(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            0,
+            cid.clone()
+        ));
+        System::assert_last_event(
+            Event::ContentCidSet {
+                device_id: bounded_dev_id.clone(),
+                proof_index: 0,
+                cid: cid.clone().try_into().unwrap(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            TelemetryProofs::proofs(&bounded_dev_id, 0)
+                .unwrap()
+                .content_cid
+                .map(|c| c.into_inner()),
+            Some(cid.clone())
+        );
+
+        // Neither a late nor a submitted CID can be replaced
+        for index in 0..2 {
+            assert_noop!(
+                TelemetryProofs::set_content_cid(
+                    RuntimeOrigin::signed(1),
+                    device_id("device-001"),
+                    index,
+                    b"bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku".to_vec()
+                ),
+                Error::<Test>::ContentCidAlreadySet
+            );
+        }
+    });
+}
+
+#[test]
+fn pruning_a_proof_removes_its_anchor() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 2);
+        assert_ok!(TelemetryProofs::set_anchor(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            anchor_ref(1)
+        ));
+
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            1,
+            10,
+        ));
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(TelemetryProofs::proof_anchor(&bounded_dev_id, 0), None);
+    });
+}
+
+#[test]
+fn on_finalize_deposits_block_proof_root() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let devices = ["device-001", "device-002", "device-003"];
+        for device in devices {
+            assert_ok!(TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id(device),
+                proof_hash(&format!("{}-hash", device)),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(device.as_bytes()),
+                None,
+                None,
+                None,
+            ));
+        }
+
+        let leaves: Vec<H256> = devices
+            .iter()
+            .map(|device| {
+                let bounded_dev_id: DeviceId = device_id(device).try_into().unwrap();
+                let bounded_hash = h256(&format!("{}-hash", device));
+                H256(sp_io::hashing::blake2_256(
+                    &(bounded_dev_id, 0u64, bounded_hash).encode(),
+                ))
+            })
+            .collect();
+        let expected_root = hash_pair(hash_pair(leaves[0], leaves[1]), leaves[2]);
+
+        let path = TelemetryProofs::block_proof_inclusion_path(2).unwrap();
+        assert_eq!(path, vec![(hash_pair(leaves[0], leaves[1]), true)]);
+        assert_eq!(
+            TelemetryProofs::compute_merkle_root(leaves[2], &path),
+            expected_root
+        );
+        assert_eq!(TelemetryProofs::block_proof_inclusion_path(3), None);
+
+        TelemetryProofs::on_finalize(1);
+
+        assert_eq!(TelemetryProofs::block_proof_root(1), Some(expected_root));
+        assert!(System::digest()
+            .logs
+            .contains(&sp_runtime::DigestItem::Consensus(
+                PROOF_ROOT_ENGINE_ID,
+                expected_root.encode(),
+            )));
+        assert!(BlockProofLeaves::<Test>::get().is_empty());
+    });
+}
+
+#[test]
+fn block_proof_roots_expire_after_retention() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 1);
+        TelemetryProofs::on_finalize(1);
+        assert!(TelemetryProofs::block_proof_root(1).is_some());
+
+        // Blocks without proofs leave no root or digest log
+        System::set_block_number(2);
+        TelemetryProofs::on_finalize(2);
+        assert_eq!(TelemetryProofs::block_proof_root(2), None);
+        assert!(TelemetryProofs::block_proof_root(1).is_some());
+
+        System::set_block_number(3);
+        TelemetryProofs::on_finalize(3);
+        assert_eq!(TelemetryProofs::block_proof_root(1), None);
+    });
+}
+
+#[test]
+fn on_proof_submitted_is_called_once_per_accepted_proof() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let device = |id: &str| -> DeviceId { device_id(id).try_into().unwrap() };
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-single"),
+            proof_hash("single"),
+            7,
+            1000,
+            2000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-single"),
+            None,
+            None,
+            None,
+        ));
+        assert_eq!(
+            SubmittedProofs::take(),
+            vec![(device("device-single"), 0, 7, 1000, 2000, 1)]
+        );
+
+        // Rejected submissions are not reported
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-single"),
+                proof_hash("single"),
+                7,
+                2000,
+                3000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-single"),
+                None,
+                None,
+                None,
+            ),
+            Error::<Test>::DuplicateProofHash
+        );
+        assert!(SubmittedProofs::get().is_empty());
+
+        // One call per accepted batch entry, none for the rejected one
+        let mut batch = batch_of(3);
+        batch[1].2 = 0;
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            batch
+        ));
+        assert_eq!(
+            SubmittedProofs::take(),
+            vec![
+                (device("device-000"), 0, 10, 1000, 2000, 1),
+                (device("device-002"), 0, 10, 1000, 2000, 1),
+            ]
+        );
+    });
+}
+
+fn batch_of(n: u32) -> Vec<BatchProofEntry> {
+    (0..n)
+        .map(|i| {
+            (
+                device_id(&format!("device-{:03}", i)),
+                proof_hash(&format!("hash{}", i)),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(format!("device-{:03}", i).as_bytes()),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn submit_batch_proofs_accepts_max_batch_size() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let max = <Test as Config>::MaxBatchSize::get();
+
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            batch_of(max)
+        ));
+        assert_eq!(TelemetryProofs::total_proofs(), max as u64);
+    });
+}
+
+#[test]
+fn submit_batch_proofs_rejects_oversized_batch_without_writes() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let max = <Test as Config>::MaxBatchSize::get();
+
+        assert_noop!(
+            TelemetryProofs::submit_batch_proofs(RuntimeOrigin::signed(1), batch_of(max + 1)),
+            Error::<Test>::BatchTooLarge
+        );
+        assert_eq!(TelemetryProofs::total_proofs(), 0);
+        assert_eq!(
+            TelemetryProofs::submitter_stats(1),
+            SubmitterInfo::default()
+        );
+    });
+}
+
+fn submit_window(window_start: u64, window_end: u64) -> DispatchResult {
+    TelemetryProofs::submit_proof(
+        RuntimeOrigin::signed(1),
+        device_id("device-001"),
+        proof_hash(&format!("hash{}", window_end)),
+        10,
+        window_start,
+        window_end,
+        1024,
+        1,
+        ProofCategory::EnergyUsage,
+        nonce(b"device-001"),
+        latest_hash("device-001"),
+        None,
+        None,
+    )
+}
+
+#[test]
+fn window_in_future_is_rejected() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(submit_window(NOW, NOW + 601), Error::<Test>::WindowInFuture);
+        assert_ok!(submit_window(NOW, NOW + 600));
+
+        // Time moving on brings the window within the drift
+        System::set_block_number(2);
+        Timestamp::set_timestamp((NOW + 1) * 1000);
+        assert_ok!(submit_window(NOW, NOW + 601));
+    });
+}
+
+#[test]
+fn window_too_old_is_rejected_when_age_is_limited() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(submit_window(1, 2));
+
+        MaxWindowAge::set(Some(3600));
+        System::set_block_number(2);
+        assert_noop!(submit_window(NOW - 3601, NOW), Error::<Test>::WindowTooOld);
+        assert_ok!(submit_window(NOW - 3600, NOW));
+
+        System::set_block_number(3);
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            vec![
+                (
+                    device_id("device-002"),
+                    proof_hash("old"),
+                    10,
+                    1,
+                    2,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-002")
+                ),
+                (
+                    device_id("device-003"),
+                    proof_hash("future"),
+                    10,
+                    NOW,
+                    NOW + 700,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-003")
+                ),
+            ],
+        ));
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 0,
+                reason: BatchRejectReason::WindowTooOld,
+            }
+            .into(),
+        );
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 1,
+                reason: BatchRejectReason::WindowInFuture,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn backfill_mode_skips_window_time_checks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MaxWindowAge::set(Some(3600));
+
+        assert_noop!(
+            TelemetryProofs::set_backfill_mode(RuntimeOrigin::signed(1), true),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(TelemetryProofs::set_backfill_mode(
+            RuntimeOrigin::root(),
+            true
+        ));
+        System::assert_last_event(Event::BackfillModeSet { enabled: true }.into());
+
+        assert_ok!(submit_window(1, 2));
+        System::set_block_number(2);
+        assert_ok!(submit_window(NOW, NOW + 10_000));
+
+        assert_ok!(TelemetryProofs::set_backfill_mode(
+            RuntimeOrigin::root(),
+            false
+        ));
+        System::set_block_number(3);
+        assert_noop!(submit_window(1, 3), Error::<Test>::WindowTooOld);
+    });
+}
+
+#[test]
+fn frozen_device_cannot_submit_proofs() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 1);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        assert_noop!(
+            TelemetryProofs::freeze_device(RuntimeOrigin::signed(1), device_id("device-001")),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(TelemetryProofs::freeze_device(
+            RuntimeOrigin::root(),
+            device_id("device-001")
+        ));
+        System::assert_last_event(
+            Event::DeviceFrozen {
+                device_id: bounded_dev_id.clone(),
+            }
+            .into(),
+        );
+        assert_noop!(
+            TelemetryProofs::freeze_device(RuntimeOrigin::root(), device_id("device-001")),
+            Error::<Test>::DeviceAlreadyFrozen
+        );
+
+        System::set_block_number(2);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash2"),
+                10,
+                2000,
+                3000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                None,
+                None,
+            ),
+            Error::<Test>::DeviceFrozen
+        );
+
+        // Existing proofs remain queryable
+        let hash1 = h256("hash1");
+        assert!(TelemetryProofs::proof_exists(&bounded_dev_id, &hash1));
+
+        assert_ok!(TelemetryProofs::unfreeze_device(
+            RuntimeOrigin::root(),
+            device_id("device-001")
+        ));
+        System::assert_last_event(
+            Event::DeviceUnfrozen {
+                device_id: bounded_dev_id.clone(),
+            }
+            .into(),
+        );
+        assert_noop!(
+            TelemetryProofs::unfreeze_device(RuntimeOrigin::root(), device_id("device-001")),
+            Error::<Test>::DeviceNotFrozen
+        );
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(1),
+            device_id("device-001"),
+            proof_hash("hash2"),
+            10,
+            2000,
+            3000,
+            1024,
+            1,
+            ProofCategory::EnergyUsage,
+            nonce(b"device-001"),
+            latest_hash("device-001"),
+            None,
+            None,
+        ));
+    });
+}
+
+#[test]
+fn frozen_device_batch_entries_are_rejected() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TelemetryProofs::freeze_device(
+            RuntimeOrigin::root(),
+            device_id("device-001")
+        ));
+
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            batch_of(3),
+        ));
+
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 1,
+                reason: BatchRejectReason::DeviceFrozen,
+            }
+            .into(),
+        );
+        System::assert_last_event(
+            Event::BatchProcessed {
+                submitted: 3,
+                accepted: 2,
+                rejected: 1,
+            }
+            .into(),
+        );
+        let frozen: DeviceId = device_id("device-001").try_into().unwrap();
+        assert_eq!(TelemetryProofs::proof_count(&frozen), 0);
+        assert_eq!(TelemetryProofs::total_proofs(), 2);
+    });
+}
+
+#[test]
+fn max_total_devices_caps_new_devices_only() {
+    new_test_ext().execute_with(|| {
+        let submit_new = |device: &str| {
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id(device),
+                proof_hash(&format!("{}-first", device)),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                0,
+                None,
+                None,
+                None,
+            )
+        };
+        assert_noop!(
+            TelemetryProofs::set_max_total_devices(RuntimeOrigin::signed(1), Some(2)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_eq!(TelemetryProofs::max_total_devices(), None);
+
+        assert_ok!(TelemetryProofs::set_max_total_devices(
+            RuntimeOrigin::root(),
+            Some(2)
+        ));
+        System::assert_last_event(Event::MaxTotalDevicesSet { max: Some(2) }.into());
+        submit_to_devices(&["device-001", "device-002"], 1, 1);
+        assert_eq!(TelemetryProofs::known_device_count(), 2);
+
+        assert_noop!(submit_new("device-003"), Error::<Test>::DeviceLimitReached);
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            batch_of(4),
+        ));
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 3,
+                reason: BatchRejectReason::DeviceLimitReached,
+            }
+            .into(),
+        );
+
+        // Known devices keep submitting at the cap
+        submit_to_devices(&["device-001", "device-002"], 2, 1);
+        assert_eq!(TelemetryProofs::known_device_count(), 2);
+
+        assert_ok!(TelemetryProofs::set_max_total_devices(
+            RuntimeOrigin::root(),
+            Some(3)
+        ));
+        assert_ok!(submit_new("device-003"));
+        assert_eq!(TelemetryProofs::known_device_count(), 3);
+        assert_noop!(submit_new("device-004"), Error::<Test>::DeviceLimitReached);
+
+        assert_ok!(TelemetryProofs::set_max_total_devices(
+            RuntimeOrigin::root(),
+            None
+        ));
+        assert_ok!(submit_new("device-004"));
+        assert_eq!(TelemetryProofs::known_device_count(), 4);
+    });
+}
+
+fn submit_at_block(who: u64, device: &str, block: u64) -> DispatchResult {
+    System::set_block_number(block);
+    TelemetryProofs::submit_proof(
+        RuntimeOrigin::signed(who),
+        device_id(device),
+        proof_hash(&format!("{}-hash{}", device, block)),
+        10,
+        block * 1000,
+        (block + 1) * 1000,
+        1024,
+        1,
+        ProofCategory::EnergyUsage,
+        nonce(device.as_bytes()),
+        latest_hash(device),
+        None,
+        None,
+    )
+}
+
+#[test]
+fn min_proof_interval_spaces_out_device_proofs() {
+    new_test_ext().execute_with(|| {
+        MinProofInterval::set(10);
+        assert_ok!(submit_at_block(1, "device-001", 1));
+
+        assert_noop!(
+            submit_at_block(1, "device-001", 10),
+            Error::<Test>::ProofTooFrequent
+        );
+        // Other devices are unaffected
+        assert_ok!(submit_at_block(1, "device-002", 10));
+        assert_ok!(submit_at_block(1, "device-001", 11));
+
+        // The storage override replaces the configured interval
+        assert_noop!(
+            TelemetryProofs::set_min_proof_interval(RuntimeOrigin::signed(1), Some(2)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(TelemetryProofs::set_min_proof_interval(
+            RuntimeOrigin::root(),
+            Some(2)
+        ));
+        System::assert_last_event(Event::MinProofIntervalSet { interval: Some(2) }.into());
+        assert_eq!(TelemetryProofs::min_proof_interval(), 2);
+        assert_noop!(
+            submit_at_block(1, "device-001", 12),
+            Error::<Test>::ProofTooFrequent
+        );
+        assert_ok!(submit_at_block(1, "device-001", 13));
+
+        assert_ok!(TelemetryProofs::set_min_proof_interval(
+            RuntimeOrigin::root(),
+            None
+        ));
+        assert_eq!(TelemetryProofs::min_proof_interval(), 10);
+        assert_noop!(
+            submit_at_block(1, "device-001", 22),
+            Error::<Test>::ProofTooFrequent
+        );
+        assert_ok!(submit_at_block(1, "device-001", 23));
+    });
+}
+
+#[test]
+fn min_proof_interval_rejects_batch_entries_individually() {
+    new_test_ext().execute_with(|| {
+        MinProofInterval::set(10);
+        assert_ok!(submit_at_block(1, "device-001", 1));
+
+        System::set_block_number(5);
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            batch_of(3),
+        ));
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 1,
+                reason: BatchRejectReason::ProofTooFrequent,
+            }
+            .into(),
+        );
+        System::assert_last_event(
+            Event::BatchProcessed {
+                submitted: 3,
+                accepted: 2,
+                rejected: 1,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn trusted_submitters_may_skip_min_proof_interval() {
+    new_test_ext_with_submitters(vec![1], true).execute_with(|| {
+        MinProofInterval::set(10);
+        assert_ok!(submit_at_block(1, "device-001", 1));
+
+        // Without the exemption trusted submitters are throttled as well
+        assert_noop!(
+            submit_at_block(1, "device-001", 2),
+            Error::<Test>::ProofTooFrequent
+        );
+
+        ExemptTrustedFromProofInterval::set(true);
+        assert_ok!(submit_at_block(1, "device-001", 2));
+        assert_noop!(
+            submit_at_block(2, "device-001", 3),
+            Error::<Test>::ProofTooFrequent
+        );
+        assert_ok!(submit_at_block(2, "device-001", 12));
+    });
+}
+
+#[test]
+fn dispatch_weights_come_from_weight_info() {
+    use frame_support::dispatch::GetDispatchInfo;
+    type Weights = <Test as Config>::WeightInfo;
+
+    let submit = Call::<Test>::submit_proof {
+        device_id: device_id("device-001"),
+        proof_hash: proof_hash("hash1"),
+        record_count: 10,
+        window_start: 1000,
+        window_end: 2000,
+        data_size_bytes: 1024,
+        schema_version: 1,
+        category: ProofCategory::EnergyUsage,
+        nonce: 0,
+        prev_proof_hash: None,
+        signature: None,
+        content_cid: None,
+    };
+    assert_eq!(submit.get_dispatch_info().weight, Weights::submit_proof());
+    assert_eq!(
+        submit.get_dispatch_info().class,
+        frame_support::dispatch::DispatchClass::Operational
+    );
+
+    let batch = Call::<Test>::submit_batch_proofs {
+        proofs: batch_of(3),
+    };
+    assert_eq!(
+        batch.get_dispatch_info().weight,
+        Weights::submit_batch_proofs(3)
+    );
+    assert_eq!(
+        batch.get_dispatch_info().class,
+        frame_support::dispatch::DispatchClass::Operational
+    );
+
+    let verify = Call::<Test>::verify_proof {
+        device_id: device_id("device-001"),
+        proof_hash: proof_hash("hash1"),
+    };
+    assert_eq!(verify.get_dispatch_info().weight, Weights::verify_proof());
+
+    let freeze = Call::<Test>::freeze_device {
+        device_id: device_id("device-001"),
+    };
+    assert_eq!(freeze.get_dispatch_info().weight, Weights::freeze_device());
+}
+
+#[test]
+fn submit_batch_proofs_refunds_weight_for_skipped_entries() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        type Weights = <Test as Config>::WeightInfo;
+
+        let full =
+            TelemetryProofs::submit_batch_proofs(RuntimeOrigin::signed(1), batch_of(4)).unwrap();
+        assert_eq!(full.actual_weight, Some(Weights::submit_batch_proofs(4)));
+
+        // Same length, but every other entry has an inverted window
+        let half_invalid: Vec<BatchProofEntry> = (0..4u32)
+            .map(|i| {
+                let device = format!("device-{:03}", i + 10);
+                let (window_start, window_end) = if i % 2 == 0 {
+                    (1000, 2000)
+                } else {
+                    (2000, 1000)
+                };
+                (
+                    device_id(&device),
+                    proof_hash(&format!("other{}", i)),
+                    10,
+                    window_start,
+                    window_end,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(device.as_bytes()),
+                )
+            })
+            .collect();
+        let partial =
+            TelemetryProofs::submit_batch_proofs(RuntimeOrigin::signed(1), half_invalid).unwrap();
+        assert_eq!(partial.actual_weight, Some(Weights::submit_batch_proofs(2)));
+        assert!(partial
+            .actual_weight
+            .unwrap()
+            .all_lt(full.actual_weight.unwrap()));
+        assert_eq!(TelemetryProofs::total_proofs(), 6);
+    });
+}
+
+#[test]
+fn try_state_detects_counter_drift() {
+    let corrupted = |corrupt: fn(&DeviceId)| {
+        new_test_ext().execute_with(|| {
+            submit_proofs_in_blocks("device-001", 1, 3);
+            submit_proofs_in_blocks("device-002", 4, 1);
+            assert_ok!(TelemetryProofs::do_try_state());
+
+            let dev: DeviceId = device_id("device-001").try_into().unwrap();
+            corrupt(&dev);
+            TelemetryProofs::do_try_state().unwrap_err()
+        })
+    };
+
+    assert_eq!(
+        corrupted(|dev| StoredProofCount::<Test>::insert(dev, 2)),
+        "retained count does not match stored proofs".into()
+    );
+    assert_eq!(
+        corrupted(|dev| OldestProofIndex::<Test>::insert(dev, 1)),
+        "stored proof outside the retained range".into()
+    );
+    assert_eq!(
+        corrupted(|_| TotalProofs::<Test>::put(5)),
+        "TotalProofs does not equal the sum of retained counts".into()
+    );
+    assert_eq!(
+        corrupted(|dev| ProofLookup::<Test>::insert(dev, [7u8; 32], 0)),
+        "lookup entry points at a missing proof".into()
+    );
+    assert_eq!(
+        corrupted(|_| {
+            let unknown: DeviceId = device_id("device-009").try_into().unwrap();
+            StoredProofCount::<Test>::insert(&unknown, 0);
+        }),
+        "retained count without a lifetime count".into()
+    );
+    assert_eq!(
+        corrupted(|_| KnownDeviceCount::<Test>::put(1)),
+        "KnownDeviceCount does not equal the number of devices with proofs".into()
+    );
+}
+
+#[test]
+fn proof_id_resolves_to_metadata_until_pruned() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 2);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        let first = TelemetryProofs::proofs(&bounded_dev_id, 0).unwrap();
+        let proof_id = TelemetryProofs::proof_id(
+            &bounded_dev_id,
+            first.proof_hash.as_bytes(),
+            first.window_start,
+            first.window_end,
+        );
+        let (device, index, metadata) = TelemetryProofs::lookup_proof(proof_id).unwrap();
+        assert_eq!(device, bounded_dev_id);
+        assert_eq!(index, 0);
+        assert_eq!(metadata.proof_hash, first.proof_hash);
+
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            1,
+            10,
+        ));
+        assert!(TelemetryProofs::proof_id_lookup(proof_id).is_none());
+        assert!(TelemetryProofs::lookup_proof(proof_id).is_none());
+    });
+}
+
+#[test]
+fn colliding_proof_id_is_rejected_as_duplicate() {
+    new_test_ext().execute_with(|| {
+        AllowSameDeviceResubmission::set(true);
+        System::set_block_number(1);
+        assert_ok!(submit_window(1000, 2000));
+
+        // Same device, hash and window yield the same proof id
+        assert_noop!(submit_window(1000, 2000), Error::<Test>::DuplicateProofId);
+
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            vec![(
+                device_id("device-001"),
+                proof_hash("hash2000"),
+                10,
+                1000,
+                2000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001")
+            )],
+        ));
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 0,
+                reason: BatchRejectReason::Duplicate,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn daily_record_counts_bucket_by_window_end_and_prune() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        BackfillMode::<Test>::put(true);
+        let submit = |window_start: u64, window_end: u64, records: u32| {
+            assert_ok!(TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash(&format!("hash{}", window_end)),
+                records,
+                window_start,
+                window_end,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                None,
+                None,
+            ));
+        };
+        let dev: DeviceId = device_id("device-001").try_into().unwrap();
+        let day = |d: u64| d * SECONDS_PER_DAY;
+
+        submit(1_000, 2_000, 10);
+        submit(80_000, day(1) + 100, 7);
+        submit(day(1) + 200, day(1) + 300, 5);
+        assert_eq!(TelemetryProofs::records_on_day(&dev, 0), 10);
+        assert_eq!(TelemetryProofs::records_on_day(&dev, 1), 12);
+
+        // Day 3 keeps days 1..=3 and drops day 0
+        submit(day(3), day(3) + 10, 4);
+        assert_eq!(TelemetryProofs::records_on_day(&dev, 0), 0);
+        assert!(!DailyRecordCounts::<Test>::contains_key(&dev, 0));
+        assert_eq!(TelemetryProofs::records_on_day(&dev, 1), 12);
+        assert_eq!(TelemetryProofs::latest_record_day(&dev), Some(3));
+
+        // A late window inside the retained days still counts
+        submit(day(2), day(2) + 10, 2);
+        assert_eq!(TelemetryProofs::records_on_day(&dev, 2), 2);
+
+        // A jump past the retention drops every older bucket
+        submit(day(10), day(10) + 10, 1);
+        assert_eq!(DailyRecordCounts::<Test>::iter_prefix(&dev).count(), 1);
+        assert_eq!(TelemetryProofs::records_on_day(&dev, 10), 1);
+
+        // Windows older than the retained days are not counted
+        submit(day(5), day(5) + 10, 9);
+        assert_eq!(TelemetryProofs::records_on_day(&dev, 5), 0);
+    });
+}
+
+#[test]
+fn proofs_expire_after_validity_period_but_stay_queryable() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(submit_at_block(1, "device-001", 10));
+        assert_ok!(submit_at_block(1, "device-001", 30));
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        let old_hash = h256("device-001-hash10");
+
+        assert_noop!(
+            TelemetryProofs::set_proof_validity_blocks(RuntimeOrigin::signed(1), Some(50)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(TelemetryProofs::set_proof_validity_blocks(
+            RuntimeOrigin::root(),
+            Some(50)
+        ));
+        System::assert_last_event(Event::ProofValidityBlocksSet { validity: Some(50) }.into());
+
+        // Exactly `validity` blocks old still counts
+        System::set_block_number(60);
+        assert!(TelemetryProofs::proof_exists(&bounded_dev_id, &old_hash));
+
+        System::set_block_number(61);
+        assert!(!TelemetryProofs::proof_exists(&bounded_dev_id, &old_hash));
+        assert!(!<TelemetryProofs as ProofProvider<_>>::proof_exists(
+            &bounded_dev_id,
+            0
+        ));
+        assert!(<TelemetryProofs as ProofProvider<_>>::proof_exists(
+            &bounded_dev_id,
+            1
+        ));
+
+        // Found but expired, distinct from not found
+        assert_ok!(TelemetryProofs::verify_proof(
+            RuntimeOrigin::signed(2),
+            device_id("device-001"),
+            old_hash.as_bytes().to_vec(),
+        ));
+        System::assert_last_event(
+            Event::ProofVerificationResult {
+                device_id: bounded_dev_id.clone(),
+                proof_hash: old_hash,
+                found: true,
+                proof_index: Some(0),
+                valid: false,
+                expired: true,
+            }
+            .into(),
+        );
+
+        // Historical queries still return the expired proof, flagged
+        let (history, next) = TelemetryProofs::get_proof_history(&bounded_dev_id, 0, 10);
+        assert_eq!(next, None);
+        let flags: Vec<(H256, bool)> = history
+            .iter()
+            .map(|(metadata, expired)| (metadata.proof_hash, *expired))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![(old_hash, true), (h256("device-001-hash30"), false)]
+        );
+        assert_eq!(
+            TelemetryProofs::proofs(&bounded_dev_id, 0)
+                .unwrap()
+                .proof_hash,
+            old_hash
+        );
+
+        // Clearing the period restores the proof without touching storage
+        assert_ok!(TelemetryProofs::set_proof_validity_blocks(
+            RuntimeOrigin::root(),
+            None
+        ));
+        assert!(TelemetryProofs::proof_exists(&bounded_dev_id, &old_hash));
+    });
+}
+
+#[test]
+fn total_records_accumulates_and_drops_revoked_proofs() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 2);
+        let dev1: DeviceId = device_id("device-001").try_into().unwrap();
+        let dev2: DeviceId = device_id("device-002").try_into().unwrap();
+        assert_eq!(TelemetryProofs::total_records(&dev1), 20);
+
+        System::set_block_number(3);
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            vec![
+                (
+                    device_id("device-001"),
+                    proof_hash("batch1"),
+                    25,
+                    3000u64,
+                    4000u64,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-001"),
+                ),
+                (
+                    device_id("device-002"),
+                    proof_hash("batch2"),
+                    40,
+                    3000u64,
+                    4000u64,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-002"),
+                ),
+                // Invalid window: skipped and not counted
+                (
+                    device_id("device-002"),
+                    proof_hash("batch3"),
+                    99,
+                    5000u64,
+                    4000u64,
+                    1024,
+                    1,
+                    ProofCategory::EnergyUsage,
+                    nonce(b"device-002") + 1,
+                ),
+            ],
+        ));
+        assert_eq!(TelemetryProofs::total_records(&dev1), 45);
+        assert_eq!(TelemetryProofs::total_records(&dev2), 40);
+
+        assert_ok!(TelemetryProofs::revoke_proof(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            2,
+            RevocationReason::CorruptedData,
+        ));
+        assert_eq!(TelemetryProofs::total_records(&dev1), 20);
+
+        // An upheld challenge revokes the proof too
+        assert_ok!(TelemetryProofs::challenge_proof(
+            RuntimeOrigin::signed(3),
+            device_id("device-001"),
+            0,
+            H256::repeat_byte(7),
+        ));
+        assert_ok!(TelemetryProofs::resolve_challenge(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            0,
+            true,
+        ));
+        assert_eq!(TelemetryProofs::total_records(&dev1), 10);
+
+        // Pruning keeps the lifetime total
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            2,
+            2,
+        ));
+        assert_eq!(TelemetryProofs::total_records(&dev1), 10);
+    });
+}
+
+#[test]
+fn remove_device_proofs_cleans_device_over_several_calls() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 7);
+        submit_proofs_in_blocks("device-002", 8, 2);
+        let dev1: DeviceId = device_id("device-001").try_into().unwrap();
+        let dev2: DeviceId = device_id("device-002").try_into().unwrap();
+        assert_ok!(TelemetryProofs::challenge_proof(
+            RuntimeOrigin::signed(3),
+            device_id("device-001"),
+            6,
+            H256::repeat_byte(7),
+        ));
+        let held = challenge_deposit_held(3);
+        assert_eq!(TelemetryProofs::known_device_count(), 2);
+        RetiredDevices::set(vec![device_id("device-001")]);
+
+        assert_noop!(
+            TelemetryProofs::remove_device_proofs(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                3
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        for (removed, complete) in [(3, false), (3, false), (1, true)] {
+            assert_ok!(TelemetryProofs::remove_device_proofs(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                3,
+            ));
+            System::assert_last_event(
+                Event::DeviceProofsRemoved {
+                    device_id: dev1.clone(),
+                    removed,
+                    complete,
+                }
+                .into(),
+            );
+        }
+
+        // No orphaned proof or index entries remain for the device
+        assert_eq!(Proofs::<Test>::iter_prefix(&dev1).count(), 0);
+        assert!(ProofIndexesByBlock::<Test>::iter().all(|(_, device, _)| device != dev1));
+        assert_eq!(ProofsByWindowEnd::<Test>::iter_prefix(&dev1).count(), 0);
+        assert_eq!(ProofLookup::<Test>::iter_prefix(&dev1).count(), 0);
+        assert!(ProofHashIndex::<Test>::iter().all(|(_, (device, _))| device != dev1));
+        assert!(ProofIdLookup::<Test>::iter().all(|(_, (device, _))| device != dev1));
+        assert_eq!(Challenges::<Test>::iter_prefix(&dev1).count(), 0);
+        assert_eq!(ProofDeposits::<Test>::iter_prefix(&dev1).count(), 0);
+        assert_eq!(DailyRecordCounts::<Test>::iter_prefix(&dev1).count(), 0);
+        assert_eq!(CategoryProofCount::<Test>::iter_prefix(&dev1).count(), 0);
+        assert!(!ProofCount::<Test>::contains_key(&dev1));
+        assert!(!StoredProofCount::<Test>::contains_key(&dev1));
+        assert!(!OldestProofIndex::<Test>::contains_key(&dev1));
+        assert_eq!(TelemetryProofs::latest_proof_block(&dev1), None);
+        assert_eq!(TelemetryProofs::latest_record_day(&dev1), None);
+        assert_eq!(TelemetryProofs::window_index_bounds(&dev1), None);
+        assert_eq!(TelemetryProofs::total_records(&dev1), 0);
+        // The challenge deposit on a removed proof is returned
+        assert!(challenge_deposit_held(3) < held);
+        // Replay protection survives the removal
+        assert_eq!(TelemetryProofs::submission_nonce(&dev1), 7);
+
+        // Other devices are untouched
+        assert_eq!(TelemetryProofs::known_device_count(), 1);
+        assert_eq!(TelemetryProofs::proof_count(&dev2), 2);
+        assert_eq!(Proofs::<Test>::iter_prefix(&dev2).count(), 2);
+        assert_eq!(TelemetryProofs::total_proofs(), 2);
+        assert_ok!(TelemetryProofs::do_try_state());
+
+        // A fully removed device has nothing left to remove
+        assert_noop!(
+            TelemetryProofs::remove_device_proofs(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                3
+            ),
+            Error::<Test>::ProofNotFound
+        );
+    });
+}
+
+#[test]
+fn remove_device_proofs_requires_a_frozen_or_retired_device() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 2);
+        let dev: DeviceId = device_id("device-001").try_into().unwrap();
+
+        // An active device could still be submitting proofs
+        assert_noop!(
+            TelemetryProofs::remove_device_proofs(
+                RuntimeOrigin::root(),
+                device_id("device-001"),
+                10
+            ),
+            Error::<Test>::DeviceNotRetired
+        );
+        assert_eq!(TelemetryProofs::stored_proof_count(&dev), 2);
+
+        assert_ok!(TelemetryProofs::freeze_device(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+        ));
+        assert_ok!(TelemetryProofs::remove_device_proofs(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            10,
+        ));
+        assert_eq!(TelemetryProofs::stored_proof_count(&dev), 0);
+    });
+}
+
+fn storage_warnings() -> Vec<u64> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            RuntimeEvent::TelemetryProofs(Event::ProofStorageNearlyFull { retained, .. }) => {
+                Some(retained)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn proof_storage_warning_fires_once_per_crossing() {
+    new_test_ext().execute_with(|| {
+        // 75% of 4 proofs: warned from the third retained proof
+        MaxProofsPerDevice::set(4);
+        assert_eq!(TelemetryProofs::storage_warning_threshold(), 3);
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+
+        submit_proofs_in_blocks("device-001", 1, 2);
+        assert!(storage_warnings().is_empty());
+
+        submit_proofs_in_blocks("device-001", 3, 1);
+        System::assert_last_event(
+            Event::ProofStorageNearlyFull {
+                device_id: bounded_dev_id.clone(),
+                retained: 3,
+                max: 4,
+            }
+            .into(),
+        );
+        assert!(TelemetryProofs::storage_warned(&bounded_dev_id).is_some());
+
+        // Staying above the threshold does not warn again
+        submit_proofs_in_blocks("device-001", 4, 1);
+        assert_eq!(storage_warnings(), vec![3]);
+
+        // Pruning to exactly the threshold keeps the warning armed
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            1,
+            1,
+        ));
+        assert!(TelemetryProofs::storage_warned(&bounded_dev_id).is_some());
+
+        // Dropping below it resets the warning
+        assert_ok!(TelemetryProofs::prune_proofs_before(
+            RuntimeOrigin::root(),
+            device_id("device-001"),
+            2,
+            1,
+        ));
+        assert!(TelemetryProofs::storage_warned(&bounded_dev_id).is_none());
+
+        submit_proofs_in_blocks("device-001", 5, 1);
+        assert_eq!(storage_warnings(), vec![3, 3]);
+    });
+}
+
+#[test]
+fn retired_device_cannot_submit_proofs() {
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 1);
+        RetiredDevices::set(vec![device_id("device-001")]);
+
+        System::set_block_number(2);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(1),
+                device_id("device-001"),
+                proof_hash("hash2"),
+                10,
+                2000,
+                3000,
+                1024,
+                1,
+                ProofCategory::EnergyUsage,
+                nonce(b"device-001"),
+                latest_hash("device-001"),
+                None,
+                None,
+            ),
+            Error::<Test>::DeviceRetired
+        );
+
+        // Existing proofs remain queryable
+        let bounded_dev_id: DeviceId = device_id("device-001").try_into().unwrap();
+        assert!(TelemetryProofs::proof_exists(
+            &bounded_dev_id,
+            &h256("hash1")
+        ));
+
+        // Batch entries for the device are rejected individually
+        assert_ok!(TelemetryProofs::submit_batch_proofs(
+            RuntimeOrigin::signed(1),
+            batch_of(3),
+        ));
+        System::assert_has_event(
+            Event::BatchEntryRejected {
+                index: 1,
+                reason: BatchRejectReason::DeviceRetired,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn decommissioned_device_proofs_are_removed_in_on_idle() {
+    use pallet_device_registry::{DecommissionReport, OnDecommission};
+
+    new_test_ext().execute_with(|| {
+        submit_proofs_in_blocks("device-001", 1, 7);
+        submit_proofs_in_blocks("device-002", 8, 2);
+        let dev1: DeviceId = device_id("device-001").try_into().unwrap();
+        let dev2: DeviceId = device_id("device-002").try_into().unwrap();
+
+        assert_eq!(
+            <TelemetryProofs as OnDecommission<DeviceId, u64>>::on_decommission(&dev1, &1),
+            Ok(DecommissionReport {
+                credits_swept: 0,
+                proofs_scheduled: 7,
+            })
+        );
+        assert!(FrozenDevices::<Test>::contains_key(&dev1));
+        assert!(PendingProofRemovals::<Test>::contains_key(&dev1));
+
+        // Room for the idle bookkeeping and three proof removals per block
+        let db_weight = <Test as frame_system::Config>::DbWeight::get();
+        let budget = db_weight
+            .reads(2)
+            .saturating_add(<() as WeightInfo>::remove_device_proofs(
+                0,
+                <Test as Config>::MaxDaysRetained::get(),
+            ))
+            .saturating_add(<() as WeightInfo>::prune_proofs_before(1).saturating_mul(3));
+
+        for (removed, complete) in [(3, false), (3, false), (1, true)] {
+            TelemetryProofs::on_idle(10, budget);
+            System::assert_last_event(
+                Event::DeviceProofsRemoved {
+                    device_id: dev1.clone(),
+                    removed,
+                    complete,
+                }
+                .into(),
+            );
+        }
+
+        assert!(!PendingProofRemovals::<Test>::contains_key(&dev1));
+        assert_eq!(Proofs::<Test>::iter_prefix(&dev1).count(), 0);
+        assert!(!StoredProofCount::<Test>::contains_key(&dev1));
+        assert_eq!(TelemetryProofs::known_device_count(), 1);
+        // The device stays frozen and other devices are untouched
+        assert!(FrozenDevices::<Test>::contains_key(&dev1));
+        assert_eq!(TelemetryProofs::stored_proof_count(&dev2), 2);
+        assert_ok!(TelemetryProofs::do_try_state());
+
+        // Nothing is queued for a device without retained proofs
+        let unknown: DeviceId = device_id("device-003").try_into().unwrap();
+        assert_eq!(
+            <TelemetryProofs as OnDecommission<DeviceId, u64>>::on_decommission(&unknown, &1),
+            Ok(DecommissionReport::default())
+        );
+        assert!(FrozenDevices::<Test>::contains_key(&unknown));
+        assert!(!PendingProofRemovals::<Test>::contains_key(&unknown));
+    });
+}
+
+#[test]
+fn offchain_worker_signs_and_submits_heartbeats() {
+    use codec::Decode;
+    use sp_core::offchain::{
+        testing::{TestOffchainExt, TestTransactionPoolExt},
+        OffchainWorkerExt, TransactionPoolExt,
+    };
+    use sp_runtime::traits::Dispatchable;
+
+    let mut ext = new_test_ext_with_submitters(vec![1], false);
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    ext.register_extension(OffchainWorkerExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        // Without a key nothing is signed
+        UintAuthorityId::set_all_keys(Vec::<u64>::new());
+        TelemetryProofs::offchain_worker(5);
+        assert!(pool_state.read().transactions.is_empty());
+
+        // Only every `HeartbeatInterval` blocks
+        UintAuthorityId::set_all_keys(vec![1u64]);
+        TelemetryProofs::offchain_worker(6);
+        assert!(pool_state.read().transactions.is_empty());
+
+        TelemetryProofs::offchain_worker(10);
+        let tx = pool_state.write().transactions.pop().unwrap();
+        assert!(pool_state.read().transactions.is_empty());
+        let tx = TestXt::<RuntimeCall, ()>::decode(&mut &tx[..]).unwrap();
+        assert_eq!(tx.signature, Some((1, ())));
+        assert_eq!(
+            tx.call,
+            RuntimeCall::TelemetryProofs(crate::Call::heartbeat {})
+        );
+
+        // The signer is a trusted submitter, so the heartbeat is recorded
+        System::set_block_number(10);
+        assert_ok!(tx.call.dispatch(RuntimeOrigin::signed(1)));
+        assert_eq!(TelemetryProofs::last_heartbeat(1), Some(10));
+        System::assert_last_event(Event::Heartbeat { who: 1 }.into());
+
+        assert_noop!(
+            TelemetryProofs::heartbeat(RuntimeOrigin::signed(2)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
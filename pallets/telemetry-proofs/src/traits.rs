@@ -0,0 +1,212 @@
+//! Interfaces the Telemetry Proofs pallet offers to other pallets.
+
+use super::*;
+use frame_support::{
+    dispatch::DispatchResult, pallet_prelude::DispatchError, traits::EnsureOrigin, weights::Weight,
+};
+use pallet_device_registry::{DecommissionReport, OnDecommission};
+
+/// Read access to stored telemetry proofs.
+///
+/// Other pallets take this as a Config associated type instead of reading
+/// the pallet's storage directly.
+pub trait ProofProvider<DeviceId> {
+    /// Whether the device's proof at `index` is a retained, valid (not
+    /// revoked, challenged or expired) `EnergyUsage` proof.
+    fn proof_exists(device: &DeviceId, index: u64) -> bool;
+
+    /// The `(window_start, window_end)` of the device's proof at `index`.
+    fn proof_window(device: &DeviceId, index: u64) -> Option<(u64, u64)>;
+}
+
+/// Knows no proofs; for tests of pallets that do not link proofs.
+impl<DeviceId> ProofProvider<DeviceId> for () {
+    fn proof_exists(_device: &DeviceId, _index: u64) -> bool {
+        false
+    }
+
+    fn proof_window(_device: &DeviceId, _index: u64) -> Option<(u64, u64)> {
+        None
+    }
+}
+
+impl<T: Config> ProofProvider<DeviceId> for Pallet<T> {
+    fn proof_exists(device: &DeviceId, index: u64) -> bool {
+        Proofs::<T>::get(device, index).is_some_and(|metadata| {
+            metadata.is_valid()
+                && metadata.category == ProofCategory::EnergyUsage
+                && !Pallet::<T>::is_expired(&metadata)
+                && !Pallet::<T>::is_compromised(device, &metadata)
+        })
+    }
+
+    fn proof_window(device: &DeviceId, index: u64) -> Option<(u64, u64)> {
+        Proofs::<T>::get(device, index).map(|metadata| (metadata.window_start, metadata.window_end))
+    }
+}
+
+/// Follow-up run by `submit_proof_with_energy` once its proof is stored.
+///
+/// The call is transactional: an error from the hook also discards the proof.
+pub trait OnProofAccepted<DeviceId> {
+    /// Act on the device's newly stored proof at `proof_index`, which
+    /// covers `energy_wh` watt-hours.
+    fn on_proof_accepted(device: &DeviceId, proof_index: u64, energy_wh: u128) -> DispatchResult;
+
+    /// Upper bound on the weight of `on_proof_accepted`.
+    fn weight() -> Weight;
+}
+
+/// Accepts every proof and does nothing.
+impl<DeviceId> OnProofAccepted<DeviceId> for () {
+    fn on_proof_accepted(
+        _device: &DeviceId,
+        _proof_index: u64,
+        _energy_wh: u128,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
+    fn weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Notification of every proof the pallet stores.
+///
+/// Lets other pallets react to proofs without the telemetry pallet depending
+/// on them. Called once per accepted proof, including each accepted entry of
+/// a batch; rejected submissions produce no call.
+pub trait OnProofSubmitted<DeviceId, BlockNumber> {
+    /// React to the device's newly stored proof at `proof_index`, submitted
+    /// at `block` and covering `record_count` records over
+    /// `window_start..window_end`.
+    fn on_proof_submitted(
+        device: &DeviceId,
+        proof_index: u64,
+        record_count: u32,
+        window_start: u64,
+        window_end: u64,
+        block: BlockNumber,
+    );
+
+    /// Upper bound on the weight of `on_proof_submitted`.
+    fn weight() -> Weight;
+}
+
+/// Nobody listens.
+impl<DeviceId, BlockNumber> OnProofSubmitted<DeviceId, BlockNumber> for () {
+    fn on_proof_submitted(
+        _device: &DeviceId,
+        _proof_index: u64,
+        _record_count: u32,
+        _window_start: u64,
+        _window_end: u64,
+        _block: BlockNumber,
+    ) {
+    }
+
+    fn weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Notifies both handlers in order.
+impl<DeviceId, BlockNumber: Copy, A, B> OnProofSubmitted<DeviceId, BlockNumber> for (A, B)
+where
+    A: OnProofSubmitted<DeviceId, BlockNumber>,
+    B: OnProofSubmitted<DeviceId, BlockNumber>,
+{
+    fn on_proof_submitted(
+        device: &DeviceId,
+        proof_index: u64,
+        record_count: u32,
+        window_start: u64,
+        window_end: u64,
+        block: BlockNumber,
+    ) {
+        A::on_proof_submitted(
+            device,
+            proof_index,
+            record_count,
+            window_start,
+            window_end,
+            block,
+        );
+        B::on_proof_submitted(
+            device,
+            proof_index,
+            record_count,
+            window_start,
+            window_end,
+            block,
+        );
+    }
+
+    fn weight() -> Weight {
+        A::weight().saturating_add(B::weight())
+    }
+}
+
+/// Freezes a decommissioned device and queues its proofs for removal by
+/// `on_idle`.
+impl<T: Config> OnDecommission<DeviceId, T::AccountId> for Pallet<T> {
+    fn on_decommission(
+        device: &DeviceId,
+        _owner: &T::AccountId,
+    ) -> Result<DecommissionReport, DispatchError> {
+        FrozenDevices::<T>::insert(device, ());
+        let retained = StoredProofCount::<T>::get(device);
+        if retained > 0 {
+            PendingProofRemovals::<T>::insert(device, ());
+        }
+        Ok(DecommissionReport {
+            credits_swept: 0,
+            proofs_scheduled: retained,
+        })
+    }
+
+    fn weight() -> Weight {
+        T::DbWeight::get().reads_writes(1, 2)
+    }
+}
+
+/// Admits signed accounts by the pallet's own `OpenSubmission` and
+/// `TrustedSubmitters`, for chains without a shared submitter origin.
+pub struct EnsureAuthorizedSubmitter<T>(core::marker::PhantomData<T>);
+
+impl<T: Config> EnsureOrigin<T::RuntimeOrigin> for EnsureAuthorizedSubmitter<T> {
+    type Success = T::AccountId;
+
+    fn try_origin(o: T::RuntimeOrigin) -> Result<Self::Success, T::RuntimeOrigin> {
+        o.into().and_then(|o| match o {
+            frame_system::RawOrigin::Signed(who)
+                if OpenSubmission::<T>::get() || TrustedSubmitters::<T>::contains_key(&who) =>
+            {
+                Ok(who)
+            }
+            r => Err(T::RuntimeOrigin::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+        // An untrusted account, so benchmarks pay for the proof deposit
+        OpenSubmission::<T>::put(true);
+        let who = <T::AccountId as codec::Decode>::decode(
+            &mut sp_runtime::traits::TrailingZeroInput::zeroes(),
+        )
+        .map_err(|_| ())?;
+        Ok(frame_system::RawOrigin::Signed(who).into())
+    }
+}
+
+/// The accounts in `TrustedSubmitters`, as `ExemptSubmitters` for chains
+/// admitting submitters with [`EnsureAuthorizedSubmitter`].
+pub struct TrustedSubmitterList<T>(core::marker::PhantomData<T>);
+
+impl<T: Config> frame_support::traits::Contains<T::AccountId> for TrustedSubmitterList<T> {
+    fn contains(who: &T::AccountId) -> bool {
+        TrustedSubmitters::<T>::contains_key(who)
+    }
+}
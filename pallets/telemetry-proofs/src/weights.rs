@@ -10,11 +10,102 @@ pub trait WeightInfo {
     /// Weight for submitting a single proof.
     fn submit_proof() -> Weight;
 
-    /// Weight for submitting a batch of proofs.
+    /// Weight for submitting a batch that stores `n` proofs.
     fn submit_batch_proofs(n: u32) -> Weight;
 
     /// Weight for verifying a proof exists.
     fn verify_proof() -> Weight;
+
+    /// Weight for verifying `n` proofs in one call.
+    fn verify_proofs(n: u32) -> Weight;
+
+    /// Weight for pruning up to `n` proofs of a device.
+    fn prune_proofs_before(n: u32) -> Weight;
+
+    /// Weight for removing up to `n` proofs of a retired device with `d`
+    /// daily record buckets.
+    fn remove_device_proofs(n: u32, d: u32) -> Weight;
+
+    /// Weight for registering a device signing key.
+    fn register_device_key() -> Weight;
+
+    /// Weight for rotating a device signing key.
+    fn rotate_device_key() -> Weight;
+
+    /// Weight for adding a trusted submitter.
+    fn add_trusted_submitter() -> Weight;
+
+    /// Weight for removing a trusted submitter.
+    fn remove_trusted_submitter() -> Weight;
+
+    /// Weight for setting the open submission flag.
+    fn set_open_submission() -> Weight;
+
+    /// Weight for revoking a proof.
+    fn revoke_proof() -> Weight;
+
+    /// Weight for challenging a proof.
+    fn challenge_proof() -> Weight;
+
+    /// Weight for resolving a proof challenge.
+    fn resolve_challenge() -> Weight;
+
+    /// Weight for expiring an unresolved challenge.
+    fn expire_challenge() -> Weight;
+
+    /// Weight for verifying Merkle inclusion with a path of `d` siblings.
+    fn verify_merkle_inclusion(d: u32) -> Weight;
+
+    /// Weight for adding a supported schema version.
+    fn add_supported_schema() -> Weight;
+
+    /// Weight for removing a supported schema version.
+    fn remove_supported_schema() -> Weight;
+
+    /// Weight for setting the retention period.
+    fn set_retention_blocks() -> Weight;
+
+    /// Weight for anchoring a proof on an external chain.
+    fn set_anchor() -> Weight;
+
+    /// Weight for computing and storing a block proof root over `n` leaves.
+    fn finalize_block_proof_root(n: u32) -> Weight;
+
+    /// Weight for switching backfill mode.
+    fn set_backfill_mode() -> Weight;
+
+    /// Weight for freezing a device.
+    fn freeze_device() -> Weight;
+
+    /// Weight for unfreezing a device.
+    fn unfreeze_device() -> Weight;
+
+    /// Weight for setting a proof quality score.
+    fn set_proof_quality() -> Weight;
+
+    /// Weight for attaching a content CID to a proof.
+    fn set_content_cid() -> Weight;
+
+    /// Weight for setting the cap on distinct devices.
+    fn set_max_total_devices() -> Weight;
+
+    /// Weight for overriding the minimum proof interval.
+    fn set_min_proof_interval() -> Weight;
+
+    /// Weight for setting the proof validity period.
+    fn set_proof_validity_blocks() -> Weight;
+
+    /// Weight of a window query on a device with `n` proofs outside the window.
+    fn get_proofs_in_window(n: u32) -> Weight;
+
+    /// Weight for marking a device signing key compromised.
+    fn mark_key_compromised() -> Weight;
+
+    /// Weight for checking `n` proofs in a compromised proof sweep.
+    fn revoke_compromised_proofs(n: u32) -> Weight;
+
+    /// Weight for recording an offchain worker heartbeat.
+    fn heartbeat() -> Weight;
 }
 
 /// Default weight implementation.
@@ -27,42 +118,351 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     /// Weight for submitting a single proof.
     ///
     /// Includes:
-    /// - Reading proof count for device
-    /// - Checking for duplicate at block
+    /// - Checking the submission mode and submitter whitelist
+    /// - Checking the schema version is supported and the device is not frozen
+    /// - Checking the device is not retired in the device registry
+    /// - Checking and advancing the device submission nonce
+    /// - Checking and counting a new device against the device cap
+    /// - Checking the minimum interval since the device's latest proof
+    /// - Checking the window against on-chain time unless backfilling
+    /// - Reading the latest stored proof to check the chain link
+    /// - Reading the device key and verifying an sr25519 signature
+    /// - Reading retained and lifetime proof counts for device
+    /// - Checking for duplicate at block and in the proof hash index
+    /// - Holding the submitter's proof deposit
+    /// - Evicting the oldest proof, its deposit and any open challenge (worst case)
     /// - Writing proof metadata
-    /// - Writing to proofs by block index
-    /// - Updating proof count
+    /// - Writing to proofs by block, window end, proof hash, proof id and proof lookup indexes
+    /// - Widening the device's window index bounds
+    /// - Appending the block proof root leaf
+    /// - Updating lifetime, per-category and retained proof counts
+    /// - Updating the device's lifetime record total
     /// - Updating total proofs
     /// - Updating latest proof block
-    /// - Emitting event
+    /// - Updating submitter statistics
+    /// - Updating the daily record count and removing an expired day
+    /// - Marking the device warned of nearly full storage
+    /// - Emitting events
     fn submit_proof() -> Weight {
-        // Base weight: ~50_000 + DB reads (2) + DB writes (5)
-        Weight::from_parts(50_000_000, 0)
-            .saturating_add(T::DbWeight::get().reads(2))
-            .saturating_add(T::DbWeight::get().writes(5))
+        // Base weight: ~50_000 + signature check + DB reads (40) + DB writes (37)
+        Weight::from_parts(100_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(40))
+            .saturating_add(T::DbWeight::get().writes(37))
     }
 
-    /// Weight for submitting a batch of proofs.
+    /// Weight for submitting a batch that stores `n` proofs.
     ///
-    /// Linear scaling with number of proofs.
+    /// Linear scaling with number of proofs, plus the submitter check. Charged
+    /// for the batch length at dispatch and for the accepted entries after.
     fn submit_batch_proofs(n: u32) -> Weight {
         // Base weight + per-proof weight
         Weight::from_parts(20_000_000, 0)
             .saturating_add(Weight::from_parts(40_000_000 * n as u64, 0))
-            .saturating_add(T::DbWeight::get().reads(2 * n as u64))
-            .saturating_add(T::DbWeight::get().writes(5 * n as u64))
+            .saturating_add(T::DbWeight::get().reads(3 + 37 * n as u64))
+            .saturating_add(T::DbWeight::get().writes(1 + 37 * n as u64))
     }
 
     /// Weight for verifying a proof exists.
     ///
     /// Includes:
-    /// - Reading proof count
-    /// - Potentially iterating through all proofs (worst case)
+    /// - Reading the proof lookup entry
+    /// - Reading the matched proof metadata and validity period
+    /// - Reading the device's compromised keys and key history
     /// - Emitting event
     fn verify_proof() -> Weight {
-        // Worst case: iterate through MaxProofsPerDevice proofs
-        Weight::from_parts(100_000_000, 0).saturating_add(T::DbWeight::get().reads(1001))
-        // count + max proofs
+        Weight::from_parts(25_000_000, 0).saturating_add(T::DbWeight::get().reads(5))
+    }
+
+    /// Weight for verifying `n` proofs in one call.
+    ///
+    /// Per query:
+    /// - Reading the proof lookup entry
+    /// - Reading the matched proof metadata and validity period
+    /// - Reading the device's compromised keys and key history
+    fn verify_proofs(n: u32) -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(Weight::from_parts(10_000_000 * n as u64, 0))
+            .saturating_add(T::DbWeight::get().reads(5 * n as u64))
+    }
+
+    /// Weight for pruning up to `n` proofs of a device.
+    ///
+    /// First reading the device's owner when the caller is not the admin origin.
+    ///
+    /// Per removed proof:
+    /// - Reading oldest index and proof metadata
+    /// - Reading and removing the block, window end, proof hash, proof id and lookup index entries
+    /// - Closing any open challenge and returning its deposit
+    /// - Updating oldest index, retained count and total proofs
+    ///
+    /// Then re-arming the storage warning if the device dropped below it.
+    fn prune_proofs_before(n: u32) -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(Weight::from_parts(25_000_000 * n as u64, 0))
+            .saturating_add(T::DbWeight::get().reads(3 + 10 * n as u64))
+            .saturating_add(T::DbWeight::get().writes(2 + 16 * n as u64))
+    }
+
+    /// Weight for removing up to `n` proofs of a retired device with `d`
+    /// daily record buckets.
+    ///
+    /// Reads the frozen flag and registry status first. Per removed proof,
+    /// as `prune_proofs_before`. Once the last proof is
+    /// gone, the device's counters, quality totals, category counts and up to
+    /// `d` daily record buckets are cleared.
+    fn remove_device_proofs(n: u32, d: u32) -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(Weight::from_parts(25_000_000 * n as u64, 0))
+            .saturating_add(T::DbWeight::get().reads(6 + 10 * n as u64))
+            .saturating_add(T::DbWeight::get().writes(16 + 16 * n as u64 + d as u64))
+    }
+
+    /// Weight for registering a device signing key.
+    ///
+    /// Operations:
+    /// - Check for an existing key
+    /// - Write the key
+    /// - Emit event
+    fn register_device_key() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for rotating a device signing key.
+    ///
+    /// Operations:
+    /// - Read the key history
+    /// - Write the updated key history
+    /// - Emit event
+    fn rotate_device_key() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for adding a trusted submitter.
+    fn add_trusted_submitter() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for removing a trusted submitter.
+    fn remove_trusted_submitter() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for setting the open submission flag.
+    fn set_open_submission() -> Weight {
+        Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for revoking a proof.
+    ///
+    /// Operations:
+    /// - Read proof metadata
+    /// - Write updated metadata
+    /// - Release the proof hash and window end index entries
+    /// - Release the proof deposit
+    /// - Subtract the proof's records from the device total
+    /// - Emit event
+    fn revoke_proof() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(8))
+            .saturating_add(T::DbWeight::get().writes(8))
+    }
+
+    /// Weight for challenging a proof.
+    ///
+    /// Operations:
+    /// - Read and update proof metadata
+    /// - Reserve the challenger's deposit
+    /// - Write the challenge
+    /// - Emit event
+    fn challenge_proof() -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    /// Weight for resolving a proof challenge.
+    ///
+    /// Worst case (uphold): take the challenge, update proof metadata,
+    /// unreserve the challenger's deposit, release the proof deposit, drop
+    /// the window end index entry and subtract the proof's records.
+    fn resolve_challenge() -> Weight {
+        Weight::from_parts(50_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(9))
+            .saturating_add(T::DbWeight::get().writes(9))
+    }
+
+    /// Weight for expiring an unresolved challenge.
+    ///
+    /// Operations:
+    /// - Read and remove the challenge
+    /// - Update proof metadata
+    /// - Unreserve the deposit
+    fn expire_challenge() -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    /// Weight for verifying Merkle inclusion with a path of `d` siblings.
+    ///
+    /// Operations:
+    /// - Read proof metadata
+    /// - Read the device's compromised keys and key history
+    /// - One blake2-256 hash per path entry
+    /// - Emit event
+    fn verify_merkle_inclusion(d: u32) -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(Weight::from_parts(2_000_000 * d as u64, 0))
+            .saturating_add(T::DbWeight::get().reads(4))
+    }
+
+    /// Weight for adding a supported schema version.
+    fn add_supported_schema() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for removing a supported schema version.
+    fn remove_supported_schema() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for setting the retention period.
+    fn set_retention_blocks() -> Weight {
+        Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for anchoring a proof on an external chain.
+    ///
+    /// Operations:
+    /// - Read proof metadata
+    /// - Check for an existing anchor
+    /// - Write the anchor
+    /// - Emit event
+    fn set_anchor() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for computing and storing a block proof root over `n` leaves.
+    ///
+    /// Operations:
+    /// - Take the block's leaves
+    /// - One blake2-256 hash per internal node
+    /// - Store the root, remove the expired root and deposit the digest log
+    fn finalize_block_proof_root(n: u32) -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    /// Weight for switching backfill mode.
+    fn set_backfill_mode() -> Weight {
+        Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for freezing a device.
+    fn freeze_device() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for unfreezing a device.
+    fn unfreeze_device() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for setting a proof quality score.
+    ///
+    /// Operations:
+    /// - Read and rewrite the proof metadata
+    /// - Update the device quality totals
+    /// - Emit event
+    fn set_proof_quality() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Weight for attaching a content CID to a proof.
+    fn set_content_cid() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for setting the cap on distinct devices.
+    fn set_max_total_devices() -> Weight {
+        Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for overriding the minimum proof interval.
+    fn set_min_proof_interval() -> Weight {
+        Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for setting the proof validity period.
+    fn set_proof_validity_blocks() -> Weight {
+        Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight of a window query on a device with `n` proofs outside the window.
+    ///
+    /// Reads the window bounds, the buckets covering the range and the
+    /// matching proofs; proofs in other buckets are never touched, so the
+    /// weight does not depend on `n`.
+    fn get_proofs_in_window(_n: u32) -> Weight {
+        Weight::from_parts(15_000_000, 0).saturating_add(T::DbWeight::get().reads(3))
+    }
+
+    /// Weight for marking a device signing key compromised.
+    ///
+    /// Operations:
+    /// - Read the device's key history and oldest proof index
+    /// - Read and update the compromised keys
+    /// - Write the sweep cursor
+    /// - Emit event
+    fn mark_key_compromised() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Weight for checking `n` proofs in a compromised proof sweep.
+    ///
+    /// Reads the sweep cursor, proof bounds, key history and compromised keys
+    /// once, then per checked proof reads its metadata and, in the worst case,
+    /// revokes it like `revoke_proof`.
+    fn revoke_compromised_proofs(n: u32) -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(Weight::from_parts(25_000_000 * n as u64, 0))
+            .saturating_add(T::DbWeight::get().reads(5 + 7 * n as u64))
+            .saturating_add(T::DbWeight::get().writes(1 + 8 * n as u64))
+    }
+
+    /// Weight for recording an offchain worker heartbeat.
+    ///
+    /// Checks the submitter origin and writes `LastHeartbeat`.
+    fn heartbeat() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
     }
 }
 
@@ -72,11 +472,131 @@ impl WeightInfo for () {
         Weight::from_parts(10_000, 0)
     }
 
-    fn submit_batch_proofs(_n: u32) -> Weight {
-        Weight::from_parts(10_000, 0)
+    fn submit_batch_proofs(n: u32) -> Weight {
+        Weight::from_parts(10_000 * (1 + n as u64), 0)
     }
 
     fn verify_proof() -> Weight {
         Weight::from_parts(10_000, 0)
     }
+
+    fn verify_proofs(n: u32) -> Weight {
+        Weight::from_parts(10_000 * (1 + n as u64), 0)
+    }
+
+    fn prune_proofs_before(_n: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn remove_device_proofs(n: u32, _d: u32) -> Weight {
+        Weight::from_parts(10_000 * (1 + n as u64), 0)
+    }
+
+    fn register_device_key() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn rotate_device_key() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn add_trusted_submitter() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn remove_trusted_submitter() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn set_open_submission() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn revoke_proof() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn challenge_proof() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn resolve_challenge() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn expire_challenge() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn verify_merkle_inclusion(_d: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn add_supported_schema() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn remove_supported_schema() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn set_retention_blocks() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn set_anchor() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn finalize_block_proof_root(_n: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn set_backfill_mode() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn freeze_device() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn unfreeze_device() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn set_proof_quality() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn set_content_cid() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn set_max_total_devices() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn set_min_proof_interval() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn set_proof_validity_blocks() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn get_proofs_in_window(_n: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn mark_key_compromised() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn revoke_compromised_proofs(_n: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn heartbeat() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
 }
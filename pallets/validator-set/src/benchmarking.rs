@@ -0,0 +1,50 @@
+//! Benchmarking setup for pallet-validator-set
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use alloc::vec::Vec;
+use frame_benchmarking::v2::*;
+use frame_support::{traits::EnsureOrigin, BoundedVec};
+
+/// Fill the validator set with `n` benchmark accounts.
+fn set_validators<T: Config>(n: u32) -> Vec<T::AccountId> {
+    let validators: Vec<T::AccountId> = (0..n).map(|i| account("validator", i, 0)).collect();
+    Validators::<T>::put(BoundedVec::truncate_from(validators.clone()));
+    validators
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn add_validator() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AddRemoveOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        set_validators::<T>(T::MaxValidators::get() - 1);
+        let validator: T::AccountId = account("new", 0, 0);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, validator.clone());
+
+        assert!(Pallet::<T>::validators().contains(&validator));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn remove_validator() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AddRemoveOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let validators = set_validators::<T>(T::MaxValidators::get());
+        let validator = validators[validators.len() - 1].clone();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, validator.clone());
+
+        assert!(!Pallet::<T>::validators().contains(&validator));
+        Ok(())
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Test);
+}
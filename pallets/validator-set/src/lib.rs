@@ -0,0 +1,299 @@
+//! # Validator Set Pallet
+//!
+//! This pallet keeps the list of block-producing validators and lets
+//! governance change it without a runtime upgrade.
+//!
+//! ## Overview
+//!
+//! The Validator Set pallet enables:
+//! - Seeding the validator set at genesis
+//! - Adding and removing validators through `AddRemoveOrigin`
+//! - Handing the set to `pallet-session` as its `SessionManager`
+//! - Disabling offending validators for `DisablePeriod` sessions
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `add_validator` - Add an account to the validator set (AddRemoveOrigin)
+//! - `remove_validator` - Remove an account from the validator set (AddRemoveOrigin)
+//!
+//! ## Session Rotation
+//!
+//! The pallet implements `pallet_session::SessionManager`. Every session
+//! rotation plans the current `Validators` list, so a change made during
+//! session `n` becomes active at the start of session `n + 2`, once the
+//! session pallet has queued it for one session. The pallet also implements
+//! the `pallet_session::historical` variant, so the runtime can keep
+//! historical session roots for key ownership proofs. Aura and GRANDPA take
+//! their authorities from the session keys of the active validators, which
+//! each validator registers with `Session::set_keys` before it is added.
+//!
+//! ## Offences
+//!
+//! Validators have no stake to slash, so the runtime's offence handler calls
+//! `disable_offender` instead. The offender is disabled through `Disabler`
+//! (`pallet_session` in the runtime) for the rest of the session, and again
+//! at the start of every session up to `DisablePeriod` sessions after the
+//! offence, as the session pallet re-enables everyone when the set rotates.
+//! Disabled validators stay in the set; governance removes them with
+//! `remove_validator`.
+//!
+//! ## Bounds
+//!
+//! The set never shrinks below `MinAuthorities`, so governance cannot halt
+//! block production by removing every validator, and never grows beyond
+//! `MaxValidators`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// Disables a validator in the active session.
+pub trait DisableValidator<AccountId> {
+    /// Disable `validator` for the rest of the session. Returns `false` if
+    /// it is not an active validator.
+    fn disable(validator: &AccountId) -> bool;
+}
+
+impl<AccountId> DisableValidator<AccountId> for () {
+    fn disable(_validator: &AccountId) -> bool {
+        false
+    }
+}
+
+impl<T: pallet_session::Config> DisableValidator<T::ValidatorId> for pallet_session::Pallet<T> {
+    fn disable(validator: &T::ValidatorId) -> bool {
+        Self::disable(validator)
+    }
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use alloc::vec::Vec;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    /// The pallet's configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Weight information for extrinsics.
+        type WeightInfo: WeightInfo;
+
+        /// Origin allowed to add and remove validators
+        type AddRemoveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Fewest validators the set may hold
+        #[pallet::constant]
+        type MinAuthorities: Get<u32>;
+
+        /// Most validators the set may hold
+        #[pallet::constant]
+        type MaxValidators: Get<u32>;
+
+        /// Disables offenders in the active session
+        type Disabler: DisableValidator<Self::AccountId>;
+
+        /// Sessions after an offence during which the offender stays disabled
+        #[pallet::constant]
+        type DisablePeriod: Get<u32>;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Validators planned for the next session
+    #[pallet::storage]
+    #[pallet::getter(fn validators)]
+    pub type Validators<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
+
+    /// Last session each offender is disabled in
+    #[pallet::storage]
+    #[pallet::getter(fn disabled_until)]
+    pub type DisabledUntil<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, OptionQuery>;
+
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Validators of the first sessions
+        pub initial_validators: Vec<T::AccountId>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            let mut validators: Vec<T::AccountId> = Vec::new();
+            for validator in &self.initial_validators {
+                assert!(
+                    !validators.contains(validator),
+                    "validator listed twice in genesis"
+                );
+                validators.push(validator.clone());
+            }
+            let validators: BoundedVec<_, T::MaxValidators> = validators
+                .try_into()
+                .expect("genesis validators exceed MaxValidators");
+            Validators::<T>::put(validators);
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A validator was added; it becomes active two sessions later
+        ValidatorAdded { validator: T::AccountId },
+        /// A validator was removed; it stops producing blocks two sessions later
+        ValidatorRemoved { validator: T::AccountId },
+        /// An offender was disabled up to and including `until_session`
+        ValidatorDisabled {
+            validator: T::AccountId,
+            until_session: u32,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The account is already a validator
+        AlreadyValidator,
+        /// The account is not a validator
+        NotValidator,
+        /// The set would drop below `MinAuthorities`
+        TooFewValidators,
+        /// The set would exceed `MaxValidators`
+        TooManyValidators,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Add a validator to the set.
+        ///
+        /// The validator must have registered session keys with
+        /// `Session::set_keys`, or it is skipped by the consensus pallets.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - AddRemoveOrigin required
+        /// - `validator` - Account of the new validator
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::add_validator())]
+        pub fn add_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+            T::AddRemoveOrigin::ensure_origin(origin)?;
+
+            Validators::<T>::try_mutate(|validators| {
+                ensure!(
+                    !validators.contains(&validator),
+                    Error::<T>::AlreadyValidator
+                );
+                validators
+                    .try_push(validator.clone())
+                    .map_err(|_| Error::<T>::TooManyValidators)
+            })?;
+
+            Self::deposit_event(Event::ValidatorAdded { validator });
+
+            Ok(())
+        }
+
+        /// Remove a validator from the set.
+        ///
+        /// # Arguments
+        ///
+        /// - `origin` - AddRemoveOrigin required
+        /// - `validator` - Account of the validator to remove
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::remove_validator())]
+        pub fn remove_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+            T::AddRemoveOrigin::ensure_origin(origin)?;
+
+            Validators::<T>::try_mutate(|validators| {
+                let position = validators
+                    .iter()
+                    .position(|v| v == &validator)
+                    .ok_or(Error::<T>::NotValidator)?;
+                ensure!(
+                    validators.len() > T::MinAuthorities::get() as usize,
+                    Error::<T>::TooFewValidators
+                );
+                validators.remove(position);
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::ValidatorRemoved { validator });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Disable a validator for an offence committed in `session`.
+        ///
+        /// The validator is disabled now and through session
+        /// `session + DisablePeriod`. A later offence extends the period but
+        /// an earlier one never shortens it.
+        pub fn disable_offender(validator: &T::AccountId, session: u32) {
+            let until_session = session
+                .saturating_add(T::DisablePeriod::get())
+                .max(DisabledUntil::<T>::get(validator).unwrap_or_default());
+            DisabledUntil::<T>::insert(validator, until_session);
+            T::Disabler::disable(validator);
+
+            Self::deposit_event(Event::ValidatorDisabled {
+                validator: validator.clone(),
+                until_session,
+            });
+        }
+    }
+
+    /// Plans the current validator set for every new session, and disables
+    /// offenders again when a session starts.
+    impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+        fn new_session(_new_index: u32) -> Option<Vec<T::AccountId>> {
+            Some(Validators::<T>::get().into_inner())
+        }
+
+        fn end_session(_end_index: u32) {}
+
+        fn start_session(start_index: u32) {
+            DisabledUntil::<T>::translate(|validator, until: u32| {
+                if until < start_index {
+                    return None;
+                }
+                T::Disabler::disable(&validator);
+                Some(until)
+            });
+        }
+    }
+
+    /// Plans the validator set for `pallet_session::historical`.
+    ///
+    /// Validators have no stake, so they are fully identified by their
+    /// account and the identification is `()`.
+    impl<T: Config> pallet_session::historical::SessionManager<T::AccountId, ()> for Pallet<T> {
+        fn new_session(new_index: u32) -> Option<Vec<(T::AccountId, ())>> {
+            <Self as pallet_session::SessionManager<_>>::new_session(new_index)
+                .map(|validators| validators.into_iter().map(|v| (v, ())).collect())
+        }
+
+        fn end_session(_end_index: u32) {}
+
+        fn start_session(start_index: u32) {
+            <Self as pallet_session::SessionManager<_>>::start_session(start_index)
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Unit tests for the Validator Set pallet.
+
+use crate::{self as pallet_validator_set, *};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{ConstU32, ConstU64},
+};
+use pallet_session::SessionManager;
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        ValidatorSet: pallet_validator_set,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+    type RuntimeTask = ();
+    type SingleBlockMigrations = ();
+    type MultiBlockMigrator = ();
+    type PreInherents = ();
+    type PostInherents = ();
+    type PostTransactions = ();
+}
+
+frame_support::parameter_types! {
+    /// Validators disabled in the active session
+    pub static Disabled: Vec<u64> = vec![];
+}
+
+/// Records disabled validators in `Disabled`
+pub struct MockDisabler;
+
+impl DisableValidator<u64> for MockDisabler {
+    fn disable(validator: &u64) -> bool {
+        Disabled::mutate(|disabled| disabled.push(*validator));
+        true
+    }
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type AddRemoveOrigin = frame_system::EnsureRoot<u64>;
+    type MinAuthorities = ConstU32<2>;
+    type MaxValidators = ConstU32<4>;
+    type Disabler = MockDisabler;
+    type DisablePeriod = ConstU32<2>;
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    pallet_validator_set::GenesisConfig::<Test> {
+        initial_validators: vec![1, 2],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+#[test]
+fn genesis_validators_are_planned_for_new_sessions() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2]);
+        assert_eq!(ValidatorSet::new_session(1), Some(vec![1, 2]));
+        assert_eq!(
+            <ValidatorSet as pallet_session::historical::SessionManager<u64, ()>>::new_session(1),
+            Some(vec![(1, ()), (2, ())])
+        );
+    });
+}
+
+#[test]
+fn add_validator_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 3));
+        System::assert_last_event(Event::ValidatorAdded { validator: 3 }.into());
+        assert_eq!(ValidatorSet::new_session(2), Some(vec![1, 2, 3]));
+
+        assert_noop!(
+            ValidatorSet::add_validator(RuntimeOrigin::root(), 3),
+            Error::<Test>::AlreadyValidator
+        );
+        assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 4));
+        assert_noop!(
+            ValidatorSet::add_validator(RuntimeOrigin::root(), 5),
+            Error::<Test>::TooManyValidators
+        );
+    });
+}
+
+#[test]
+fn remove_validator_keeps_min_authorities() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 3));
+        assert_ok!(ValidatorSet::remove_validator(RuntimeOrigin::root(), 1));
+        System::assert_last_event(Event::ValidatorRemoved { validator: 1 }.into());
+        assert_eq!(ValidatorSet::new_session(2), Some(vec![2, 3]));
+
+        assert_noop!(
+            ValidatorSet::remove_validator(RuntimeOrigin::root(), 1),
+            Error::<Test>::NotValidator
+        );
+        assert_noop!(
+            ValidatorSet::remove_validator(RuntimeOrigin::root(), 2),
+            Error::<Test>::TooFewValidators
+        );
+    });
+}
+
+#[test]
+fn only_add_remove_origin_changes_the_set() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            ValidatorSet::add_validator(RuntimeOrigin::signed(1), 3),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ValidatorSet::remove_validator(RuntimeOrigin::signed(1), 2),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn offenders_stay_disabled_for_the_disable_period() {
+    new_test_ext().execute_with(|| {
+        // An offence in session 3 disables the validator now...
+        ValidatorSet::disable_offender(&1, 3);
+        System::assert_last_event(
+            Event::ValidatorDisabled {
+                validator: 1,
+                until_session: 5,
+            }
+            .into(),
+        );
+        assert_eq!(Disabled::take(), vec![1]);
+
+        // ...and again as sessions 4 and 5 start
+        for session in [4, 5] {
+            ValidatorSet::start_session(session);
+            assert_eq!(Disabled::take(), vec![1]);
+        }
+        ValidatorSet::start_session(6);
+        assert!(Disabled::take().is_empty());
+        assert_eq!(ValidatorSet::disabled_until(1), None);
+
+        // Offenders stay in the planned set
+        assert_eq!(ValidatorSet::new_session(7), Some(vec![1, 2]));
+    });
+}
+
+#[test]
+fn an_earlier_offence_does_not_shorten_the_disable_period() {
+    new_test_ext().execute_with(|| {
+        ValidatorSet::disable_offender(&1, 5);
+        ValidatorSet::disable_offender(&1, 3);
+        assert_eq!(ValidatorSet::disabled_until(1), Some(7));
+
+        ValidatorSet::disable_offender(&1, 6);
+        assert_eq!(ValidatorSet::disabled_until(1), Some(8));
+    });
+}
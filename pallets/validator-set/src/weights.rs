@@ -0,0 +1,55 @@
+//! Weight calculations for the Validator Set pallet.
+//!
+//! These weights ensure proper transaction fee calculation
+//! and prevent denial-of-service attacks.
+
+use frame_support::{traits::Get, weights::Weight};
+
+/// Weight functions for the pallet.
+pub trait WeightInfo {
+    /// Weight for adding a validator.
+    fn add_validator() -> Weight;
+
+    /// Weight for removing a validator.
+    fn remove_validator() -> Weight;
+}
+
+/// Default weight implementation.
+///
+/// Placeholder weights that should be replaced with benchmark results.
+pub struct SubstrateWeight<T>(core::marker::PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    /// Weight for adding a validator.
+    ///
+    /// Operations:
+    /// - Read and write the validator set
+    /// - Emit event
+    fn add_validator() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Weight for removing a validator.
+    ///
+    /// Operations:
+    /// - Read and write the validator set
+    /// - Emit event
+    fn remove_validator() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+}
+
+/// Unit implementation for testing.
+impl WeightInfo for () {
+    fn add_validator() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn remove_validator() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+}
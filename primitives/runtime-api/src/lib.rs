@@ -0,0 +1,26 @@
+//! # ARED Runtime API
+//!
+//! Per-device queries that span several ARED pallets, so the dashboard can
+//! fetch a device's state in one call instead of one storage query per
+//! pallet. Implemented by the runtime on top of the pallets' public helpers.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+
+pub use ared_primitives::DeviceSummary;
+
+sp_api::decl_runtime_apis! {
+    /// Combined read access to a device's state.
+    pub trait AredDeviceApi<BlockNumber>
+    where
+        BlockNumber: Codec,
+    {
+        /// Credits, energy and proof totals of a device. Unknown devices and
+        /// ids longer than a `DeviceId` report an all-zero summary.
+        fn device_summary(device_id: Vec<u8>) -> DeviceSummary<BlockNumber>;
+    }
+}
@@ -0,0 +1,195 @@
+//! # ARED Primitives
+//!
+//! Types shared by the ARED pallets and the runtime.
+//!
+//! ## Device Ids
+//!
+//! [`DeviceId`] is the bounded device identifier every pallet keys its
+//! storage by. Defining it once means the pallets cannot disagree on the
+//! maximum length, which would otherwise make an id stored by one pallet
+//! unreachable from another.
+//!
+//! Pallets index their high-volume per-device events under the topic
+//! [`DeviceId::topic`], `blake2_256` of the raw id bytes, so clients can
+//! follow one device through `System::event_topics` without decoding every
+//! block.
+//!
+//! `DeviceId` encodes exactly like the `BoundedVec<u8, ConstU32<64>>` the
+//! pallets used before, so switching to it needs no storage migration.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::ops::Deref;
+use frame_support::{traits::ConstU32, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+
+/// Maximum length of a device id, in bytes
+pub const MAX_DEVICE_ID_LENGTH: u32 = 64;
+
+/// [`MAX_DEVICE_ID_LENGTH`] as a `Get<u32>` bound
+pub type MaxDeviceIdLength = ConstU32<MAX_DEVICE_ID_LENGTH>;
+
+/// Identifier of an edge device, at most [`MAX_DEVICE_ID_LENGTH`] bytes
+#[derive(
+    Clone,
+    Default,
+    Encode,
+    Decode,
+    TypeInfo,
+    MaxEncodedLen,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    RuntimeDebug,
+)]
+pub struct DeviceId(BoundedVec<u8, MaxDeviceIdLength>);
+
+impl DeviceId {
+    /// The raw id bytes.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0.into_inner()
+    }
+
+    /// Event topic of the device: `blake2_256` of the raw id bytes (not of
+    /// their SCALE encoding).
+    pub fn topic(&self) -> [u8; 32] {
+        sp_io::hashing::blake2_256(&self.0)
+    }
+
+    /// Whether the id is valid UTF-8.
+    pub fn is_utf8(&self) -> bool {
+        core::str::from_utf8(&self.0).is_ok()
+    }
+
+    /// Whether the id is a hyphenated UUID, e.g.
+    /// `123e4567-e89b-12d3-a456-426614174000`, in either case.
+    pub fn is_uuid(&self) -> bool {
+        self.0.len() == 36
+            && self.0.iter().enumerate().all(|(i, byte)| match i {
+                8 | 13 | 18 | 23 => *byte == b'-',
+                _ => byte.is_ascii_hexdigit(),
+            })
+    }
+}
+
+impl Deref for DeviceId {
+    type Target = BoundedVec<u8, MaxDeviceIdLength>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for DeviceId {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<BoundedVec<u8, MaxDeviceIdLength>> for DeviceId {
+    fn from(id: BoundedVec<u8, MaxDeviceIdLength>) -> Self {
+        Self(id)
+    }
+}
+
+impl From<DeviceId> for BoundedVec<u8, MaxDeviceIdLength> {
+    fn from(id: DeviceId) -> Self {
+        id.0
+    }
+}
+
+impl From<DeviceId> for Vec<u8> {
+    fn from(id: DeviceId) -> Self {
+        id.into_inner()
+    }
+}
+
+/// Fails if the id is longer than [`MAX_DEVICE_ID_LENGTH`].
+impl TryFrom<Vec<u8>> for DeviceId {
+    type Error = ();
+
+    fn try_from(id: Vec<u8>) -> Result<Self, Self::Error> {
+        BoundedVec::try_from(id).map(Self).map_err(|_| ())
+    }
+}
+
+/// Fails if the id is longer than [`MAX_DEVICE_ID_LENGTH`].
+impl TryFrom<&[u8]> for DeviceId {
+    type Error = ();
+
+    fn try_from(id: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_from(id.to_vec())
+    }
+}
+
+/// A device's state across the ARED pallets, as returned by the
+/// `AredDeviceApi` runtime API.
+#[derive(Clone, Default, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+pub struct DeviceSummary<BlockNumber> {
+    /// Carbon credits held by the device
+    pub credits: u128,
+    /// Energy in Wh recorded but not yet claimed as credits
+    pub pending_energy_wh: u128,
+    /// Lifetime energy in Wh recorded for the device
+    pub total_energy_wh: u128,
+    /// Lifetime number of telemetry proofs submitted for the device
+    pub proof_count: u64,
+    /// Block of the device's latest retained proof
+    pub latest_proof_block: Option<BlockNumber>,
+    /// Number of credit claims made for the device
+    pub issuance_count: u32,
+}
+
+/// What claiming a device's accumulated energy would issue, as returned by
+/// the `CarbonCreditsApi` runtime API.
+#[derive(Clone, Default, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+pub struct ClaimPreview {
+    /// Energy in Wh the claim would convert
+    pub energy_wh: u128,
+    /// CO2 avoided by that energy, in kg
+    pub co2_kg: u128,
+    /// Credits the claim would issue
+    pub credits: u128,
+    /// Whether the energy reaches the minimum a claim needs
+    pub meets_minimum: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn length_is_bounded() {
+        assert!(DeviceId::try_from(vec![b'a'; 64]).is_ok());
+        assert!(DeviceId::try_from(vec![b'a'; 65]).is_err());
+    }
+
+    #[test]
+    fn encodes_like_a_bounded_vec() {
+        let raw: BoundedVec<u8, MaxDeviceIdLength> = b"device-001".to_vec().try_into().unwrap();
+        let id = DeviceId::from(raw.clone());
+        assert_eq!(id.encode(), raw.encode());
+        assert_eq!(DeviceId::decode(&mut &raw.encode()[..]).unwrap(), id);
+    }
+
+    #[test]
+    fn topic_hashes_the_raw_bytes() {
+        let id = DeviceId::try_from(&b"device-001"[..]).unwrap();
+        assert_eq!(id.topic(), sp_io::hashing::blake2_256(b"device-001"));
+        assert_ne!(id.topic(), sp_io::hashing::blake2_256(&id.encode()));
+    }
+
+    #[test]
+    fn recognises_uuids() {
+        let uuid = DeviceId::try_from(&b"123e4567-e89b-12d3-A456-426614174000"[..]).unwrap();
+        assert!(uuid.is_uuid());
+        assert!(uuid.is_utf8());
+        assert!(!DeviceId::try_from(&b"device-001"[..]).unwrap().is_uuid());
+        assert!(!DeviceId::try_from(&[0xffu8][..]).unwrap().is_utf8());
+    }
+}
@@ -0,0 +1,368 @@
+//! Genesis presets of the ARED Edge runtime.
+//!
+//! `GenesisBuilder::get_preset` serves these JSON patches of
+//! `RuntimeGenesisConfig`, so the node and `chain-spec-builder` build chain
+//! specs from the runtime itself:
+//! - `development` - single validator (Alice) with sudo
+//! - `local_testnet` - two validators (Alice, Bob) with sudo
+//! - `live` - three validators governed by the technical committee
+//!
+//! ## Account Structure
+//!
+//! The ARED Edge network uses a hierarchy of accounts:
+//! - **Root/Sudo**: Administrative control (development only)
+//! - **Technical Committee**: Parameter governance on live chains, where
+//!   there is no sudo key (seeded with the validator operators)
+//! - **Bridge Account**: Submits telemetry proofs from the Ingest Service; a
+//!   2-of-3 multisig of the `Bridge1`..`Bridge3` signer keys
+//! - **Validator Accounts**: Block producers (Aura) and finalizers (Grandpa)
+//!
+//! All keys are derived from well-known seeds. The `live` preset is a
+//! placeholder for pre-production testing; real deployments load a chain spec
+//! with externally generated keys (see docs/KEY_GENERATION.md).
+
+use crate::{
+    opaque::SessionKeys, AccountId, Balance, CarbonCreditAssetId, CarbonCreditAssetOwner,
+    ImOnlineId, Multisig, ARED,
+};
+use alloc::{vec, vec::Vec};
+use serde_json::{json, Value};
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
+use sp_core::{crypto::get_public_from_string_or_panic, sr25519, H256};
+use sp_genesis_builder::PresetId;
+
+/// Preset of a live chain, with placeholder keys
+pub const LIVE_RUNTIME_PRESET: &str = "live";
+
+/// Initial balance for pre-funded accounts.
+const INITIAL_BALANCE: Balance = ARED;
+
+/// Initial balance for the bridge multisig account (0.1 ARED).
+const BRIDGE_BALANCE: Balance = ARED / 10;
+
+/// Initial balance for each bridge signer key, covering transaction fees and
+/// the deposit held while a multisig operation awaits approvals.
+const BRIDGE_SIGNER_BALANCE: Balance = BRIDGE_BALANCE / 2;
+
+/// ARED minted to the author of every block (0.001 ARED).
+const BLOCK_REWARD: Balance = ARED / 1_000;
+
+/// Approvals of bridge signer keys needed to dispatch a bridge call.
+pub const BRIDGE_MULTISIG_THRESHOLD: u16 = 2;
+
+/// Decimals of the carbon credit asset: 1,000 credits are one tonne of CO2e.
+const CARBON_CREDIT_DECIMALS: u8 = 3;
+
+/// Ed25519 public keys of the placeholder live node keys 0x..01 to 0x..03,
+/// the `--node-key` of each validator.
+const LIVE_NODE_KEYS: [[u8; 32]; 3] = [
+    [
+        0x1c, 0xe5, 0xf0, 0x0e, 0xf6, 0xe8, 0x93, 0x74, 0xaf, 0xb6, 0x25, 0xf1, 0xae, 0x4c, 0x15,
+        0x46, 0xd3, 0x12, 0x34, 0xe8, 0x7e, 0x3c, 0x3f, 0x51, 0xa6, 0x2b, 0x91, 0xdd, 0x6b, 0xfa,
+        0x57, 0xdf,
+    ],
+    [
+        0xda, 0xcd, 0xe7, 0x71, 0x4d, 0x85, 0x51, 0xf6, 0x74, 0xb8, 0xbb, 0x4b, 0x54, 0x23, 0x93,
+        0x83, 0xc7, 0x6a, 0x2b, 0x28, 0x6f, 0xa4, 0x36, 0xe9, 0x3b, 0x2b, 0x7e, 0xb2, 0x26, 0xbf,
+        0x4d, 0xe7,
+    ],
+    [
+        0x87, 0x6a, 0x7b, 0x49, 0x84, 0xf9, 0x80, 0x06, 0xdc, 0x8d, 0x66, 0x6e, 0x28, 0xb6, 0x0d,
+        0xe3, 0x07, 0x30, 0x98, 0x35, 0xd7, 0x75, 0xe7, 0x75, 0x5c, 0xc7, 0x70, 0x32, 0x8c, 0xda,
+        0xcf, 0x2e,
+    ],
+];
+
+/// Generate an account ID from seed.
+pub fn get_account_id_from_seed(seed: &str) -> AccountId {
+    get_public_from_string_or_panic::<sr25519::Public>(seed).into()
+}
+
+/// Generate authority keys (Aura, Grandpa, ImOnline) from seed.
+pub fn authority_keys_from_seed(seed: &str) -> (AuraId, GrandpaId, ImOnlineId) {
+    (
+        get_public_from_string_or_panic::<AuraId>(seed),
+        get_public_from_string_or_panic::<GrandpaId>(seed),
+        get_public_from_string_or_panic::<ImOnlineId>(seed),
+    )
+}
+
+/// Derive the bridge multisig account of `signers` at
+/// `BRIDGE_MULTISIG_THRESHOLD`.
+///
+/// The address is the same one `Multisig::as_multi` dispatches from, so the
+/// signer order does not matter.
+pub fn bridge_multisig_account(signers: &[AccountId]) -> AccountId {
+    let mut signers = signers.to_vec();
+    signers.sort();
+    Multisig::multi_account_id(&signers, BRIDGE_MULTISIG_THRESHOLD)
+}
+
+/// The seed-derived bridge signer keys (`Bridge1`..`Bridge3`) and their
+/// 2-of-3 multisig account, which holds the bridge's funds and memberships.
+pub fn bridge_multisig_from_seeds() -> ([AccountId; 3], AccountId) {
+    let signers = ["Bridge1", "Bridge2", "Bridge3"].map(get_account_id_from_seed);
+    let multisig = bridge_multisig_account(&signers);
+    (signers, multisig)
+}
+
+/// Genesis of the `tCO2e credit` asset mirroring carbon credits.
+///
+/// The asset is owned by the keyless carbon credits pallet account and is
+/// sufficient, so credit holders need no ARED balance.
+fn carbon_credit_asset_genesis() -> Value {
+    let id = CarbonCreditAssetId::get();
+    let owner = CarbonCreditAssetOwner::get();
+
+    json!({
+        "assets": [[id, owner, true, 1]],
+        "metadata": [[
+            id,
+            b"tCO2e credit".to_vec(),
+            b"tCO2e".to_vec(),
+            CARBON_CREDIT_DECIMALS
+        ]]
+    })
+}
+
+/// Session genesis entry `[account, validator id, keys]` for a validator
+/// whose Aura, Grandpa and ImOnline keys are derived from `seed`.
+///
+/// Aura and Grandpa take their first authorities from these session keys.
+fn session_keys_from_seed(validator: &AccountId, seed: &str) -> Value {
+    let (aura, grandpa, im_online) = authority_keys_from_seed(seed);
+
+    json!([
+        validator,
+        validator,
+        SessionKeys {
+            aura,
+            grandpa,
+            im_online
+        }
+    ])
+}
+
+/// Node authorization genesis entry `[peer id bytes, owner]` for a
+/// well-known node with the Ed25519 node key `public`.
+///
+/// The PeerId of an Ed25519 key is the identity multihash of its protobuf
+/// encoding: a fixed 6-byte prefix followed by the key.
+fn well_known_node(public: &[u8; 32], owner: &AccountId) -> Value {
+    let mut peer_id = vec![0x00, 0x24, 0x08, 0x01, 0x12, 0x20];
+    peer_id.extend_from_slice(public);
+
+    json!([peer_id, owner])
+}
+
+/// Genesis configuration for development network.
+fn development_genesis() -> Value {
+    let alice = get_account_id_from_seed("Alice");
+    let bob = get_account_id_from_seed("Bob");
+    let (bridge_signers, bridge) = bridge_multisig_from_seeds();
+
+    json!({
+        "balances": {
+            "balances": [
+                [alice, INITIAL_BALANCE],
+                [bob, INITIAL_BALANCE / 10],
+                [bridge, BRIDGE_BALANCE],
+                [bridge_signers[0], BRIDGE_SIGNER_BALANCE],
+                [bridge_signers[1], BRIDGE_SIGNER_BALANCE],
+                [bridge_signers[2], BRIDGE_SIGNER_BALANCE]
+            ]
+        },
+        "validatorSet": {
+            "initialValidators": [alice]
+        },
+        "session": {
+            "keys": [session_keys_from_seed(&alice, "Alice")]
+        },
+        "sudo": {
+            "key": alice
+        },
+        // The sudo key doubles as identity registrar on dev chains
+        "operatorIdentities": {
+            "registrars": [alice],
+            "identities": [
+                [bridge, b"ARED Bridge".to_vec()],
+                [alice, b"ARED Validator Alice".to_vec()]
+            ]
+        },
+        "blockRewards": {
+            "blockReward": BLOCK_REWARD
+        },
+        "assets": carbon_credit_asset_genesis(),
+        "bridgeMembers": {
+            "members": [bridge]
+        },
+        "telemetryProofs": {
+            "supportedSchemas": [1]
+        },
+        "deviceRegistry": {
+            "devices": [[
+                bridge,
+                b"device-001".to_vec(),
+                b"ARED-STOVE-1".to_vec(),
+                H256::repeat_byte(0x01),
+                H256::repeat_byte(0x02)
+            ]]
+        }
+    })
+}
+
+/// Genesis configuration for local testnet.
+fn local_testnet_genesis() -> Value {
+    let alice = get_account_id_from_seed("Alice");
+    let bob = get_account_id_from_seed("Bob");
+    let charlie = get_account_id_from_seed("Charlie");
+    let (bridge_signers, bridge) = bridge_multisig_from_seeds();
+
+    json!({
+        "balances": {
+            "balances": [
+                [alice, INITIAL_BALANCE],
+                [bob, INITIAL_BALANCE],
+                [charlie, INITIAL_BALANCE / 10],
+                [bridge, BRIDGE_BALANCE],
+                [bridge_signers[0], BRIDGE_SIGNER_BALANCE],
+                [bridge_signers[1], BRIDGE_SIGNER_BALANCE],
+                [bridge_signers[2], BRIDGE_SIGNER_BALANCE]
+            ]
+        },
+        "validatorSet": {
+            "initialValidators": [alice, bob]
+        },
+        "session": {
+            "keys": [
+                session_keys_from_seed(&alice, "Alice"),
+                session_keys_from_seed(&bob, "Bob")
+            ]
+        },
+        "sudo": {
+            "key": alice
+        },
+        // The sudo key doubles as identity registrar on dev chains
+        "operatorIdentities": {
+            "registrars": [alice],
+            "identities": [
+                [bridge, b"ARED Bridge".to_vec()],
+                [alice, b"ARED Validator Alice".to_vec()],
+                [bob, b"ARED Validator Bob".to_vec()]
+            ]
+        },
+        "blockRewards": {
+            "blockReward": BLOCK_REWARD
+        },
+        "assets": carbon_credit_asset_genesis(),
+        "bridgeMembers": {
+            "members": [bridge]
+        },
+        "telemetryProofs": {
+            "supportedSchemas": [1]
+        },
+        "deviceRegistry": {
+            "devices": [[
+                bridge,
+                b"device-001".to_vec(),
+                b"ARED-STOVE-1".to_vec(),
+                H256::repeat_byte(0x01),
+                H256::repeat_byte(0x02)
+            ]]
+        }
+    })
+}
+
+/// Placeholder genesis configuration for a live network.
+///
+/// WARNING: These keys are derived from well-known seeds and are NOT secure.
+/// For mainnet, generate a chain spec JSON externally using `subkey` and
+/// pass its path via `CHAIN_SPEC_PATH` or `--chain=/path/to/spec.json`.
+fn live_genesis() -> Value {
+    let root = get_account_id_from_seed("Root");
+    let (bridge_signers, bridge) = bridge_multisig_from_seeds();
+    let validator1 = get_account_id_from_seed("Validator1");
+    let validator2 = get_account_id_from_seed("Validator2");
+    let validator3 = get_account_id_from_seed("Validator3");
+
+    json!({
+        "balances": {
+            "balances": [
+                [root, INITIAL_BALANCE],
+                [bridge, BRIDGE_BALANCE],
+                [bridge_signers[0], BRIDGE_SIGNER_BALANCE],
+                [bridge_signers[1], BRIDGE_SIGNER_BALANCE],
+                [bridge_signers[2], BRIDGE_SIGNER_BALANCE],
+                [validator1, BRIDGE_BALANCE],
+                [validator2, BRIDGE_BALANCE],
+                [validator3, BRIDGE_BALANCE]
+            ]
+        },
+        "validatorSet": {
+            "initialValidators": [validator1, validator2, validator3]
+        },
+        "session": {
+            "keys": [
+                session_keys_from_seed(&validator1, "Validator1"),
+                session_keys_from_seed(&validator2, "Validator2"),
+                session_keys_from_seed(&validator3, "Validator3")
+            ]
+        },
+        // PeerIds 12D3KooWBmAw.., 12D3KooWQYV9.. and 12D3KooWJvyP..
+        "nodeAuthorization": {
+            "nodes": [
+                well_known_node(&LIVE_NODE_KEYS[0], &validator1),
+                well_known_node(&LIVE_NODE_KEYS[1], &validator2),
+                well_known_node(&LIVE_NODE_KEYS[2], &validator3)
+            ]
+        },
+        "blockRewards": {
+            "blockReward": BLOCK_REWARD
+        },
+        "assets": carbon_credit_asset_genesis(),
+        "bridgeMembers": {
+            "members": [bridge]
+        },
+        "telemetryProofs": {
+            "supportedSchemas": [1]
+        },
+        // No sudo on live chains: the technical committee governs parameters,
+        // and the call filter blocks sudo and plain balance transfers
+        "productionMode": {
+            "enabled": true
+        },
+        "technicalCommittee": {
+            "members": [validator1, validator2, validator3]
+        },
+        // Committee members judge identities; operators set their own
+        "operatorIdentities": {
+            "registrars": [validator1, validator2, validator3]
+        }
+    })
+}
+
+/// The JSON patch of preset `id`, or `None` for an unknown preset.
+pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
+    let patch = match id.try_into() {
+        Ok(sp_genesis_builder::DEV_RUNTIME_PRESET) => development_genesis(),
+        Ok(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET) => local_testnet_genesis(),
+        Ok(LIVE_RUNTIME_PRESET) => live_genesis(),
+        _ => return None,
+    };
+
+    Some(
+        serde_json::to_string(&patch)
+            .expect("serialization to json is expected to work; qed")
+            .into_bytes(),
+    )
+}
+
+/// Names of the presets served by `get_preset`.
+pub fn preset_names() -> Vec<PresetId> {
+    vec![
+        PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
+        PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+        PresetId::from(LIVE_RUNTIME_PRESET),
+    ]
+}
@@ -11,14 +11,21 @@ extern crate alloc;
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+pub mod genesis_config_presets;
+
+#[cfg(test)]
+mod tests;
+
 use alloc::{vec, vec::Vec};
+use ared_primitives::DeviceId;
+pub use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
     generic, impl_opaque_keys,
-    traits::{BlakeTwo256, Block as BlockT, IdentifyAccount, Verify},
+    traits::{AccountIdConversion, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify},
     transaction_validity::{TransactionSource, TransactionValidity},
     ApplyExtrinsicResult, ExtrinsicInclusionMode, MultiSignature,
 };
@@ -42,6 +49,9 @@ pub type Signature = MultiSignature;
 /// Balance type
 pub type Balance = u128;
 
+/// One ARED token in the smallest unit (18 decimals)
+pub const ARED: Balance = 1_000_000_000_000_000_000;
+
 /// Block number type
 pub type BlockNumber = u32;
 
@@ -78,8 +88,28 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
+/// Storage migrations run in full within the upgrade block.
+///
+/// Only migrations bounded by a small, known amount of state belong here;
+/// anything that walks a growing map goes in [`MultiBlockMigrationsList`].
+/// See "Adding a Migration" in `docs/RUNTIME_CONFIGURATION.md`.
+pub type Migrations = ();
+
+/// Stepped migrations run by `pallet-migrations` after an upgrade, oldest
+/// first, a weight-limited step per block while transactions are held back.
+///
+/// Each does nothing once its pallet's on-chain storage version is reached,
+/// so members stay listed until every network has upgraded past them.
+pub type MultiBlockMigrationsList = (
+    pallet_telemetry_proofs::migrations::v9::MigrateV0ToV9<Runtime>,
+    pallet_carbon_credits::migrations::v1::MigrateToV1<Runtime>,
+    pallet_carbon_credits::migrations::v2::MigrateToV2<Runtime>,
+    pallet_telemetry_proofs::migrations::v10::MigrateChallengeDepositsToHolds<Runtime, Balances>,
+);
+
 /// The `TransactionExtension` to the basic transaction logic.
 /// Aligned with subxt's SubstrateExtrinsicParams for client compatibility.
 pub type TxExtension = (
@@ -88,7 +118,10 @@ pub type TxExtension = (
     frame_system::CheckGenesis<Runtime>,
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
-    pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    pallet_skip_feeless_payment::SkipCheckIfFeeless<
+        Runtime,
+        pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    >,
 );
 
 /// Signed extras for transactions (alias for backward compatibility)
@@ -108,6 +141,7 @@ pub mod opaque {
         pub struct SessionKeys {
             pub aura: Aura,
             pub grandpa: Grandpa,
+            pub im_online: ImOnline,
         }
     }
 }
@@ -118,7 +152,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: sp_runtime::create_runtime_str!("ared-edge"),
     impl_name: sp_runtime::create_runtime_str!("ared-edge-node"),
     authoring_version: 1,
-    spec_version: 104,
+    spec_version: 142,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -126,10 +160,16 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 };
 
 /// Maximum block weight
-#[allow(dead_code)]
 const MAXIMUM_BLOCK_WEIGHT: Weight =
     Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND.saturating_mul(2), u64::MAX);
 
+/// Share of block weight and length open to normal transactions; the rest is
+/// reserved for operational ones such as proof and energy submissions
+const NORMAL_DISPATCH_RATIO: sp_runtime::Perbill = sp_runtime::Perbill::from_percent(75);
+
+/// Maximum encoded block length (5 MiB)
+const MAXIMUM_BLOCK_LENGTH: u32 = 5 * 1024 * 1024;
+
 /// Block execution time target (6 seconds)
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
 pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
@@ -148,13 +188,33 @@ pub fn native_version() -> sp_version::NativeVersion {
     }
 }
 
+frame_support::parameter_types! {
+    /// Normal transactions fill at most 75% of a block, so telemetry anchoring
+    /// keeps a quarter of every block during congestion
+    pub RuntimeBlockWeights: frame_system::limits::BlockWeights =
+        frame_system::limits::BlockWeights::with_sensible_defaults(
+            MAXIMUM_BLOCK_WEIGHT,
+            NORMAL_DISPATCH_RATIO,
+        );
+    pub RuntimeBlockLength: frame_system::limits::BlockLength =
+        frame_system::limits::BlockLength::max_with_normal_ratio(
+            MAXIMUM_BLOCK_LENGTH,
+            NORMAL_DISPATCH_RATIO,
+        );
+}
+
 // Frame system configuration
 #[derive_impl(frame_system::config_preludes::SolochainDefaultConfig as frame_system::DefaultConfig)]
 impl frame_system::Config for Runtime {
+    type BaseCallFilter = BaseCallFilter;
     type Block = Block;
+    type BlockWeights = RuntimeBlockWeights;
+    type BlockLength = RuntimeBlockLength;
     type BlockHashCount = frame_support::traits::ConstU32<256>;
     type Version = ();
     type AccountData = pallet_balances::AccountData<Balance>;
+    /// Blocks carry only inherents while a stepped migration is running
+    type MultiBlockMigrator = MultiBlockMigrations;
 }
 
 // Timestamp pallet configuration
@@ -168,7 +228,8 @@ impl pallet_timestamp::Config for Runtime {
 // Aura consensus configuration
 impl pallet_aura::Config for Runtime {
     type AuthorityId = AuraId;
-    type DisabledValidators = ();
+    /// Validators disabled for an offence may not author blocks
+    type DisabledValidators = Session;
     type MaxAuthorities = frame_support::traits::ConstU32<32>;
     type AllowMultipleBlocksPerSlot = frame_support::traits::ConstBool<false>;
     type SlotDuration = pallet_aura::MinimumPeriodTimesTwo<Runtime>;
@@ -180,9 +241,207 @@ impl pallet_grandpa::Config for Runtime {
     type WeightInfo = ();
     type MaxAuthorities = frame_support::traits::ConstU32<32>;
     type MaxNominators = frame_support::traits::ConstU32<0>;
-    type MaxSetIdSessionEntries = ();
-    type KeyOwnerProof = sp_core::Void;
-    type EquivocationReportSystem = ();
+    type MaxSetIdSessionEntries = MaxSetIdSessionEntries;
+    type KeyOwnerProof = sp_session::MembershipProof;
+    type EquivocationReportSystem =
+        pallet_grandpa::EquivocationReportSystem<Self, Offences, Historical, ReportLongevity>;
+}
+
+frame_support::parameter_types! {
+    /// Equivocation reports are accepted for one week
+    pub const ReportLongevity: u64 = 7 * DAYS as u64;
+    /// Set id to session mappings kept for reports: one per hourly session for a week
+    pub const MaxSetIdSessionEntries: u64 = 7 * 24;
+}
+
+// Unsigned transactions from the runtime, e.g. GRANDPA equivocation reports
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+    RuntimeCall: From<C>,
+{
+    type Extrinsic = UncheckedExtrinsic;
+    type OverarchingCall = RuntimeCall;
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+    type Public = <Signature as Verify>::Signer;
+    type Signature = Signature;
+}
+
+// Signed transactions from offchain workers, e.g. telemetry heartbeats
+impl<C> frame_system::offchain::CreateSignedTransaction<C> for Runtime
+where
+    RuntimeCall: From<C>,
+{
+    fn create_transaction<S: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+        call: RuntimeCall,
+        public: <Signature as Verify>::Signer,
+        account: AccountId,
+        nonce: Nonce,
+    ) -> Option<(
+        RuntimeCall,
+        <UncheckedExtrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload,
+    )> {
+        use codec::Encode;
+        use frame_support::traits::Get;
+        use sp_runtime::{generic::Era, SaturatedConversion};
+
+        // Mortal for as long as the block hash stays queryable
+        let period = <Runtime as frame_system::Config>::BlockHashCount::get() as u64;
+        let current_block = System::block_number()
+            .saturated_into::<u64>()
+            // The offchain worker runs on the parent of the block being built
+            .saturating_sub(1);
+        let extra: SignedExtra = (
+            frame_system::CheckSpecVersion::<Runtime>::new(),
+            frame_system::CheckTxVersion::<Runtime>::new(),
+            frame_system::CheckGenesis::<Runtime>::new(),
+            frame_system::CheckEra::<Runtime>::from(Era::mortal(period, current_block)),
+            frame_system::CheckNonce::<Runtime>::from(nonce),
+            pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
+                pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+            ),
+        );
+        let raw_payload = generic::SignedPayload::new(call, extra).ok()?;
+        let signature = raw_payload.using_encoded(|payload| S::sign(payload, public))?;
+        let (call, extra, _) = raw_payload.deconstruct();
+        Some((call, (account.into(), signature, extra)))
+    }
+}
+
+// Block author lookup, used to credit offence reports
+impl pallet_authorship::Config for Runtime {
+    type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+    /// Authoring a block counts as being online for the session and earns
+    /// the block reward
+    type EventHandler = (ImOnline, BlockRewards);
+}
+
+frame_support::parameter_types! {
+    /// Sessions last one hour
+    pub const SessionPeriod: BlockNumber = HOURS;
+    /// The first session ends at the first multiple of `SessionPeriod`
+    pub const SessionOffset: BlockNumber = 0;
+}
+
+// Session configuration: Aura and GRANDPA authorities follow the validator set
+impl pallet_session::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type ValidatorId = AccountId;
+    type ValidatorIdOf = sp_runtime::traits::ConvertInto;
+    type ShouldEndSession = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+    type NextSessionRotation = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+    type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, ValidatorSet>;
+    type SessionHandler =
+        <opaque::SessionKeys as sp_runtime::traits::OpaqueKeys>::KeyTypeIdProviders;
+    type Keys = opaque::SessionKeys;
+    type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
+// Historical session roots, so key ownership can be proven for past sessions
+impl pallet_session::historical::Config for Runtime {
+    type FullIdentification = ();
+    type FullIdentificationOf = ValidatorIdentificationOf;
+}
+
+/// Validators have no stake, so an account fully identifies an offender
+pub struct ValidatorIdentificationOf;
+
+impl sp_runtime::traits::Convert<AccountId, Option<()>> for ValidatorIdentificationOf {
+    fn convert(_validator: AccountId) -> Option<()> {
+        Some(())
+    }
+}
+
+// Offences configuration
+impl pallet_offences::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
+    type OnOffenceHandler = DisableOffenders;
+}
+
+/// Disables offending validators for the validator set's `DisablePeriod`.
+///
+/// The chain is permissioned and validators have no stake to slash, so
+/// offences are answered by stopping the validator from authoring. Aura
+/// rejects blocks from disabled validators, and the validator set disables
+/// them again at the start of each session until the period ends.
+pub struct DisableOffenders;
+
+impl
+    sp_staking::offence::OnOffenceHandler<
+        AccountId,
+        pallet_session::historical::IdentificationTuple<Runtime>,
+        Weight,
+    > for DisableOffenders
+{
+    fn on_offence(
+        offenders: &[sp_staking::offence::OffenceDetails<
+            AccountId,
+            pallet_session::historical::IdentificationTuple<Runtime>,
+        >],
+        _slash_fraction: &[sp_runtime::Perbill],
+        session: sp_staking::SessionIndex,
+    ) -> Weight {
+        use frame_support::traits::Get;
+
+        for details in offenders {
+            let (validator, ()) = &details.offender;
+            ValidatorSet::disable_offender(validator, session);
+        }
+        // Reads the offender's period, validators and disabled validators,
+        // writes the period and disabled validators
+        <Runtime as frame_system::Config>::DbWeight::get()
+            .reads_writes(3, 2)
+            .saturating_mul(offenders.len() as u64)
+    }
+}
+
+frame_support::parameter_types! {
+    /// Heartbeats are ordered ahead of all other unsigned transactions
+    pub const ImOnlineUnsignedPriority: sp_runtime::transaction_validity::TransactionPriority =
+        sp_runtime::transaction_validity::TransactionPriority::MAX;
+}
+
+// Validator liveness: heartbeats, reporting `SomeOffline` at session end
+impl pallet_im_online::Config for Runtime {
+    type AuthorityId = ImOnlineId;
+    type RuntimeEvent = RuntimeEvent;
+    type NextSessionRotation = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+    type ValidatorSet = Historical;
+    type ReportUnresponsiveness = Offences;
+    type UnsignedPriority = ImOnlineUnsignedPriority;
+    type WeightInfo = pallet_im_online::weights::SubstrateWeight<Runtime>;
+    /// Matches `MaxValidators`
+    type MaxKeys = frame_support::traits::ConstU32<32>;
+    type MaxPeerInHeartbeats = frame_support::traits::ConstU32<10_000>;
+}
+
+// Permissioned networking: only well-known nodes and their connections may peer
+impl pallet_node_authorization::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxWellKnownNodes = frame_support::traits::ConstU32<32>;
+    /// Encoded libp2p PeerIds are 38 bytes for Ed25519 node keys
+    type MaxPeerIdLength = frame_support::traits::ConstU32<128>;
+    type AddOrigin = EnsureRootOrHalfCommittee;
+    type RemoveOrigin = EnsureRootOrHalfCommittee;
+    type SwapOrigin = EnsureRootOrHalfCommittee;
+    type ResetOrigin = EnsureRootOrHalfCommittee;
+    type WeightInfo = ();
+}
+
+// Validator set configuration
+impl pallet_validator_set::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_validator_set::weights::SubstrateWeight<Runtime>;
+    type AddRemoveOrigin = EnsureRootOrHalfCommittee;
+    type MinAuthorities = frame_support::traits::ConstU32<1>;
+    /// Matches `MaxAuthorities` of Aura and GRANDPA
+    type MaxValidators = frame_support::traits::ConstU32<32>;
+    /// Offenders are disabled in the active session
+    type Disabler = Session;
+    /// Offenders stay disabled for a day of hourly sessions
+    type DisablePeriod = frame_support::traits::ConstU32<24>;
 }
 
 // Balances pallet configuration
@@ -196,10 +455,25 @@ impl pallet_balances::Config for Runtime {
     type ExistentialDeposit = frame_support::traits::ConstU128<500>;
     type AccountStore = System;
     type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
-    type RuntimeHoldReason = ();
-    type RuntimeFreezeReason = ();
-    type FreezeIdentifier = ();
-    type MaxFreezes = ();
+    /// Hold reasons declared by the pallets, e.g. device registration and
+    /// proof deposits; every reason can hold funds on an account at once
+    type RuntimeHoldReason = RuntimeHoldReason;
+    /// Freeze reasons declared by the pallets, one freeze per reason
+    type RuntimeFreezeReason = RuntimeFreezeReason;
+    type FreezeIdentifier = RuntimeFreezeReason;
+    type MaxFreezes = frame_support::traits::VariantCountOf<RuntimeFreezeReason>;
+}
+
+// Block author rewards, set by governance
+impl pallet_block_rewards::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_block_rewards::weights::SubstrateWeight<Runtime>;
+    type Currency = Balances;
+    type UpdateOrigin = EnsureRootOrHalfCommittee;
+    /// Caps inflation from block rewards at 10 ARED per block
+    type MaxBlockReward = frame_support::traits::ConstU128<{ 10 * ARED }>;
+    /// Base fees not routed to the author are kept in the treasury account
+    type FeeDestination = frame_support::traits::fungible::ResolveTo<TreasuryAccount, Balances>;
 }
 
 /// Constant fee multiplier for transaction payment
@@ -213,14 +487,26 @@ impl frame_support::traits::Get<FixedU128> for ConstantFeeMultiplier {
 // Transaction payment configuration
 impl pallet_transaction_payment::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
-    type OnChargeTransaction = FungibleAdapter<Balances, ()>;
-    type OperationalFeeMultiplier = frame_support::traits::ConstU8<5>;
+    /// Tips go to the block author; base fees go to the treasury unless
+    /// governance routes them to the author too
+    type OnChargeTransaction =
+        FungibleAdapter<Balances, pallet_block_rewards::DealWithFees<Runtime>>;
+    /// Proof and energy submissions are operational and feeless for bridge
+    /// members, so the multiplier only lifts the priority of other payers;
+    /// it is kept at 1 so they gain little over normal transactions
+    type OperationalFeeMultiplier = frame_support::traits::ConstU8<1>;
     type WeightToFee = frame_support::weights::IdentityFee<Balance>;
     type LengthToFee = frame_support::weights::IdentityFee<Balance>;
     type FeeMultiplierUpdate =
         pallet_transaction_payment::ConstFeeMultiplier<ConstantFeeMultiplier>;
 }
 
+// Skip feeless payment: bridge members' telemetry calls marked `feeless_if`
+// are not charged; the wrapped extension encodes like `ChargeTransactionPayment`
+impl pallet_skip_feeless_payment::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+}
+
 // Sudo pallet configuration (for development)
 impl pallet_sudo::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
@@ -228,26 +514,495 @@ impl pallet_sudo::Config for Runtime {
     type WeightInfo = pallet_sudo::weights::SubstrateWeight<Runtime>;
 }
 
+// Production mode: switched on at genesis on production chains, or for good by governance
+impl pallet_production_mode::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_production_mode::weights::SubstrateWeight<Runtime>;
+    type EnableOrigin = EnsureRootOrHalfCommittee;
+    /// Runtimes built with the `production` feature always run in production mode
+    type ForceEnabled = frame_support::traits::ConstBool<{ cfg!(feature = "production") }>;
+}
+
+/// Call filter of the runtime.
+///
+/// In production mode it blocks sudo and plain balance transfers, which do
+/// not belong on the permissioned chain; they fail with `CallFiltered`.
+/// System, consensus, telemetry and carbon calls are never blocked. Only
+/// Root origins, such as root-track scheduler calls, bypass the filter;
+/// committee motions dispatch with the collective origin and are filtered.
+pub struct BaseCallFilter;
+
+impl frame_support::traits::Contains<RuntimeCall> for BaseCallFilter {
+    fn contains(call: &RuntimeCall) -> bool {
+        if !ProductionMode::is_enabled() {
+            return true;
+        }
+        !matches!(
+            call,
+            RuntimeCall::Sudo(_)
+                | RuntimeCall::Balances(
+                    pallet_balances::Call::transfer_allow_death { .. }
+                        | pallet_balances::Call::transfer_keep_alive { .. }
+                        | pallet_balances::Call::transfer_all { .. }
+                        | pallet_balances::Call::force_transfer { .. }
+                )
+        )
+    }
+}
+
+// Utility pallet: batches calls, e.g. a proof and its energy record in one
+// `batch_all` that lands atomically
+impl pallet_utility::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
+    type PalletsOrigin = OriginCaller;
+    type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+}
+
+frame_support::parameter_types! {
+    /// Held from the first approver of a multisig operation: 0.01 ARED...
+    pub const MultisigDepositBase: Balance = ARED / 100;
+    /// ...plus 0.001 ARED per signatory
+    pub const MultisigDepositFactor: Balance = ARED / 1_000;
+}
+
+// Multisig pallet: the bridge account is a 2-of-3 multisig of the bridge
+// signer keys, so no single key can submit telemetry or record energy
+impl pallet_multisig::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
+    type Currency = Balances;
+    type DepositBase = MultisigDepositBase;
+    type DepositFactor = MultisigDepositFactor;
+    type MaxSignatories = frame_support::traits::ConstU32<10>;
+    type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
+}
+
+frame_support::parameter_types! {
+    /// Hold reason for noted preimages
+    pub const PreimageHoldReason: RuntimeHoldReason =
+        RuntimeHoldReason::Preimage(pallet_preimage::HoldReason::Preimage);
+    /// Held per noted preimage: 0.01 ARED...
+    pub const PreimageBaseDeposit: Balance = ARED / 100;
+    /// ...plus 0.00001 ARED per byte
+    pub const PreimageByteDeposit: Balance = ARED / 100_000;
+}
+
+// Preimage pallet: stores scheduled calls too large to inline
+impl pallet_preimage::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+    type Currency = Balances;
+    type ManagerOrigin = EnsureRootOrHalfCommittee;
+    type Consideration = frame_support::traits::fungible::HoldConsideration<
+        AccountId,
+        Balances,
+        PreimageHoldReason,
+        frame_support::traits::LinearStoragePrice<
+            PreimageBaseDeposit,
+            PreimageByteDeposit,
+            Balance,
+        >,
+    >;
+}
+
+frame_support::parameter_types! {
+    /// Scheduled calls may fill up to 80% of a block, leaving room for
+    /// telemetry extrinsics in the same 6 second slot
+    pub MaximumSchedulerWeight: Weight =
+        sp_runtime::Perbill::from_percent(80) * MAXIMUM_BLOCK_WEIGHT;
+}
+
+// Scheduler pallet: runs recurring governance calls such as settlement
+impl pallet_scheduler::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type PalletsOrigin = OriginCaller;
+    type RuntimeCall = RuntimeCall;
+    type MaximumWeight = MaximumSchedulerWeight;
+    /// Root or a technical committee motion; calls dispatch with that origin
+    type ScheduleOrigin = EnsureRootOrHalfCommittee;
+    /// Agendas per block; recurring settlement needs only a few
+    type MaxScheduledPerBlock = frame_support::traits::ConstU32<50>;
+    type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+    type OriginPrivilegeCmp = frame_support::traits::EqualPrivilegeOnly;
+    type Preimages = Preimage;
+}
+
+frame_support::parameter_types! {
+    /// Stepped migrations may fill up to 80% of a block, as scheduled calls do
+    pub MultiBlockMigrationsServiceWeight: Weight =
+        sp_runtime::Perbill::from_percent(80) * MAXIMUM_BLOCK_WEIGHT;
+}
+
+// Multi-block migrations: storage migrations too large for a single block
+impl pallet_migrations::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    #[cfg(not(feature = "runtime-benchmarks"))]
+    type Migrations = MultiBlockMigrationsList;
+    #[cfg(feature = "runtime-benchmarks")]
+    type Migrations = pallet_migrations::mock_helpers::MockedMigrations;
+    /// Large enough for a device id and proof index
+    type CursorMaxLen = frame_support::traits::ConstU32<65_536>;
+    type IdentifierMaxLen = frame_support::traits::ConstU32<256>;
+    type MigrationStatusHandler = ();
+    /// A failed migration leaves state half converted, so blocks stay
+    /// limited to inherents rather than run on it
+    type FailedMigrationHandler = frame_support::migrations::FreezeChainOnFailedMigration;
+    type MaxServiceWeight = MultiBlockMigrationsServiceWeight;
+    type WeightInfo = pallet_migrations::weights::SubstrateWeight<Runtime>;
+}
+
+// Bridge membership: the accounts that submit telemetry proofs and energy
+impl pallet_membership::Config<BridgeMembershipInstance> for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type AddOrigin = EnsureRootOrHalfCommittee;
+    type RemoveOrigin = EnsureRootOrHalfCommittee;
+    type SwapOrigin = EnsureRootOrHalfCommittee;
+    type ResetOrigin = EnsureRootOrHalfCommittee;
+    type PrimeOrigin = EnsureRootOrHalfCommittee;
+    type MembershipInitialized = ();
+    type MembershipChanged = ();
+    /// One account per bridge deployment, with room for rotation
+    type MaxMembers = frame_support::traits::ConstU32<32>;
+    type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+/// Membership instance holding the bridge accounts
+pub type BridgeMembershipInstance = pallet_membership::Instance1;
+
+/// Signed origin of an account in `BridgeMembers`
+pub type EnsureBridgeMember = frame_system::EnsureSignedBy<BridgeMembers, AccountId>;
+
+frame_support::parameter_types! {
+    /// Motions left open for three days before they can be closed unanimously
+    pub const TechnicalMotionDuration: BlockNumber = 3 * DAYS;
+    /// A motion may dispatch a call of up to half a block
+    pub MaxTechnicalProposalWeight: Weight =
+        sp_runtime::Perbill::from_percent(50) * MAXIMUM_BLOCK_WEIGHT;
+}
+
+// Technical committee: governs runtime parameters on chains without sudo
+impl pallet_collective::Config<TechnicalCollective> for Runtime {
+    type RuntimeOrigin = RuntimeOrigin;
+    type Proposal = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type MotionDuration = TechnicalMotionDuration;
+    type MaxProposals = frame_support::traits::ConstU32<100>;
+    /// Operators and partners, with room to grow
+    type MaxMembers = frame_support::traits::ConstU32<16>;
+    type DefaultVote = pallet_collective::PrimeDefaultVote;
+    type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+    /// The committee manages its own members, as production has no sudo key
+    type SetMembersOrigin = EnsureRootOrHalfCommittee;
+    type MaxProposalWeight = MaxTechnicalProposalWeight;
+}
+
+/// Collective instance of the technical committee
+pub type TechnicalCollective = pallet_collective::Instance1;
+
+/// Root, or at least half of the technical committee
+pub type EnsureRootOrHalfCommittee = frame_support::traits::EitherOfDiverse<
+    frame_system::EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 2>,
+>;
+
+// Governance origins: the origins referenda tracks dispatch approved
+// proposals with
+impl pallet_governance_origins::Config for Runtime {}
+
+frame_support::parameter_types! {
+    /// Tokens voted with conviction stay locked for multiples of a week
+    pub const VoteLockingPeriod: BlockNumber = 7 * DAYS;
+}
+
+// Conviction voting: token holders vote on referenda, locking their tokens
+// for longer to weigh their vote more
+impl pallet_conviction_voting::Config for Runtime {
+    type WeightInfo = pallet_conviction_voting::weights::SubstrateWeight<Runtime>;
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type Polls = Referenda;
+    /// Support is measured against the issuance not held in inactive accounts
+    type MaxTurnout =
+        frame_support::traits::tokens::currency::ActiveIssuanceOf<Balances, AccountId>;
+    /// Concurrent votes per account, one per ongoing referendum
+    type MaxVotes = frame_support::traits::ConstU32<20>;
+    type VoteLockingPeriod = VoteLockingPeriod;
+}
+
+frame_support::parameter_types! {
+    /// Reserved from whoever submits a referendum: 1 ARED
+    pub const SubmissionDeposit: Balance = ARED;
+    /// Referenda without a decision deposit are dropped after two weeks
+    pub const UndecidingTimeout: BlockNumber = 14 * DAYS;
+}
+
+// Referenda pallet: token holders decide proposals on the tracks in
+// `TracksInfo`
+impl pallet_referenda::Config for Runtime {
+    type WeightInfo = pallet_referenda::weights::SubstrateWeight<Runtime>;
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type Scheduler = Scheduler;
+    type Currency = Balances;
+    /// Any account may submit; the proposal origin picks the track
+    type SubmitOrigin = frame_system::EnsureSigned<AccountId>;
+    type CancelOrigin = EnsureRootOrHalfCommittee;
+    type KillOrigin = frame_system::EnsureRoot<AccountId>;
+    /// Deposits of killed referenda are burned
+    type Slash = ();
+    type Votes = pallet_conviction_voting::VotesOf<Runtime>;
+    type Tally = pallet_conviction_voting::TallyOf<Runtime>;
+    type SubmissionDeposit = SubmissionDeposit;
+    /// Referenda waiting for a deciding slot per track
+    type MaxQueued = frame_support::traits::ConstU32<10>;
+    type UndecidingTimeout = UndecidingTimeout;
+    type AlarmInterval = frame_support::traits::ConstU32<1>;
+    type Tracks = TracksInfo;
+    type Preimages = Preimage;
+}
+
+/// Referenda tracks of the runtime, each mapped to one governance origin
+pub struct TracksInfo;
+
+impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
+    type Id = u16;
+    type RuntimeOrigin = <RuntimeOrigin as frame_support::traits::OriginTrait>::PalletsOrigin;
+
+    fn tracks() -> &'static [(Self::Id, pallet_referenda::TrackInfo<Balance, BlockNumber>)] {
+        use pallet_referenda::Curve;
+        use sp_runtime::Perbill;
+
+        static DATA: [(u16, pallet_referenda::TrackInfo<Balance, BlockNumber>); 1] = [(
+            0,
+            pallet_referenda::TrackInfo {
+                // Carbon methodology parameters, such as the emission factor
+                name: "methodology",
+                max_deciding: 5,
+                decision_deposit: 10 * ARED,
+                prepare_period: HOURS,
+                decision_period: 7 * DAYS,
+                confirm_period: DAYS,
+                min_enactment_period: DAYS,
+                // Approval falls from 100% to a simple majority over the
+                // decision period
+                min_approval: Curve::LinearDecreasing {
+                    length: Perbill::from_percent(100),
+                    floor: Perbill::from_percent(50),
+                    ceil: Perbill::from_percent(100),
+                },
+                // Support falls from half to a tenth of the active issuance
+                min_support: Curve::LinearDecreasing {
+                    length: Perbill::from_percent(100),
+                    floor: Perbill::from_percent(10),
+                    ceil: Perbill::from_percent(50),
+                },
+            },
+        )];
+        &DATA[..]
+    }
+
+    fn track_for(origin: &Self::RuntimeOrigin) -> Result<Self::Id, ()> {
+        match origin {
+            OriginCaller::GovernanceOrigins(pallet_governance_origins::Origin::Methodology) => {
+                Ok(0)
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+pallet_referenda::impl_tracksinfo_get!(TracksInfo, Balance, BlockNumber);
+
+frame_support::parameter_types! {
+    /// Held for an identity: 0.01 ARED...
+    pub const IdentityBasicDeposit: Balance = ARED / 100;
+    /// ...plus 0.00001 ARED per byte of identity information
+    pub const IdentityByteDeposit: Balance = ARED / 100_000;
+    /// Held per sub-account of an identity
+    pub const IdentitySubAccountDeposit: Balance = ARED / 100;
+    /// Extra fields allowed in an identity
+    pub const MaxAdditionalFields: u32 = 16;
+}
+
+// Identity pallet: names for operational accounts in wallets and explorers
+impl pallet_identity::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type BasicDeposit = IdentityBasicDeposit;
+    type ByteDeposit = IdentityByteDeposit;
+    type SubAccountDeposit = IdentitySubAccountDeposit;
+    type MaxSubAccounts = frame_support::traits::ConstU32<16>;
+    type IdentityInformation = pallet_identity::legacy::IdentityInfo<MaxAdditionalFields>;
+    type MaxRegistrars = frame_support::traits::ConstU32<8>;
+    /// Deposits of identities killed by `ForceOrigin` are burned
+    type Slashed = ();
+    type ForceOrigin = EnsureRootOrHalfCommittee;
+    type RegistrarOrigin = EnsureRootOrHalfCommittee;
+    type OffchainSignature = Signature;
+    type SigningPublicKey = <Signature as Verify>::Signer;
+    type UsernameAuthorityOrigin = EnsureRootOrHalfCommittee;
+    type PendingUsernameExpiration = frame_support::traits::ConstU32<{ 7 * DAYS }>;
+    type MaxSuffixLength = frame_support::traits::ConstU32<7>;
+    type MaxUsernameLength = frame_support::traits::ConstU32<32>;
+    type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
+// Registrars and operator identities seeded at genesis
+impl pallet_operator_identities::Config for Runtime {
+    type IdentityFromName = pallet_operator_identities::DisplayName<MaxAdditionalFields>;
+}
+
+frame_support::parameter_types! {
+    /// Pallet id of the on-chain treasury account
+    pub const TreasuryPalletId: frame_support::PalletId = frame_support::PalletId(*b"ared/trs");
+    /// Account collecting forfeited deposits and base transaction fees
+    pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
+    /// Proof windows may start at most 30 days before on-chain time
+    pub const MaxProofWindowAge: Option<u64> = Some(30 * 24 * 60 * 60);
+    /// Warn operators once a device holds 90% of `MaxProofsPerDevice`
+    pub const ProofStorageWarningPercent: sp_runtime::Percent = sp_runtime::Percent::from_percent(90);
+}
+
 // ARED Telemetry Proofs pallet configuration
 impl pallet_telemetry_proofs::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = pallet_telemetry_proofs::weights::SubstrateWeight<Runtime>;
-    /// Maximum device ID length (UUID = 36 chars, with buffer = 64)
-    type MaxDeviceIdLength = frame_support::traits::ConstU32<64>;
-    /// Maximum proof hash length (SHA-256 hex = 64 chars, with buffer = 128)
-    type MaxProofLength = frame_support::traits::ConstU32<128>;
+    type AdminOrigin = EnsureRootOrHalfCommittee;
+    /// Only bridge members submit proofs
+    type SubmitterOrigin = EnsureBridgeMember;
+    /// Bridge members submit proofs without fees
+    type FeelessSubmitters = BridgeMembers;
+    /// Bridge members are trusted: no proof deposits, and no proof interval
+    type ExemptSubmitters = BridgeMembers;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type NativeBalance = Balances;
+    /// Deposit held per proof from submitters outside `ExemptSubmitters`
+    /// (0.001 tokens); every submitter here is a bridge member, so none is
+    /// held unless the runtime admits other submitters
+    type ProofDeposit = frame_support::traits::ConstU128<1_000_000_000_000_000>;
+    type DisputeOrigin = EnsureRootOrHalfCommittee;
+    type AnchorOrigin = EnsureRootOrHalfCommittee;
+    type OracleOrigin = EnsureRootOrHalfCommittee;
+    /// Deposit held from a challenger (0.01 tokens)
+    type ChallengeDeposit = frame_support::traits::ConstU128<10_000_000_000_000_000>;
+    /// Unresolved challenges may be expired after one week
+    type ChallengePeriod = frame_support::traits::ConstU32<{ 7 * DAYS }>;
+    type TreasuryAccount = TreasuryAccount;
+    /// A proof hash may only be anchored once while its proof is live
+    type AllowSameDeviceResubmission = frame_support::traits::ConstBool<false>;
+    /// Index proof hashes offchain for explorers (nodes need --enable-offchain-indexing)
+    type OffchainIndexing = frame_support::traits::ConstBool<true>;
     /// Maximum proofs in a single batch submission
     type MaxBatchSize = frame_support::traits::ConstU32<100>;
+    /// Unsigned proofs may queue up to 16 nonces ahead of a device's
+    type UnsignedNonceLookahead = frame_support::traits::ConstU64<16>;
+    /// Records per proof (one reading per second over a day, with margin)
+    type MaxRecordsPerProof = frame_support::traits::ConstU32<1_000_000>;
+    /// IPFS CIDs (CIDv1 base32 is 59 characters for sha2-256)
+    type MaxCidLength = frame_support::traits::ConstU32<64>;
     /// Maximum proof records per device (data retention limit)
     type MaxProofsPerDevice = frame_support::traits::ConstU32<10000>;
+    /// Evict the oldest proof once a device reaches its retention limit
+    type PruneOldestProofs = frame_support::traits::ConstBool<true>;
+    type ProofStorageWarningPercent = ProofStorageWarningPercent;
+    /// Signing keys remembered per device for historical verification
+    type MaxKeyHistory = frame_support::traits::ConstU32<8>;
+    /// Maximum proofs returned by a single query helper
+    type MaxQueryResults = frame_support::traits::ConstU32<1000>;
+    /// Maximum proofs returned by one page of a paged query
+    type MaxPageSize = frame_support::traits::ConstU32<100>;
+    /// Merkle inclusion paths cover batches of up to 2^32 records
+    type MaxMerkleDepth = frame_support::traits::ConstU32<32>;
+    /// Proofs accepted per block (ten full batches)
+    type MaxProofsPerBlock = frame_support::traits::ConstU32<1000>;
+    /// Per-block proof roots are kept for one week
+    type ProofRootRetention = frame_support::traits::ConstU32<{ 7 * DAYS }>;
+    type TimeProvider = Timestamp;
+    /// Retired devices in the registry may not submit proofs
+    type Devices = DeviceRegistry;
+    /// Energy submitted with a proof is recorded by the carbon credits pallet
+    type OnProofAccepted = CarbonCredits;
+    type OnProofSubmitted = ();
+    /// Proof windows may end at most 10 minutes ahead of on-chain time
+    type MaxClockDrift = frame_support::traits::ConstU64<600>;
+    type MaxWindowAge = MaxProofWindowAge;
+    /// Aggregation windows are 15 minutes; proofs for a device at most every 5 minutes
+    type MinProofInterval = frame_support::traits::ConstU32<{ 5 * MINUTES }>;
+    /// Bridge members catching up on a backlog are not throttled
+    type ExemptTrustedFromProofInterval = frame_support::traits::ConstBool<true>;
+    /// Window queries read one index bucket per day of the queried range
+    type WindowBucketSeconds = frame_support::traits::ConstU64<86_400>;
+    /// A day holds 96 fifteen-minute windows; leave room for backfilled duplicates
+    type MaxProofsPerWindowBucket = frame_support::traits::ConstU32<512>;
+    /// Daily record counts are kept for 90 days
+    type MaxDaysRetained = frame_support::traits::ConstU32<90>;
+    /// Bridge nodes sign heartbeats with their `ared` offchain key
+    type AuthorityId = pallet_telemetry_proofs::crypto::OffchainAuthId;
+    /// A heartbeat from every bridge node every 10 minutes
+    type HeartbeatInterval = frame_support::traits::ConstU32<{ 10 * MINUTES }>;
 }
 
+frame_support::parameter_types! {
+    /// Asset mirroring carbon credits withdrawn to accounts ("tCO2e credit")
+    pub const CarbonCreditAssetId: u32 = 1;
+    /// Pallet id of the account owning the carbon credit asset
+    pub const CarbonCreditsPalletId: frame_support::PalletId =
+        frame_support::PalletId(*b"ared/ccr");
+    /// Owner of the carbon credit asset; no key controls it
+    pub CarbonCreditAssetOwner: AccountId = CarbonCreditsPalletId::get().into_account_truncating();
+    /// Held for creating an asset: 1 ARED
+    pub const AssetDeposit: Balance = ARED;
+    /// Held per asset account of a non-sufficient asset: 0.001 ARED
+    pub const AssetAccountDeposit: Balance = ARED / 1_000;
+    /// Held for asset metadata: 0.01 ARED...
+    pub const AssetMetadataDepositBase: Balance = ARED / 100;
+    /// ...plus 0.00001 ARED per byte
+    pub const AssetMetadataDepositPerByte: Balance = ARED / 100_000;
+    /// Held per approval: 0.001 ARED
+    pub const AssetApprovalDeposit: Balance = ARED / 1_000;
+}
+
+// Assets pallet: carbon credits withdrawn to accounts are mirrored as
+// asset `CarbonCreditAssetId` for wallets and exchanges
+impl pallet_assets::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type RemoveItemsLimit = frame_support::traits::ConstU32<1000>;
+    type AssetId = u32;
+    type AssetIdParameter = codec::Compact<u32>;
+    type Currency = Balances;
+    type CreateOrigin =
+        frame_support::traits::AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+    /// Governance may force-create, reconfigure and destroy assets
+    type ForceOrigin = EnsureRootOrHalfCommittee;
+    type AssetDeposit = AssetDeposit;
+    type AssetAccountDeposit = AssetAccountDeposit;
+    type MetadataDepositBase = AssetMetadataDepositBase;
+    type MetadataDepositPerByte = AssetMetadataDepositPerByte;
+    type ApprovalDeposit = AssetApprovalDeposit;
+    type StringLimit = frame_support::traits::ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
+/// The carbon credit asset as a single `fungible` token
+pub type CarbonCreditAsset =
+    frame_support::traits::fungible::ItemOf<Assets, CarbonCreditAssetId, AccountId>;
+
 // ARED Carbon Credits pallet configuration
 impl pallet_carbon_credits::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = pallet_carbon_credits::weights::SubstrateWeight<Runtime>;
-    /// Maximum device ID length
-    type MaxDeviceIdLength = frame_support::traits::ConstU32<64>;
+    /// Only bridge members record energy
+    type SubmitterOrigin = EnsureBridgeMember;
+    /// Bridge members record energy without fees
+    type FeelessSubmitters = BridgeMembers;
     /// Credits per ton of CO2 avoided (1000 credits = 1 carbon credit token)
     type CreditsPerTonCO2 = frame_support::traits::ConstU128<1000>;
     /// Default emission factor: 1.5 kg CO2/kWh (scaled by 1000)
@@ -257,6 +1012,55 @@ impl pallet_carbon_credits::Config for Runtime {
     type MinClaimableEnergy = frame_support::traits::ConstU128<1000>;
     /// Maximum issuance records per device
     type MaxIssuanceRecords = frame_support::traits::ConstU32<10000>;
+    /// Emission factor changes take effect one day after announcement
+    type FactorDelay = frame_support::traits::ConstU32<DAYS>;
+    /// Energy records may reference proofs stored by the telemetry pallet
+    type ProofProvider = TelemetryProofs;
+    /// Only devices active in the registry may record energy or claim credits
+    type Devices = DeviceRegistry;
+    /// Emission factor changes and energy corrections pass the technical
+    /// committee or a methodology referendum
+    type AdminOrigin = frame_support::traits::EitherOfDiverse<
+        EnsureRootOrHalfCommittee,
+        pallet_governance_origins::EnsureMethodology,
+    >;
+    /// Energy corrections kept per device for audits
+    type MaxAdjustmentLog = frame_support::traits::ConstU32<16>;
+    /// Adjustment reasons (a ticket reference and short note)
+    type MaxAdjustmentReasonLength = frame_support::traits::ConstU32<128>;
+    /// Account credits are minted to and burned from the carbon credit asset
+    type CreditAsset = pallet_carbon_credits::FungibleCreditAsset<CarbonCreditAsset>;
+    type TimeProvider = Timestamp;
+    /// Energy without proofs may run a week of a stove's maximum power ahead
+    /// of elapsed time, enough for bridges that report unproven energy weekly
+    type UnprovenEnergyPeriod = frame_support::traits::ConstU64<{ 7 * 24 * 60 * 60 }>;
+}
+
+// ARED Device Registry pallet configuration
+impl pallet_device_registry::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_device_registry::weights::SubstrateWeight<Runtime>;
+    type AdminOrigin = EnsureRootOrHalfCommittee;
+    /// Attestations are anchored by governance until manufacturers are onboarded
+    type ManufacturerOrigin = EnsureRootOrHalfCommittee;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type NativeBalance = Balances;
+    /// Deposit held per registered device (0.01 tokens)
+    type RegistrationDeposit = frame_support::traits::ConstU128<10_000_000_000_000_000>;
+    /// Maximum device model identifier length
+    type MaxModelLength = frame_support::traits::ConstU32<64>;
+    /// Devices registered by one batch, matching the telemetry proof batch size
+    type MaxBatchSize = frame_support::traits::ConstU32<100>;
+    /// Replaced metadata hashes kept per device for audits
+    type MaxMetadataHistory = frame_support::traits::ConstU32<16>;
+    /// Maximum length of a stove model description
+    type MaxModelDescriptionLength = frame_support::traits::ConstU32<128>;
+    /// Rating assumed for stove models missing from the model registry (3 kW)
+    type DefaultMaxPowerW = frame_support::traits::ConstU32<3_000>;
+    /// Decommissioning freezes and prunes proofs and sweeps credits to the owner
+    type OnDecommission = (TelemetryProofs, CarbonCredits);
+    /// New owners must accept transfers, so devices are not sent to dead accounts
+    type RequireTransferAcceptance = frame_support::traits::ConstBool<true>;
 }
 
 // Construct the runtime
@@ -266,23 +1070,70 @@ construct_runtime!(
         System: frame_system,
         Timestamp: pallet_timestamp,
 
-        // Consensus
+        // Consensus (the validator set and session come first so their
+        // genesis seeds the Aura and GRANDPA authorities)
+        ValidatorSet: pallet_validator_set,
+        Session: pallet_session,
+        Historical: pallet_session::historical,
+        Authorship: pallet_authorship,
         Aura: pallet_aura,
         Grandpa: pallet_grandpa,
+        Offences: pallet_offences,
+        ImOnline: pallet_im_online,
+
+        // Networking (an empty node list leaves the network open)
+        NodeAuthorization: pallet_node_authorization,
 
         // Monetary
         Balances: pallet_balances,
         TransactionPayment: pallet_transaction_payment,
+        SkipFeelessPayment: pallet_skip_feeless_payment,
+        Assets: pallet_assets,
+        BlockRewards: pallet_block_rewards,
+
+        // Utility
+        Utility: pallet_utility,
+        Multisig: pallet_multisig,
+        Preimage: pallet_preimage,
+        Scheduler: pallet_scheduler,
 
-        // Governance (dev only)
+        // Governance (sudo is only given a key on dev and local chains)
         Sudo: pallet_sudo,
+        ProductionMode: pallet_production_mode,
+        BridgeMembers: pallet_membership::<Instance1>,
+        TechnicalCommittee: pallet_collective::<Instance1>,
+        Identity: pallet_identity,
+        OperatorIdentities: pallet_operator_identities,
+        GovernanceOrigins: pallet_governance_origins,
+        ConvictionVoting: pallet_conviction_voting,
+        Referenda: pallet_referenda,
 
         // ARED Custom Pallets
         TelemetryProofs: pallet_telemetry_proofs,
         CarbonCredits: pallet_carbon_credits,
+        DeviceRegistry: pallet_device_registry,
+
+        // Upgrades (last, so existing pallet indices are unchanged)
+        MultiBlockMigrations: pallet_migrations,
     }
 );
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benches {
+    frame_benchmarking::define_benchmarks!(
+        [frame_benchmarking, BaselineBench::<Runtime>]
+        [frame_system, SystemBench::<Runtime>]
+        [pallet_balances, Balances]
+        [pallet_timestamp, Timestamp]
+        [pallet_validator_set, ValidatorSet]
+        [pallet_block_rewards, BlockRewards]
+        [pallet_production_mode, ProductionMode]
+        [pallet_telemetry_proofs, TelemetryProofs]
+        [pallet_carbon_credits, CarbonCredits]
+        [pallet_device_registry, DeviceRegistry]
+    );
+}
+
 // Implement runtime APIs
 impl_runtime_apis! {
     impl sp_api::Core<Block> for Runtime {
@@ -346,7 +1197,14 @@ impl_runtime_apis! {
 
     impl sp_offchain::OffchainWorkerApi<Block> for Runtime {
         fn offchain_worker(header: &<Block as BlockT>::Header) {
-            Executive::offchain_worker(header)
+            Executive::offchain_worker(header);
+
+            // The node authorization worker always restricts peers to the
+            // authorized nodes. Without well-known nodes (dev and local
+            // chains) that would isolate the node, so keep the network open.
+            if pallet_node_authorization::WellKnownNodes::<Runtime>::get().is_empty() {
+                sp_io::offchain::set_authorized_nodes(Vec::new(), false);
+            }
         }
     }
 
@@ -370,20 +1228,27 @@ impl_runtime_apis! {
         }
 
         fn submit_report_equivocation_unsigned_extrinsic(
-            _equivocation_proof: sp_consensus_grandpa::EquivocationProof<
+            equivocation_proof: sp_consensus_grandpa::EquivocationProof<
                 <Block as BlockT>::Hash,
                 sp_runtime::traits::NumberFor<Block>,
             >,
-            _key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
+            key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
         ) -> Option<()> {
-            None
+            let key_owner_proof = key_owner_proof.decode()?;
+
+            Grandpa::submit_unsigned_equivocation_report(equivocation_proof, key_owner_proof)
         }
 
         fn generate_key_ownership_proof(
             _set_id: sp_consensus_grandpa::SetId,
-            _authority_id: GrandpaId,
+            authority_id: GrandpaId,
         ) -> Option<sp_consensus_grandpa::OpaqueKeyOwnershipProof> {
-            None
+            use codec::Encode;
+            use frame_support::traits::KeyOwnerProofSystem;
+
+            Historical::prove((sp_consensus_grandpa::KEY_TYPE, authority_id))
+                .map(|proof| proof.encode())
+                .map(sp_consensus_grandpa::OpaqueKeyOwnershipProof::new)
         }
     }
 
@@ -429,17 +1294,236 @@ impl_runtime_apis! {
         }
     }
 
+    impl telemetry_proofs_runtime_api::TelemetryProofsApi<
+        Block,
+        pallet_telemetry_proofs::ProofMetadata<Runtime>,
+        BlockNumber,
+    > for Runtime {
+        fn proof_count(device_id: Vec<u8>) -> u64 {
+            DeviceId::try_from(device_id)
+                .map(TelemetryProofs::proof_count)
+                .unwrap_or_default()
+        }
+
+        fn latest_proof_block(device_id: Vec<u8>) -> Option<BlockNumber> {
+            DeviceId::try_from(device_id)
+                .ok()
+                .and_then(TelemetryProofs::latest_proof_block)
+        }
+
+        fn proofs_in_window(
+            device_id: Vec<u8>,
+            start: u64,
+            end: u64,
+        ) -> Vec<pallet_telemetry_proofs::ProofMetadata<Runtime>> {
+            DeviceId::try_from(device_id)
+                .map(|id| TelemetryProofs::get_proofs_in_window(&id, start, end, None))
+                .unwrap_or_default()
+        }
+
+        fn proofs_paged(
+            device_id: Vec<u8>,
+            start_index: u64,
+            count: u32,
+        ) -> (Vec<pallet_telemetry_proofs::ProofMetadata<Runtime>>, Option<u64>) {
+            DeviceId::try_from(device_id)
+                .map(|id| TelemetryProofs::get_proofs_paged(&id, start_index, count))
+                .unwrap_or_default()
+        }
+
+        fn proof_history(
+            device_id: Vec<u8>,
+            start_index: u64,
+            count: u32,
+        ) -> (Vec<(pallet_telemetry_proofs::ProofMetadata<Runtime>, bool)>, Option<u64>) {
+            DeviceId::try_from(device_id)
+                .map(|id| TelemetryProofs::get_proof_history(&id, start_index, count))
+                .unwrap_or_default()
+        }
+
+        fn recent_proofs(
+            device_id: Vec<u8>,
+            n: u32,
+        ) -> Vec<pallet_telemetry_proofs::ProofMetadata<Runtime>> {
+            DeviceId::try_from(device_id)
+                .map(|id| TelemetryProofs::get_recent_proofs(&id, n))
+                .unwrap_or_default()
+        }
+
+        fn proof_exists(device_id: Vec<u8>, proof_hash: Vec<u8>) -> bool {
+            match (
+                DeviceId::try_from(device_id),
+                TelemetryProofs::normalize_proof_hash(&proof_hash),
+            ) {
+                (Ok(id), Some(hash)) => TelemetryProofs::proof_exists(&id, &hash),
+                _ => false,
+            }
+        }
+
+        fn records_on_day(device_id: Vec<u8>, day: u32) -> u32 {
+            DeviceId::try_from(device_id)
+                .map(|id| TelemetryProofs::records_on_day(id, day))
+                .unwrap_or_default()
+        }
+
+        fn lookup_proof(
+            proof_id: [u8; 32],
+        ) -> Option<(Vec<u8>, u64, pallet_telemetry_proofs::ProofMetadata<Runtime>)> {
+            TelemetryProofs::lookup_proof(proof_id.into())
+                .map(|(device_id, index, metadata)| (device_id.into_inner(), index, metadata))
+        }
+
+        fn total_records(device_id: Vec<u8>) -> u64 {
+            DeviceId::try_from(device_id)
+                .map(TelemetryProofs::total_records)
+                .unwrap_or_default()
+        }
+
+        fn submission_nonce(device_id: Vec<u8>) -> u64 {
+            DeviceId::try_from(device_id)
+                .map(|id| TelemetryProofs::submission_nonce(id))
+                .unwrap_or_default()
+        }
+    }
+
+    impl device_registry_runtime_api::DeviceRegistryApi<Block> for Runtime {
+        fn devices_in_area(prefix: Vec<u8>) -> u32 {
+            pallet_device_registry::AreaPrefix::try_from(prefix)
+                .map(DeviceRegistry::devices_in_area)
+                .unwrap_or_default()
+        }
+    }
+
+    impl carbon_credits_runtime_api::CarbonCreditsApi<Block> for Runtime {
+        fn device_credits(device_id: Vec<u8>) -> u128 {
+            DeviceId::try_from(device_id)
+                .map(|id| CarbonCredits::get_device_credits(&id))
+                .unwrap_or_default()
+        }
+
+        fn pending_energy(device_id: Vec<u8>) -> u128 {
+            DeviceId::try_from(device_id)
+                .map(|id| CarbonCredits::get_pending_energy(&id))
+                .unwrap_or_default()
+        }
+
+        fn calculate_credits(energy_wh: u128) -> u128 {
+            CarbonCredits::calculate_credits(energy_wh)
+        }
+
+        fn stats() -> carbon_credits_runtime_api::CarbonStats {
+            let (total_credits_issued, total_co2_avoided_kg, active_devices) =
+                CarbonCredits::get_stats();
+            carbon_credits_runtime_api::CarbonStats {
+                total_credits_issued,
+                total_co2_avoided_kg,
+                active_devices,
+            }
+        }
+
+        fn claimable_credits(device_id: Vec<u8>) -> carbon_credits_runtime_api::ClaimPreview {
+            DeviceId::try_from(device_id)
+                .map(|id| CarbonCredits::claim_preview(&id))
+                .unwrap_or_default()
+        }
+    }
+
+    impl ared_runtime_api::AredDeviceApi<Block, BlockNumber> for Runtime {
+        fn device_summary(device_id: Vec<u8>) -> ared_primitives::DeviceSummary<BlockNumber> {
+            let Ok(id) = DeviceId::try_from(device_id) else {
+                return Default::default();
+            };
+            ared_primitives::DeviceSummary {
+                credits: CarbonCredits::get_device_credits(&id),
+                pending_energy_wh: CarbonCredits::get_pending_energy(&id),
+                total_energy_wh: CarbonCredits::total_energy(&id),
+                proof_count: TelemetryProofs::proof_count(&id),
+                latest_proof_block: TelemetryProofs::latest_proof_block(&id),
+                issuance_count: CarbonCredits::issuance_count(&id),
+            }
+        }
+    }
+
     impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
         fn build_state(config: Vec<u8>) -> sp_genesis_builder::Result {
             frame_support::genesis_builder_helper::build_state::<RuntimeGenesisConfig>(config)
         }
 
         fn get_preset(id: &Option<sp_genesis_builder::PresetId>) -> Option<Vec<u8>> {
-            frame_support::genesis_builder_helper::get_preset::<RuntimeGenesisConfig>(id, |_| None)
+            frame_support::genesis_builder_helper::get_preset::<RuntimeGenesisConfig>(
+                id,
+                genesis_config_presets::get_preset,
+            )
         }
 
         fn preset_names() -> Vec<sp_genesis_builder::PresetId> {
-            vec![]
+            genesis_config_presets::preset_names()
+        }
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    impl frame_benchmarking::Benchmark<Block> for Runtime {
+        fn benchmark_metadata(extra: bool) -> (
+            Vec<frame_benchmarking::BenchmarkList>,
+            Vec<frame_support::traits::StorageInfo>,
+        ) {
+            use frame_benchmarking::{baseline, Benchmarking, BenchmarkList};
+            use frame_support::traits::StorageInfoTrait;
+            use frame_system_benchmarking::Pallet as SystemBench;
+            use baseline::Pallet as BaselineBench;
+
+            let mut list = Vec::<BenchmarkList>::new();
+            list_benchmarks!(list, extra);
+
+            let storage_info = AllPalletsWithSystem::storage_info();
+
+            (list, storage_info)
+        }
+
+        fn dispatch_benchmark(
+            config: frame_benchmarking::BenchmarkConfig,
+        ) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, alloc::string::String> {
+            use frame_benchmarking::{baseline, Benchmarking, BenchmarkBatch};
+            use frame_support::traits::WhitelistedStorageKeys;
+            use frame_system_benchmarking::Pallet as SystemBench;
+            use baseline::Pallet as BaselineBench;
+            use sp_storage::TrackedStorageKey;
+
+            impl frame_system_benchmarking::Config for Runtime {}
+            impl baseline::Config for Runtime {}
+
+            // Reads and writes of these keys (block number, events, the
+            // caller's account, ...) are not charged to the benchmarked call
+            let whitelist: Vec<TrackedStorageKey> =
+                AllPalletsWithSystem::whitelisted_storage_keys();
+
+            let mut batches = Vec::<BenchmarkBatch>::new();
+            let params = (&config, &whitelist);
+            add_benchmarks!(params, batches);
+
+            Ok(batches)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    impl frame_try_runtime::TryRuntime<Block> for Runtime {
+        fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
+            use frame_support::traits::Get;
+
+            // Panics so try-runtime-cli reports the failing migration or check
+            let weight = Executive::try_runtime_upgrade(checks).unwrap();
+            (weight, <Runtime as frame_system::Config>::BlockWeights::get().max_block)
+        }
+
+        fn execute_block(
+            block: Block,
+            state_root_check: bool,
+            signature_check: bool,
+            select: frame_try_runtime::TryStateSelect,
+        ) -> Weight {
+            // `select` decides which pallets' `try_state` hooks run after the block
+            Executive::try_execute_block(block, state_root_check, signature_check, select)
+                .expect("execute-block failed")
         }
     }
 }
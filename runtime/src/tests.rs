@@ -0,0 +1,2392 @@
+//! Runtime-level tests.
+
+use super::*;
+use ared_runtime_api::runtime_decl_for_ared_device_api::AredDeviceApiV1;
+use carbon_credits_runtime_api::runtime_decl_for_carbon_credits_api::CarbonCreditsApiV1;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::BuildStorage;
+use telemetry_proofs_runtime_api::runtime_decl_for_telemetry_proofs_api::TelemetryProofsApiV1;
+
+fn bridge() -> AccountId {
+    AccountId::new([1u8; 32])
+}
+
+fn owner() -> AccountId {
+    AccountId::new([2u8; 32])
+}
+
+fn committee_member(n: u8) -> AccountId {
+    AccountId::new([10 + n; 32])
+}
+
+fn validator(n: u8) -> AccountId {
+    AccountId::new([20 + n; 32])
+}
+
+fn grandpa_pair(n: u8) -> sp_core::ed25519::Pair {
+    sp_core::Pair::from_seed(&[n; 32])
+}
+
+fn session_keys(n: u8) -> opaque::SessionKeys {
+    use sp_core::Pair;
+
+    opaque::SessionKeys {
+        aura: sp_core::sr25519::Pair::from_seed(&[n; 32]).public().into(),
+        grandpa: grandpa_pair(n).public().into(),
+        im_online: sp_core::sr25519::Pair::from_seed(&[n + 100; 32])
+            .public()
+            .into(),
+    }
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+    RuntimeGenesisConfig {
+        balances: pallet_balances::GenesisConfig {
+            balances: vec![(owner(), 1_000_000_000_000_000_000)],
+        },
+        assets: pallet_assets::GenesisConfig {
+            assets: vec![(
+                CarbonCreditAssetId::get(),
+                CarbonCreditAssetOwner::get(),
+                true,
+                1,
+            )],
+            metadata: vec![(
+                CarbonCreditAssetId::get(),
+                b"tCO2e credit".to_vec(),
+                b"tCO2e".to_vec(),
+                3,
+            )],
+            ..Default::default()
+        },
+        validator_set: pallet_validator_set::GenesisConfig {
+            initial_validators: vec![validator(1)],
+        },
+        session: pallet_session::GenesisConfig {
+            keys: vec![(validator(1), validator(1), session_keys(1))],
+            ..Default::default()
+        },
+        technical_committee: pallet_collective::GenesisConfig {
+            members: (1..=3).map(committee_member).collect(),
+            ..Default::default()
+        },
+        bridge_members: pallet_membership::GenesisConfig {
+            members: vec![bridge()].try_into().unwrap(),
+            ..Default::default()
+        },
+        device_registry: pallet_device_registry::GenesisConfig {
+            devices: vec![(
+                bridge(),
+                b"device-001".to_vec(),
+                b"ARED-STOVE-1".to_vec(),
+                Default::default(),
+                Default::default(),
+            )],
+        },
+        ..Default::default()
+    }
+    .build_storage()
+    .unwrap()
+    .into()
+}
+
+#[test]
+fn telemetry_proofs_api_reads_pallet_state() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(7);
+        Timestamp::set_timestamp(1_000_000_000);
+        let device = b"device-001".to_vec();
+        let hash = [1u8; 32].to_vec();
+
+        assert_eq!(Runtime::proof_count(device.clone()), 0);
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            hash.clone(),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+            0,
+            None,
+            None,
+            None,
+        ));
+
+        assert_eq!(Runtime::proof_count(device.clone()), 1);
+        assert_eq!(Runtime::submission_nonce(device.clone()), 1);
+        assert_eq!(Runtime::latest_proof_block(device.clone()), Some(7));
+        let proofs = Runtime::proofs_in_window(device.clone(), 0, 1500);
+        assert_eq!(proofs.len(), 1);
+        assert_eq!(proofs[0].record_count, 10);
+        let (page, next) = Runtime::proofs_paged(device.clone(), 0, 10);
+        assert_eq!(page.len(), 1);
+        assert_eq!(next, None);
+        assert!(Runtime::proof_exists(device.clone(), hash));
+        assert!(!Runtime::proof_exists(device.clone(), [2u8; 32].to_vec()));
+        // Malformed hashes are reported as absent
+        assert!(!Runtime::proof_exists(device.clone(), b"other".to_vec()));
+        let proof_id = TelemetryProofs::proof_id(&device, &[1u8; 32], 1000, 2000);
+        let (found_device, index, metadata) = Runtime::lookup_proof(proof_id.0).unwrap();
+        assert_eq!((found_device, index), (device, 0));
+        assert_eq!(metadata.record_count, 10);
+        assert!(Runtime::lookup_proof([0u8; 32]).is_none());
+
+        // Oversized ids are reported as absent rather than failing
+        assert_eq!(Runtime::proof_count(vec![0u8; 65]), 0);
+    });
+}
+
+#[test]
+fn proof_submitted_index_links_energy_records() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        Timestamp::set_timestamp(1_000_000_000);
+        let device = b"device-001".to_vec();
+        let bounded: DeviceId = device.clone().try_into().unwrap();
+
+        for (hash, start) in [([1u8; 32].to_vec(), 1000), ([2u8; 32].to_vec(), 2000)] {
+            let expected = TelemetryProofs::next_proof_index(&bounded);
+            assert_ok!(TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(bridge()),
+                device.clone(),
+                hash.clone(),
+                10,
+                start,
+                start + 1000,
+                1024,
+                1,
+                pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+                TelemetryProofs::submission_nonce(&bounded),
+                TelemetryProofs::latest_proof_hash(&bounded).map(|h| h.as_bytes().to_vec()),
+                None,
+                None,
+            ));
+
+            // The bridge reads the index from the event, not from state
+            let proof_index = System::events()
+                .into_iter()
+                .rev()
+                .find_map(|record| match record.event {
+                    RuntimeEvent::TelemetryProofs(
+                        pallet_telemetry_proofs::Event::ProofSubmitted { proof_index, .. },
+                    ) => Some(proof_index),
+                    _ => None,
+                })
+                .unwrap();
+            assert_eq!(proof_index, expected);
+
+            assert_ok!(CarbonCredits::record_energy(
+                RuntimeOrigin::signed(bridge()),
+                device.clone(),
+                800,
+                Some(proof_index),
+            ));
+            let metadata = TelemetryProofs::get_proof(&bounded, proof_index).unwrap();
+            assert_eq!(metadata.proof_hash.as_bytes(), &hash[..]);
+        }
+        assert_eq!(TelemetryProofs::next_proof_index(&bounded), 2);
+    });
+}
+
+#[test]
+fn credit_claim_references_a_real_proof() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        Timestamp::set_timestamp(1_000_000_000);
+        let device = b"device-001".to_vec();
+
+        // 6000 s at the default 3 kW rating allows up to 5 kWh
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            [1u8; 32].to_vec(),
+            10,
+            1000,
+            7000,
+            1024,
+            1,
+            pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+            0,
+            None,
+            None,
+            None,
+        ));
+
+        // Only proofs the telemetry pallet holds can back energy records
+        assert_noop!(
+            CarbonCredits::record_energy(
+                RuntimeOrigin::signed(bridge()),
+                device.clone(),
+                5_000,
+                Some(1),
+            ),
+            pallet_carbon_credits::Error::<Runtime>::ProofNotFound
+        );
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            5_000,
+            Some(0),
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+        ));
+
+        let bounded: DeviceId = device.try_into().unwrap();
+        assert_eq!(
+            <TelemetryProofs as pallet_telemetry_proofs::ProofProvider<_>>::proof_window(
+                &bounded, 0
+            ),
+            Some((1000, 7000))
+        );
+        assert_eq!(CarbonCredits::energy_accumulated(&bounded), 0);
+    });
+}
+
+#[test]
+fn proof_with_energy_is_recorded_atomically() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        Timestamp::set_timestamp(1_000_000_000);
+        let device = b"device-001".to_vec();
+        let bounded: DeviceId = device.clone().try_into().unwrap();
+        let submit = |hash: [u8; 32], start: u64, energy_wh: u128| {
+            TelemetryProofs::submit_proof_with_energy(
+                RuntimeOrigin::signed(bridge()),
+                device.clone(),
+                hash.to_vec(),
+                10,
+                start,
+                start + 3600,
+                1024,
+                1,
+                pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+                TelemetryProofs::submission_nonce(&bounded),
+                TelemetryProofs::latest_proof_hash(&bounded).map(|h| h.as_bytes().to_vec()),
+                None,
+                None,
+                energy_wh,
+            )
+        };
+
+        // One hour at the default 3 kW rating allows up to 3 kWh
+        assert_noop!(
+            submit([1u8; 32], 1000, 3_001),
+            pallet_carbon_credits::Error::<Runtime>::EnergyImplausible
+        );
+        assert_eq!(TelemetryProofs::next_proof_index(&bounded), 0);
+
+        assert_ok!(submit([1u8; 32], 1000, 2_000));
+        assert_ok!(submit([2u8; 32], 4600, 2_500));
+
+        let carbon_device: DeviceId = device.clone().try_into().unwrap();
+        assert_eq!(
+            CarbonCredits::latest_energy_record(&carbon_device),
+            Some(pallet_carbon_credits::EnergyRecord {
+                energy_wh: 2_500,
+                block_number: 3,
+                proof_index: Some(1),
+            })
+        );
+        assert_eq!(CarbonCredits::energy_accumulated(&carbon_device), 4_500);
+        assert!(TelemetryProofs::get_proof(&bounded, 1).is_some());
+
+        // A rejected proof leaves the energy unrecorded
+        assert_noop!(
+            submit([2u8; 32], 8200, 100),
+            pallet_telemetry_proofs::Error::<Runtime>::DuplicateProofHash
+        );
+    });
+}
+
+#[test]
+fn batch_all_records_proof_and_energy_together() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        Timestamp::set_timestamp(1_000_000_000);
+        let device = b"device-001".to_vec();
+        let bounded: DeviceId = device.clone().try_into().unwrap();
+        let calls = |energy_wh: u128| {
+            vec![
+                RuntimeCall::TelemetryProofs(pallet_telemetry_proofs::Call::submit_proof {
+                    device_id: device.clone(),
+                    proof_hash: [1u8; 32].to_vec(),
+                    record_count: 10,
+                    window_start: 1000,
+                    window_end: 4600,
+                    data_size_bytes: 1024,
+                    schema_version: 1,
+                    category: pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+                    nonce: 0,
+                    prev_proof_hash: None,
+                    signature: None,
+                    content_cid: None,
+                }),
+                RuntimeCall::CarbonCredits(pallet_carbon_credits::Call::record_energy {
+                    device_id: device.clone(),
+                    energy_wh,
+                    proof_index: Some(0),
+                }),
+            ]
+        };
+
+        // One hour at the default 3 kW rating allows up to 3 kWh; the
+        // rejected record rolls back the proof and its error is reported
+        let result = Utility::batch_all(RuntimeOrigin::signed(bridge()), calls(3_001));
+        assert_eq!(
+            result.map_err(|e| e.error),
+            Err(pallet_carbon_credits::Error::<Runtime>::EnergyImplausible.into())
+        );
+        assert_eq!(TelemetryProofs::next_proof_index(&bounded), 0);
+        assert_eq!(CarbonCredits::energy_accumulated(&bounded), 0);
+
+        assert_ok!(Utility::batch_all(
+            RuntimeOrigin::signed(bridge()),
+            calls(2_000)
+        ));
+        assert_eq!(TelemetryProofs::next_proof_index(&bounded), 1);
+        assert_eq!(CarbonCredits::energy_accumulated(&bounded), 2_000);
+        System::assert_has_event(pallet_utility::Event::BatchCompleted.into());
+    });
+}
+
+#[test]
+fn bridge_multisig_records_energy_with_two_approvals() {
+    use frame_support::dispatch::GetDispatchInfo;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let signers: Vec<AccountId> = (20..23).map(|n| AccountId::new([n; 32])).collect();
+        let multisig = Multisig::multi_account_id(&signers, 2);
+        for signer in &signers {
+            assert_ok!(Balances::force_set_balance(
+                RuntimeOrigin::root(),
+                signer.clone().into(),
+                ARED,
+            ));
+        }
+        assert_ok!(BridgeMembers::add_member(
+            RuntimeOrigin::root(),
+            multisig.clone().into()
+        ));
+
+        let device = b"device-001".to_vec();
+        let bounded: DeviceId = device.clone().try_into().unwrap();
+        let call = RuntimeCall::CarbonCredits(pallet_carbon_credits::Call::record_energy {
+            device_id: device.clone(),
+            energy_wh: 500,
+            proof_index: None,
+        });
+
+        // A single signer key is not a bridge member
+        assert_noop!(
+            CarbonCredits::record_energy(
+                RuntimeOrigin::signed(signers[0].clone()),
+                device,
+                500,
+                None,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        // The first approval only opens the operation and holds its deposit
+        let timepoint = Multisig::timepoint();
+        assert_ok!(Multisig::as_multi(
+            RuntimeOrigin::signed(signers[0].clone()),
+            2,
+            vec![signers[1].clone(), signers[2].clone()],
+            None,
+            Box::new(call.clone()),
+            Weight::zero(),
+        ));
+        assert_eq!(CarbonCredits::energy_accumulated(&bounded), 0);
+        assert_eq!(
+            Balances::reserved_balance(&signers[0]),
+            MultisigDepositBase::get() + 2 * MultisigDepositFactor::get()
+        );
+
+        // The second approval dispatches the call from the multisig account
+        assert_ok!(Multisig::as_multi(
+            RuntimeOrigin::signed(signers[2].clone()),
+            2,
+            vec![signers[0].clone(), signers[1].clone()],
+            Some(timepoint),
+            Box::new(call.clone()),
+            call.get_dispatch_info().weight,
+        ));
+        assert_eq!(CarbonCredits::energy_accumulated(&bounded), 500);
+        assert_eq!(Balances::reserved_balance(&signers[0]), 0);
+    });
+}
+
+#[test]
+fn scheduler_dispatches_recurring_governance_calls() {
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let call =
+            RuntimeCall::CarbonCredits(pallet_carbon_credits::Call::announce_emission_factor {
+                new_factor: 1_200,
+            });
+        let task = *b"ared/carbon/factor-review/000001";
+
+        // Only governance may schedule calls
+        assert_noop!(
+            Scheduler::schedule_named(
+                RuntimeOrigin::signed(bridge()),
+                task,
+                11,
+                None,
+                0,
+                Box::new(call.clone()),
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        // Run 10 blocks out, then once more 10 blocks later
+        assert_ok!(Scheduler::schedule_named(
+            RuntimeOrigin::root(),
+            task,
+            11,
+            Some((10, 2)),
+            0,
+            Box::new(call),
+        ));
+
+        let announcements = || {
+            System::events()
+                .into_iter()
+                .filter(|record| {
+                    matches!(
+                        record.event,
+                        RuntimeEvent::CarbonCredits(
+                            pallet_carbon_credits::Event::EmissionFactorAnnounced { .. }
+                        )
+                    )
+                })
+                .count()
+        };
+        for block in 2..=10 {
+            System::set_block_number(block);
+            Scheduler::on_initialize(block);
+        }
+        assert_eq!(announcements(), 0);
+
+        System::set_block_number(11);
+        Scheduler::on_initialize(11);
+        // The call dispatched with the Root origin it was scheduled with
+        System::assert_has_event(
+            pallet_scheduler::Event::<Runtime>::Dispatched {
+                task: (11, 0),
+                id: Some(task),
+                result: Ok(()),
+            }
+            .into(),
+        );
+        assert_eq!(
+            CarbonCredits::pending_emission_factor(),
+            Some((1_200, 11 + DAYS))
+        );
+
+        for block in 12..=21 {
+            System::set_block_number(block);
+            Scheduler::on_initialize(block);
+        }
+        assert_eq!(announcements(), 2);
+        assert_eq!(
+            CarbonCredits::pending_emission_factor(),
+            Some((1_200, 21 + DAYS))
+        );
+
+        // The task has run its two occurrences and is gone
+        assert_noop!(
+            Scheduler::cancel_named(RuntimeOrigin::root(), task),
+            pallet_scheduler::Error::<Runtime>::NotFound
+        );
+    });
+}
+
+#[test]
+fn carbon_credit_asset_supply_tracks_issued_minus_retired() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let device = b"device-001".to_vec();
+        let bounded: DeviceId = device.clone().try_into().unwrap();
+        let asset = CarbonCreditAssetId::get();
+        let supply_matches = || {
+            Assets::total_supply(asset)
+                == CarbonCredits::total_credits_issued() - CarbonCredits::total_credits_retired()
+        };
+
+        assert_eq!(Assets::total_supply(asset), 0);
+        assert_eq!(
+            <Assets as frame_support::traits::fungibles::metadata::Inspect<AccountId>>::decimals(
+                asset
+            ),
+            3
+        );
+
+        // 100 kWh at 1.5 kg CO2/kWh: 150 credits
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            100_000,
+            None,
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+        ));
+        assert_eq!(CarbonCredits::credits_balance(&bounded), 150);
+        // Credits left on the device are not minted yet
+        assert_eq!(Assets::total_supply(asset), 0);
+
+        // Only the device owner (the bridge in genesis) may mint them
+        assert_noop!(
+            CarbonCredits::withdraw_credits(
+                RuntimeOrigin::signed(committee_member(1)),
+                device.clone(),
+                150,
+            ),
+            pallet_carbon_credits::Error::<Runtime>::NotDeviceOwner
+        );
+        assert_eq!(Assets::total_supply(asset), 0);
+
+        // The owner holds no ARED: the asset is sufficient on its own
+        assert_ok!(CarbonCredits::withdraw_credits(
+            RuntimeOrigin::signed(bridge()),
+            device,
+            150,
+        ));
+        assert_eq!(Assets::balance(asset, bridge()), 150);
+        assert!(supply_matches());
+
+        assert_ok!(CarbonCredits::retire_credits(
+            RuntimeOrigin::signed(bridge()),
+            40,
+        ));
+        assert_eq!(Assets::balance(asset, bridge()), 110);
+        assert_eq!(Assets::total_supply(asset), 110);
+        assert!(supply_matches());
+    });
+}
+
+#[test]
+fn carbon_credits_transferred_as_assets_are_retired_by_their_holder() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let device = b"device-001".to_vec();
+        let asset = CarbonCreditAssetId::get();
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            100_000,
+            None,
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+        ));
+        assert_ok!(CarbonCredits::withdraw_credits(
+            RuntimeOrigin::signed(bridge()),
+            device,
+            150,
+        ));
+
+        // Sell 60 credits with a plain asset transfer
+        assert_ok!(Assets::transfer(
+            RuntimeOrigin::signed(bridge()),
+            asset.into(),
+            owner().into(),
+            60,
+        ));
+        assert_eq!(CarbonCredits::account_credits(&bridge()), 90);
+        assert_eq!(CarbonCredits::account_credits(&owner()), 60);
+
+        // The seller can no longer retire them; the buyer can
+        assert_noop!(
+            CarbonCredits::retire_credits(RuntimeOrigin::signed(bridge()), 91),
+            pallet_carbon_credits::Error::<Runtime>::InsufficientCredits
+        );
+        assert_ok!(CarbonCredits::retire_credits(
+            RuntimeOrigin::signed(owner()),
+            60,
+        ));
+        assert_eq!(Assets::balance(asset, owner()), 0);
+        assert_eq!(CarbonCredits::total_credits_retired(), 60);
+        assert_eq!(
+            Assets::total_supply(asset),
+            CarbonCredits::total_credits_issued() - CarbonCredits::total_credits_retired()
+        );
+    });
+}
+
+#[test]
+fn bridge_members_submit_telemetry_without_fees() {
+    use codec::Encode;
+    use frame_support::dispatch::GetDispatchInfo;
+    use sp_runtime::traits::{Dispatchable, SignedExtension};
+
+    type FeeExtension = pallet_skip_feeless_payment::SkipCheckIfFeeless<
+        Runtime,
+        pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    >;
+
+    // Charges fees as a signed extrinsic would around the dispatch
+    let apply = |who: AccountId, call: RuntimeCall| {
+        let info = call.get_dispatch_info();
+        let len = call.encoded_size();
+        let extension: FeeExtension =
+            pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0).into();
+        let pre = extension.pre_dispatch(&who, &call, &info, len).unwrap();
+        let result = call.dispatch(RuntimeOrigin::signed(who));
+        let post_info = result.unwrap_or_else(|err| err.post_info);
+        let result = result.map(|_| ()).map_err(|err| err.error);
+        assert_ok!(FeeExtension::post_dispatch(
+            Some(pre),
+            &info,
+            &post_info,
+            len,
+            &result
+        ));
+        result
+    };
+    let submit = |hash: u8| {
+        RuntimeCall::TelemetryProofs(pallet_telemetry_proofs::Call::submit_proof {
+            device_id: b"device-001".to_vec(),
+            proof_hash: [hash; 32].to_vec(),
+            record_count: 10,
+            window_start: 1000,
+            window_end: 2000,
+            data_size_bytes: 1024,
+            schema_version: 1,
+            category: pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+            nonce: 0,
+            prev_proof_hash: None,
+            signature: None,
+            content_cid: None,
+        })
+    };
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1_000_000_000);
+
+        // The bridge holds no ARED at all and still anchors proofs and energy
+        assert_eq!(Balances::free_balance(bridge()), 0);
+        assert_ok!(apply(bridge(), submit(1)));
+        assert_ok!(apply(
+            bridge(),
+            RuntimeCall::CarbonCredits(pallet_carbon_credits::Call::record_energy {
+                device_id: b"device-001".to_vec(),
+                energy_wh: 500,
+                proof_index: Some(0),
+            })
+        ));
+        assert_eq!(Balances::free_balance(bridge()), 0);
+        assert_eq!(
+            TelemetryProofs::proof_count(&DeviceId::try_from(b"device-001".to_vec()).unwrap()),
+            1
+        );
+
+        // Anyone else pays the usual fee, and is still refused
+        let call = submit(2);
+        let fee = TransactionPayment::compute_fee(
+            call.encoded_size() as u32,
+            &call.get_dispatch_info(),
+            0,
+        );
+        assert!(fee > 0);
+        let before = Balances::free_balance(owner());
+        assert_eq!(
+            apply(owner(), call),
+            Err(sp_runtime::DispatchError::BadOrigin)
+        );
+        assert_eq!(Balances::free_balance(owner()), before - fee);
+    });
+}
+
+#[test]
+fn device_summary_combines_both_pallets() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        Timestamp::set_timestamp(1_000_000_000);
+        let device = b"device-001".to_vec();
+
+        assert_eq!(
+            Runtime::device_summary(device.clone()),
+            ared_primitives::DeviceSummary::default()
+        );
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            [1u8; 32].to_vec(),
+            10,
+            1000,
+            7000,
+            1024,
+            1,
+            pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+            0,
+            None,
+            None,
+            None,
+        ));
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            4_000,
+            Some(0),
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+        ));
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            500,
+            None,
+        ));
+
+        // 4 kWh at the default 1.5 kg CO2/kWh is 6 kg, or 6 credits
+        assert_eq!(
+            Runtime::device_summary(device),
+            ared_primitives::DeviceSummary {
+                credits: 6,
+                pending_energy_wh: 500,
+                total_energy_wh: 4_500,
+                proof_count: 1,
+                latest_proof_block: Some(3),
+                issuance_count: 1,
+            }
+        );
+        assert_eq!(
+            Runtime::device_summary(vec![b'a'; 65]),
+            ared_primitives::DeviceSummary::default()
+        );
+    });
+}
+
+/// Externalities built by the runtime's genesis builder from a JSON patch,
+/// the way a node builds them from a chain spec.
+fn genesis_builder_ext() -> sp_io::TestExternalities {
+    let patch = serde_json::json!({
+        "bridgeMembers": {
+            "members": [bridge().to_string()]
+        },
+        "deviceRegistry": {
+            "devices": [[
+                bridge().to_string(),
+                b"device-001".to_vec(),
+                b"ARED-STOVE-1".to_vec(),
+                sp_core::H256::repeat_byte(0x01),
+                sp_core::H256::repeat_byte(0x02)
+            ]]
+        }
+    });
+    let mut ext = sp_io::TestExternalities::default();
+    ext.execute_with(|| {
+        frame_support::genesis_builder_helper::build_state::<RuntimeGenesisConfig>(
+            patch.to_string().into_bytes(),
+        )
+        .unwrap();
+    });
+    ext
+}
+
+#[test]
+fn carbon_credits_api_reads_pallet_state() {
+    genesis_builder_ext().execute_with(|| {
+        System::set_block_number(1);
+        let device = b"device-001".to_vec();
+
+        assert_eq!(Runtime::device_credits(device.clone()), 0);
+        assert_eq!(Runtime::pending_energy(device.clone()), 0);
+        assert_eq!(
+            Runtime::stats(),
+            carbon_credits_runtime_api::CarbonStats::default()
+        );
+        // 4 kWh at the default 1.5 kg CO2/kWh is 6 kg, or 6 credits
+        assert_eq!(Runtime::calculate_credits(4_000), 6);
+
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            4_000,
+            None,
+        ));
+        assert_eq!(Runtime::pending_energy(device.clone()), 4_000);
+        let preview = carbon_credits_runtime_api::ClaimPreview {
+            energy_wh: 4_000,
+            co2_kg: 6,
+            credits: 6,
+            meets_minimum: true,
+        };
+        assert_eq!(Runtime::claimable_credits(device.clone()), preview);
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+        ));
+
+        assert_eq!(Runtime::device_credits(device.clone()), 6);
+        assert_eq!(Runtime::pending_energy(device.clone()), 0);
+        assert_eq!(
+            Runtime::claimable_credits(device),
+            carbon_credits_runtime_api::ClaimPreview::default()
+        );
+        assert_eq!(
+            Runtime::stats(),
+            carbon_credits_runtime_api::CarbonStats {
+                total_credits_issued: 6,
+                total_co2_avoided_kg: 6,
+                active_devices: 1,
+            }
+        );
+        assert_eq!(Runtime::device_credits(vec![b'a'; 65]), 0);
+        assert_eq!(
+            Runtime::claimable_credits(vec![b'a'; 65]),
+            carbon_credits_runtime_api::ClaimPreview::default()
+        );
+    });
+}
+
+#[test]
+fn committee_motion_updates_emission_factor() {
+    use codec::Encode;
+    use frame_support::dispatch::GetDispatchInfo;
+    use sp_runtime::traits::Hash;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let call =
+            RuntimeCall::CarbonCredits(pallet_carbon_credits::Call::announce_emission_factor {
+                new_factor: 2_000,
+            });
+        let call_len = call.encoded_size() as u32;
+
+        // A lone member, directly or as a one-vote motion, is not enough
+        assert_noop!(
+            CarbonCredits::announce_emission_factor(
+                RuntimeOrigin::signed(committee_member(1)),
+                2_000,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            CarbonCredits::announce_emission_factor(
+                pallet_collective::RawOrigin::<AccountId, TechnicalCollective>::Members(1, 3)
+                    .into(),
+                2_000,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        // Two of three members carry the motion
+        assert_ok!(TechnicalCommittee::propose(
+            RuntimeOrigin::signed(committee_member(1)),
+            2,
+            Box::new(call.clone()),
+            call_len,
+        ));
+        let hash = BlakeTwo256::hash_of(&call);
+        for member in [1, 2] {
+            assert_ok!(TechnicalCommittee::vote(
+                RuntimeOrigin::signed(committee_member(member)),
+                hash,
+                0,
+                true,
+            ));
+        }
+        assert_ok!(TechnicalCommittee::close(
+            RuntimeOrigin::signed(committee_member(3)),
+            hash,
+            0,
+            call.get_dispatch_info().weight,
+            call_len,
+        ));
+
+        assert_eq!(
+            CarbonCredits::pending_emission_factor(),
+            Some((2_000, 1 + DAYS))
+        );
+    });
+}
+
+#[test]
+fn committee_majority_administers_carbon_credits() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let majority = || {
+            RuntimeOrigin::from(
+                pallet_collective::RawOrigin::<AccountId, TechnicalCollective>::Members(2, 3),
+            )
+        };
+        let device_id = b"device-001".to_vec();
+        let reason: frame_support::BoundedVec<_, _> =
+            b"reprocessed telemetry".to_vec().try_into().unwrap();
+
+        // Without sudo, a committee majority announces and cancels factors...
+        assert_ok!(CarbonCredits::announce_emission_factor(majority(), 1_700));
+        assert_noop!(
+            CarbonCredits::cancel_pending_factor(RuntimeOrigin::signed(committee_member(1))),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(CarbonCredits::cancel_pending_factor(majority()));
+        assert_eq!(CarbonCredits::pending_emission_factor(), None);
+
+        // ...and corrects energy totals, which the device owner cannot
+        assert_noop!(
+            CarbonCredits::adjust_total_energy(
+                RuntimeOrigin::signed(bridge()),
+                device_id.clone(),
+                5_000,
+                reason.clone(),
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(CarbonCredits::adjust_total_energy(
+            majority(),
+            device_id.clone(),
+            5_000,
+            reason,
+        ));
+        let bounded: DeviceId = device_id.try_into().unwrap();
+        assert_eq!(CarbonCredits::total_energy(&bounded), 5_000);
+    });
+}
+
+#[test]
+fn committee_majority_governs_bridge_telemetry_and_devices() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let majority = || {
+            RuntimeOrigin::from(
+                pallet_collective::RawOrigin::<AccountId, TechnicalCollective>::Members(2, 3),
+            )
+        };
+        let device_id = b"device-001".to_vec();
+
+        // Bridge membership
+        assert_noop!(
+            BridgeMembers::add_member(RuntimeOrigin::signed(committee_member(1)), owner().into()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BridgeMembers::add_member(majority(), owner().into()));
+        assert!(
+            pallet_membership::Members::<Runtime, BridgeMembershipInstance>::get()
+                .contains(&owner())
+        );
+
+        // Telemetry admin, dispute and oracle origins
+        assert_ok!(TelemetryProofs::set_max_total_devices(majority(), Some(10)));
+        assert_eq!(TelemetryProofs::max_total_devices(), Some(10));
+        assert_noop!(
+            TelemetryProofs::resolve_challenge(majority(), device_id.clone(), 0, true),
+            pallet_telemetry_proofs::Error::<Runtime>::ChallengeNotFound
+        );
+        assert_noop!(
+            TelemetryProofs::set_proof_quality(majority(), device_id.clone(), 0, 90),
+            pallet_telemetry_proofs::Error::<Runtime>::ProofNotFound
+        );
+
+        // Device registry admin
+        assert_ok!(DeviceRegistry::set_device_status(
+            majority(),
+            device_id.clone(),
+            pallet_device_registry::DeviceStatus::Suspended,
+        ));
+        let bounded: DeviceId = device_id.try_into().unwrap();
+        assert_eq!(
+            DeviceRegistry::device_status(&bounded),
+            Some(pallet_device_registry::DeviceStatus::Suspended)
+        );
+    });
+}
+
+#[test]
+fn methodology_referendum_updates_emission_factor() {
+    use codec::Encode;
+    use frame_support::traits::{schedule::DispatchTime, Bounded, Hooks};
+    use pallet_conviction_voting::{AccountVote, Conviction, Vote};
+    use sp_runtime::traits::Hash;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let run_to = |target: BlockNumber| {
+            for block in System::block_number() + 1..=target {
+                System::set_block_number(block);
+                Scheduler::on_initialize(block);
+                CarbonCredits::on_initialize(block);
+            }
+        };
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            owner().into(),
+            1_000 * ARED,
+        ));
+
+        // Note the proposal's preimage and submit it by hash
+        let call =
+            RuntimeCall::CarbonCredits(pallet_carbon_credits::Call::announce_emission_factor {
+                new_factor: 1_800,
+            });
+        let encoded = call.encode();
+        assert_ok!(Preimage::note_preimage(
+            RuntimeOrigin::signed(owner()),
+            encoded.clone(),
+        ));
+        let proposal = Bounded::Lookup {
+            hash: BlakeTwo256::hash(&encoded),
+            len: encoded.len() as u32,
+        };
+
+        // Referenda cannot dispatch with Root, only with a track's origin
+        assert_noop!(
+            Referenda::submit(
+                RuntimeOrigin::signed(owner()),
+                Box::new(frame_system::RawOrigin::Root.into()),
+                proposal.clone(),
+                DispatchTime::After(0),
+            ),
+            pallet_referenda::Error::<Runtime>::NoTrack
+        );
+        assert_ok!(Referenda::submit(
+            RuntimeOrigin::signed(owner()),
+            Box::new(pallet_governance_origins::Origin::Methodology.into()),
+            proposal,
+            DispatchTime::After(0),
+        ));
+        assert_ok!(Referenda::place_decision_deposit(
+            RuntimeOrigin::signed(owner()),
+            0,
+        ));
+        assert_ok!(ConvictionVoting::vote(
+            RuntimeOrigin::signed(owner()),
+            0,
+            AccountVote::Standard {
+                vote: Vote {
+                    aye: true,
+                    conviction: Conviction::Locked1x,
+                },
+                balance: 900 * ARED,
+            },
+        ));
+
+        // Deciding starts after the prepare period, and the referendum is
+        // confirmed a day later
+        let deciding = 1 + HOURS;
+        run_to(deciding);
+        System::assert_has_event(
+            pallet_referenda::Event::<Runtime>::ConfirmStarted { index: 0 }.into(),
+        );
+        let confirmed = deciding + DAYS;
+        run_to(confirmed);
+        assert!(matches!(
+            pallet_referenda::ReferendumInfoFor::<Runtime>::get(0),
+            Some(pallet_referenda::ReferendumInfo::Approved(..))
+        ));
+
+        // The proposal is enacted with the methodology origin after the
+        // track's enactment period, which announces the new factor...
+        let enacted = confirmed + DAYS;
+        run_to(enacted - 1);
+        assert_eq!(CarbonCredits::pending_emission_factor(), None);
+        run_to(enacted);
+        assert_eq!(
+            CarbonCredits::pending_emission_factor(),
+            Some((1_800, enacted + DAYS))
+        );
+
+        // ...and the factor applies after the pallet's own delay
+        run_to(enacted + DAYS);
+        assert_eq!(CarbonCredits::emission_factor(), 1_800);
+    });
+}
+
+#[test]
+fn decommission_winds_down_device_across_pallets() {
+    use frame_support::traits::Hooks;
+    use pallet_telemetry_proofs::WeightInfo;
+
+    new_test_ext().execute_with(|| {
+        Timestamp::set_timestamp(1_000_000_000);
+        let device = b"device-001".to_vec();
+        let bounded: DeviceId = device.clone().try_into().unwrap();
+
+        // 2000 five-minute windows, at most `MaxProofsPerBlock` per block
+        for block in 1..=4u32 {
+            System::set_block_number(block);
+            for i in (block - 1) * 500..block * 500 {
+                let mut hash = [0u8; 32];
+                hash[..4].copy_from_slice(&(i + 1).to_le_bytes());
+                let start = 1_000 + u64::from(i) * 300;
+                assert_ok!(TelemetryProofs::submit_proof(
+                    RuntimeOrigin::signed(bridge()),
+                    device.clone(),
+                    hash.to_vec(),
+                    10,
+                    start,
+                    start + 300,
+                    1024,
+                    1,
+                    pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+                    TelemetryProofs::submission_nonce(&bounded),
+                    TelemetryProofs::latest_proof_hash(&bounded).map(|h| h.as_bytes().to_vec()),
+                    None,
+                    None,
+                ));
+            }
+            TelemetryProofs::on_finalize(block);
+        }
+        assert_eq!(TelemetryProofs::stored_proof_count(&bounded), 2_000);
+
+        // 15 credits from a 10 kWh claim, then 5 kWh left pending
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            10_000,
+            None,
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+        ));
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            5_000,
+            None,
+        ));
+
+        // A rejected decommission changes nothing
+        assert_noop!(
+            DeviceRegistry::decommission_device(RuntimeOrigin::signed(owner()), device.clone()),
+            pallet_device_registry::Error::<Runtime>::NotDeviceOwner
+        );
+
+        assert_ok!(DeviceRegistry::decommission_device(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+        ));
+        let decommissioned: Vec<_> = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                RuntimeEvent::DeviceRegistry(
+                    event @ pallet_device_registry::Event::DeviceDecommissioned { .. },
+                ) => Some(event),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            decommissioned,
+            vec![pallet_device_registry::Event::DeviceDecommissioned {
+                device_id: bounded.clone(),
+                owner: bridge(),
+                from: pallet_device_registry::DeviceStatus::Active,
+                credits_swept: 22,
+                proofs_scheduled: 2_000,
+            }]
+        );
+        assert_eq!(
+            DeviceRegistry::devices(&bounded).unwrap().status,
+            pallet_device_registry::DeviceStatus::Retired
+        );
+        assert!(TelemetryProofs::frozen_devices(&bounded).is_some());
+        assert_eq!(CarbonCredits::credits_balance(&bounded), 0);
+        assert_eq!(CarbonCredits::energy_accumulated(&bounded), 0);
+        assert_eq!(CarbonCredits::account_credits(&bridge()), 22);
+
+        System::set_block_number(5);
+        assert_noop!(
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(bridge()),
+                device.clone(),
+                [0xff; 32].to_vec(),
+                10,
+                700_000,
+                700_300,
+                1024,
+                1,
+                pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+                TelemetryProofs::submission_nonce(&bounded),
+                TelemetryProofs::latest_proof_hash(&bounded).map(|h| h.as_bytes().to_vec()),
+                None,
+                None,
+            ),
+            pallet_telemetry_proofs::Error::<Runtime>::DeviceFrozen
+        );
+
+        // Idle weight for the bookkeeping and 500 removals per block
+        let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+        let budget = db_weight
+            .reads(2)
+            .saturating_add(
+                <Runtime as pallet_telemetry_proofs::Config>::WeightInfo::remove_device_proofs(
+                    0, 90,
+                ),
+            )
+            .saturating_add(
+                <Runtime as pallet_telemetry_proofs::Config>::WeightInfo::prune_proofs_before(1)
+                    .saturating_mul(500),
+            );
+        for (block, remaining) in [(5u32, 1_500u64), (6, 1_000), (7, 500), (8, 0)] {
+            System::set_block_number(block);
+            TelemetryProofs::on_idle(block, budget);
+            assert_eq!(TelemetryProofs::stored_proof_count(&bounded), remaining);
+        }
+        System::assert_last_event(
+            pallet_telemetry_proofs::Event::DeviceProofsRemoved {
+                device_id: bounded.clone(),
+                removed: 500,
+                complete: true,
+            }
+            .into(),
+        );
+        assert!(TelemetryProofs::pending_proof_removals(&bounded).is_none());
+        assert_eq!(TelemetryProofs::proof_count(&bounded), 0);
+        // Replay protection survives the removal
+        assert_eq!(TelemetryProofs::submission_nonce(&bounded), 2_000);
+    });
+}
+
+#[test]
+fn bridge_membership_gates_proofs_and_energy() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        Timestamp::set_timestamp(1_000_000_000);
+        let device = b"device-001".to_vec();
+        let bounded: DeviceId = device.clone().try_into().unwrap();
+        let submit = |who: AccountId, hash: [u8; 32], start: u64| {
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(who),
+                device.clone(),
+                hash.to_vec(),
+                10,
+                start,
+                start + 3600,
+                1024,
+                1,
+                pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+                TelemetryProofs::submission_nonce(&bounded),
+                TelemetryProofs::latest_proof_hash(&bounded).map(|h| h.as_bytes().to_vec()),
+                None,
+                None,
+            )
+        };
+
+        assert_noop!(
+            BridgeMembers::add_member(RuntimeOrigin::signed(bridge()), owner().into()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BridgeMembers::add_member(
+            RuntimeOrigin::root(),
+            owner().into()
+        ));
+        System::assert_last_event(
+            pallet_membership::Event::<Runtime, BridgeMembershipInstance>::MemberAdded.into(),
+        );
+
+        assert_ok!(submit(owner(), [1u8; 32], 1000));
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(owner()),
+            device.clone(),
+            1_000,
+            Some(0),
+        ));
+
+        assert_ok!(BridgeMembers::remove_member(
+            RuntimeOrigin::root(),
+            owner().into()
+        ));
+        System::assert_last_event(
+            pallet_membership::Event::<Runtime, BridgeMembershipInstance>::MemberRemoved.into(),
+        );
+
+        System::set_block_number(200);
+        assert_noop!(
+            submit(owner(), [2u8; 32], 4600),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            CarbonCredits::record_energy(
+                RuntimeOrigin::signed(owner()),
+                device.clone(),
+                1_000,
+                Some(0),
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        // The remaining member is unaffected
+        assert_ok!(submit(bridge(), [2u8; 32], 4600));
+    });
+}
+
+#[test]
+fn unsigned_proofs_are_checked_by_the_transaction_pool() {
+    use codec::Encode;
+    use sp_core::Pair;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    let key = sp_core::sr25519::Pair::from_seed(&[7u8; 32]);
+    let public = sp_runtime::MultiSigner::from(key.public());
+    let key_account = public.clone().into_account();
+    let validate = |xt: UncheckedExtrinsic| {
+        Executive::validate_transaction(TransactionSource::External, xt, Default::default())
+    };
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        Timestamp::set_timestamp(1_000_000_000);
+        let bounded: DeviceId = b"device-001".to_vec().try_into().unwrap();
+        let payload = pallet_telemetry_proofs::ProofPayload {
+            device_id: b"device-001".to_vec(),
+            proof_hash: [1u8; 32].to_vec(),
+            record_count: 10,
+            window_start: 1000,
+            window_end: 4600,
+            data_size_bytes: 1024,
+            schema_version: 1,
+            category: pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+            nonce: 0,
+            genesis_hash: System::block_hash(0),
+            public: public.clone(),
+        };
+        let signature: Signature = key.sign(&payload.encode()).into();
+        let unsigned = |signature: Signature| {
+            UncheckedExtrinsic::new_unsigned(RuntimeCall::TelemetryProofs(
+                pallet_telemetry_proofs::Call::submit_proof_unsigned {
+                    payload: payload.clone(),
+                    signature,
+                },
+            ))
+        };
+
+        // The signing key must belong to a bridge member
+        assert_eq!(
+            validate(unsigned(signature.clone())),
+            Err(InvalidTransaction::BadSigner.into())
+        );
+        assert_ok!(BridgeMembers::add_member(
+            RuntimeOrigin::root(),
+            key_account.into()
+        ));
+
+        // Accepted without the key holding any ARED; a forged signature is not
+        assert!(validate(unsigned(signature.clone())).is_ok());
+        let forged: Signature = sp_core::sr25519::Pair::from_seed(&[8u8; 32])
+            .sign(&payload.encode())
+            .into();
+        assert_eq!(
+            validate(unsigned(forged)),
+            Err(InvalidTransaction::BadProof.into())
+        );
+
+        // Included once, after which the same extrinsic is stale
+        assert_eq!(
+            Executive::apply_extrinsic(unsigned(signature.clone())),
+            Ok(Ok(()))
+        );
+        assert_eq!(TelemetryProofs::submission_nonce(&bounded), 1);
+        assert_eq!(
+            validate(unsigned(signature)),
+            Err(InvalidTransaction::Stale.into())
+        );
+
+        // The next proof may queue behind its predecessor, but cannot be
+        // included before it
+        let mut ahead = payload.clone();
+        ahead.proof_hash = [2u8; 32].to_vec();
+        ahead.window_start = 4600;
+        ahead.window_end = 8200;
+        ahead.nonce = 2;
+        let ahead_xt = UncheckedExtrinsic::new_unsigned(RuntimeCall::TelemetryProofs(
+            pallet_telemetry_proofs::Call::submit_proof_unsigned {
+                signature: key.sign(&ahead.encode()).into(),
+                payload: ahead,
+            },
+        ));
+        let queued = validate(ahead_xt.clone()).unwrap();
+        assert_eq!(queued.requires.len(), 1);
+        assert_eq!(
+            Executive::apply_extrinsic(ahead_xt),
+            Err(InvalidTransaction::Future.into())
+        );
+    });
+}
+
+#[test]
+fn energy_records_reject_non_energy_proofs() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        Timestamp::set_timestamp(1_000_000_000);
+        let device = b"device-001".to_vec();
+
+        assert_ok!(TelemetryProofs::submit_proof(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            [1u8; 32].to_vec(),
+            10,
+            1000,
+            2000,
+            1024,
+            1,
+            pallet_telemetry_proofs::ProofCategory::Temperature,
+            0,
+            None,
+            None,
+            None,
+        ));
+
+        assert_noop!(
+            CarbonCredits::record_energy(RuntimeOrigin::signed(bridge()), device, 5_000, Some(0)),
+            pallet_carbon_credits::Error::<Runtime>::ProofNotFound
+        );
+    });
+}
+
+#[test]
+fn device_status_gates_credits_and_proofs() {
+    use pallet_device_registry::DeviceStatus;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        Timestamp::set_timestamp(1_000_000_000);
+        let device = b"device-002".to_vec();
+        let submit = |hash: u8, start: u64| {
+            let bounded: DeviceId = device.clone().try_into().unwrap();
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(bridge()),
+                device.clone(),
+                [hash; 32].to_vec(),
+                10,
+                start,
+                start + 6000,
+                1024,
+                1,
+                pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+                TelemetryProofs::submission_nonce(&bounded),
+                TelemetryProofs::latest_proof_hash(&bounded).map(|h| h.as_bytes().to_vec()),
+                None,
+                None,
+            )
+        };
+        let set_status = |origin: RuntimeOrigin, status| {
+            DeviceRegistry::set_device_status(origin, device.clone(), status)
+        };
+        // Unregistered devices may anchor proofs but earn no credits
+        assert_ok!(submit(1, 1000));
+        assert_noop!(
+            CarbonCredits::record_energy(
+                RuntimeOrigin::signed(bridge()),
+                device.clone(),
+                5_000,
+                Some(0)
+            ),
+            pallet_carbon_credits::Error::<Runtime>::DeviceNotActive
+        );
+
+        assert_ok!(DeviceRegistry::register_device(
+            RuntimeOrigin::signed(owner()),
+            device.clone(),
+            pallet_device_registry::DeviceMetadata {
+                model: b"ARED-STOVE-1".to_vec().try_into().unwrap(),
+                firmware_hash: Default::default(),
+                location_hash: Default::default(),
+                attestation_hash: None,
+                geohash: None,
+            },
+        ));
+        assert_noop!(
+            CarbonCredits::record_energy(
+                RuntimeOrigin::signed(bridge()),
+                device.clone(),
+                5_000,
+                Some(0)
+            ),
+            pallet_carbon_credits::Error::<Runtime>::DeviceNotActive
+        );
+
+        assert_ok!(set_status(
+            RuntimeOrigin::signed(owner()),
+            DeviceStatus::Active
+        ));
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            5_000,
+            Some(0),
+        ));
+
+        // Suspended devices keep submitting proofs but cannot claim
+        assert_ok!(set_status(RuntimeOrigin::root(), DeviceStatus::Suspended));
+        assert_noop!(
+            CarbonCredits::claim_credits(RuntimeOrigin::signed(bridge()), device.clone()),
+            pallet_carbon_credits::Error::<Runtime>::DeviceNotActive
+        );
+        assert_ok!(submit(2, 7000));
+
+        assert_ok!(set_status(RuntimeOrigin::root(), DeviceStatus::Active));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+        ));
+
+        // Retired devices may no longer submit proofs
+        assert_ok!(set_status(
+            RuntimeOrigin::signed(owner()),
+            DeviceStatus::Retired
+        ));
+        assert_noop!(
+            submit(3, 13000),
+            pallet_telemetry_proofs::Error::<Runtime>::DeviceRetired
+        );
+    });
+}
+
+#[test]
+fn validator_added_by_governance_becomes_authority_after_session_rotation() {
+    use frame_support::traits::OnFinalize;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let authorities = || {
+            (
+                pallet_aura::Authorities::<Runtime>::get().into_inner(),
+                Grandpa::grandpa_authorities(),
+            )
+        };
+        assert_eq!(
+            authorities(),
+            (
+                vec![session_keys(1).aura],
+                vec![(session_keys(1).grandpa, 1)]
+            )
+        );
+
+        // The new validator registers its keys before governance adds it
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            validator(2).into(),
+            ARED,
+        ));
+        assert_ok!(Session::set_keys(
+            RuntimeOrigin::signed(validator(2)),
+            session_keys(2),
+            vec![],
+        ));
+        assert_noop!(
+            ValidatorSet::add_validator(RuntimeOrigin::signed(owner()), validator(2)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(ValidatorSet::add_validator(
+            RuntimeOrigin::root(),
+            validator(2)
+        ));
+
+        // The first rotation only queues the new set
+        Session::rotate_session();
+        Grandpa::on_finalize(1);
+        assert_eq!(authorities().0, vec![session_keys(1).aura]);
+
+        System::set_block_number(2);
+        Session::rotate_session();
+        Grandpa::on_finalize(2);
+        assert_eq!(
+            authorities(),
+            (
+                vec![session_keys(1).aura, session_keys(2).aura],
+                vec![(session_keys(1).grandpa, 1), (session_keys(2).grandpa, 1)]
+            )
+        );
+        assert_eq!(Session::validators(), vec![validator(1), validator(2)]);
+    });
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn try_runtime_upgrade_passes_checks_on_genesis_state() {
+    use frame_try_runtime::{runtime_decl_for_try_runtime::TryRuntimeV1, UpgradeCheckSelect};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (weight, max_block) = Runtime::on_runtime_upgrade(UpgradeCheckSelect::All);
+        assert!(weight.all_lte(max_block));
+    });
+}
+
+/// Run the upgrade hooks, then step the multi-block migrations one block at a
+/// time until they finish, returning the number of blocks they took.
+fn upgrade_and_run_multi_block_migrations() -> u32 {
+    use frame_support::migrations::MultiStepMigrator;
+
+    Executive::execute_on_runtime_upgrade();
+    let mut blocks = 0;
+    while MultiBlockMigrations::ongoing() {
+        System::set_block_number(System::block_number() + 1);
+        MultiBlockMigrations::step();
+        blocks += 1;
+    }
+    blocks
+}
+
+#[test]
+fn try_runtime_upgrade_migrates_old_state_across_blocks() {
+    use frame_support::traits::{
+        fungible::InspectHold, Get, GetStorageVersion, ReservableCurrency, StorageVersion,
+    };
+    use pallet_telemetry_proofs::migrations::v0;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let device = b"device-001".to_vec();
+        let bounded: DeviceId = device.clone().try_into().unwrap();
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+            10_000,
+            None,
+        ));
+        assert_ok!(CarbonCredits::claim_credits(
+            RuntimeOrigin::signed(bridge()),
+            device.clone(),
+        ));
+        assert_ok!(CarbonCredits::record_energy(
+            RuntimeOrigin::signed(bridge()),
+            device,
+            3_000,
+            None,
+        ));
+
+        // Carbon credits state as stored before the pallet was versioned
+        let _ = pallet_carbon_credits::ClaimedEnergy::<Runtime>::clear(u32::MAX, None);
+        StorageVersion::new(0).put::<CarbonCredits>();
+
+        // Telemetry proofs as stored before the pallet was versioned, more
+        // than a block can migrate
+        let proofs = 2_500u64;
+        let hash = |index: u64| sp_io::hashing::blake2_256(&index.to_le_bytes());
+        for index in 0..proofs {
+            let block = index as BlockNumber + 1;
+            v0::Proofs::<Runtime>::insert(
+                &bounded,
+                index,
+                v0::ProofMetadataV0::<Runtime> {
+                    proof_hash: hash(index).to_vec(),
+                    block_number: block,
+                    timestamp: 0,
+                    record_count: 10,
+                    window_start: index * 3_600,
+                    window_end: (index + 1) * 3_600,
+                },
+            );
+            v0::ProofsByBlock::<Runtime>::insert(block, &bounded, hash(index).to_vec());
+        }
+        pallet_telemetry_proofs::ProofCount::<Runtime>::insert(&bounded, proofs);
+        pallet_telemetry_proofs::TotalProofs::<Runtime>::put(proofs);
+        StorageVersion::new(0).put::<TelemetryProofs>();
+
+        // A challenge whose deposit was reserved rather than held
+        let deposit = <Runtime as pallet_telemetry_proofs::Config>::ChallengeDeposit::get();
+        assert_ok!(Balances::reserve(&owner(), deposit));
+        pallet_telemetry_proofs::Challenges::<Runtime>::insert(
+            &bounded,
+            7,
+            pallet_telemetry_proofs::ProofChallenge::<Runtime> {
+                challenger: owner(),
+                deposit,
+                evidence_hash: Default::default(),
+                challenged_at: 1,
+            },
+        );
+
+        assert!(upgrade_and_run_multi_block_migrations() > 1);
+        System::assert_has_event(pallet_migrations::Event::UpgradeCompleted.into());
+        assert_eq!(TelemetryProofs::on_chain_storage_version(), 10);
+        assert_eq!(
+            Balances::balance_on_hold(
+                &RuntimeHoldReason::TelemetryProofs(
+                    pallet_telemetry_proofs::HoldReason::ChallengeDeposit
+                ),
+                &owner(),
+            ),
+            deposit
+        );
+        assert_eq!(Balances::reserved_balance(owner()), deposit);
+        assert_eq!(TelemetryProofs::stored_proof_count(&bounded), proofs);
+        assert_eq!(
+            TelemetryProofs::find_proof_index(&bounded, &sp_core::H256(hash(7))),
+            Some(7)
+        );
+        assert_eq!(v0::ProofsByBlock::<Runtime>::iter().count(), 0);
+        assert_eq!(CarbonCredits::on_chain_storage_version(), 2);
+        assert_eq!(CarbonCredits::claimed_energy(&bounded), 10_000);
+
+        // A second upgrade skips the completed migrations and changes nothing
+        System::reset_events();
+        let block = System::block_number();
+        let migrated = sp_io::storage::root(sp_runtime::StateVersion::V1);
+        assert_eq!(upgrade_and_run_multi_block_migrations(), 1);
+        System::reset_events();
+        System::set_block_number(block);
+        assert_eq!(sp_io::storage::root(sp_runtime::StateVersion::V1), migrated);
+    });
+}
+
+#[test]
+fn grandpa_equivocation_report_disables_the_offender() {
+    use frame_support::traits::KeyOwnerProofSystem;
+    use sp_core::Pair;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let offender = grandpa_pair(1);
+        let set_id = Grandpa::current_set_id();
+        let round = 1;
+
+        // Two different prevotes signed by the same authority in one round
+        let signed_prevote = |target_hash: Hash| -> (
+            finality_grandpa::Prevote<Hash, BlockNumber>,
+            sp_consensus_grandpa::AuthoritySignature,
+        ) {
+            let prevote = finality_grandpa::Prevote {
+                target_hash,
+                target_number: 1,
+            };
+            let payload = sp_consensus_grandpa::localized_payload(
+                round,
+                set_id,
+                &finality_grandpa::Message::Prevote(prevote.clone()),
+            );
+            (prevote, offender.sign(&payload).into())
+        };
+        let equivocation_proof = sp_consensus_grandpa::EquivocationProof::<Hash, BlockNumber>::new(
+            set_id,
+            sp_consensus_grandpa::Equivocation::Prevote(finality_grandpa::Equivocation {
+                round_number: round,
+                identity: offender.public().into(),
+                first: signed_prevote(Hash::repeat_byte(1)),
+                second: signed_prevote(Hash::repeat_byte(2)),
+            }),
+        );
+        let key_owner_proof = Historical::prove((
+            sp_consensus_grandpa::KEY_TYPE,
+            GrandpaId::from(offender.public()),
+        ))
+        .unwrap();
+
+        assert!(Session::disabled_validators().is_empty());
+        assert_ok!(Grandpa::report_equivocation_unsigned(
+            RuntimeOrigin::none(),
+            Box::new(equivocation_proof.clone()),
+            key_owner_proof.clone(),
+        ));
+
+        // validator(1) is the only validator, at index 0
+        assert_eq!(Session::disabled_validators(), vec![0]);
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::Offences(pallet_offences::Event::Offence { .. })
+        )));
+        assert_noop!(
+            Grandpa::report_equivocation_unsigned(
+                RuntimeOrigin::none(),
+                Box::new(equivocation_proof),
+                key_owner_proof,
+            ),
+            pallet_grandpa::Error::<Runtime>::DuplicateOffenceReport
+        );
+    });
+}
+
+#[test]
+fn validators_without_heartbeats_are_reported_offline_at_session_end() {
+    use pallet_authorship::EventHandler;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // No heartbeat and no authored block during the session
+        Session::rotate_session();
+        System::assert_has_event(
+            pallet_im_online::Event::SomeOffline {
+                offline: vec![(validator(1), ())],
+            }
+            .into(),
+        );
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::Offences(pallet_offences::Event::Offence { .. })
+        )));
+
+        // Authoring a block counts as being online
+        System::reset_events();
+        System::set_block_number(2);
+        ImOnline::note_author(validator(1));
+        Session::rotate_session();
+        System::assert_has_event(pallet_im_online::Event::AllGood.into());
+    });
+}
+
+#[test]
+fn offenders_stay_disabled_for_the_following_sessions() {
+    use pallet_authorship::EventHandler;
+    use sp_staking::offence::ReportOffence;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let session = Session::current_index();
+
+        // A synthetic offence by validator(1), the only validator
+        assert_ok!(Offences::report_offence(
+            vec![],
+            pallet_im_online::UnresponsivenessOffence {
+                session_index: session,
+                validator_set_count: 1,
+                offenders: vec![(validator(1), ())],
+            },
+        ));
+        System::assert_has_event(
+            pallet_validator_set::Event::ValidatorDisabled {
+                validator: validator(1),
+                until_session: session + 24,
+            }
+            .into(),
+        );
+        assert_eq!(Session::disabled_validators(), vec![0]);
+
+        // Each rotation re-enables everyone, and the validator set disables
+        // the offender again until its period ends
+        for n in 1..=25 {
+            System::set_block_number(1 + n);
+            ImOnline::note_author(validator(1));
+            Session::rotate_session();
+            assert_eq!(Session::current_index(), session + n);
+            if n <= 24 {
+                assert_eq!(Session::disabled_validators(), vec![0]);
+            } else {
+                assert!(Session::disabled_validators().is_empty());
+            }
+        }
+        assert_eq!(ValidatorSet::disabled_until(validator(1)), None);
+        // The offender was never removed from the set
+        assert_eq!(Session::validators(), vec![validator(1)]);
+    });
+}
+
+#[test]
+fn governance_adds_well_known_nodes_and_swaps_their_keys() {
+    use sp_core::OpaquePeerId;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let node = OpaquePeerId(vec![1; 38]);
+        let new_key = OpaquePeerId(vec![2; 38]);
+
+        assert_noop!(
+            NodeAuthorization::add_well_known_node(
+                RuntimeOrigin::signed(validator(1)),
+                node.clone(),
+                validator(1).into(),
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(NodeAuthorization::add_well_known_node(
+            RuntimeOrigin::root(),
+            node.clone(),
+            validator(1).into(),
+        ));
+        assert!(pallet_node_authorization::WellKnownNodes::<Runtime>::get().contains(&node));
+        assert_eq!(
+            pallet_node_authorization::Owners::<Runtime>::get(&node),
+            Some(validator(1))
+        );
+
+        // The owner hands the node over to another operator
+        assert_ok!(NodeAuthorization::transfer_node(
+            RuntimeOrigin::signed(validator(1)),
+            node.clone(),
+            validator(2).into(),
+        ));
+        assert_eq!(
+            pallet_node_authorization::Owners::<Runtime>::get(&node),
+            Some(validator(2))
+        );
+
+        // Rotating the node key keeps the owner
+        assert_ok!(NodeAuthorization::swap_well_known_node(
+            RuntimeOrigin::root(),
+            node.clone(),
+            new_key.clone(),
+        ));
+        let nodes = pallet_node_authorization::WellKnownNodes::<Runtime>::get();
+        assert!(!nodes.contains(&node) && nodes.contains(&new_key));
+        assert_eq!(
+            pallet_node_authorization::Owners::<Runtime>::get(&node),
+            None
+        );
+        assert_eq!(
+            pallet_node_authorization::Owners::<Runtime>::get(&new_key),
+            Some(validator(2))
+        );
+    });
+}
+
+#[test]
+fn aura_block_author_earns_the_block_reward() {
+    use codec::Encode;
+    use frame_support::traits::Hooks;
+    use sp_runtime::{Digest, DigestItem};
+
+    new_test_ext().execute_with(|| {
+        let reward = ARED / 1_000;
+        assert_ok!(BlockRewards::set_block_reward(
+            RuntimeOrigin::root(),
+            reward
+        ));
+        let before = Balances::free_balance(validator(1));
+
+        for block in 1..=3 {
+            // validator(1) is the only authority, so it owns every slot
+            let slot = sp_consensus_aura::Slot::from(u64::from(block));
+            let digest = Digest {
+                logs: vec![DigestItem::PreRuntime(
+                    sp_consensus_aura::AURA_ENGINE_ID,
+                    slot.encode(),
+                )],
+            };
+            System::initialize(&block, &System::parent_hash(), &digest);
+            Authorship::on_initialize(block);
+            Authorship::on_finalize(block);
+        }
+
+        assert_eq!(Balances::free_balance(validator(1)), before + 3 * reward);
+        System::assert_has_event(
+            pallet_block_rewards::Event::AuthorRewarded {
+                author: validator(1),
+                reward,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn tips_go_to_the_block_author_and_fees_to_the_treasury() {
+    use codec::Encode;
+    use frame_support::{dispatch::GetDispatchInfo, traits::Hooks};
+    use sp_runtime::{
+        traits::{Dispatchable, SignedExtension},
+        Digest, DigestItem,
+    };
+
+    type ChargeTransactionPayment = pallet_transaction_payment::ChargeTransactionPayment<Runtime>;
+
+    new_test_ext().execute_with(|| {
+        // validator(1) is the only authority, so it authors block 1
+        let digest = Digest {
+            logs: vec![DigestItem::PreRuntime(
+                sp_consensus_aura::AURA_ENGINE_ID,
+                sp_consensus_aura::Slot::from(1u64).encode(),
+            )],
+        };
+        System::initialize(&1, &System::parent_hash(), &digest);
+        Authorship::on_initialize(1);
+        let author_before = Balances::free_balance(validator(1));
+
+        let tip = ARED / 1_000;
+        let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let info = call.get_dispatch_info();
+        let len = call.encoded_size();
+        let fee = TransactionPayment::compute_fee(len as u32, &info, 0);
+        let payer_before = Balances::free_balance(owner());
+
+        let pre = ChargeTransactionPayment::from(tip)
+            .pre_dispatch(&owner(), &call, &info, len)
+            .unwrap();
+        let post_info = call.dispatch(RuntimeOrigin::signed(owner())).unwrap();
+        assert_ok!(ChargeTransactionPayment::post_dispatch(
+            Some(pre),
+            &info,
+            &post_info,
+            len,
+            &Ok(())
+        ));
+
+        assert_eq!(Balances::free_balance(owner()), payer_before - fee - tip);
+        assert_eq!(Balances::free_balance(validator(1)), author_before + tip);
+        assert_eq!(Balances::free_balance(TreasuryAccount::get()), fee);
+    });
+}
+
+#[test]
+fn identity_deposit_is_held_on_top_of_the_existential_deposit() {
+    use codec::Encode;
+    use frame_support::traits::Get;
+    use sp_runtime::traits::Convert;
+
+    new_test_ext().execute_with(|| {
+        let info = pallet_operator_identities::DisplayName::<MaxAdditionalFields>::convert(
+            b"ARED Operator".to_vec(),
+        )
+        .unwrap();
+        let deposit = IdentityBasicDeposit::get()
+            + IdentityByteDeposit::get() * info.encoded_size() as Balance;
+        let free = Balances::free_balance(owner());
+
+        assert_ok!(Identity::set_identity(
+            RuntimeOrigin::signed(owner()),
+            Box::new(info.clone()),
+        ));
+        let (registration, _username) =
+            pallet_identity::IdentityOf::<Runtime>::get(owner()).unwrap();
+        assert_eq!(registration.info, info);
+        assert_eq!(registration.deposit, deposit);
+        assert_eq!(Balances::reserved_balance(owner()), deposit);
+        assert_eq!(Balances::free_balance(owner()), free - deposit);
+
+        // An account holding only the existential deposit cannot pay it
+        let existential_deposit = <Runtime as pallet_balances::Config>::ExistentialDeposit::get();
+        assert_ok!(Balances::transfer_allow_death(
+            RuntimeOrigin::signed(owner()),
+            validator(2).into(),
+            existential_deposit,
+        ));
+        assert_noop!(
+            Identity::set_identity(RuntimeOrigin::signed(validator(2)), Box::new(info)),
+            pallet_balances::Error::<Runtime>::InsufficientBalance
+        );
+
+        // Clearing the identity returns the whole deposit
+        assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(owner())));
+        assert_eq!(Balances::reserved_balance(owner()), 0);
+        assert_eq!(Balances::free_balance(owner()), free - existential_deposit);
+    });
+}
+
+#[test]
+fn offchain_heartbeat_is_signed_by_the_node_key_and_dispatched() {
+    use codec::Decode;
+    use sp_core::offchain::{
+        testing::{TestOffchainExt, TestTransactionPoolExt},
+        OffchainWorkerExt, TransactionPoolExt,
+    };
+    use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+
+    let keystore = MemoryKeystore::new();
+    let public = keystore
+        .sr25519_generate_new(pallet_telemetry_proofs::KEY_TYPE, None)
+        .unwrap();
+    let node = AccountId::from(public.0);
+
+    let mut ext = new_test_ext();
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    ext.register_extension(OffchainWorkerExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    ext.register_extension(KeystoreExt::new(keystore));
+
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(BridgeMembers::add_member(
+            RuntimeOrigin::root(),
+            node.clone().into()
+        ));
+        // The account must exist for its nonce to be checked
+        assert_ok!(Balances::transfer_allow_death(
+            RuntimeOrigin::signed(owner()),
+            node.clone().into(),
+            ARED,
+        ));
+
+        assert_eq!(TelemetryProofs::send_heartbeats(), 1);
+        let tx = pool_state.write().transactions.pop().unwrap();
+        let xt = UncheckedExtrinsic::decode(&mut &tx[..]).unwrap();
+        assert_eq!(
+            xt.function,
+            RuntimeCall::TelemetryProofs(pallet_telemetry_proofs::Call::heartbeat {})
+        );
+
+        // Signature, era and nonce pass the full transaction checks, and
+        // the bridge member pays no fee
+        assert_eq!(Executive::apply_extrinsic(xt), Ok(Ok(())));
+        assert_eq!(TelemetryProofs::last_heartbeat(&node), Some(1));
+        assert_eq!(Balances::free_balance(&node), ARED);
+        assert_eq!(System::account_nonce(&node), 1);
+    });
+}
+
+#[test]
+fn bridge_members_are_trusted_submitters() {
+    use frame_support::traits::fungible::InspectHold;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        Timestamp::set_timestamp(1_000_000_000);
+        let device = b"device-001".to_vec();
+        let bounded: DeviceId = device.clone().try_into().unwrap();
+        let submitter = validator(2);
+        let reason =
+            RuntimeHoldReason::TelemetryProofs(pallet_telemetry_proofs::HoldReason::ProofDeposit);
+        let submit = |hash: u8, start: u64| {
+            TelemetryProofs::submit_proof(
+                RuntimeOrigin::signed(submitter.clone()),
+                device.clone(),
+                [hash; 32].to_vec(),
+                10,
+                start,
+                start + 3600,
+                1024,
+                1,
+                pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+                TelemetryProofs::submission_nonce(&bounded),
+                TelemetryProofs::latest_proof_hash(&bounded).map(|h| h.as_bytes().to_vec()),
+                None,
+                None,
+            )
+        };
+
+        // Membership alone makes a new bridge account trusted; the pallet's
+        // own whitelist stays empty
+        assert_ok!(BridgeMembers::add_member(
+            RuntimeOrigin::root(),
+            submitter.clone().into()
+        ));
+        assert!(TelemetryProofs::trusted_submitters(&submitter).is_none());
+
+        // No deposit is held, and a backlog is not throttled by the interval
+        assert_ok!(submit(1, 1000));
+        assert_ok!(submit(2, 4600));
+        assert_eq!(Balances::balance_on_hold(&reason, &submitter), 0);
+        assert_eq!(TelemetryProofs::stored_proof_count(&bounded), 2);
+    });
+}
+
+#[test]
+fn telemetry_fits_a_block_full_of_normal_transactions() {
+    use codec::Encode;
+    use frame_support::{
+        dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo},
+        traits::Get,
+    };
+    use sp_runtime::{
+        traits::SignedExtension,
+        transaction_validity::{InvalidTransaction, TransactionValidityError},
+    };
+
+    new_test_ext().execute_with(|| {
+        let weights = <Runtime as frame_system::Config>::BlockWeights::get();
+        let check_weight = frame_system::CheckWeight::<Runtime>::new();
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let heavy = DispatchInfo {
+            weight: weights.get(DispatchClass::Normal).max_extrinsic.unwrap(),
+            class: DispatchClass::Normal,
+            ..Default::default()
+        };
+
+        // Heavy normal transactions until their share of the block is used up
+        let mut included = 0;
+        while check_weight
+            .clone()
+            .pre_dispatch(&owner(), &remark, &heavy, 0)
+            .is_ok()
+        {
+            included += 1;
+        }
+        assert!(included > 0);
+        assert_eq!(
+            check_weight
+                .clone()
+                .pre_dispatch(&owner(), &remark, &remark.get_dispatch_info(), 0),
+            Err(TransactionValidityError::Invalid(
+                InvalidTransaction::ExhaustsResources
+            ))
+        );
+
+        // Proof and energy submissions still fit in the reserved space
+        let submit = RuntimeCall::TelemetryProofs(pallet_telemetry_proofs::Call::submit_proof {
+            device_id: b"device-001".to_vec(),
+            proof_hash: [1u8; 32].to_vec(),
+            record_count: 10,
+            window_start: 1000,
+            window_end: 4600,
+            data_size_bytes: 1024,
+            schema_version: 1,
+            category: pallet_telemetry_proofs::ProofCategory::EnergyUsage,
+            nonce: 0,
+            prev_proof_hash: None,
+            signature: None,
+            content_cid: None,
+        });
+        let record = RuntimeCall::CarbonCredits(pallet_carbon_credits::Call::record_energy {
+            device_id: b"device-001".to_vec(),
+            energy_wh: 500,
+            proof_index: Some(0),
+        });
+        for call in [submit, record] {
+            let info = call.get_dispatch_info();
+            assert_eq!(info.class, DispatchClass::Operational);
+            assert_ok!(check_weight.clone().pre_dispatch(
+                &bridge(),
+                &call,
+                &info,
+                call.encoded_size()
+            ));
+        }
+    });
+}
+
+/// Dispatch `call` as a signed extrinsic from `who` would, through the call filter.
+fn dispatch_filtered(who: AccountId, call: RuntimeCall) -> sp_runtime::DispatchResult {
+    use sp_runtime::traits::Dispatchable;
+
+    call.dispatch(RuntimeOrigin::signed(who))
+        .map(|_| ())
+        .map_err(|err| err.error)
+}
+
+#[cfg(not(feature = "production"))]
+#[test]
+fn production_mode_filters_sudo_and_balance_transfers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let transfer = || {
+            RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+                dest: bridge().into(),
+                value: ARED,
+            })
+        };
+        let sudo = || {
+            RuntimeCall::Sudo(pallet_sudo::Call::sudo {
+                call: Box::new(RuntimeCall::System(frame_system::Call::remark {
+                    remark: vec![],
+                })),
+            })
+        };
+
+        // Off by default: transfers go through, sudo only lacks a key
+        assert!(!ProductionMode::is_enabled());
+        assert_ok!(dispatch_filtered(owner(), transfer()));
+        assert_eq!(
+            dispatch_filtered(owner(), sudo()),
+            Err(pallet_sudo::Error::<Runtime>::RequireSudo.into())
+        );
+
+        assert_noop!(
+            ProductionMode::enable(RuntimeOrigin::signed(owner())),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(ProductionMode::enable(RuntimeOrigin::root()));
+        assert!(ProductionMode::is_enabled());
+        assert_noop!(
+            ProductionMode::enable(RuntimeOrigin::root()),
+            pallet_production_mode::Error::<Runtime>::AlreadyEnabled
+        );
+
+        let filtered = Err(frame_system::Error::<Runtime>::CallFiltered.into());
+        assert_eq!(dispatch_filtered(owner(), transfer()), filtered);
+        assert_eq!(dispatch_filtered(owner(), sudo()), filtered);
+        assert_eq!(
+            dispatch_filtered(
+                owner(),
+                RuntimeCall::Balances(pallet_balances::Call::transfer_all {
+                    dest: bridge().into(),
+                    keep_alive: false,
+                })
+            ),
+            filtered
+        );
+
+        // System, telemetry and carbon calls still dispatch
+        assert_ok!(dispatch_filtered(
+            owner(),
+            RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+        ));
+        assert_ok!(dispatch_filtered(
+            bridge(),
+            RuntimeCall::TelemetryProofs(pallet_telemetry_proofs::Call::heartbeat {})
+        ));
+        assert_ok!(dispatch_filtered(
+            bridge(),
+            RuntimeCall::CarbonCredits(pallet_carbon_credits::Call::record_energy {
+                device_id: b"device-001".to_vec(),
+                energy_wh: 500,
+                proof_index: None,
+            })
+        ));
+    });
+}
+
+#[cfg(feature = "production")]
+#[test]
+fn production_feature_filters_without_the_storage_flag() {
+    new_test_ext().execute_with(|| {
+        assert!(!pallet_production_mode::Enabled::<Runtime>::get());
+        assert!(ProductionMode::is_enabled());
+        assert_eq!(
+            dispatch_filtered(
+                owner(),
+                RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+                    dest: bridge().into(),
+                    value: ARED,
+                })
+            ),
+            Err(frame_system::Error::<Runtime>::CallFiltered.into())
+        );
+        assert_noop!(
+            ProductionMode::enable(RuntimeOrigin::root()),
+            pallet_production_mode::Error::<Runtime>::AlreadyEnabled
+        );
+    });
+}
+
+/// Apply the JSON `patch` onto `base`, as the node does with genesis presets.
+fn merge_json(base: &mut serde_json::Value, patch: serde_json::Value) {
+    match (base, patch) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge_json(base.entry(key).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
+#[test]
+fn genesis_presets_deserialize_and_build() {
+    let names = genesis_config_presets::preset_names();
+    assert_eq!(names.len(), 3);
+
+    for name in names {
+        let patch = genesis_config_presets::get_preset(&name).unwrap();
+        let patch: serde_json::Value = serde_json::from_slice(&patch).unwrap();
+        let mut config = serde_json::to_value(RuntimeGenesisConfig::default()).unwrap();
+        merge_json(&mut config, patch);
+
+        let config: RuntimeGenesisConfig = serde_json::from_value(config)
+            .unwrap_or_else(|e| panic!("preset {name:?} does not deserialize: {e}"));
+        let live = name == genesis_config_presets::LIVE_RUNTIME_PRESET.into();
+        sp_io::TestExternalities::from(config.build_storage().unwrap()).execute_with(|| {
+            assert!(!ValidatorSet::validators().is_empty());
+            assert_eq!(
+                pallet_membership::Members::<Runtime, BridgeMembershipInstance>::get().len(),
+                1
+            );
+            assert_eq!(
+                ProductionMode::is_enabled(),
+                live || cfg!(feature = "production")
+            );
+            assert_eq!(Sudo::key().is_some(), !live);
+        });
+    }
+
+    assert!(genesis_config_presets::get_preset(&"unknown".into()).is_none());
+}